@@ -1,27 +1,42 @@
+use account_utils::validator_definitions::ValidatorDefinitions;
 use clap::{App, Arg, ArgMatches};
 use environment::Environment;
+use eth2::{types::StateId, BeaconNodeHttpClient, Timeouts};
+use sensitive_url::SensitiveUrl;
 use slashing_protection::{
     interchange::Interchange, InterchangeError, InterchangeImportOutcome, SlashingDatabase,
     SLASHING_PROTECTION_FILENAME,
 };
+use std::collections::HashSet;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use types::{BeaconState, Epoch, EthSpec, PublicKeyBytes, Slot};
 
 pub const CMD: &str = "slashing-protection";
 pub const IMPORT_CMD: &str = "import";
 pub const EXPORT_CMD: &str = "export";
+pub const PRUNE_CMD: &str = "prune";
+pub const CHECK_CMD: &str = "check";
 
 pub const IMPORT_FILE_ARG: &str = "IMPORT-FILE";
 pub const EXPORT_FILE_ARG: &str = "EXPORT-FILE";
 
 pub const MINIFY_FLAG: &str = "minify";
 pub const PUBKEYS_FLAG: &str = "pubkeys";
+pub const EPOCH_FLAG: &str = "epoch";
+pub const BEACON_SERVER_FLAG: &str = "beacon-node";
+pub const NO_VACUUM_FLAG: &str = "no-vacuum";
+
+pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
-        .about("Import or export slashing protection data to or from another client")
+        .about(
+            "Import or export slashing protection data to or from another client, or prune \
+             and check the local slashing protection database",
+        )
         .subcommand(
             App::new(IMPORT_CMD)
                 .about("Import an interchange file")
@@ -73,6 +88,46 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                         ),
                 ),
         )
+        .subcommand(
+            App::new(PRUNE_CMD)
+                .about(
+                    "Prune records prior to a finalized epoch for all validators in the \
+                     database, including disabled validators. The latest block and attestation \
+                     of each validator are always retained. The validator client must not be \
+                     running.",
+                )
+                .arg(
+                    Arg::with_name(EPOCH_FLAG)
+                        .long(EPOCH_FLAG)
+                        .takes_value(true)
+                        .value_name("EPOCH")
+                        .help(
+                            "Prune records prior to this epoch, which must be finalized. If \
+                             omitted the finalized epoch is fetched from the beacon node",
+                        ),
+                )
+                .arg(
+                    Arg::with_name(BEACON_SERVER_FLAG)
+                        .long(BEACON_SERVER_FLAG)
+                        .value_name("NETWORK_ADDRESS")
+                        .help("Address to a beacon node HTTP API")
+                        .default_value(DEFAULT_BEACON_NODE)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name(NO_VACUUM_FLAG)
+                        .long(NO_VACUUM_FLAG)
+                        .help(
+                            "Do not compact the database file after pruning. Compaction may \
+                             take some time for large databases and requires free disk space \
+                             of up to twice the size of the database",
+                        ),
+                ),
+        )
+        .subcommand(App::new(CHECK_CMD).about(
+            "Check the consistency of the slashing protection database, and list validators \
+             which are registered in the database but unknown to the validator client",
+        ))
 }
 
 pub fn cli_run<T: EthSpec>(
@@ -84,6 +139,7 @@ pub fn cli_run<T: EthSpec>(
 
     let eth2_network_config = env
         .eth2_network_config
+        .as_ref()
         .ok_or("Unable to get testnet configuration from the environment")?;
 
     let genesis_validators_root = eth2_network_config
@@ -228,21 +284,8 @@ pub fn cli_run<T: EthSpec>(
                 None
             };
 
-            if !slashing_protection_db_path.exists() {
-                return Err(format!(
-                    "No slashing protection database exists at: {}",
-                    slashing_protection_db_path.display()
-                ));
-            }
-
-            let slashing_protection_database = SlashingDatabase::open(&slashing_protection_db_path)
-                .map_err(|e| {
-                    format!(
-                        "Unable to open database at {}: {:?}",
-                        slashing_protection_db_path.display(),
-                        e
-                    )
-                })?;
+            let slashing_protection_database =
+                open_existing_database(&slashing_protection_db_path)?;
 
             let mut interchange = slashing_protection_database
                 .export_interchange_info(genesis_validators_root, selected_pubkeys.as_deref())
@@ -266,7 +309,146 @@ pub fn cli_run<T: EthSpec>(
 
             Ok(())
         }
+        (PRUNE_CMD, Some(matches)) => {
+            let epoch = if let Some(epoch) = clap_utils::parse_optional::<u64>(matches, EPOCH_FLAG)?
+            {
+                Epoch::new(epoch)
+            } else {
+                let server_url: String = clap_utils::parse_required(matches, BEACON_SERVER_FLAG)?;
+                let client = BeaconNodeHttpClient::new(
+                    SensitiveUrl::parse(&server_url)
+                        .map_err(|e| format!("Failed to parse beacon http server: {:?}", e))?,
+                    Timeouts::set_all(Duration::from_secs(env.eth2_config.spec.seconds_per_slot)),
+                );
+                env.runtime().block_on(get_finalized_epoch(&client))?
+            };
+            let no_vacuum = matches.is_present(NO_VACUUM_FLAG);
+
+            let slashing_protection_database =
+                open_existing_database(&slashing_protection_db_path)?;
+
+            let new_min_slot = epoch.start_slot(T::slots_per_epoch());
+            eprint!(
+                "Pruning records prior to epoch {} (slot {})",
+                epoch.as_u64(),
+                new_min_slot.as_u64()
+            );
+            let summary = slashing_protection_database
+                .prune_all_validators(new_min_slot, epoch)
+                .map_err(|e| format!("Error during pruning: {:?}", e))?;
+            eprintln!(" [done].");
+            eprintln!("- blocks pruned: {}", summary.blocks_pruned);
+            eprintln!("- attestations pruned: {}", summary.attestations_pruned);
+
+            if !no_vacuum {
+                eprint!("Compacting database file");
+                slashing_protection_database
+                    .vacuum()
+                    .map_err(|e| format!("Error during compaction: {:?}", e))?;
+                eprintln!(" [done].");
+            }
+
+            Ok(())
+        }
+        (CHECK_CMD, Some(_)) => {
+            let slashing_protection_database =
+                open_existing_database(&slashing_protection_db_path)?;
+
+            let report = slashing_protection_database
+                .check_integrity()
+                .map_err(|e| format!("Error during integrity check: {:?}", e))?;
+            let registered_validators = slashing_protection_database
+                .with_transaction(|txn| {
+                    slashing_protection_database.list_all_registered_validators(txn)
+                })
+                .map_err(|e| format!("Unable to list registered validators: {:?}", e))?;
+
+            let validator_definitions = ValidatorDefinitions::open(&validator_base_dir)
+                .map_err(|e| format!("Unable to open validator definitions: {:?}", e))?;
+            let known_pubkeys = validator_definitions
+                .as_slice()
+                .iter()
+                .map(|def| def.voting_public_key.compress())
+                .collect::<HashSet<_>>();
+            let unknown_validators = registered_validators
+                .iter()
+                .map(|(_, pubkey)| pubkey)
+                .filter(|pubkey| !known_pubkeys.contains(pubkey))
+                .collect::<Vec<_>>();
+
+            let display_pubkeys = |description: &str, pubkeys: &[PublicKeyBytes]| {
+                eprintln!("- {}: {}", description, pubkeys.len());
+                for pubkey in pubkeys {
+                    eprintln!("    - {:?}", pubkey);
+                }
+            };
+
+            eprintln!("Registered validators: {}", registered_validators.len());
+            eprintln!("- SQLite errors: {}", report.sqlite_errors.len());
+            for error in &report.sqlite_errors {
+                eprintln!("    - {}", error);
+            }
+            eprintln!(
+                "- foreign key violations: {}",
+                report.foreign_key_violations
+            );
+            eprintln!("- orphaned attestations: {}", report.orphaned_attestations);
+            display_pubkeys(
+                "validators with source exceeding target",
+                &report.source_exceeds_target,
+            );
+            display_pubkeys("validators with surround votes", &report.surround_votes);
+            display_pubkeys(
+                "validators with inconsistent watermarks",
+                &report.watermark_violations,
+            );
+            display_pubkeys(
+                "validators without records",
+                &report.validators_without_records,
+            );
+            eprintln!(
+                "- validators unknown to the validator client: {}",
+                unknown_validators.len()
+            );
+            for pubkey in unknown_validators {
+                eprintln!("    - {:?}", pubkey);
+            }
+
+            if report.is_consistent() {
+                eprintln!("Database is consistent.");
+                Ok(())
+            } else {
+                Err(
+                    "ERROR: the slashing protection database is inconsistent, see above.\n\
+                     IT IS NOT SAFE TO START VALIDATING until the cause has been investigated."
+                        .to_string(),
+                )
+            }
+        }
         ("", _) => Err("No subcommand provided, see --help for options".to_string()),
         (command, _) => Err(format!("No such subcommand `{}`", command)),
     }
 }
+
+fn open_existing_database(path: &Path) -> Result<SlashingDatabase, String> {
+    if !path.exists() {
+        return Err(format!(
+            "No slashing protection database exists at: {}",
+            path.display()
+        ));
+    }
+
+    SlashingDatabase::open(path)
+        .map_err(|e| format!("Unable to open database at {}: {:?}", path.display(), e))
+}
+
+async fn get_finalized_epoch(client: &BeaconNodeHttpClient) -> Result<Epoch, String> {
+    let finality_checkpoints = client
+        .get_beacon_states_finality_checkpoints(StateId::Head)
+        .await
+        .map_err(|e| format!("Failed to get finality checkpoints: {:?}", e))?
+        .ok_or("Beacon node returned no finality checkpoints")?
+        .data;
+
+    Ok(finality_checkpoints.finalized.epoch)
+}
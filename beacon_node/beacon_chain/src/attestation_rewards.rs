@@ -0,0 +1,320 @@
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::types::{IdealAttestationRewards, StandardAttestationRewards, TotalAttestationRewards};
+use eth2_serde_utils::quoted_u64::Quoted;
+use safe_arith::SafeArith;
+use state_processing::common::altair::BaseRewardPerIncrement;
+use state_processing::common::base::{get_base_reward, get_base_reward_from_effective_balance};
+use state_processing::per_epoch_processing::altair::{
+    process_inactivity_updates,
+    process_justification_and_finalization as process_justification_and_finalization_altair,
+    rewards_and_penalties::{get_flag_index_deltas, get_inactivity_penalty_deltas},
+    ParticipationCache,
+};
+use state_processing::per_epoch_processing::base::{
+    process_justification_and_finalization as process_justification_and_finalization_base,
+    rewards_and_penalties::{get_attestation_deltas, get_inclusion_delay_delta},
+    ValidatorStatuses,
+};
+use state_processing::per_epoch_processing::{Delta, EpochProcessingError};
+use std::collections::BTreeSet;
+use types::consts::altair::{
+    PARTICIPATION_FLAG_WEIGHTS, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX,
+    TIMELY_TARGET_FLAG_INDEX, WEIGHT_DENOMINATOR,
+};
+use types::{BeaconState, Epoch, RelativeEpoch};
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Compute the rewards and penalties applied to `validators` for their attestations during
+    /// `epoch`, along with the ideal rewards for a perfectly performing validator at each
+    /// effective balance.
+    ///
+    /// The `state` must be from the last slot of `epoch + 1`, prior to the epoch transition which
+    /// applies the rewards. An empty `validators` list computes rewards for every eligible
+    /// validator. Ineligible validators are omitted from the result.
+    pub fn compute_attestation_rewards(
+        &self,
+        epoch: Epoch,
+        mut state: BeaconState<T::EthSpec>,
+        validators: &[usize],
+    ) -> Result<StandardAttestationRewards, BeaconChainError> {
+        let state_epoch = state.current_epoch();
+        if state_epoch != epoch.safe_add(1)? {
+            return Err(BeaconChainError::AttestationRewardsEpochMismatch {
+                state_epoch,
+                rewards_epoch: epoch,
+            });
+        }
+
+        if let Some(&index) = validators
+            .iter()
+            .find(|&&index| index >= state.validators().len())
+        {
+            return Err(BeaconChainError::ValidatorIndexUnknown(index));
+        }
+
+        state.build_committee_cache(RelativeEpoch::Previous, &self.spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+
+        if let BeaconState::Base(_) = state {
+            self.compute_attestation_rewards_base(state, validators)
+        } else {
+            self.compute_attestation_rewards_altair(state, validators)
+        }
+    }
+
+    fn compute_attestation_rewards_altair(
+        &self,
+        mut state: BeaconState<T::EthSpec>,
+        validators: &[usize],
+    ) -> Result<StandardAttestationRewards, BeaconChainError> {
+        let spec = &self.spec;
+
+        // Bring the state up to the point in epoch processing where rewards are calculated.
+        let participation_cache = ParticipationCache::new(&state, spec)?;
+        process_justification_and_finalization_altair(&state, &participation_cache)?
+            .apply_changes_to_state(&mut state);
+        process_inactivity_updates(&mut state, &participation_cache, spec)?;
+
+        let previous_epoch = state.previous_epoch();
+        let total_active_balance = participation_cache.current_epoch_total_active_balance();
+        let num_validators = state.validators().len();
+
+        let mut flag_deltas = vec![];
+        for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
+            let mut deltas = vec![Delta::default(); num_validators];
+            get_flag_index_deltas(
+                &mut deltas,
+                &state,
+                flag_index,
+                total_active_balance,
+                &participation_cache,
+                spec,
+            )?;
+            flag_deltas.push(deltas);
+        }
+
+        let mut inactivity_deltas = vec![Delta::default(); num_validators];
+        get_inactivity_penalty_deltas(&mut inactivity_deltas, &state, &participation_cache, spec)?;
+
+        let flag_delta = |flag_index: usize, index: usize| -> Result<i64, BeaconChainError> {
+            flag_deltas
+                .get(flag_index)
+                .and_then(|deltas| deltas.get(index))
+                .ok_or_else(|| EpochProcessingError::DeltaOutOfBounds(index).into())
+                .map(delta_to_i64)
+        };
+
+        let total_rewards = selected_validators(
+            participation_cache
+                .eligible_validator_indices()
+                .iter()
+                .copied(),
+            validators,
+        )
+        .into_iter()
+        .map(|index| {
+            Ok(TotalAttestationRewards {
+                validator_index: index as u64,
+                head: flag_delta(TIMELY_HEAD_FLAG_INDEX, index)?,
+                target: flag_delta(TIMELY_TARGET_FLAG_INDEX, index)?,
+                source: flag_delta(TIMELY_SOURCE_FLAG_INDEX, index)?,
+                inclusion_delay: None,
+                inactivity: inactivity_deltas
+                    .get(index)
+                    .map(delta_to_i64)
+                    .ok_or(EpochProcessingError::DeltaOutOfBounds(index))?,
+            })
+        })
+        .collect::<Result<Vec<_>, BeaconChainError>>()?;
+
+        // Compute the ideal reward for each flag at every effective balance.
+        let base_reward_per_increment = BaseRewardPerIncrement::new(total_active_balance, spec)?;
+        let active_increments = total_active_balance.safe_div(spec.effective_balance_increment)?;
+        let in_inactivity_leak = state.is_in_inactivity_leak(previous_epoch, spec);
+
+        let mut ideal_flag_rewards = vec![];
+        for (flag_index, &weight) in PARTICIPATION_FLAG_WEIGHTS.iter().enumerate() {
+            let unslashed_participating_increments = participation_cache
+                .get_unslashed_participating_indices(flag_index, previous_epoch)?
+                .total_balance()
+                .map_err(EpochProcessingError::from)?
+                .safe_div(spec.effective_balance_increment)?;
+
+            let ideal_reward = |effective_balance: u64| -> Result<u64, BeaconChainError> {
+                if in_inactivity_leak {
+                    return Ok(0);
+                }
+                let base_reward = effective_balance
+                    .safe_div(spec.effective_balance_increment)?
+                    .safe_mul(base_reward_per_increment.as_u64())?;
+                Ok(base_reward
+                    .safe_mul(weight)?
+                    .safe_mul(unslashed_participating_increments)?
+                    .safe_div(active_increments.safe_mul(WEIGHT_DENOMINATOR)?)?)
+            };
+
+            ideal_flag_rewards.push(
+                self.ideal_effective_balances()
+                    .map(ideal_reward)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+
+        let ideal_rewards = self
+            .ideal_effective_balances()
+            .enumerate()
+            .map(|(i, effective_balance)| {
+                let ideal_flag_reward = |flag_index: usize| {
+                    ideal_flag_rewards
+                        .get(flag_index)
+                        .and_then(|rewards| rewards.get(i))
+                        .copied()
+                        .ok_or(EpochProcessingError::InvalidFlagIndex(flag_index))
+                };
+                Ok(IdealAttestationRewards {
+                    effective_balance,
+                    head: ideal_flag_reward(TIMELY_HEAD_FLAG_INDEX)?,
+                    target: ideal_flag_reward(TIMELY_TARGET_FLAG_INDEX)?,
+                    source: ideal_flag_reward(TIMELY_SOURCE_FLAG_INDEX)?,
+                    inclusion_delay: None,
+                })
+            })
+            .collect::<Result<Vec<_>, BeaconChainError>>()?;
+
+        Ok(StandardAttestationRewards {
+            ideal_rewards,
+            total_rewards,
+        })
+    }
+
+    fn compute_attestation_rewards_base(
+        &self,
+        mut state: BeaconState<T::EthSpec>,
+        validators: &[usize],
+    ) -> Result<StandardAttestationRewards, BeaconChainError> {
+        let spec = &self.spec;
+
+        // Bring the state up to the point in epoch processing where rewards are calculated.
+        let mut validator_statuses = ValidatorStatuses::new(&state, spec)?;
+        validator_statuses.process_attestations(&state)?;
+        process_justification_and_finalization_base(
+            &state,
+            &validator_statuses.total_balances,
+            spec,
+        )?
+        .apply_changes_to_state(&mut state);
+
+        let deltas = get_attestation_deltas(&state, &validator_statuses, spec)?;
+
+        let previous_epoch = state.previous_epoch();
+        let total_balances = &validator_statuses.total_balances;
+        let total_active_balance = total_balances.current_epoch();
+
+        let mut eligible_indices = vec![];
+        for index in 0..state.validators().len() {
+            if state.is_eligible_validator(previous_epoch, index)? {
+                eligible_indices.push(index);
+            }
+        }
+
+        let total_rewards = selected_validators(eligible_indices, validators)
+            .into_iter()
+            .map(|index| {
+                let delta = deltas
+                    .get(index)
+                    .ok_or(EpochProcessingError::DeltaOutOfBounds(index))?;
+                let status = validator_statuses
+                    .statuses
+                    .get(index)
+                    .ok_or(EpochProcessingError::ValidatorStatusesInconsistent)?;
+
+                // The inclusion delay delta in `deltas` also contains any rewards earned by this
+                // validator as a proposer, so compute the attester's portion separately.
+                let base_reward = get_base_reward(&state, index, total_active_balance, spec)?;
+                let (inclusion_delay_delta, _) =
+                    get_inclusion_delay_delta(status, base_reward, spec)?;
+
+                Ok(TotalAttestationRewards {
+                    validator_index: index as u64,
+                    head: delta_to_i64(&delta.head_delta),
+                    target: delta_to_i64(&delta.target_delta),
+                    source: delta_to_i64(&delta.source_delta),
+                    inclusion_delay: Some(Quoted {
+                        value: inclusion_delay_delta.rewards,
+                    }),
+                    inactivity: delta_to_i64(&delta.inactivity_penalty_delta),
+                })
+            })
+            .collect::<Result<Vec<_>, BeaconChainError>>()?;
+
+        let finality_delay = previous_epoch
+            .safe_sub(state.finalized_checkpoint().epoch)?
+            .as_u64();
+        let in_inactivity_leak = finality_delay > spec.min_epochs_to_inactivity_penalty;
+        let total_increments = total_active_balance.safe_div(spec.effective_balance_increment)?;
+
+        let ideal_rewards = self
+            .ideal_effective_balances()
+            .map(|effective_balance| {
+                let base_reward = get_base_reward_from_effective_balance(
+                    effective_balance,
+                    total_active_balance,
+                    spec,
+                )?;
+                let component_reward = |attesting_balance: u64| -> Result<u64, BeaconChainError> {
+                    if in_inactivity_leak {
+                        Ok(base_reward)
+                    } else {
+                        Ok(base_reward
+                            .safe_mul(
+                                attesting_balance.safe_div(spec.effective_balance_increment)?,
+                            )?
+                            .safe_div(total_increments)?)
+                    }
+                };
+                let proposer_reward = base_reward.safe_div(spec.proposer_reward_quotient)?;
+
+                Ok(IdealAttestationRewards {
+                    effective_balance,
+                    head: component_reward(total_balances.previous_epoch_head_attesters())?,
+                    target: component_reward(total_balances.previous_epoch_target_attesters())?,
+                    source: component_reward(total_balances.previous_epoch_attesters())?,
+                    inclusion_delay: Some(Quoted {
+                        value: base_reward.safe_sub(proposer_reward)?,
+                    }),
+                })
+            })
+            .collect::<Result<Vec<_>, BeaconChainError>>()?;
+
+        Ok(StandardAttestationRewards {
+            ideal_rewards,
+            total_rewards,
+        })
+    }
+
+    /// Every possible effective balance, from one increment up to the maximum.
+    fn ideal_effective_balances(&self) -> impl Iterator<Item = u64> {
+        let increment = self.spec.effective_balance_increment;
+        let num_increments = self.spec.max_effective_balance / increment;
+        (1..=num_increments).map(move |i| i * increment)
+    }
+}
+
+/// Filter `eligible` to the `requested` validators, or return all of `eligible` if none were
+/// requested. The result is sorted by validator index.
+fn selected_validators(
+    eligible: impl IntoIterator<Item = usize>,
+    requested: &[usize],
+) -> BTreeSet<usize> {
+    let eligible = eligible.into_iter();
+    if requested.is_empty() {
+        eligible.collect()
+    } else {
+        let requested = requested.iter().copied().collect::<BTreeSet<_>>();
+        eligible.filter(|index| requested.contains(index)).collect()
+    }
+}
+
+fn delta_to_i64(delta: &Delta) -> i64 {
+    delta.rewards as i64 - delta.penalties as i64
+}
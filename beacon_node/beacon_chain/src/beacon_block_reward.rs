@@ -0,0 +1,151 @@
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::types::StandardBlockReward;
+use safe_arith::SafeArith;
+use state_processing::per_block_processing::{
+    altair::sync_committee::compute_sync_aggregate_rewards, get_slashable_indices_modular,
+    process_operations::altair::process_attestation,
+};
+use state_processing::VerifySignatures;
+use std::collections::HashSet;
+use types::{BeaconBlockRef, BeaconState, BeaconStateError, ExecPayload, Hash256, RelativeEpoch};
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Compute the rewards paid to the proposer of `block`, split by the operation that earned
+    /// them.
+    ///
+    /// The `state` must be the pre-state of `block` advanced to `block.slot()`. It will be
+    /// mutated by the application of the block's attestations.
+    pub fn compute_beacon_block_reward<Payload: ExecPayload<T::EthSpec>>(
+        &self,
+        block: BeaconBlockRef<'_, T::EthSpec, Payload>,
+        block_root: Hash256,
+        state: &mut BeaconState<T::EthSpec>,
+    ) -> Result<StandardBlockReward, BeaconChainError> {
+        if block.slot() != state.slot() {
+            return Err(BeaconChainError::BlockRewardSlotError);
+        }
+
+        state.build_committee_cache(RelativeEpoch::Previous, &self.spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+
+        let proposer_index = block.proposer_index();
+
+        let sync_aggregate_reward =
+            self.compute_beacon_block_sync_aggregate_reward(block, state)?;
+
+        let (proposer_slashing_reward, attester_slashing_reward) =
+            self.compute_beacon_block_slashing_rewards(block, state)?;
+
+        let attestation_reward = if let BeaconState::Base(_) = state {
+            self.compute_block_reward(block, block_root, state, false)?
+                .attestation_rewards
+                .total
+        } else {
+            self.compute_beacon_block_attestation_reward_altair(block, state)?
+        };
+
+        let total = attestation_reward
+            .safe_add(sync_aggregate_reward)?
+            .safe_add(proposer_slashing_reward)?
+            .safe_add(attester_slashing_reward)?;
+
+        Ok(StandardBlockReward {
+            proposer_index,
+            total,
+            attestations: attestation_reward,
+            sync_aggregate: sync_aggregate_reward,
+            proposer_slashings: proposer_slashing_reward,
+            attester_slashings: attester_slashing_reward,
+        })
+    }
+
+    fn compute_beacon_block_sync_aggregate_reward<Payload: ExecPayload<T::EthSpec>>(
+        &self,
+        block: BeaconBlockRef<'_, T::EthSpec, Payload>,
+        state: &BeaconState<T::EthSpec>,
+    ) -> Result<u64, BeaconChainError> {
+        if let Ok(sync_aggregate) = block.body().sync_aggregate() {
+            let (_, proposer_reward_per_bit) = compute_sync_aggregate_rewards(state, &self.spec)
+                .map_err(|_| BeaconChainError::BlockRewardSyncError)?;
+            Ok(sync_aggregate.sync_committee_bits.num_set_bits() as u64 * proposer_reward_per_bit)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Return the `(proposer_slashing_reward, attester_slashing_reward)` for `block`.
+    ///
+    /// A validator that is slashed more than once in the same block only earns the proposer a
+    /// reward for the first slashing.
+    fn compute_beacon_block_slashing_rewards<Payload: ExecPayload<T::EthSpec>>(
+        &self,
+        block: BeaconBlockRef<'_, T::EthSpec, Payload>,
+        state: &BeaconState<T::EthSpec>,
+    ) -> Result<(u64, u64), BeaconChainError> {
+        let current_epoch = state.current_epoch();
+        let mut slashed_indices = HashSet::new();
+
+        let whistleblower_reward = |index: u64| -> Result<u64, BeaconChainError> {
+            Ok(state
+                .get_validator(index as usize)?
+                .effective_balance
+                .safe_div(self.spec.whistleblower_reward_quotient)?)
+        };
+
+        let mut proposer_slashing_reward = 0;
+        for proposer_slashing in block.body().proposer_slashings() {
+            let index = proposer_slashing.signed_header_1.message.proposer_index;
+            if slashed_indices.insert(index) {
+                proposer_slashing_reward.safe_add_assign(whistleblower_reward(index)?)?;
+            }
+        }
+
+        let mut attester_slashing_reward = 0;
+        for attester_slashing in block.body().attester_slashings() {
+            let slashable_indices =
+                get_slashable_indices_modular(state, attester_slashing, |index, validator| {
+                    validator.is_slashable_at(current_epoch) && !slashed_indices.contains(&index)
+                })
+                .map_err(|_| BeaconChainError::BlockRewardSlashingError)?;
+
+            for index in slashable_indices {
+                slashed_indices.insert(index);
+                attester_slashing_reward.safe_add_assign(whistleblower_reward(index)?)?;
+            }
+        }
+
+        Ok((proposer_slashing_reward, attester_slashing_reward))
+    }
+
+    /// Compute the attestation reward for an Altair (or later) block by applying its
+    /// attestations to `state` and observing the change in the proposer's balance.
+    fn compute_beacon_block_attestation_reward_altair<Payload: ExecPayload<T::EthSpec>>(
+        &self,
+        block: BeaconBlockRef<'_, T::EthSpec, Payload>,
+        state: &mut BeaconState<T::EthSpec>,
+    ) -> Result<u64, BeaconChainError> {
+        let proposer_index = block.proposer_index() as usize;
+        let proposer_balance = |state: &BeaconState<T::EthSpec>| {
+            state
+                .balances()
+                .get(proposer_index)
+                .copied()
+                .ok_or(BeaconStateError::UnknownValidator(proposer_index))
+        };
+
+        let initial_balance = proposer_balance(state)?;
+
+        for (att_index, attestation) in block.body().attestations().iter().enumerate() {
+            process_attestation(
+                state,
+                attestation,
+                att_index,
+                proposer_index as u64,
+                VerifySignatures::False,
+                &self.spec,
+            )?;
+        }
+
+        Ok(proposer_balance(state)?.safe_sub(initial_balance)?)
+    }
+}
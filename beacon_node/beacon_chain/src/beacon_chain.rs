@@ -17,7 +17,7 @@ use crate::early_attester_cache::EarlyAttesterCache;
 use crate::errors::{BeaconChainError as Error, BlockProductionError};
 use crate::eth1_chain::{Eth1Chain, Eth1ChainBackend};
 use crate::events::ServerSentEventHandler;
-use crate::execution_payload::{get_execution_payload, PreparePayloadHandle};
+use crate::execution_payload::{get_execution_payload, PreparePayloadHandle, PreparedPayload};
 use crate::fork_choice_signal::{ForkChoiceSignalRx, ForkChoiceSignalTx, ForkChoiceWaitResult};
 use crate::head_tracker::HeadTracker;
 use crate::historical_blocks::HistoricalBlockError;
@@ -37,10 +37,12 @@ use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use crate::persisted_fork_choice::PersistedForkChoice;
 use crate::pre_finalization_cache::PreFinalizationBlockCache;
+use crate::proposal_diagnostics_cache::ProposalDiagnosticsCache;
 use crate::proposer_prep_service::PAYLOAD_PREPARATION_LOOKAHEAD_FACTOR;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
-use crate::snapshot_cache::SnapshotCache;
+use crate::snapshot_cache::{BlockProductionPreState, SnapshotCache};
 use crate::sync_committee_verification::{
+    batch_verify_sync_committee_messages, batch_verify_sync_contributions,
     Error as SyncCommitteeError, VerifiedSyncCommitteeMessage, VerifiedSyncContribution,
 };
 use crate::timeout_rw_lock::TimeoutRwLock;
@@ -52,13 +54,17 @@ use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::BeaconForkChoiceStore;
 use crate::BeaconSnapshot;
 use crate::{metrics, BeaconChainError};
-use eth2::types::{EventKind, SseBlock, SyncDuty};
+use eth2::lighthouse::{ProposalDiagnostics, ProposalPayloadDiagnostics};
+use eth2::types::{
+    EventKind, ForkVersionedResponse, SseBlock, SseExtendedPayloadAttributes, SsePayloadAttributes,
+    SyncDuty,
+};
 use execution_layer::{
     BuilderParams, ChainHealth, ExecutionLayer, FailedCondition, PayloadAttributes, PayloadStatus,
 };
 use fork_choice::{
     AttestationFromBlock, ExecutionStatus, ForkChoice, ForkchoiceUpdateParameters,
-    InvalidationOperation, PayloadVerificationStatus,
+    InvalidationOperation, PayloadVerificationStatus, ProposerHeadError,
 };
 use futures::channel::mpsc::Sender;
 use itertools::process_results;
@@ -83,8 +89,10 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::prelude::*;
 use std::marker::PhantomData;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use store::backup::BackupManifest;
 use store::iter::{BlockRootsIterator, ParentRootBlockIterator, StateRootsIterator};
 use store::{
     DatabaseBlock, Error as DBError, HotColdDB, KeyValueStore, KeyValueStoreOp, StoreItem, StoreOp,
@@ -118,6 +126,7 @@ pub const BEACON_CHAIN_DB_KEY: Hash256 = Hash256::zero();
 pub const OP_POOL_DB_KEY: Hash256 = Hash256::zero();
 pub const ETH1_CACHE_DB_KEY: Hash256 = Hash256::zero();
 pub const FORK_CHOICE_DB_KEY: Hash256 = Hash256::zero();
+pub const GOSSIP_CACHES_DB_KEY: Hash256 = Hash256::zero();
 
 /// Defines how old a block can be before it's no longer a candidate for the early attester cache.
 const EARLY_ATTESTER_CACHE_HISTORIC_SLOTS: u64 = 4;
@@ -251,6 +260,36 @@ struct PartialBeaconBlock<E: EthSpec, Payload> {
     voluntary_exits: Vec<SignedVoluntaryExit>,
     sync_aggregate: Option<SyncAggregate<E>>,
     prepare_payload_handle: Option<PreparePayloadHandle<Payload>>,
+    timings: ProductionTimings,
+}
+
+/// Used internally to record the duration of each phase of block production.
+struct ProductionTimings {
+    start: Instant,
+    request_delay: Option<Duration>,
+    state_load_time: Option<Duration>,
+    state_advance_time: Duration,
+    op_pool_packing_time: Duration,
+    payload_wait_time: Duration,
+    payload: Option<ProposalPayloadDiagnostics>,
+}
+
+impl ProductionTimings {
+    fn new<S: SlotClock>(slot: Slot, slot_clock: &S) -> Self {
+        let request_delay = slot_clock
+            .now_duration()
+            .zip(slot_clock.start_of(slot))
+            .and_then(|(now, slot_start)| now.checked_sub(slot_start));
+        Self {
+            start: Instant::now(),
+            request_delay,
+            state_load_time: None,
+            state_advance_time: Duration::default(),
+            op_pool_packing_time: Duration::default(),
+            payload_wait_time: Duration::default(),
+            payload: None,
+        }
+    }
 }
 
 pub type BeaconForkChoice<T> = ForkChoice<
@@ -365,6 +404,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub early_attester_cache: EarlyAttesterCache<T::EthSpec>,
     /// A cache used to keep track of various block timings.
     pub block_times_cache: Arc<RwLock<BlockTimesCache>>,
+    /// A cache used to keep track of the timings of blocks produced by this node.
+    pub proposal_diagnostics_cache: RwLock<ProposalDiagnosticsCache>,
     /// A cache used to track pre-finalization block roots for quick rejection.
     pub pre_finalization_block_cache: PreFinalizationBlockCache,
     /// Sender given to tasks, so that if they encounter a state in which execution cannot
@@ -476,6 +517,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Persist the head, fork choice, op pool, eth1 cache and gossip caches, then write a
+    /// consistent backup of the database to `backup_dir`.
+    pub fn backup_database(&self, backup_dir: &Path) -> Result<BackupManifest, Error> {
+        self.persist_head_and_fork_choice()?;
+        self.persist_op_pool()?;
+        self.persist_eth1_cache()?;
+        self.persist_gossip_caches()?;
+
+        Ok(self.store.backup(backup_dir)?)
+    }
+
     /// Returns the slot _right now_ according to `self.slot_clock`. Returns `Err` if the slot is
     /// unavailable.
     ///
@@ -1726,6 +1778,30 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Performs the same validation as `Self::verify_sync_committee_message_for_gossip`, but for
+    /// multiple messages using batch BLS verification. Batch verification can provide
+    /// significant CPU-time savings compared to individual verification.
+    pub fn batch_verify_sync_committee_messages_for_gossip<I>(
+        &self,
+        messages: I,
+    ) -> Result<Vec<Result<VerifiedSyncCommitteeMessage, SyncCommitteeError>>, SyncCommitteeError>
+    where
+        I: Iterator<Item = (SyncCommitteeMessage, SyncSubnetId)> + ExactSizeIterator,
+    {
+        metrics::inc_counter_by(
+            &metrics::SYNC_MESSAGE_PROCESSING_REQUESTS,
+            messages.len() as u64,
+        );
+        let _timer = metrics::start_timer(&metrics::SYNC_MESSAGE_GOSSIP_VERIFICATION_TIMES);
+
+        let results = batch_verify_sync_committee_messages(messages, self)?;
+        metrics::inc_counter_by(
+            &metrics::SYNC_MESSAGE_PROCESSING_SUCCESSES,
+            results.iter().filter(|result| result.is_ok()).count() as u64,
+        );
+        Ok(results)
+    }
+
     /// Accepts some `SyncCommitteeMessage` from the network and attempts to verify it, returning `Ok(_)` if
     /// it is valid to be (re)broadcast on the gossip network.
     pub fn verify_sync_committee_message_for_gossip(
@@ -1742,6 +1818,29 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Performs the same validation as `Self::verify_sync_contribution_for_gossip`, but for
+    /// multiple contributions using batch BLS verification. Batch verification can provide
+    /// significant CPU-time savings compared to individual verification.
+    pub fn batch_verify_sync_contributions_for_gossip<I>(
+        &self,
+        sync_contributions: I,
+    ) -> Result<Vec<Result<VerifiedSyncContribution<T>, SyncCommitteeError>>, SyncCommitteeError>
+    where
+        I: Iterator<Item = SignedContributionAndProof<T::EthSpec>> + ExactSizeIterator,
+    {
+        metrics::inc_counter_by(
+            &metrics::SYNC_CONTRIBUTION_PROCESSING_REQUESTS,
+            sync_contributions.len() as u64,
+        );
+        let _timer = metrics::start_timer(&metrics::SYNC_CONTRIBUTION_GOSSIP_VERIFICATION_TIMES);
+
+        let results = batch_verify_sync_contributions(sync_contributions, self)?;
+        for verified in results.iter().flatten() {
+            self.register_verified_sync_contribution(verified);
+        }
+        Ok(results)
+    }
+
     /// Accepts some `SignedContributionAndProof` from the network and attempts to verify it,
     /// returning `Ok(_)` if it is valid to be (re)broadcast on the gossip network.
     pub fn verify_sync_contribution_for_gossip(
@@ -1751,18 +1850,23 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         metrics::inc_counter(&metrics::SYNC_CONTRIBUTION_PROCESSING_REQUESTS);
         let _timer = metrics::start_timer(&metrics::SYNC_CONTRIBUTION_GOSSIP_VERIFICATION_TIMES);
         VerifiedSyncContribution::verify(sync_contribution, self).map(|v| {
-            if let Some(event_handler) = self.event_handler.as_ref() {
-                if event_handler.has_contribution_subscribers() {
-                    event_handler.register(EventKind::ContributionAndProof(Box::new(
-                        v.aggregate().clone(),
-                    )));
-                }
-            }
-            metrics::inc_counter(&metrics::SYNC_CONTRIBUTION_PROCESSING_SUCCESSES);
+            self.register_verified_sync_contribution(&v);
             v
         })
     }
 
+    /// Emits an event and updates metrics for a contribution which has passed gossip verification.
+    fn register_verified_sync_contribution(&self, verified: &VerifiedSyncContribution<T>) {
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_contribution_subscribers() {
+                event_handler.register(EventKind::ContributionAndProof(Box::new(
+                    verified.aggregate().clone(),
+                )));
+            }
+        }
+        metrics::inc_counter(&metrics::SYNC_CONTRIBUTION_PROCESSING_SUCCESSES);
+    }
+
     /// Accepts some attestation-type object and attempts to verify it in the context of fork
     /// choice. If it is valid it is applied to `self.fork_choice`.
     ///
@@ -2065,6 +2169,33 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             })?)
     }
 
+    /// Performs the same verification as `Self::verify_voluntary_exit_for_gossip` for each of
+    /// `exits`, verifying their signatures in a single batch. If the batch is invalid, each
+    /// signature is verified individually.
+    pub fn batch_verify_voluntary_exits_for_gossip(
+        &self,
+        exits: Vec<SignedVoluntaryExit>,
+    ) -> Result<Vec<Result<ObservationOutcome<SignedVoluntaryExit>, Error>>, Error> {
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self
+            .observed_voluntary_exits
+            .lock()
+            .batch_verify_and_observe(exits, &wall_clock_state, &self.spec)
+            .into_iter()
+            .map(|result| {
+                let outcome = result?;
+                if let Some(event_handler) = self.event_handler.as_ref() {
+                    if event_handler.has_exit_subscribers() {
+                        if let ObservationOutcome::New(exit) = outcome.clone() {
+                            event_handler.register(EventKind::VoluntaryExit(exit.into_inner()));
+                        }
+                    }
+                }
+                Ok(outcome)
+            })
+            .collect())
+    }
+
     /// Accept a pre-verified exit and queue it for inclusion in an appropriate block.
     pub fn import_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit>) {
         if self.eth1_chain.is_some() {
@@ -2072,17 +2203,68 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Register a slashing event if `outcome` is a newly observed slashing and anyone is
+    /// subscribed to it.
+    fn emit_slashing_event<S: Clone>(
+        &self,
+        outcome: &ObservationOutcome<S>,
+        has_subscribers: fn(&ServerSentEventHandler<T::EthSpec>) -> bool,
+        to_event: fn(Box<S>) -> EventKind<T::EthSpec>,
+    ) {
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if has_subscribers(event_handler) {
+                if let ObservationOutcome::New(slashing) = outcome {
+                    event_handler.register(to_event(Box::new(slashing.as_inner().clone())));
+                }
+            }
+        }
+    }
+
     /// Verify a proposer slashing before allowing it to propagate on the gossip network.
     pub fn verify_proposer_slashing_for_gossip(
         &self,
         proposer_slashing: ProposerSlashing,
     ) -> Result<ObservationOutcome<ProposerSlashing>, Error> {
         let wall_clock_state = self.wall_clock_state()?;
-        Ok(self.observed_proposer_slashings.lock().verify_and_observe(
-            proposer_slashing,
-            &wall_clock_state,
-            &self.spec,
-        )?)
+        Ok(self
+            .observed_proposer_slashings
+            .lock()
+            .verify_and_observe(proposer_slashing, &wall_clock_state, &self.spec)
+            .map(|outcome| {
+                // this method is called for both API and gossip slashings, so this covers all
+                // proposer slashing events
+                self.emit_slashing_event(
+                    &outcome,
+                    ServerSentEventHandler::has_proposer_slashing_subscribers,
+                    EventKind::ProposerSlashing,
+                );
+                outcome
+            })?)
+    }
+
+    /// Performs the same verification as `Self::verify_proposer_slashing_for_gossip` for each of
+    /// `proposer_slashings`, verifying their signatures in a single batch. If the batch is
+    /// invalid, each signature is verified individually.
+    pub fn batch_verify_proposer_slashings_for_gossip(
+        &self,
+        proposer_slashings: Vec<ProposerSlashing>,
+    ) -> Result<Vec<Result<ObservationOutcome<ProposerSlashing>, Error>>, Error> {
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self
+            .observed_proposer_slashings
+            .lock()
+            .batch_verify_and_observe(proposer_slashings, &wall_clock_state, &self.spec)
+            .into_iter()
+            .map(|result| {
+                let outcome = result?;
+                self.emit_slashing_event(
+                    &outcome,
+                    ServerSentEventHandler::has_proposer_slashing_subscribers,
+                    EventKind::ProposerSlashing,
+                );
+                Ok(outcome)
+            })
+            .collect())
     }
 
     /// Accept some proposer slashing and queue it for inclusion in an appropriate block.
@@ -2098,11 +2280,45 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         attester_slashing: AttesterSlashing<T::EthSpec>,
     ) -> Result<ObservationOutcome<AttesterSlashing<T::EthSpec>>, Error> {
         let wall_clock_state = self.wall_clock_state()?;
-        Ok(self.observed_attester_slashings.lock().verify_and_observe(
-            attester_slashing,
-            &wall_clock_state,
-            &self.spec,
-        )?)
+        Ok(self
+            .observed_attester_slashings
+            .lock()
+            .verify_and_observe(attester_slashing, &wall_clock_state, &self.spec)
+            .map(|outcome| {
+                // this method is called for both API and gossip slashings, so this covers all
+                // attester slashing events
+                self.emit_slashing_event(
+                    &outcome,
+                    ServerSentEventHandler::has_attester_slashing_subscribers,
+                    EventKind::AttesterSlashing,
+                );
+                outcome
+            })?)
+    }
+
+    /// Performs the same verification as `Self::verify_attester_slashing_for_gossip` for each of
+    /// `attester_slashings`, verifying their signatures in a single batch. If the batch is
+    /// invalid, each signature is verified individually.
+    pub fn batch_verify_attester_slashings_for_gossip(
+        &self,
+        attester_slashings: Vec<AttesterSlashing<T::EthSpec>>,
+    ) -> Result<Vec<Result<ObservationOutcome<AttesterSlashing<T::EthSpec>>, Error>>, Error> {
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self
+            .observed_attester_slashings
+            .lock()
+            .batch_verify_and_observe(attester_slashings, &wall_clock_state, &self.spec)
+            .into_iter()
+            .map(|result| {
+                let outcome = result?;
+                self.emit_slashing_event(
+                    &outcome,
+                    ServerSentEventHandler::has_attester_slashing_subscribers,
+                    EventKind::AttesterSlashing,
+                );
+                Ok(outcome)
+            })
+            .collect())
     }
 
     /// Accept a verified attester slashing and:
@@ -3112,6 +3328,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         validator_graffiti: Option<Graffiti>,
         verification: ProduceBlockVerification,
     ) -> Result<BeaconBlockAndState<T::EthSpec, Payload>, BlockProductionError> {
+        let mut timings = ProductionTimings::new(slot, &self.slot_clock);
+
         // Part 1/2 (blocking)
         //
         // Load the parent state from disk.
@@ -3125,17 +3343,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .ok_or(BlockProductionError::ShuttingDown)?
             .await
             .map_err(BlockProductionError::TokioJoin)??;
+        timings.state_load_time = Some(timings.start.elapsed());
 
         // Part 2/2 (async, with some blocking components)
         //
         // Produce the block upon the state
-        self.produce_block_on_state::<Payload>(
+        self.produce_block_on_state_with_timings::<Payload>(
             state,
             state_root_opt,
             slot,
             randao_reveal,
             validator_graffiti,
             verification,
+            timings,
         )
         .await
     }
@@ -3166,8 +3386,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             (head.head_slot(), head.head_block_root())
         };
         let (state, state_root_opt) = if head_slot < slot {
+            // Attempt a proposer re-org if configured and the conditions are right.
+            if let Some(re_org_state) = self.get_state_for_re_org(slot, head_block_root) {
+                (re_org_state.pre_state, re_org_state.state_root)
+            }
             // Normal case: proposing a block atop the current head. Use the snapshot cache.
-            if let Some(pre_state) = self
+            else if let Some(pre_state) = self
                 .snapshot_cache
                 .try_read_for(BLOCK_PROCESSING_CACHE_LOCK_TIMEOUT)
                 .and_then(|snapshot_cache| {
@@ -3207,6 +3431,181 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok((state, state_root_opt))
     }
 
+    /// Returns the state upon which to build a block at `slot` which re-orgs `canonical_head`, if
+    /// proposer re-orgs are enabled and the head is a late, weak block which may be re-orged.
+    ///
+    /// The re-org is only attempted if the state of the head's parent is in the snapshot cache.
+    fn get_state_for_re_org(
+        &self,
+        slot: Slot,
+        canonical_head: Hash256,
+    ) -> Option<BlockProductionPreState<T::EthSpec>> {
+        let re_org_threshold = self.config.re_org_threshold?;
+
+        if self.spec.proposer_score_boost.is_none() {
+            warn!(
+                self.log,
+                "Ignoring proposer re-org configuration";
+                "reason" => "this network does not have proposer boost enabled"
+            );
+            return None;
+        }
+
+        let head_slot = self
+            .canonical_head
+            .fork_choice_read_lock()
+            .get_block(&canonical_head)?
+            .slot;
+        if !self.block_observed_after_attestation_deadline(canonical_head, head_slot) {
+            debug!(
+                self.log,
+                "Not attempting re-org";
+                "reason" => "head not late"
+            );
+            return None;
+        }
+
+        let proposer_head_timer =
+            metrics::start_timer(&metrics::BLOCK_PRODUCTION_GET_PROPOSER_HEAD_TIMES);
+        let proposer_head = self
+            .canonical_head
+            .fork_choice_read_lock()
+            .get_proposer_head(
+                slot,
+                canonical_head,
+                re_org_threshold,
+                self.config.re_org_max_epochs_since_finalization,
+            )
+            .map_err(|e| match e {
+                ProposerHeadError::DoNotReOrg(reason) => debug!(
+                    self.log,
+                    "Not attempting re-org";
+                    "reason" => %reason,
+                ),
+                ProposerHeadError::Error(e) => warn!(
+                    self.log,
+                    "Not attempting re-org";
+                    "error" => ?e,
+                ),
+            })
+            .ok()?;
+        drop(proposer_head_timer);
+
+        let re_org_parent_block = proposer_head.parent_node.root;
+        let pre_state = self
+            .snapshot_cache
+            .try_read_for(BLOCK_PROCESSING_CACHE_LOCK_TIMEOUT)
+            .and_then(|snapshot_cache| {
+                snapshot_cache.get_state_for_block_production(re_org_parent_block)
+            })
+            .or_else(|| {
+                debug!(
+                    self.log,
+                    "Not attempting re-org";
+                    "reason" => "missed snapshot cache",
+                    "parent_block" => ?re_org_parent_block,
+                );
+                None
+            })?;
+
+        metrics::inc_counter(&metrics::BLOCK_PRODUCTION_RE_ORG_ATTEMPTS);
+        info!(
+            self.log,
+            "Attempting re-org due to weak head";
+            "weak_head" => ?canonical_head,
+            "parent" => ?re_org_parent_block,
+            "head_weight" => proposer_head.head_weight,
+            "threshold_weight" => proposer_head.re_org_weight_threshold,
+            "slot" => slot,
+        );
+
+        Some(pre_state)
+    }
+
+    /// Returns `true` if the execution payload for the proposal at `current_slot + 1` should be
+    /// prepared upon the parent of `head_block_root`, in anticipation of a proposer re-org.
+    ///
+    /// This mirrors the checks of `get_state_for_re_org`, except that the head's weight is read
+    /// before the end of its slot rather than at the start of the proposal slot.
+    fn should_prepare_re_org(
+        &self,
+        head_block_root: Hash256,
+        head_slot: Slot,
+        current_slot: Slot,
+    ) -> bool {
+        let re_org_threshold = match self.config.re_org_threshold {
+            Some(threshold) if self.spec.proposer_score_boost.is_some() => threshold,
+            _ => return false,
+        };
+
+        // Only the block from the current slot may be re-orged by the next proposer.
+        if head_slot != current_slot
+            || !self.block_observed_after_attestation_deadline(head_block_root, head_slot)
+        {
+            return false;
+        }
+
+        let info = match self
+            .canonical_head
+            .fork_choice_read_lock()
+            .get_preliminary_proposer_head(
+                head_block_root,
+                re_org_threshold,
+                self.config.re_org_max_epochs_since_finalization,
+            ) {
+            Ok(info) => info,
+            Err(ProposerHeadError::DoNotReOrg(reason)) => {
+                debug!(
+                    self.log,
+                    "Not preparing re-org";
+                    "reason" => %reason,
+                );
+                return false;
+            }
+            Err(ProposerHeadError::Error(e)) => {
+                warn!(
+                    self.log,
+                    "Not preparing re-org";
+                    "error" => ?e,
+                );
+                return false;
+            }
+        };
+
+        if info.head_weight >= info.re_org_weight_threshold {
+            debug!(
+                self.log,
+                "Not preparing re-org";
+                "reason" => "head not weak",
+                "head_weight" => info.head_weight,
+                "threshold_weight" => info.re_org_weight_threshold,
+            );
+            return false;
+        }
+
+        true
+    }
+
+    /// Returns `true` if the block with `block_root` at `block_slot` was first observed after the
+    /// attestation deadline of its slot.
+    pub fn block_observed_after_attestation_deadline(
+        &self,
+        block_root: Hash256,
+        block_slot: Slot,
+    ) -> bool {
+        let slot_start = match self.slot_clock.start_of(block_slot) {
+            Some(slot_start) => slot_start,
+            None => return false,
+        };
+        let block_delays = self
+            .block_times_cache
+            .read()
+            .get_block_delays(block_root, slot_start);
+        block_delays.observed.map_or(false, |delay| {
+            delay > self.slot_clock.unagg_attestation_production_delay()
+        })
+    }
+
     /// Produce a block for some `slot` upon the given `state`.
     ///
     /// Typically the `self.produce_block()` function should be used, instead of calling this
@@ -3227,6 +3626,32 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         randao_reveal: Signature,
         validator_graffiti: Option<Graffiti>,
         verification: ProduceBlockVerification,
+    ) -> Result<BeaconBlockAndState<T::EthSpec, Payload>, BlockProductionError> {
+        let timings = ProductionTimings::new(produce_at_slot, &self.slot_clock);
+        self.produce_block_on_state_with_timings(
+            state,
+            state_root_opt,
+            produce_at_slot,
+            randao_reveal,
+            validator_graffiti,
+            verification,
+            timings,
+        )
+        .await
+    }
+
+    /// Same as `produce_block_on_state`, but continues recording the phases of block production
+    /// in `timings`.
+    #[allow(clippy::too_many_arguments)]
+    async fn produce_block_on_state_with_timings<Payload: ExecPayload<T::EthSpec>>(
+        self: &Arc<Self>,
+        state: BeaconState<T::EthSpec>,
+        state_root_opt: Option<Hash256>,
+        produce_at_slot: Slot,
+        randao_reveal: Signature,
+        validator_graffiti: Option<Graffiti>,
+        verification: ProduceBlockVerification,
+        timings: ProductionTimings,
     ) -> Result<BeaconBlockAndState<T::EthSpec, Payload>, BlockProductionError> {
         // Part 1/3 (blocking)
         //
@@ -3242,6 +3667,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         produce_at_slot,
                         randao_reveal,
                         validator_graffiti,
+                        timings,
                     )
                 },
                 "produce_partial_beacon_block",
@@ -3255,11 +3681,25 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Wait for the execution layer to return an execution payload (if one is required).
         let prepare_payload_handle = partial_beacon_block.prepare_payload_handle.take();
         let execution_payload = if let Some(prepare_payload_handle) = prepare_payload_handle {
-            let execution_payload = prepare_payload_handle
+            let payload_wait_start = Instant::now();
+            let PreparedPayload {
+                payload,
+                metadata,
+                fetch_time,
+            } = prepare_payload_handle
                 .await
                 .map_err(BlockProductionError::TokioJoin)?
                 .ok_or(BlockProductionError::ShuttingDown)??;
-            Some(execution_payload)
+
+            let timings = &mut partial_beacon_block.timings;
+            timings.payload_wait_time = payload_wait_start.elapsed();
+            timings.payload = metadata.map(|metadata| ProposalPayloadDiagnostics {
+                source: metadata.source,
+                reason: metadata.reason,
+                builder_bid_value: metadata.builder_bid_value,
+                fetch_time_ms: fetch_time.as_millis() as u64,
+            });
+            Some(payload)
         } else {
             None
         };
@@ -3291,6 +3731,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         produce_at_slot: Slot,
         randao_reveal: Signature,
         validator_graffiti: Option<Graffiti>,
+        mut timings: ProductionTimings,
     ) -> Result<PartialBeaconBlock<T::EthSpec, Payload>, BlockProductionError> {
         let eth1_chain = self
             .eth1_chain
@@ -3306,11 +3747,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
 
         let slot_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_SLOT_PROCESS_TIMES);
+        let state_advance_start = Instant::now();
 
         // Ensure the state has performed a complete transition into the required slot.
         complete_state_advance(&mut state, state_root_opt, produce_at_slot, &self.spec)?;
 
+        timings.state_advance_time = state_advance_start.elapsed();
         drop(slot_timer);
+        let op_pool_packing_start = Instant::now();
 
         state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
 
@@ -3428,6 +3872,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             Some(sync_aggregate)
         };
 
+        timings.op_pool_packing_time = op_pool_packing_start.elapsed();
+
         Ok(PartialBeaconBlock {
             state,
             slot,
@@ -3443,6 +3889,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             voluntary_exits,
             sync_aggregate,
             prepare_payload_handle,
+            timings,
         })
     }
 
@@ -3470,7 +3917,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             // this function. We can assume that the handle has already been consumed in order to
             // produce said `execution_payload`.
             prepare_payload_handle: _,
+            timings,
         } = partial_beacon_block;
+        let block_completion_start = Instant::now();
 
         let inner_block = match &state {
             BeaconState::Base(_) => BeaconBlock::Base(BeaconBlockBase {
@@ -3571,9 +4020,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let (mut block, _) = block.deconstruct();
         *block.state_root_mut() = state_root;
+        let block_completion_time = block_completion_start.elapsed();
 
         metrics::inc_counter(&metrics::BLOCK_PRODUCTION_SUCCESSES);
 
+        self.register_proposal_diagnostics(ProposalDiagnostics {
+            slot: block.slot(),
+            proposer_index: block.proposer_index(),
+            parent_root: block.parent_root(),
+            block_root: block.canonical_root(),
+            blinded: matches!(Payload::block_type(), BlockType::Blinded),
+            request_delay_ms: timings.request_delay.map(|delay| delay.as_millis() as u64),
+            state_load_time_ms: timings.state_load_time.map(|time| time.as_millis() as u64),
+            state_advance_time_ms: timings.state_advance_time.as_millis() as u64,
+            op_pool_packing_time_ms: timings.op_pool_packing_time.as_millis() as u64,
+            payload_wait_time_ms: timings.payload_wait_time.as_millis() as u64,
+            block_completion_time_ms: block_completion_time.as_millis() as u64,
+            total_time_ms: timings.start.elapsed().as_millis() as u64,
+            payload: timings.payload,
+            signing_round_trip_ms: None,
+        });
+
         trace!(
             self.log,
             "Produced beacon block";
@@ -3585,6 +4052,68 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok((block, state))
     }
 
+    /// Records the diagnostics of a block produced by this node and emits them as a server-sent
+    /// event.
+    fn register_proposal_diagnostics(&self, diagnostics: ProposalDiagnostics) {
+        debug!(
+            self.log,
+            "Block production timings";
+            "slot" => diagnostics.slot,
+            "total_time_ms" => diagnostics.total_time_ms,
+            "state_load_time_ms" => ?diagnostics.state_load_time_ms,
+            "state_advance_time_ms" => diagnostics.state_advance_time_ms,
+            "op_pool_packing_time_ms" => diagnostics.op_pool_packing_time_ms,
+            "payload_wait_time_ms" => diagnostics.payload_wait_time_ms,
+            "block_completion_time_ms" => diagnostics.block_completion_time_ms,
+            "payload_source" => ?diagnostics.payload.as_ref().map(|payload| payload.source),
+        );
+
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_proposal_diagnostics_subscribers() {
+                event_handler.register(EventKind::ProposalDiagnostics(Box::new(
+                    diagnostics.clone(),
+                )));
+            }
+        }
+
+        self.proposal_diagnostics_cache
+            .write()
+            .insert(diagnostics, self.slot_clock.now_duration());
+    }
+
+    /// Records that `block` was published via the HTTP API at `seen_timestamp`.
+    ///
+    /// If the block was produced by this node, the signing round-trip is recorded in its
+    /// diagnostics, which are emitted as a server-sent event.
+    pub fn register_published_block<Payload: ExecPayload<T::EthSpec>>(
+        &self,
+        block: &SignedBeaconBlock<T::EthSpec, Payload>,
+        seen_timestamp: Duration,
+    ) {
+        // Avoid computing the block root for blocks that we did not produce.
+        if !self
+            .proposal_diagnostics_cache
+            .read()
+            .contains_slot(block.slot())
+        {
+            return;
+        }
+
+        let diagnostics = self.proposal_diagnostics_cache.write().set_time_published(
+            block.slot(),
+            block.canonical_root(),
+            seen_timestamp,
+        );
+
+        if let Some(diagnostics) = diagnostics {
+            if let Some(event_handler) = self.event_handler.as_ref() {
+                if event_handler.has_proposal_diagnostics_subscribers() {
+                    event_handler.register(EventKind::ProposalDiagnostics(Box::new(diagnostics)));
+                }
+            }
+        }
+    }
+
     /// This method must be called whenever an execution engine indicates that a payload is
     /// invalid.
     ///
@@ -3718,9 +4247,18 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .clone()
             .ok_or(Error::ExecutionLayerMissing)?;
 
+        // Payload attributes are computed for every proposer if they are subscribed to via the
+        // event stream, e.g. by external builders.
+        let payload_attributes_subscribers =
+            self.event_handler.as_ref().map_or(false, |event_handler| {
+                event_handler.has_payload_attributes_subscribers()
+            });
+
         // Nothing to do if there are no proposers registered with the EL, exit early to avoid
         // wasting cycles.
-        if !execution_layer.has_any_proposer_preparation_data().await {
+        if !payload_attributes_subscribers
+            && !execution_layer.has_any_proposer_preparation_data().await
+        {
             return Ok(());
         }
 
@@ -3730,8 +4268,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Use a blocking task since blocking the core executor on the canonical head read lock can
         // block the core tokio executor.
         let chain = self.clone();
-        let (head_slot, head_root, head_decision_root, head_random, forkchoice_update_params) =
-            self.spawn_blocking_handle(
+        let (
+            head_slot,
+            head_root,
+            head_decision_root,
+            head_random,
+            head_payload_block_number,
+            head_parent,
+            forkchoice_update_params,
+        ) = self
+            .spawn_blocking_handle(
                 move || {
                     let cached_head = chain.canonical_head.cached_head();
                     let head_block_root = cached_head.head_block_root();
@@ -3739,11 +4285,28 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         .snapshot
                         .beacon_state
                         .proposer_shuffling_decision_root(head_block_root)?;
+                    let head_block = cached_head.snapshot.beacon_block.message();
+                    let head_payload = head_block.execution_payload().ok();
+                    let head_payload_block_number =
+                        head_payload.map_or(0, |payload| payload.block_number());
+                    // The root, execution block hash and RANDAO mix of the head's parent, which
+                    // are required to prepare a payload which re-orgs the head.
+                    let head_parent = head_payload
+                        .filter(|payload| payload.block_hash() != ExecutionBlockHash::zero())
+                        .map(|payload| {
+                            (
+                                head_block.parent_root(),
+                                payload.parent_hash(),
+                                payload.prev_randao(),
+                            )
+                        });
                     Ok::<_, Error>((
                         cached_head.head_slot(),
                         head_block_root,
                         decision_root,
                         cached_head.head_random()?,
+                        head_payload_block_number,
+                        head_parent,
                         cached_head.forkchoice_update_parameters(),
                     ))
                 },
@@ -3835,37 +4398,108 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         };
 
         // If the execution layer doesn't have any proposer data for this validator then we assume
-        // it's not connected to this BN and no action is required.
-        if !execution_layer
+        // it's not connected to this BN and no action is required, unless the payload attributes
+        // are subscribed to.
+        let is_local_proposer = execution_layer
             .has_proposer_preparation_data(proposer as u64)
-            .await
-        {
+            .await;
+        if !is_local_proposer && !payload_attributes_subscribers {
             return Ok(());
         }
 
+        // If the head is a late, weak block which the proposer is expected to re-org, prepare a
+        // payload upon its parent instead.
+        let chain = self.clone();
+        let prepare_re_org = head_parent.is_some()
+            && self
+                .spawn_blocking_handle(
+                    move || chain.should_prepare_re_org(head_root, head_slot, current_slot),
+                    "prepare_beacon_proposer_re_org",
+                )
+                .await?;
+        let (proposal_parent_root, prev_randao, parent_block_number, forkchoice_update_params) =
+            match head_parent {
+                Some((parent_root, parent_hash, parent_random)) if prepare_re_org => {
+                    info!(
+                        self.log,
+                        "Preparing payload to re-org weak head";
+                        "weak_head" => ?head_root,
+                        "parent" => ?parent_root,
+                        "prepare_slot" => prepare_slot,
+                    );
+                    (
+                        parent_root,
+                        parent_random,
+                        head_payload_block_number.saturating_sub(1),
+                        ForkchoiceUpdateParameters {
+                            head_root: parent_root,
+                            head_hash: Some(parent_hash),
+                            ..forkchoice_update_params
+                        },
+                    )
+                }
+                _ => (
+                    head_root,
+                    head_random,
+                    head_payload_block_number,
+                    forkchoice_update_params,
+                ),
+            };
+
         let payload_attributes = PayloadAttributes {
             timestamp: self
                 .slot_clock
                 .start_of(prepare_slot)
                 .ok_or(Error::InvalidSlot(prepare_slot))?
                 .as_secs(),
-            prev_randao: head_random,
+            prev_randao,
             suggested_fee_recipient: execution_layer
                 .get_suggested_fee_recipient(proposer as u64)
                 .await,
         };
 
+        if payload_attributes_subscribers {
+            if let Some(event_handler) = self.event_handler.as_ref() {
+                event_handler.register(EventKind::PayloadAttributes(ForkVersionedResponse {
+                    version: Some(self.spec.fork_name_at_slot::<T::EthSpec>(prepare_slot)),
+                    data: SseExtendedPayloadAttributes {
+                        proposal_slot: prepare_slot,
+                        proposer_index: proposer as u64,
+                        parent_block_root: proposal_parent_root,
+                        parent_block_number,
+                        parent_block_hash: forkchoice_update_params
+                            .head_hash
+                            .unwrap_or_else(ExecutionBlockHash::zero),
+                        payload_attributes: SsePayloadAttributes {
+                            timestamp: payload_attributes.timestamp,
+                            prev_randao: payload_attributes.prev_randao,
+                            suggested_fee_recipient: payload_attributes.suggested_fee_recipient,
+                        },
+                    },
+                }));
+            }
+        }
+
+        if !is_local_proposer {
+            return Ok(());
+        }
+
         debug!(
             self.log,
             "Preparing beacon proposer";
             "payload_attributes" => ?payload_attributes,
-            "head_root" => ?head_root,
+            "head_root" => ?proposal_parent_root,
             "prepare_slot" => prepare_slot,
             "validator" => proposer,
         );
 
         let already_known = execution_layer
-            .insert_proposer(prepare_slot, head_root, proposer as u64, payload_attributes)
+            .insert_proposer(
+                prepare_slot,
+                proposal_parent_root,
+                proposer as u64,
+                payload_attributes,
+            )
             .await;
         // Only push a log to the user if this is the first time we've seen this proposer for this
         // slot.
@@ -3923,6 +4557,75 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Returns the parameters which should be sent to the execution layer in place of `params`.
+    ///
+    /// If `Self::prepare_beacon_proposer` has prepared a payload at `current_slot + 1` upon the
+    /// parent of a late, weak head, and the head is still weak, then the head's parent is returned
+    /// as the head. Otherwise `params` is returned unchanged.
+    async fn overridden_forkchoice_update_params(
+        self: &Arc<Self>,
+        current_slot: Slot,
+        params: ForkchoiceUpdateParameters,
+    ) -> Result<ForkchoiceUpdateParameters, Error> {
+        if self.config.re_org_threshold.is_none() {
+            return Ok(params);
+        }
+
+        let execution_layer = self
+            .execution_layer
+            .as_ref()
+            .ok_or(Error::ExecutionLayerMissing)?;
+
+        let chain = self.clone();
+        let head_root = params.head_root;
+        let head_parent = self
+            .spawn_blocking_handle(
+                move || {
+                    let fork_choice = chain.canonical_head.fork_choice_read_lock();
+                    let head_block = fork_choice.get_block(&head_root)?;
+                    let parent_root = head_block.parent_root?;
+                    let parent_hash = fork_choice
+                        .get_block(&parent_root)?
+                        .execution_status
+                        .block_hash()?;
+                    drop(fork_choice);
+
+                    chain
+                        .should_prepare_re_org(head_root, head_block.slot, current_slot)
+                        .then(|| (parent_root, parent_hash))
+                },
+                "overridden_forkchoice_update_params",
+            )
+            .await?;
+        let (parent_root, parent_hash) = match head_parent {
+            Some(head_parent) => head_parent,
+            None => return Ok(params),
+        };
+
+        // Only override the head if a proposal upon its parent has actually been prepared.
+        if execution_layer
+            .payload_attributes(current_slot + 1, parent_root)
+            .await
+            .is_none()
+        {
+            return Ok(params);
+        }
+
+        debug!(
+            self.log,
+            "Overriding head for forkchoiceUpdated";
+            "weak_head" => ?head_root,
+            "parent" => ?parent_root,
+            "current_slot" => current_slot,
+        );
+
+        Ok(ForkchoiceUpdateParameters {
+            head_root: parent_root,
+            head_hash: Some(parent_hash),
+            ..params
+        })
+    }
+
     pub async fn update_execution_engine_forkchoice(
         self: &Arc<Self>,
         current_slot: Slot,
@@ -3964,6 +4667,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // `execution_engine_forkchoice_lock` apart from the one here.
         let forkchoice_lock = execution_layer.execution_engine_forkchoice_lock().await;
 
+        // If a payload has been prepared which re-orgs the head, continue to send its parent as the
+        // head so the payload being built is not replaced.
+        let params = self
+            .overridden_forkchoice_update_params(current_slot, params)
+            .await?;
+
         let (head_block_root, head_hash, justified_hash, finalized_hash) = if let Some(head_hash) =
             params.head_hash
         {
@@ -4289,6 +4998,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             // sync anyway).
             self.naive_aggregation_pool.write().prune(slot);
             self.block_times_cache.write().prune(slot);
+            self.proposal_diagnostics_cache.write().prune(slot);
 
             // Don't run heavy-weight tasks during sync.
             if self.best_slot() + MAX_PER_SLOT_FORK_CHOICE_DISTANCE < slot {
@@ -4792,7 +5502,9 @@ impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
         let drop = || -> Result<(), Error> {
             self.persist_head_and_fork_choice()?;
             self.persist_op_pool()?;
-            self.persist_eth1_cache()
+            self.persist_eth1_cache()?;
+            self.persist_gossip_caches()?;
+            self.persist_validator_monitor_history()
         };
 
         if let Err(e) = drop() {
@@ -22,7 +22,7 @@ use futures::channel::mpsc::Sender;
 use operation_pool::{OperationPool, PersistedOperationPool};
 use parking_lot::RwLock;
 use slasher::Slasher;
-use slog::{crit, error, info, Logger};
+use slog::{crit, error, info, warn, Logger};
 use slot_clock::{SlotClock, TestingSlotClock};
 use std::marker::PhantomData;
 use std::sync::Arc;
@@ -747,25 +747,25 @@ where
             store_migrator,
             slot_clock,
             op_pool: self.op_pool.ok_or("Cannot build without op pool")?,
-            // TODO: allow for persisting and loading the pool from disk.
+            // Restored from disk by `BeaconChain::load_gossip_caches` below.
             naive_aggregation_pool: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             naive_sync_aggregation_pool: <_>::default(),
-            // TODO: allow for persisting and loading the pool from disk.
+            // Restored from disk by `BeaconChain::load_gossip_caches` below.
             observed_attestations: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_sync_contributions: <_>::default(),
-            // TODO: allow for persisting and loading the pool from disk.
+            // Restored from disk by `BeaconChain::load_gossip_caches` below.
             observed_gossip_attesters: <_>::default(),
-            // TODO: allow for persisting and loading the pool from disk.
+            // Restored from disk by `BeaconChain::load_gossip_caches` below.
             observed_block_attesters: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_sync_contributors: <_>::default(),
-            // TODO: allow for persisting and loading the pool from disk.
+            // Restored from disk by `BeaconChain::load_gossip_caches` below.
             observed_aggregators: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_sync_aggregators: <_>::default(),
-            // TODO: allow for persisting and loading the pool from disk.
+            // Restored from disk by `BeaconChain::load_gossip_caches` below.
             observed_block_producers: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_voluntary_exits: <_>::default(),
@@ -789,6 +789,7 @@ where
             shuffling_cache: TimeoutRwLock::new(ShufflingCache::new()),
             beacon_proposer_cache: <_>::default(),
             block_times_cache: <_>::default(),
+            proposal_diagnostics_cache: <_>::default(),
             pre_finalization_block_cache: <_>::default(),
             validator_pubkey_cache: TimeoutRwLock::new(validator_pubkey_cache),
             attester_cache: <_>::default(),
@@ -802,6 +803,16 @@ where
             validator_monitor: RwLock::new(validator_monitor),
         };
 
+        // Restore any gossip caches persisted prior to the last shutdown. These caches are not
+        // required for correctness, so failing to load them is not fatal.
+        if let Err(e) = beacon_chain.load_gossip_caches(current_slot) {
+            warn!(
+                log,
+                "Unable to load persisted gossip caches";
+                "error" => ?e
+            );
+        }
+
         let head = beacon_chain.head_snapshot();
 
         // Prime the attester cache with the head state.
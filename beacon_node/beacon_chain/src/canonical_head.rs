@@ -828,6 +828,24 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.op_pool.prune_attestations(self.epoch()?);
         }
 
+        // Periodically persist the gossip caches so that an unclean shutdown does not lose them.
+        if is_epoch_transition {
+            if let Err(e) = self.persist_gossip_caches() {
+                warn!(
+                    self.log,
+                    "Failed to persist gossip caches";
+                    "error" => ?e
+                );
+            }
+            if let Err(e) = self.persist_validator_monitor_history() {
+                warn!(
+                    self.log,
+                    "Failed to persist validator monitor history";
+                    "error" => ?e
+                );
+            }
+        }
+
         // Register server-sent-events for a new head.
         if let Some(event_handler) = self
             .event_handler
@@ -1,4 +1,7 @@
-pub use eth2::types::{EventKind, SseBlock, SseFinalizedCheckpoint, SseHead};
+pub use eth2::types::{
+    EventKind, SseBlock, SseBlockGossip, SseExtendedPayloadAttributes, SseFinalizedCheckpoint,
+    SseHead, SsePayloadAttributes,
+};
 use slog::{trace, Logger};
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::{error::SendError, Receiver, Sender};
@@ -16,6 +19,11 @@ pub struct ServerSentEventHandler<T: EthSpec> {
     contribution_tx: Sender<EventKind<T>>,
     late_head: Sender<EventKind<T>>,
     block_reward_tx: Sender<EventKind<T>>,
+    payload_attributes_tx: Sender<EventKind<T>>,
+    proposer_slashing_tx: Sender<EventKind<T>>,
+    attester_slashing_tx: Sender<EventKind<T>>,
+    block_gossip_tx: Sender<EventKind<T>>,
+    proposal_diagnostics_tx: Sender<EventKind<T>>,
     log: Logger,
 }
 
@@ -34,6 +42,11 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         let (contribution_tx, _) = broadcast::channel(capacity);
         let (late_head, _) = broadcast::channel(capacity);
         let (block_reward_tx, _) = broadcast::channel(capacity);
+        let (payload_attributes_tx, _) = broadcast::channel(capacity);
+        let (proposer_slashing_tx, _) = broadcast::channel(capacity);
+        let (attester_slashing_tx, _) = broadcast::channel(capacity);
+        let (block_gossip_tx, _) = broadcast::channel(capacity);
+        let (proposal_diagnostics_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -45,6 +58,11 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
             contribution_tx,
             late_head,
             block_reward_tx,
+            payload_attributes_tx,
+            proposer_slashing_tx,
+            attester_slashing_tx,
+            block_gossip_tx,
+            proposal_diagnostics_tx,
             log,
         }
     }
@@ -72,6 +90,16 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
                 .map(|count| trace!(self.log, "Registering server-sent late head event"; "receiver_count" => count)),
             EventKind::BlockReward(block_reward) => self.block_reward_tx.send(EventKind::BlockReward(block_reward))
                 .map(|count| trace!(self.log, "Registering server-sent contribution and proof event"; "receiver_count" => count)),
+            EventKind::PayloadAttributes(payload_attributes) => self.payload_attributes_tx.send(EventKind::PayloadAttributes(payload_attributes))
+                .map(|count| trace!(self.log, "Registering server-sent payload attributes event"; "receiver_count" => count)),
+            EventKind::ProposerSlashing(proposer_slashing) => self.proposer_slashing_tx.send(EventKind::ProposerSlashing(proposer_slashing))
+                .map(|count| trace!(self.log, "Registering server-sent proposer slashing event"; "receiver_count" => count)),
+            EventKind::AttesterSlashing(attester_slashing) => self.attester_slashing_tx.send(EventKind::AttesterSlashing(attester_slashing))
+                .map(|count| trace!(self.log, "Registering server-sent attester slashing event"; "receiver_count" => count)),
+            EventKind::BlockGossip(block_gossip) => self.block_gossip_tx.send(EventKind::BlockGossip(block_gossip))
+                .map(|count| trace!(self.log, "Registering server-sent block gossip event"; "receiver_count" => count)),
+            EventKind::ProposalDiagnostics(diagnostics) => self.proposal_diagnostics_tx.send(EventKind::ProposalDiagnostics(diagnostics))
+                .map(|count| trace!(self.log, "Registering server-sent proposal diagnostics event"; "receiver_count" => count)),
         };
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
@@ -114,6 +142,26 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         self.block_reward_tx.subscribe()
     }
 
+    pub fn subscribe_payload_attributes(&self) -> Receiver<EventKind<T>> {
+        self.payload_attributes_tx.subscribe()
+    }
+
+    pub fn subscribe_proposer_slashing(&self) -> Receiver<EventKind<T>> {
+        self.proposer_slashing_tx.subscribe()
+    }
+
+    pub fn subscribe_attester_slashing(&self) -> Receiver<EventKind<T>> {
+        self.attester_slashing_tx.subscribe()
+    }
+
+    pub fn subscribe_block_gossip(&self) -> Receiver<EventKind<T>> {
+        self.block_gossip_tx.subscribe()
+    }
+
+    pub fn subscribe_proposal_diagnostics(&self) -> Receiver<EventKind<T>> {
+        self.proposal_diagnostics_tx.subscribe()
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
         self.attestation_tx.receiver_count() > 0
     }
@@ -149,4 +197,24 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
     pub fn has_block_reward_subscribers(&self) -> bool {
         self.block_reward_tx.receiver_count() > 0
     }
+
+    pub fn has_payload_attributes_subscribers(&self) -> bool {
+        self.payload_attributes_tx.receiver_count() > 0
+    }
+
+    pub fn has_proposer_slashing_subscribers(&self) -> bool {
+        self.proposer_slashing_tx.receiver_count() > 0
+    }
+
+    pub fn has_attester_slashing_subscribers(&self) -> bool {
+        self.attester_slashing_tx.receiver_count() > 0
+    }
+
+    pub fn has_block_gossip_subscribers(&self) -> bool {
+        self.block_gossip_tx.receiver_count() > 0
+    }
+
+    pub fn has_proposal_diagnostics_subscribers(&self) -> bool {
+        self.proposal_diagnostics_tx.receiver_count() > 0
+    }
 }
@@ -12,7 +12,7 @@ use crate::{
     BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, BlockProductionError,
     ExecutionPayloadError,
 };
-use execution_layer::{BuilderParams, PayloadStatus};
+use execution_layer::{BuilderParams, PayloadMetadata, PayloadStatus};
 use fork_choice::{InvalidationOperation, PayloadVerificationStatus};
 use proto_array::{Block as ProtoBlock, ExecutionStatus};
 use slog::debug;
@@ -22,12 +22,22 @@ use state_processing::per_block_processing::{
     partially_verify_execution_payload,
 };
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::task::JoinHandle;
 use types::*;
 
-pub type PreparePayloadResult<Payload> = Result<Payload, BlockProductionError>;
+pub type PreparePayloadResult<Payload> = Result<PreparedPayload<Payload>, BlockProductionError>;
 pub type PreparePayloadHandle<Payload> = JoinHandle<Option<PreparePayloadResult<Payload>>>;
 
+/// An execution payload for inclusion in a block, along with a description of how it was obtained.
+pub struct PreparedPayload<Payload> {
+    pub payload: Payload,
+    /// `None` if the default payload is used since the merge transition has not yet occurred.
+    pub metadata: Option<PayloadMetadata>,
+    /// The time taken to obtain the payload.
+    pub fetch_time: Duration,
+}
+
 #[derive(PartialEq)]
 pub enum AllowOptimisticImport {
     Yes,
@@ -340,6 +350,7 @@ pub fn get_execution_payload<
         .clone()
         .spawn_handle(
             async move {
+                let start = Instant::now();
                 prepare_execution_payload::<T, Payload>(
                     &chain,
                     is_merge_transition_complete,
@@ -350,6 +361,11 @@ pub fn get_execution_payload<
                     builder_params,
                 )
                 .await
+                .map(|(payload, metadata)| PreparedPayload {
+                    payload,
+                    metadata,
+                    fetch_time: start.elapsed(),
+                })
             },
             "get_execution_payload",
         )
@@ -360,7 +376,8 @@ pub fn get_execution_payload<
 
 /// Prepares an execution payload for inclusion in a block.
 ///
-/// Will return `Ok(None)` if the merge fork has occurred, but a terminal block has not been found.
+/// The returned `PayloadMetadata` is `None` if the default payload is used since the merge fork has
+/// occurred, but a terminal block has not been reached.
 ///
 /// ## Errors
 ///
@@ -381,7 +398,7 @@ pub async fn prepare_execution_payload<T, Payload>(
     proposer_index: u64,
     latest_execution_payload_header_block_hash: ExecutionBlockHash,
     builder_params: BuilderParams,
-) -> Result<Payload, BlockProductionError>
+) -> Result<(Payload, Option<PayloadMetadata>), BlockProductionError>
 where
     T: BeaconChainTypes,
     Payload: ExecPayload<T::EthSpec> + Default,
@@ -401,7 +418,7 @@ where
         if is_terminal_block_hash_set && !is_activation_epoch_reached {
             // Use the "empty" payload if there's a terminal block hash, but we haven't reached the
             // terminal block epoch yet.
-            return Ok(<_>::default());
+            return Ok((<_>::default(), None));
         }
 
         let terminal_pow_block_hash = execution_layer
@@ -414,7 +431,7 @@ where
         } else {
             // If the merge transition hasn't occurred yet and the EL hasn't found the terminal
             // block, return an "empty" payload.
-            return Ok(<_>::default());
+            return Ok((<_>::default(), None));
         }
     } else {
         latest_execution_payload_header_block_hash
@@ -441,8 +458,8 @@ where
     // Note: the suggested_fee_recipient is stored in the `execution_layer`, it will add this parameter.
     //
     // This future is not executed here, it's up to the caller to await it.
-    let execution_payload = execution_layer
-        .get_payload::<Payload>(
+    let (execution_payload, metadata) = execution_layer
+        .get_payload_with_metadata::<Payload>(
             parent_hash,
             timestamp,
             random,
@@ -454,5 +471,5 @@ where
         .await
         .map_err(BlockProductionError::GetPayloadFailed)?;
 
-    Ok(execution_payload)
+    Ok((execution_payload, Some(metadata)))
 }
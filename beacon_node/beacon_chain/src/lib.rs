@@ -1,6 +1,8 @@
 #![recursion_limit = "128"] // For lazy-static
+mod attestation_rewards;
 pub mod attestation_verification;
 mod attester_cache;
+mod beacon_block_reward;
 mod beacon_chain;
 mod beacon_fork_choice_store;
 pub mod beacon_proposer_cache;
@@ -31,16 +33,20 @@ pub mod observed_operations;
 pub mod otb_verification_service;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
+mod persisted_gossip_caches;
 mod pre_finalization_cache;
+mod proposal_diagnostics_cache;
 pub mod proposer_prep_service;
 pub mod schema_change;
 mod shuffling_cache;
 mod snapshot_cache;
 pub mod state_advance_timer;
+pub mod sync_committee_rewards;
 pub mod sync_committee_verification;
 pub mod test_utils;
 mod timeout_rw_lock;
 pub mod validator_monitor;
+mod validator_monitor_history;
 pub mod validator_pubkey_cache;
 
 pub use self::beacon_chain::{
@@ -59,9 +65,10 @@ pub use block_verification::{BlockError, ExecutionPayloadError, GossipVerifiedBl
 pub use canonical_head::{CachedHead, CanonicalHead, CanonicalHeadRwLock};
 pub use eth1_chain::{Eth1Chain, Eth1ChainBackend};
 pub use events::ServerSentEventHandler;
-pub use fork_choice::{ExecutionStatus, ForkchoiceUpdateParameters};
+pub use fork_choice::{ExecutionStatus, ForkChoiceStore, ForkchoiceUpdateParameters};
 pub use metrics::scrape_for_metrics;
 pub use parking_lot;
+pub use persisted_beacon_chain::verify_head_tracker;
 pub use slot_clock;
 pub use state_processing::per_block_processing::errors::{
     AttestationValidationError, AttesterSlashingValidationError, DepositValidationError,
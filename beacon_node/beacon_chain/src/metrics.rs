@@ -98,6 +98,14 @@ lazy_static! {
         "beacon_block_production_state_load_seconds",
         "Time taken to load the base state for block production"
     );
+    pub static ref BLOCK_PRODUCTION_GET_PROPOSER_HEAD_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_block_production_get_proposer_head_times",
+        "Time taken for fork choice to compute the proposer head before block production"
+    );
+    pub static ref BLOCK_PRODUCTION_RE_ORG_ATTEMPTS: Result<IntCounter> = try_create_int_counter(
+        "beacon_block_production_re_org_attempts_total",
+        "Count of blocks produced upon the parent of the head in order to re-org it"
+    );
     pub static ref BLOCK_PRODUCTION_SLOT_PROCESS_TIMES: Result<Histogram> = try_create_histogram(
         "beacon_block_production_slot_process_seconds",
         "Time taken to advance the state to the block production slot"
@@ -336,6 +344,10 @@ lazy_static! {
         try_create_histogram("beacon_persist_op_pool", "Time taken to persist the operations pool");
     pub static ref PERSIST_ETH1_CACHE: Result<Histogram> =
         try_create_histogram("beacon_persist_eth1_cache", "Time taken to persist the eth1 caches");
+    pub static ref PERSIST_GOSSIP_CACHES: Result<Histogram> =
+        try_create_histogram("beacon_persist_gossip_caches", "Time taken to persist the gossip caches");
+    pub static ref PERSIST_VALIDATOR_MONITOR_HISTORY: Result<Histogram> =
+        try_create_histogram("beacon_persist_validator_monitor_history", "Time taken to persist the validator monitor history");
     pub static ref PERSIST_FORK_CHOICE: Result<Histogram> =
         try_create_histogram("beacon_persist_fork_choice", "Time taken to persist the fork choice struct");
 
@@ -893,6 +905,45 @@ lazy_static! {
         "Time spent on the signature verification of sync message processing"
     );
 
+    /*
+     * Batch Sync Committee Processing
+     */
+    pub static ref SYNC_MESSAGE_PROCESSING_BATCH_SIGNATURE_SETUP_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_sync_committee_message_processing_batch_signature_setup_seconds",
+        "Time spent on setting up for the signature verification of batch sync message processing"
+    );
+    pub static ref SYNC_MESSAGE_PROCESSING_BATCH_SIGNATURE_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_sync_committee_message_processing_batch_signature_seconds",
+        "Time spent on the signature verification of batch sync message processing"
+    );
+    pub static ref SYNC_CONTRIBUTION_PROCESSING_BATCH_SIGNATURE_SETUP_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_sync_contribution_processing_batch_signature_setup_seconds",
+        "Time spent on setting up for the signature verification of batch sync contribution processing"
+    );
+    pub static ref SYNC_CONTRIBUTION_PROCESSING_BATCH_SIGNATURE_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_sync_contribution_processing_batch_signature_seconds",
+        "Time spent on the signature verification of batch sync contribution processing"
+    );
+
+    /*
+     * Batch Exit and Slashing Processing
+     */
+    pub static ref OPERATION_BATCH_SIGNATURE_SETUP_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "beacon_operation_batch_signature_setup_seconds",
+        "Time spent on setting up for the signature verification of a batch of exits or slashings",
+        &["type"]
+    );
+    pub static ref OPERATION_BATCH_SIGNATURE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "beacon_operation_batch_signature_seconds",
+        "Time spent on the signature verification of a batch of exits or slashings",
+        &["type"]
+    );
+    pub static ref BATCH_SIGNATURE_VERIFICATION_FAILURES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_batch_signature_verification_failures_total",
+        "Count of signature batches which failed and were verified individually instead",
+        &["type"]
+    );
+
     /*
      * Checkpoint sync & backfill
      */
@@ -279,6 +279,14 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             }
         };
 
+        // Prune the execution payloads of newly finalized blocks, which can be reconstructed from
+        // the execution layer on demand.
+        if db.prune_payloads() {
+            if let Err(e) = db.try_prune_execution_payloads(false) {
+                warn!(log, "Execution payload pruning failed"; "error" => ?e);
+            }
+        }
+
         // Finally, compact the database so that new free space is properly reclaimed.
         if let Err(e) = Self::run_compaction(
             db,
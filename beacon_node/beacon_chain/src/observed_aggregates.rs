@@ -200,6 +200,29 @@ impl<T: TreeHash + SlotData + Consts, E: EthSpec> ObservedAggregates<T, E> {
             .and_then(|set| set.observe_item(item, root))
     }
 
+    /// Store `root` in `self` as the root of an item at `slot`.
+    ///
+    /// This is used when restoring `self` from disk, where only the roots are available.
+    pub fn observe_root(&mut self, slot: Slot, root: Hash256) -> Result<ObserveOutcome, Error> {
+        let index = self.get_set_index(slot)?;
+
+        self.sets
+            .get_mut(index)
+            .ok_or(Error::InvalidSetIndex(index))
+            .and_then(|set| set.observe_item(&slot, root))
+    }
+
+    /// Returns the roots observed by `self` for each slot, in ascending slot order.
+    pub fn observed_roots(&self) -> Vec<(Slot, Vec<Hash256>)> {
+        let mut observed = self
+            .sets
+            .iter()
+            .map(|set| (set.slot, set.set.iter().copied().collect()))
+            .collect::<Vec<_>>();
+        observed.sort_unstable_by_key(|(slot, _)| *slot);
+        observed
+    }
+
     /// Check to see if the `root` of `item` is in self.
     ///
     /// `root` must equal `a.tree_hash_root()`.
@@ -369,6 +392,43 @@ mod tests {
                     );
                 }
 
+                #[test]
+                fn observed_roots_round_trip() {
+                    let mut store = $type::default();
+
+                    single_slot_test(&mut store, Slot::new(0));
+                    single_slot_test(&mut store, Slot::new(1));
+
+                    let observed = store.observed_roots();
+                    assert_eq!(
+                        observed.iter().map(|(slot, _)| *slot).collect::<Vec<_>>(),
+                        vec![Slot::new(0), Slot::new(1)],
+                        "should return roots in slot order"
+                    );
+
+                    let mut restored = $type::<E>::default();
+                    for (slot, roots) in observed {
+                        for root in roots {
+                            assert_eq!(
+                                restored.observe_root(slot, root),
+                                Ok(ObserveOutcome::New),
+                                "should observe restored root"
+                            );
+                        }
+                    }
+
+                    for slot in [Slot::new(0), Slot::new(1)] {
+                        for i in 0..NUM_ELEMENTS as u64 {
+                            let a = $method_name(slot, i);
+                            assert_eq!(
+                                restored.is_known(&a, a.tree_hash_root()),
+                                Ok(true),
+                                "should know restored item"
+                            );
+                        }
+                    }
+                }
+
                 #[test]
                 fn mulitple_contiguous_slots() {
                     let mut store = $type::default();
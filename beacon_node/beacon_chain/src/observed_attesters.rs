@@ -80,6 +80,10 @@ pub trait Item {
 
     /// Returns `true` if `validator_index` has been stored in `self`.
     fn contains(&self, validator_index: usize) -> bool;
+
+    /// Returns the indices of all validators that have been stored in `self`, in no particular
+    /// order.
+    fn validator_indices(&self) -> Vec<usize>;
 }
 
 /// Stores a `BitVec` that represents which validator indices have attested or sent sync committee
@@ -132,6 +136,15 @@ impl Item for EpochBitfield {
     fn contains(&self, validator_index: usize) -> bool {
         self.bitfield.get(validator_index).map_or(false, |bit| *bit)
     }
+
+    fn validator_indices(&self) -> Vec<usize> {
+        self.bitfield
+            .iter()
+            .enumerate()
+            .filter(|(_i, bit)| **bit)
+            .map(|(i, _bit)| i)
+            .collect()
+    }
 }
 
 /// Stores a `HashSet` of which validator indices have created an aggregate during an
@@ -171,6 +184,10 @@ impl Item for EpochHashSet {
     fn contains(&self, validator_index: usize) -> bool {
         self.set.contains(&validator_index)
     }
+
+    fn validator_indices(&self) -> Vec<usize> {
+        self.set.iter().copied().collect()
+    }
 }
 
 /// Stores a `HashSet` of which validator indices have created a sync aggregate during a
@@ -211,6 +228,10 @@ impl<E: EthSpec> Item for SyncContributorSlotHashSet<E> {
     fn contains(&self, validator_index: usize) -> bool {
         self.set.contains(&validator_index)
     }
+
+    fn validator_indices(&self) -> Vec<usize> {
+        self.set.iter().copied().collect()
+    }
 }
 
 /// Stores a `HashSet` of which validator indices have created a sync aggregate during a
@@ -249,6 +270,10 @@ impl Item for SyncAggregatorSlotHashSet {
     fn contains(&self, validator_index: usize) -> bool {
         self.set.contains(&validator_index)
     }
+
+    fn validator_indices(&self) -> Vec<usize> {
+        self.set.iter().copied().collect()
+    }
 }
 
 /// A container that stores some number of `T` items.
@@ -344,6 +369,14 @@ impl<T: Item, E: EthSpec> AutoPruningEpochContainer<T, E> {
         self.items.get(&epoch).map(|item| item.validator_count())
     }
 
+    /// Returns an iterator over each epoch in `self` and the indices of the validators observed
+    /// during that epoch.
+    pub fn observed_validators(&self) -> impl Iterator<Item = (Epoch, Vec<usize>)> + '_ {
+        self.items
+            .iter()
+            .map(|(epoch, item)| (*epoch, item.validator_indices()))
+    }
+
     fn sanitize_request(&self, epoch: Epoch, validator_index: usize) -> Result<(), Error> {
         if validator_index > E::ValidatorRegistryLimit::to_usize() {
             return Err(Error::ValidatorIndexTooHigh(validator_index));
@@ -606,6 +639,30 @@ mod tests {
                     assert_eq!(store.items.len(), 1, "should have a single bitfield stored");
                 }
 
+                #[test]
+                fn observed_validators() {
+                    let mut store = $type::default();
+
+                    single_period_test(&mut store, Epoch::new(0));
+                    single_period_test(&mut store, Epoch::new(1));
+
+                    let mut observed = store.observed_validators().collect::<Vec<_>>();
+                    observed.sort_unstable_by_key(|(epoch, _)| *epoch);
+                    for (_, indices) in observed.iter_mut() {
+                        indices.sort_unstable();
+                    }
+
+                    let expected_indices = vec![0, 1, 2, 3, 5, 6, 7, 18, 22];
+                    assert_eq!(
+                        observed,
+                        vec![
+                            (Epoch::new(0), expected_indices.clone()),
+                            (Epoch::new(1), expected_indices)
+                        ],
+                        "should return all observed validators"
+                    );
+                }
+
                 #[test]
                 fn mulitple_contiguous_periods() {
                     let mut store = $type::default();
@@ -53,13 +53,26 @@ impl<E: EthSpec> ObservedBlockProducers<E> {
     /// - `block.proposer_index` is greater than `VALIDATOR_REGISTRY_LIMIT`.
     /// - `block.slot` is equal to or less than the latest pruned `finalized_slot`.
     pub fn observe_proposer(&mut self, block: BeaconBlockRef<'_, E>) -> Result<bool, Error> {
-        self.sanitize_block(block)?;
+        self.observe_proposer_at_slot(block.slot(), block.proposer_index())
+    }
+
+    /// Observe that a block was produced by `proposer_index` at `slot`, as per
+    /// `Self::observe_proposer`.
+    ///
+    /// This is used when restoring `self` from disk, where only the slot and proposer are
+    /// available.
+    pub fn observe_proposer_at_slot(
+        &mut self,
+        slot: Slot,
+        proposer_index: u64,
+    ) -> Result<bool, Error> {
+        self.sanitize(slot, proposer_index)?;
 
         let did_not_exist = self
             .items
-            .entry(block.slot())
+            .entry(slot)
             .or_insert_with(|| HashSet::with_capacity(E::SlotsPerEpoch::to_usize()))
-            .insert(block.proposer_index());
+            .insert(proposer_index);
 
         Ok(!did_not_exist)
     }
@@ -73,7 +86,7 @@ impl<E: EthSpec> ObservedBlockProducers<E> {
     /// - `block.proposer_index` is greater than `VALIDATOR_REGISTRY_LIMIT`.
     /// - `block.slot` is equal to or less than the latest pruned `finalized_slot`.
     pub fn proposer_has_been_observed(&self, block: BeaconBlockRef<'_, E>) -> Result<bool, Error> {
-        self.sanitize_block(block)?;
+        self.sanitize(block.slot(), block.proposer_index())?;
 
         let exists = self
             .items
@@ -83,16 +96,16 @@ impl<E: EthSpec> ObservedBlockProducers<E> {
         Ok(exists)
     }
 
-    /// Returns `Ok(())` if the given `block` is sane.
-    fn sanitize_block(&self, block: BeaconBlockRef<'_, E>) -> Result<(), Error> {
-        if block.proposer_index() >= E::ValidatorRegistryLimit::to_u64() {
-            return Err(Error::ValidatorIndexTooHigh(block.proposer_index()));
+    /// Returns `Ok(())` if the given `slot` and `proposer_index` are sane.
+    fn sanitize(&self, slot: Slot, proposer_index: u64) -> Result<(), Error> {
+        if proposer_index >= E::ValidatorRegistryLimit::to_u64() {
+            return Err(Error::ValidatorIndexTooHigh(proposer_index));
         }
 
         let finalized_slot = self.finalized_slot;
-        if finalized_slot > 0 && block.slot() <= finalized_slot {
+        if finalized_slot > 0 && slot <= finalized_slot {
             return Err(Error::FinalizedBlock {
-                slot: block.slot(),
+                slot,
                 finalized_slot,
             });
         }
@@ -115,6 +128,17 @@ impl<E: EthSpec> ObservedBlockProducers<E> {
         self.items.retain(|slot, _set| *slot > finalized_slot);
     }
 
+    /// Returns the proposers observed by `self` for each slot, in ascending slot order.
+    pub fn observed_proposers(&self) -> Vec<(Slot, Vec<u64>)> {
+        let mut observed = self
+            .items
+            .iter()
+            .map(|(slot, proposers)| (*slot, proposers.iter().copied().collect()))
+            .collect::<Vec<_>>();
+        observed.sort_unstable_by_key(|(slot, _)| *slot);
+        observed
+    }
+
     /// Returns `true` if the given `validator_index` has been stored in `self` at `epoch`.
     ///
     /// This is useful for doppelganger detection.
@@ -400,4 +424,53 @@ mod tests {
             "only one proposer should be present in slot 1"
         );
     }
+
+    #[test]
+    fn observed_proposers_round_trip() {
+        let mut cache = ObservedBlockProducers::default();
+
+        cache
+            .observe_proposer(get_block(1, 0).to_ref())
+            .expect("should observe block");
+        cache
+            .observe_proposer(get_block(0, 1).to_ref())
+            .expect("should observe block");
+        cache
+            .observe_proposer(get_block(0, 0).to_ref())
+            .expect("should observe block");
+
+        let mut observed = cache.observed_proposers();
+        for (_, proposers) in observed.iter_mut() {
+            proposers.sort_unstable();
+        }
+        assert_eq!(
+            observed,
+            vec![(Slot::new(0), vec![0, 1]), (Slot::new(1), vec![0])],
+            "should return proposers in slot order"
+        );
+
+        let mut restored = ObservedBlockProducers::<E>::default();
+        for (slot, proposers) in observed {
+            for proposer in proposers {
+                assert_eq!(
+                    restored.observe_proposer_at_slot(slot, proposer),
+                    Ok(false),
+                    "should observe restored proposer"
+                );
+            }
+        }
+
+        for (slot, proposer) in [(0, 0), (0, 1), (1, 0)] {
+            assert_eq!(
+                restored.proposer_has_been_observed(get_block(slot, proposer).to_ref()),
+                Ok(true),
+                "restored proposer should be known"
+            );
+        }
+        assert_eq!(
+            restored.proposer_has_been_observed(get_block(1, 1).to_ref()),
+            Ok(false),
+            "unknown proposer should not be known"
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use crate::metrics;
+use bls::verify_signature_sets;
 use derivative::Derivative;
 use smallvec::SmallVec;
 use state_processing::{SigVerifiedOp, VerifyOperation};
@@ -36,6 +38,9 @@ pub enum ObservationOutcome<T> {
 
 /// Trait for exits and slashings which can be observed using `ObservedOperations`.
 pub trait ObservableOperation<E: EthSpec>: VerifyOperation<E> + Sized {
+    /// A name for this type of operation, used as a metrics label.
+    const TYPE_NAME: &'static str;
+
     /// The set of validator indices involved in this operation.
     ///
     /// See the comment on `observed_validator_indices` above for detail.
@@ -43,18 +48,24 @@ pub trait ObservableOperation<E: EthSpec>: VerifyOperation<E> + Sized {
 }
 
 impl<E: EthSpec> ObservableOperation<E> for SignedVoluntaryExit {
+    const TYPE_NAME: &'static str = "voluntary_exit";
+
     fn observed_validators(&self) -> SmallVec<[u64; SMALL_VEC_SIZE]> {
         std::iter::once(self.message.validator_index).collect()
     }
 }
 
 impl<E: EthSpec> ObservableOperation<E> for ProposerSlashing {
+    const TYPE_NAME: &'static str = "proposer_slashing";
+
     fn observed_validators(&self) -> SmallVec<[u64; SMALL_VEC_SIZE]> {
         std::iter::once(self.signed_header_1.message.proposer_index).collect()
     }
 }
 
 impl<E: EthSpec> ObservableOperation<E> for AttesterSlashing<E> {
+    const TYPE_NAME: &'static str = "attester_slashing";
+
     fn observed_validators(&self) -> SmallVec<[u64; SMALL_VEC_SIZE]> {
         let attestation_1_indices = self
             .attestation_1
@@ -107,4 +118,83 @@ impl<T: ObservableOperation<E>, E: EthSpec> ObservedOperations<T, E> {
 
         Ok(ObservationOutcome::New(verified_op))
     }
+
+    /// Performs the same verification as `Self::verify_and_observe` for each of `ops`, but checks
+    /// all of their signatures in a single batch.
+    ///
+    /// If the batch is invalid then the signature of each op is verified individually, so the
+    /// result for each op is identical to that of `Self::verify_and_observe`. The results are
+    /// returned in the same order as `ops`.
+    pub fn batch_verify_and_observe(
+        &mut self,
+        ops: Vec<T>,
+        head_state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Vec<Result<ObservationOutcome<T>, T::Error>> {
+        // Only check the signatures of ops which are not already known. An op for which the
+        // signature sets cannot be constructed is left out of the batch, it will fail (or succeed)
+        // during individual verification instead.
+        let setup_timer = metrics::start_timer_vec(
+            &metrics::OPERATION_BATCH_SIGNATURE_SETUP_TIMES,
+            &[T::TYPE_NAME],
+        );
+
+        let mut in_batch = vec![false; ops.len()];
+        let mut signature_sets = Vec::with_capacity(ops.len());
+        for (op, in_batch) in ops.iter().zip(in_batch.iter_mut()) {
+            if self.is_known(op) {
+                continue;
+            }
+            if let Ok(sets) = op.signature_sets(head_state, spec) {
+                signature_sets.extend(sets);
+                *in_batch = true;
+            }
+        }
+
+        metrics::stop_timer(setup_timer);
+
+        let mut batch_is_valid = false;
+        if !signature_sets.is_empty() {
+            let _timer = metrics::start_timer_vec(
+                &metrics::OPERATION_BATCH_SIGNATURE_TIMES,
+                &[T::TYPE_NAME],
+            );
+            batch_is_valid = verify_signature_sets(signature_sets.iter());
+            if !batch_is_valid {
+                metrics::inc_counter_vec(
+                    &metrics::BATCH_SIGNATURE_VERIFICATION_FAILURES,
+                    &[T::TYPE_NAME],
+                );
+            }
+        }
+
+        ops.into_iter()
+            .zip(in_batch)
+            .map(|(op, in_batch)| {
+                // Duplicates within the batch are handled here, since the first op observed for a
+                // set of validators will cause any later ops for the same set to be known.
+                if self.is_known(&op) {
+                    return Ok(ObservationOutcome::AlreadyKnown);
+                }
+
+                let verified_op = if in_batch && batch_is_valid {
+                    op.validate_with_verified_signatures(head_state, spec)?
+                } else {
+                    op.validate(head_state, spec)?
+                };
+
+                self.observed_validator_indices
+                    .extend(verified_op.as_inner().observed_validators());
+
+                Ok(ObservationOutcome::New(verified_op))
+            })
+            .collect()
+    }
+
+    /// Returns `true` if all of the validator indices of `op` have been previously observed.
+    fn is_known(&self, op: &T) -> bool {
+        op.observed_validators()
+            .iter()
+            .all(|index| self.observed_validator_indices.contains(index))
+    }
 }
@@ -1,8 +1,10 @@
-use crate::head_tracker::SszHeadTracker;
+use crate::beacon_chain::BEACON_CHAIN_DB_KEY;
+use crate::head_tracker::{HeadTracker, SszHeadTracker};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
-use store::{DBColumn, Error as StoreError, StoreItem};
-use types::Hash256;
+use store::verify::{Check, CheckReport, Issue};
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, KeyValueStore, StoreItem};
+use types::{EthSpec, Hash256};
 
 /// Dummy value to use for the canonical head block root, see below.
 pub const DUMMY_CANONICAL_HEAD_BLOCK_ROOT: Hash256 = Hash256::repeat_byte(0xff);
@@ -34,3 +36,74 @@ impl StoreItem for PersistedBeaconChain {
         Self::from_ssz_bytes(bytes).map_err(Into::into)
     }
 }
+
+/// Check that every head in the persisted head tracker has a block and a state.
+///
+/// Heads that are missing either are removed from the head tracker on repair.
+pub fn verify_head_tracker<E, Hot, Cold>(
+    store: &HotColdDB<E, Hot, Cold>,
+    repair: bool,
+) -> Result<CheckReport, StoreError>
+where
+    E: EthSpec,
+    Hot: KeyValueStore<E> + ItemStore<E>,
+    Cold: KeyValueStore<E> + ItemStore<E>,
+{
+    let mut report = CheckReport::new(Check::HeadTracker);
+
+    let mut persisted = match store.get_item::<PersistedBeaconChain>(&BEACON_CHAIN_DB_KEY)? {
+        Some(persisted) => persisted,
+        None => {
+            report.add_issue(Issue::MissingHeadTracker, false);
+            return Ok(report);
+        }
+    };
+    let head_tracker = match HeadTracker::from_ssz_container(&persisted.ssz_head_tracker) {
+        Ok(head_tracker) => head_tracker,
+        Err(e) => {
+            report.add_issue(
+                Issue::InvalidHeadTracker {
+                    error: format!("{:?}", e),
+                },
+                false,
+            );
+            return Ok(report);
+        }
+    };
+
+    let mut missing_heads = vec![];
+    for (block_root, slot) in head_tracker.heads() {
+        report.items_checked += 1;
+
+        let issue = match store.get_blinded_block(&block_root)? {
+            None => Issue::MissingHeadBlock { block_root, slot },
+            Some(block) => {
+                let state_root = block.state_root();
+                if store.load_hot_state_summary(&state_root)?.is_some()
+                    || store.load_cold_state_slot(&state_root)?.is_some()
+                {
+                    continue;
+                }
+                Issue::MissingHeadState {
+                    block_root,
+                    state_root,
+                }
+            }
+        };
+        missing_heads.push(block_root);
+        report.add_issue(issue, repair);
+    }
+
+    if repair && !missing_heads.is_empty() {
+        {
+            let mut heads = head_tracker.0.write();
+            for block_root in &missing_heads {
+                heads.remove(block_root);
+            }
+        }
+        persisted.ssz_head_tracker = head_tracker.to_ssz_container();
+        store.put_item(&BEACON_CHAIN_DB_KEY, &persisted)?;
+    }
+
+    Ok(report)
+}
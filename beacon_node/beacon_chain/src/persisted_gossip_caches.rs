@@ -0,0 +1,243 @@
+//! Persistence for the caches which are populated by gossip and are short-lived, but which are
+//! expensive to lose when the node restarts part-way through an epoch:
+//!
+//! - The naive aggregation pool, so that unaggregated attestations remain available to our
+//!   aggregators and proposers.
+//! - The observed attestations, attesters, aggregators and block producers, so that duplicate
+//!   messages which were already processed before the restart continue to be ignored.
+//!
+//! The caches are written to disk on shutdown and at each epoch transition, and are restored on
+//! start-up. Only entries that would still be retained by each cache at the current slot are
+//! restored. The caches for sync committee messages are not persisted since they are only relevant
+//! for a single slot.
+use crate::beacon_chain::GOSSIP_CACHES_DB_KEY;
+use crate::observed_attesters::{AutoPruningEpochContainer, Item};
+use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes};
+use slog::{debug, warn};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::{Attestation, Epoch, EthSpec, Hash256, Slot};
+
+/// The indices of the validators observed in a single epoch.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedEpochIndices {
+    pub epoch: Epoch,
+    pub indices: Vec<u64>,
+}
+
+/// The indices of the validators observed in a single slot.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedSlotIndices {
+    pub slot: Slot,
+    pub indices: Vec<u64>,
+}
+
+/// The roots of the items observed in a single slot.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedSlotRoots {
+    pub slot: Slot,
+    pub roots: Vec<Hash256>,
+}
+
+/// An SSZ-encodable snapshot of the gossip caches of a `BeaconChain`.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct PersistedGossipCaches<E: EthSpec> {
+    pub naive_aggregation_pool: Vec<Attestation<E>>,
+    pub observed_attestations: Vec<PersistedSlotRoots>,
+    pub observed_gossip_attesters: Vec<PersistedEpochIndices>,
+    pub observed_block_attesters: Vec<PersistedEpochIndices>,
+    pub observed_aggregators: Vec<PersistedEpochIndices>,
+    pub observed_block_producers: Vec<PersistedSlotIndices>,
+}
+
+impl<E: EthSpec> StoreItem for PersistedGossipCaches<E> {
+    fn db_column() -> DBColumn {
+        DBColumn::GossipCaches
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Self::from_ssz_bytes(bytes).map_err(Into::into)
+    }
+}
+
+fn persist_epoch_container<T: Item, E: EthSpec>(
+    container: &AutoPruningEpochContainer<T, E>,
+) -> Vec<PersistedEpochIndices> {
+    let mut persisted = container
+        .observed_validators()
+        .map(|(epoch, indices)| PersistedEpochIndices {
+            epoch,
+            indices: indices.into_iter().map(|i| i as u64).collect(),
+        })
+        .collect::<Vec<_>>();
+    persisted.sort_unstable_by_key(|item| item.epoch);
+    persisted
+}
+
+fn restore_epoch_container<T: Item, E: EthSpec>(
+    container: &mut AutoPruningEpochContainer<T, E>,
+    persisted: Vec<PersistedEpochIndices>,
+    current_epoch: Epoch,
+) {
+    for PersistedEpochIndices { epoch, indices } in persisted {
+        for index in indices {
+            // Entries which are no longer relevant will be rejected or pruned.
+            let _ = container.observe_validator(epoch, index as usize);
+        }
+    }
+    container.prune(current_epoch);
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Persists the gossip caches of `self` to disk.
+    ///
+    /// See the module-level documentation for the caches which are persisted.
+    pub fn persist_gossip_caches(&self) -> Result<(), BeaconChainError> {
+        let _timer = metrics::start_timer(&metrics::PERSIST_GOSSIP_CACHES);
+
+        // Sort by slot so that restoring the pool never inserts an attestation from a slot that
+        // has already been pruned.
+        let mut naive_aggregation_pool = self
+            .naive_aggregation_pool
+            .read()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        naive_aggregation_pool.sort_unstable_by_key(|a| a.data.slot);
+
+        let persisted = PersistedGossipCaches {
+            naive_aggregation_pool,
+            observed_attestations: self
+                .observed_attestations
+                .read()
+                .observed_roots()
+                .into_iter()
+                .map(|(slot, roots)| PersistedSlotRoots { slot, roots })
+                .collect(),
+            observed_gossip_attesters: persist_epoch_container(
+                &*self.observed_gossip_attesters.read(),
+            ),
+            observed_block_attesters: persist_epoch_container(
+                &*self.observed_block_attesters.read(),
+            ),
+            observed_aggregators: persist_epoch_container(&*self.observed_aggregators.read()),
+            observed_block_producers: self
+                .observed_block_producers
+                .read()
+                .observed_proposers()
+                .into_iter()
+                .map(|(slot, indices)| PersistedSlotIndices { slot, indices })
+                .collect(),
+        };
+
+        self.store.put_item(&GOSSIP_CACHES_DB_KEY, &persisted)?;
+
+        Ok(())
+    }
+
+    /// Restores the gossip caches persisted by `Self::persist_gossip_caches`, if any.
+    ///
+    /// Entries which are no longer relevant at `current_slot` are discarded. The caches are
+    /// expected to be empty prior to calling this function.
+    pub fn load_gossip_caches(&self, current_slot: Slot) -> Result<(), BeaconChainError> {
+        let persisted = match self
+            .store
+            .get_item::<PersistedGossipCaches<T::EthSpec>>(&GOSSIP_CACHES_DB_KEY)?
+        {
+            Some(persisted) => persisted,
+            None => return Ok(()),
+        };
+        let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
+
+        let PersistedGossipCaches {
+            naive_aggregation_pool,
+            observed_attestations,
+            observed_gossip_attesters,
+            observed_block_attesters,
+            observed_aggregators,
+            observed_block_producers,
+        } = persisted;
+
+        {
+            let mut pool = self.naive_aggregation_pool.write();
+            for attestation in &naive_aggregation_pool {
+                if let Err(e) = pool.insert(attestation) {
+                    debug!(
+                        self.log,
+                        "Discarding persisted attestation";
+                        "error" => ?e,
+                        "slot" => attestation.data.slot,
+                    );
+                }
+            }
+            pool.prune(current_slot);
+        }
+
+        {
+            let mut observed = self.observed_attestations.write();
+            for PersistedSlotRoots { slot, roots } in observed_attestations {
+                for root in roots {
+                    let _ = observed.observe_root(slot, root);
+                }
+            }
+            observed.prune(current_slot);
+        }
+
+        restore_epoch_container(
+            &mut *self.observed_gossip_attesters.write(),
+            observed_gossip_attesters,
+            current_epoch,
+        );
+        restore_epoch_container(
+            &mut *self.observed_block_attesters.write(),
+            observed_block_attesters,
+            current_epoch,
+        );
+        restore_epoch_container(
+            &mut *self.observed_aggregators.write(),
+            observed_aggregators,
+            current_epoch,
+        );
+
+        {
+            let finalized_slot = self
+                .canonical_head
+                .cached_head()
+                .finalized_checkpoint()
+                .epoch
+                .start_slot(T::EthSpec::slots_per_epoch());
+            let mut observed = self.observed_block_producers.write();
+            for PersistedSlotIndices { slot, indices } in observed_block_producers {
+                for index in indices {
+                    if let Err(e) = observed.observe_proposer_at_slot(slot, index) {
+                        warn!(
+                            self.log,
+                            "Unable to restore observed block producer";
+                            "error" => ?e,
+                            "slot" => slot,
+                        );
+                    }
+                }
+            }
+            observed.prune(finalized_slot);
+        }
+
+        debug!(
+            self.log,
+            "Restored gossip caches";
+            "naive_aggregation_pool" => self.naive_aggregation_pool.read().num_items(),
+            "observed_gossip_attesters" => self
+                .observed_gossip_attesters
+                .read()
+                .observed_validator_count(current_epoch)
+                .unwrap_or(0),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! This module provides the `ProposalDiagnosticsCache` which contains a breakdown of the time spent
+//! in each phase of producing a block, for each block produced by this beacon node.
+//!
+//! Together with the `BlockTimesCache`, this allows the root cause of a missed or late proposal to
+//! be determined. In particular, it allows us to distinguish between the following scenarios:
+//! - The parent state was slow to load or advance.
+//! - Packing operations from the op pool was slow.
+//! - The execution engine or builder was slow to provide a payload.
+//! - The validator client was slow to sign and publish the block.
+
+use eth2::lighthouse::ProposalDiagnostics;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use types::{Hash256, Slot};
+
+/// The number of slots for which diagnostics are retained.
+pub const PROPOSAL_DIAGNOSTICS_RETAINED_SLOTS: u64 = 64;
+
+struct CachedProposal {
+    diagnostics: ProposalDiagnostics,
+    /// The time at which the unsigned block was returned, if known.
+    produced_at: Option<Duration>,
+}
+
+#[derive(Default)]
+pub struct ProposalDiagnosticsCache {
+    proposals: BTreeMap<Slot, Vec<CachedProposal>>,
+}
+
+impl ProposalDiagnosticsCache {
+    /// Records the diagnostics of a newly produced block. `produced_at` is the time since the UNIX
+    /// epoch at which the block was produced.
+    pub fn insert(&mut self, diagnostics: ProposalDiagnostics, produced_at: Option<Duration>) {
+        self.proposals
+            .entry(diagnostics.slot)
+            .or_default()
+            .push(CachedProposal {
+                diagnostics,
+                produced_at,
+            });
+    }
+
+    /// Returns the diagnostics of all blocks produced at `slot`, in the order they were produced.
+    pub fn get(&self, slot: Slot) -> Vec<ProposalDiagnostics> {
+        self.proposals
+            .get(&slot)
+            .map(|proposals| {
+                proposals
+                    .iter()
+                    .map(|proposal| proposal.diagnostics.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if any block was produced at `slot`.
+    pub fn contains_slot(&self, slot: Slot) -> bool {
+        self.proposals.contains_key(&slot)
+    }
+
+    /// Records that the block with `block_root` was published at `published_at`, returning the
+    /// updated diagnostics if the block was produced by this node and not already published.
+    pub fn set_time_published(
+        &mut self,
+        slot: Slot,
+        block_root: Hash256,
+        published_at: Duration,
+    ) -> Option<ProposalDiagnostics> {
+        let proposal = self
+            .proposals
+            .get_mut(&slot)?
+            .iter_mut()
+            .find(|proposal| proposal.diagnostics.block_root == block_root)?;
+
+        if proposal.diagnostics.signing_round_trip_ms.is_some() {
+            return None;
+        }
+        proposal.diagnostics.signing_round_trip_ms = Some(
+            published_at
+                .checked_sub(proposal.produced_at?)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        );
+        Some(proposal.diagnostics.clone())
+    }
+
+    /// Prune the cache to only store the most recent `PROPOSAL_DIAGNOSTICS_RETAINED_SLOTS`.
+    pub fn prune(&mut self, current_slot: Slot) {
+        let oldest_retained_slot =
+            current_slot.saturating_sub(PROPOSAL_DIAGNOSTICS_RETAINED_SLOTS - 1);
+        self.proposals = self.proposals.split_off(&oldest_retained_slot);
+    }
+}
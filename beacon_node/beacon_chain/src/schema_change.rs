@@ -7,7 +7,7 @@ mod migration_schema_v8;
 mod migration_schema_v9;
 mod types;
 
-use crate::beacon_chain::{BeaconChainTypes, FORK_CHOICE_DB_KEY};
+use crate::beacon_chain::{BeaconChainTypes, FORK_CHOICE_DB_KEY, GOSSIP_CACHES_DB_KEY};
 use crate::persisted_fork_choice::{
     PersistedForkChoiceV1, PersistedForkChoiceV10, PersistedForkChoiceV11, PersistedForkChoiceV7,
     PersistedForkChoiceV8,
@@ -18,7 +18,7 @@ use std::path::Path;
 use std::sync::Arc;
 use store::hot_cold_store::{HotColdDB, HotColdDBError};
 use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION};
-use store::{Error as StoreError, StoreItem};
+use store::{get_key_for_col, DBColumn, Error as StoreError, KeyValueStoreOp, StoreItem};
 
 /// Migrate the database from one schema version to another, applying all requisite mutations.
 pub fn migrate_schema<T: BeaconChainTypes>(
@@ -196,6 +196,20 @@ pub fn migrate_schema<T: BeaconChainTypes>(
 
             Ok(())
         }
+        // Upgrade from v11 to v12 adding the persisted gossip caches. The caches are simply empty
+        // until they are first persisted, so no changes to the database are required.
+        (SchemaVersion(11), SchemaVersion(12)) => db.store_schema_version_atomically(to, vec![]),
+        // Downgrade from v12 to v11 removing the persisted gossip caches.
+        (SchemaVersion(12), SchemaVersion(11)) => {
+            let ops = vec![KeyValueStoreOp::DeleteKey(get_key_for_col(
+                DBColumn::GossipCaches.into(),
+                GOSSIP_CACHES_DB_KEY.as_bytes(),
+            ))];
+
+            db.store_schema_version_atomically(to, ops)?;
+
+            Ok(())
+        }
         // Anything else is an error.
         (_, _) => Err(HotColdDBError::UnsupportedSchemaVersion {
             target_version: to,
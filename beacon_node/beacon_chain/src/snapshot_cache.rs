@@ -12,8 +12,9 @@ use types::{
 pub const DEFAULT_SNAPSHOT_CACHE_SIZE: usize = 4;
 
 /// The minimum block delay to clone the state in the cache instead of removing it.
-/// This helps keep block processing fast during re-orgs from late blocks.
-const MINIMUM_BLOCK_DELAY_FOR_CLONE: Duration = Duration::from_secs(6);
+/// This helps keep block processing fast during re-orgs from late blocks, and keeps the parent
+/// state available for proposers who re-org blocks observed after the attestation deadline.
+const MINIMUM_BLOCK_DELAY_FOR_CLONE: Duration = Duration::from_secs(4);
 
 /// This snapshot is to be used for verifying a child of `self.beacon_block`.
 #[derive(Debug)]
@@ -0,0 +1,77 @@
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::types::SyncCommitteeReward;
+use safe_arith::SafeArith;
+use state_processing::per_block_processing::altair::sync_committee::compute_sync_aggregate_rewards;
+use std::collections::HashMap;
+use types::{BeaconBlockRef, BeaconState, BeaconStateError, ExecPayload};
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Compute the net reward paid to each member of the sync committee by the sync aggregate in
+    /// `block`.
+    ///
+    /// Rewards paid to the block proposer for including the aggregate are not counted here.
+    ///
+    /// The `state` must be the pre-state of `block` advanced to `block.slot()`.
+    pub fn compute_sync_committee_rewards<Payload: ExecPayload<T::EthSpec>>(
+        &self,
+        block: BeaconBlockRef<'_, T::EthSpec, Payload>,
+        state: &mut BeaconState<T::EthSpec>,
+    ) -> Result<Vec<SyncCommitteeReward>, BeaconChainError> {
+        if block.slot() != state.slot() {
+            return Err(BeaconChainError::BlockRewardSlotError);
+        }
+
+        let sync_aggregate = block
+            .body()
+            .sync_aggregate()
+            .map_err(|_| BeaconChainError::BlockRewardSyncError)?;
+
+        let (participant_reward, _) = compute_sync_aggregate_rewards(state, &self.spec)
+            .map_err(|_| BeaconChainError::BlockRewardSyncError)?;
+
+        let sync_committee = state.current_sync_committee()?.clone();
+        let sync_committee_indices = state.get_sync_committee_indices(&sync_committee)?;
+
+        // Track the balance of each committee member as the aggregate is applied, so that
+        // penalties saturate at zero in the same way they do during block processing.
+        let mut initial_balances = HashMap::new();
+        let mut balances = HashMap::new();
+        for &index in &sync_committee_indices {
+            let balance = *state
+                .balances()
+                .get(index)
+                .ok_or(BeaconStateError::UnknownValidator(index))?;
+            initial_balances.insert(index, balance);
+            balances.insert(index, balance);
+        }
+
+        for (&index, participation_bit) in sync_committee_indices
+            .iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+        {
+            let balance = balances
+                .get_mut(&index)
+                .ok_or(BeaconStateError::UnknownValidator(index))?;
+            if participation_bit {
+                balance.safe_add_assign(participant_reward)?;
+            } else {
+                *balance = balance.saturating_sub(participant_reward);
+            }
+        }
+
+        // Report each validator once, in the order they first appear in the committee.
+        let mut rewards = Vec::with_capacity(initial_balances.len());
+        for index in sync_committee_indices {
+            if let (Some(initial), Some(&current)) =
+                (initial_balances.remove(&index), balances.get(&index))
+            {
+                rewards.push(SyncCommitteeReward {
+                    validator_index: index as u64,
+                    reward: current as i64 - initial as i64,
+                });
+            }
+        }
+
+        Ok(rewards)
+    }
+}
@@ -25,15 +25,20 @@
 //!                                ▼
 //!                  impl SignatureVerifiedSyncContribution
 //! ```
+//!
+//! Both types may also be verified in batches (see the `batch` module), which shares the
+//! signature verification across many messages.
+mod batch;
 
 use crate::observed_attesters::SlotSubcommitteeIndex;
 use crate::{
     beacon_chain::{MAXIMUM_GOSSIP_CLOCK_DISPARITY, VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT},
     metrics,
     observed_aggregates::ObserveOutcome,
+    validator_pubkey_cache::ValidatorPubkeyCache,
     BeaconChain, BeaconChainError, BeaconChainTypes,
 };
-use bls::{verify_signature_sets, PublicKeyBytes};
+use bls::{verify_signature_sets, PublicKeyBytes, SignatureSet};
 use derivative::Derivative;
 use safe_arith::ArithError;
 use slot_clock::SlotClock;
@@ -56,6 +61,8 @@ use types::{
     SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId,
 };
 
+pub use batch::{batch_verify_sync_committee_messages, batch_verify_sync_contributions};
+
 /// Returned when a sync committee contribution was not successfully verified. It might not have been verified for
 /// two reasons:
 ///
@@ -246,6 +253,35 @@ impl From<ContributionError> for Error {
     }
 }
 
+/// Used to avoid double-checking signatures.
+#[derive(Copy, Clone)]
+enum CheckSyncCommitteeSignature {
+    Yes,
+    No,
+}
+
+/// Wraps a `SignedContributionAndProof` that has been verified up until the point that its
+/// signatures can be checked.
+///
+/// These contributions have *not* undergone signature verification.
+struct PartiallyVerifiedSyncContribution<T: BeaconChainTypes> {
+    signed_aggregate: SignedContributionAndProof<T::EthSpec>,
+    participant_pubkeys: Vec<PublicKeyBytes>,
+    contribution_root: Hash256,
+    observed_key: SlotSubcommitteeIndex,
+}
+
+/// Wraps a `SyncCommitteeMessage` that has been verified up until the point that its signature can
+/// be checked.
+///
+/// These messages have *not* undergone signature verification.
+struct PartiallyVerifiedSyncCommitteeMessage {
+    sync_message: SyncCommitteeMessage,
+    subnet_id: SyncSubnetId,
+    subnet_positions: HashMap<SyncSubnetId, Vec<usize>>,
+    pubkey: PublicKeyBytes,
+}
+
 /// Wraps a `SignedContributionAndProof` that has been verified for propagation on the gossip network.\
 #[derive(Derivative)]
 #[derivative(Clone(bound = "T: BeaconChainTypes"))]
@@ -261,10 +297,9 @@ pub struct VerifiedSyncCommitteeMessage {
     subnet_positions: HashMap<SyncSubnetId, Vec<usize>>,
 }
 
-impl<T: BeaconChainTypes> VerifiedSyncContribution<T> {
-    /// Returns `Ok(Self)` if the `signed_aggregate` is valid to be (re)published on the gossip
-    /// network.
-    pub fn verify(
+impl<T: BeaconChainTypes> PartiallyVerifiedSyncContribution<T> {
+    /// Run the checks that happen before signature verification.
+    fn verify(
         signed_aggregate: SignedContributionAndProof<T::EthSpec>,
         chain: &BeaconChain<T>,
     ) -> Result<Self, Error> {
@@ -346,13 +381,51 @@ impl<T: BeaconChainTypes> VerifiedSyncContribution<T> {
             .filter_map(|(pubkey, bit)| bit.then(|| pubkey))
             .collect::<Vec<_>>();
 
-        // Ensure that all signatures are valid.
-        if !verify_signed_aggregate_signatures(
-            chain,
-            &signed_aggregate,
-            participant_pubkeys.as_slice(),
-        )? {
-            return Err(Error::InvalidSignature);
+        Ok(Self {
+            signed_aggregate,
+            participant_pubkeys,
+            contribution_root,
+            observed_key,
+        })
+    }
+}
+
+impl<T: BeaconChainTypes> VerifiedSyncContribution<T> {
+    /// Returns `Ok(Self)` if the `signed_aggregate` is valid to be (re)published on the gossip
+    /// network.
+    pub fn verify(
+        signed_aggregate: SignedContributionAndProof<T::EthSpec>,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let partial = PartiallyVerifiedSyncContribution::verify(signed_aggregate, chain)?;
+        Self::from_partial(partial, chain, CheckSyncCommitteeSignature::Yes)
+    }
+
+    /// Complete the verification of a partially verified contribution.
+    fn from_partial(
+        partial: PartiallyVerifiedSyncContribution<T>,
+        chain: &BeaconChain<T>,
+        check_signature: CheckSyncCommitteeSignature,
+    ) -> Result<Self, Error> {
+        let PartiallyVerifiedSyncContribution {
+            signed_aggregate,
+            participant_pubkeys,
+            contribution_root,
+            observed_key,
+        } = partial;
+
+        match check_signature {
+            CheckSyncCommitteeSignature::Yes => {
+                // Ensure that all signatures are valid.
+                if !verify_signed_aggregate_signatures(
+                    chain,
+                    &signed_aggregate,
+                    participant_pubkeys.as_slice(),
+                )? {
+                    return Err(Error::InvalidSignature);
+                }
+            }
+            CheckSyncCommitteeSignature::No => (),
         }
 
         let contribution = &signed_aggregate.message.contribution;
@@ -413,13 +486,9 @@ impl<T: BeaconChainTypes> VerifiedSyncContribution<T> {
     }
 }
 
-impl VerifiedSyncCommitteeMessage {
-    /// Returns `Ok(Self)` if the `sync_message` is valid to be (re)published on the gossip
-    /// network.
-    ///
-    /// `subnet_id` is the subnet from which we received this sync message. This function will
-    /// verify that it was received on the correct subnet.
-    pub fn verify<T: BeaconChainTypes>(
+impl PartiallyVerifiedSyncCommitteeMessage {
+    /// Run the checks that happen before signature verification.
+    fn verify<T: BeaconChainTypes>(
         sync_message: SyncCommitteeMessage,
         subnet_id: SyncSubnetId,
         chain: &BeaconChain<T>,
@@ -465,8 +534,51 @@ impl VerifiedSyncCommitteeMessage {
             });
         }
 
-        // The aggregate signature of the sync committee message is valid.
-        verify_sync_committee_message(chain, &sync_message, &pubkey)?;
+        Ok(Self {
+            sync_message,
+            subnet_id,
+            subnet_positions,
+            pubkey,
+        })
+    }
+}
+
+impl VerifiedSyncCommitteeMessage {
+    /// Returns `Ok(Self)` if the `sync_message` is valid to be (re)published on the gossip
+    /// network.
+    ///
+    /// `subnet_id` is the subnet from which we received this sync message. This function will
+    /// verify that it was received on the correct subnet.
+    pub fn verify<T: BeaconChainTypes>(
+        sync_message: SyncCommitteeMessage,
+        subnet_id: SyncSubnetId,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let partial =
+            PartiallyVerifiedSyncCommitteeMessage::verify(sync_message, subnet_id, chain)?;
+        Self::from_partial(partial, chain, CheckSyncCommitteeSignature::Yes)
+    }
+
+    /// Complete the verification of a partially verified sync committee message.
+    fn from_partial<T: BeaconChainTypes>(
+        partial: PartiallyVerifiedSyncCommitteeMessage,
+        chain: &BeaconChain<T>,
+        check_signature: CheckSyncCommitteeSignature,
+    ) -> Result<Self, Error> {
+        let PartiallyVerifiedSyncCommitteeMessage {
+            sync_message,
+            subnet_id,
+            subnet_positions,
+            pubkey,
+        } = partial;
+
+        match check_signature {
+            // The aggregate signature of the sync committee message is valid.
+            CheckSyncCommitteeSignature::Yes => {
+                verify_sync_committee_message(chain, &sync_message, &pubkey)?
+            }
+            CheckSyncCommitteeSignature::No => (),
+        }
 
         // Now that the sync committee message has been fully verified, store that we have received a valid
         // sync committee message from this validator.
@@ -474,6 +586,7 @@ impl VerifiedSyncCommitteeMessage {
         // It's important to double check that the sync committee message still hasn't been observed, since
         // there can be a race-condition if we receive two sync committee messages at the same time and
         // process them in different threads.
+        let validator_index = sync_message.validator_index;
         if chain
             .observed_sync_contributors
             .write()
@@ -568,6 +681,24 @@ pub fn verify_signed_aggregate_signatures<T: BeaconChainTypes>(
         .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
         .ok_or(BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
 
+    let signature_sets = signed_aggregate_signature_sets(
+        chain,
+        &pubkey_cache,
+        signed_aggregate,
+        participant_pubkeys,
+    )?;
+
+    Ok(verify_signature_sets(signature_sets.iter()))
+}
+
+/// Returns the three signature sets which must be valid for `signed_aggregate` to be valid. See
+/// `verify_signed_aggregate_signatures`.
+fn signed_aggregate_signature_sets<'a, T: BeaconChainTypes>(
+    chain: &'a BeaconChain<T>,
+    pubkey_cache: &'a ValidatorPubkeyCache<T>,
+    signed_aggregate: &'a SignedContributionAndProof<T::EthSpec>,
+    participant_pubkeys: &[PublicKeyBytes],
+) -> Result<Vec<SignatureSet<'a>>, Error> {
     let aggregator_index = signed_aggregate.message.aggregator_index;
     if aggregator_index >= pubkey_cache.len() as u64 {
         return Err(Error::AggregatorPubkeyUnknown(aggregator_index));
@@ -577,7 +708,7 @@ pub fn verify_signed_aggregate_signatures<T: BeaconChainTypes>(
         (signed_aggregate.message.contribution.slot + 1).epoch(T::EthSpec::slots_per_epoch());
     let fork = chain.spec.fork_at_epoch(next_slot_epoch);
 
-    let signature_sets = vec![
+    Ok(vec![
         signed_sync_aggregate_selection_proof_signature_set(
             |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
             signed_aggregate,
@@ -613,9 +744,7 @@ pub fn verify_signed_aggregate_signatures<T: BeaconChainTypes>(
             &chain.spec,
         )
         .map_err(BeaconChainError::SignatureSetError)?,
-    ];
-
-    Ok(verify_signature_sets(signature_sets.iter()))
+    ])
 }
 
 /// Verifies that the signature of the `sync_message` is valid.
@@ -632,6 +761,37 @@ pub fn verify_sync_committee_message<T: BeaconChainTypes>(
         .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
         .ok_or(BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
 
+    let agg_sig = AggregateSignature::from(&sync_message.signature);
+    let signature_set = sync_committee_message_signature_set(
+        chain,
+        &pubkey_cache,
+        sync_message,
+        &agg_sig,
+        pubkey_bytes,
+    )?;
+
+    metrics::stop_timer(signature_setup_timer);
+
+    let _signature_verification_timer =
+        metrics::start_timer(&metrics::SYNC_MESSAGE_PROCESSING_SIGNATURE_TIMES);
+
+    if signature_set.verify() {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
+/// Returns the signature set which must be valid for `sync_message` to be valid.
+///
+/// The `signature` must be the `sync_message.signature` converted into an `AggregateSignature`.
+fn sync_committee_message_signature_set<'a, T: BeaconChainTypes>(
+    chain: &'a BeaconChain<T>,
+    pubkey_cache: &'a ValidatorPubkeyCache<T>,
+    sync_message: &SyncCommitteeMessage,
+    signature: &'a AggregateSignature,
+    pubkey_bytes: &PublicKeyBytes,
+) -> Result<SignatureSet<'a>, Error> {
     let pubkey = pubkey_cache
         .get_pubkey_from_pubkey_bytes(pubkey_bytes)
         .map(Cow::Borrowed)
@@ -640,26 +800,14 @@ pub fn verify_sync_committee_message<T: BeaconChainTypes>(
     let next_slot_epoch = (sync_message.get_slot() + 1).epoch(T::EthSpec::slots_per_epoch());
     let fork = chain.spec.fork_at_epoch(next_slot_epoch);
 
-    let agg_sig = AggregateSignature::from(&sync_message.signature);
-    let signature_set = sync_committee_message_set_from_pubkeys::<T::EthSpec>(
+    sync_committee_message_set_from_pubkeys::<T::EthSpec>(
         pubkey,
-        &agg_sig,
+        signature,
         sync_message.slot.epoch(T::EthSpec::slots_per_epoch()),
         sync_message.beacon_block_root,
         &fork,
         chain.genesis_validators_root,
         &chain.spec,
     )
-    .map_err(BeaconChainError::SignatureSetError)?;
-
-    metrics::stop_timer(signature_setup_timer);
-
-    let _signature_verification_timer =
-        metrics::start_timer(&metrics::SYNC_MESSAGE_PROCESSING_SIGNATURE_TIMES);
-
-    if signature_set.verify() {
-        Ok(())
-    } else {
-        Err(Error::InvalidSignature)
-    }
+    .map_err(|e| BeaconChainError::SignatureSetError(e).into())
 }
@@ -0,0 +1,191 @@
+//! These two `batch_...` functions provide verification of batches of sync committee messages and
+//! contributions. They provide significant CPU-time savings by performing batch verification of
+//! BLS signatures.
+//!
+//! In each function, messages are "partially verified" (i.e., all checks which precede signature
+//! verification are performed). Then, all messages which were successfully partially verified have
+//! their signatures verified in a batch. If that signature batch fails then all signatures are
+//! verified independently.
+//!
+//! The outcome of each function is a `Vec<Result>` with a one-to-one mapping to the messages
+//! supplied as input. Each result provides the exact success or failure result of the corresponding
+//! message, with no loss of fidelity when compared to individual verification.
+use super::{
+    signed_aggregate_signature_sets, sync_committee_message_signature_set,
+    CheckSyncCommitteeSignature, Error, PartiallyVerifiedSyncCommitteeMessage,
+    PartiallyVerifiedSyncContribution, VerifiedSyncCommitteeMessage, VerifiedSyncContribution,
+};
+use crate::{
+    beacon_chain::VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT, metrics, BeaconChain, BeaconChainError,
+    BeaconChainTypes,
+};
+use bls::verify_signature_sets;
+use types::*;
+
+/// Verify sync committee contributions using batch BLS signature verification.
+///
+/// See module-level docs for more info.
+pub fn batch_verify_sync_contributions<T, I>(
+    contributions: I,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<Result<VerifiedSyncContribution<T>, Error>>, Error>
+where
+    T: BeaconChainTypes,
+    I: Iterator<Item = SignedContributionAndProof<T::EthSpec>> + ExactSizeIterator,
+{
+    let mut num_partially_verified = 0;
+
+    // Perform partial verification of all contributions, collecting the results.
+    let partial_results = contributions
+        .map(|signed_aggregate| {
+            let result = PartiallyVerifiedSyncContribution::verify(signed_aggregate, chain);
+            if result.is_ok() {
+                num_partially_verified += 1;
+            }
+            result
+        })
+        .collect::<Vec<_>>();
+
+    // May be set to `No` if batch verification succeeds.
+    let mut check_signatures = CheckSyncCommitteeSignature::Yes;
+
+    // Perform batch BLS verification, if any contribution signatures are worth checking.
+    if num_partially_verified > 0 {
+        let signature_setup_timer = metrics::start_timer(
+            &metrics::SYNC_CONTRIBUTION_PROCESSING_BATCH_SIGNATURE_SETUP_TIMES,
+        );
+
+        let pubkey_cache = chain
+            .validator_pubkey_cache
+            .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+            .ok_or(BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
+
+        let mut signature_sets = Vec::with_capacity(num_partially_verified * 3);
+
+        // Iterate, flattening to get only the `Ok` values.
+        for partial in partial_results.iter().flatten() {
+            signature_sets.extend(signed_aggregate_signature_sets(
+                chain,
+                &pubkey_cache,
+                &partial.signed_aggregate,
+                &partial.participant_pubkeys,
+            )?);
+        }
+
+        metrics::stop_timer(signature_setup_timer);
+
+        let _signature_verification_timer =
+            metrics::start_timer(&metrics::SYNC_CONTRIBUTION_PROCESSING_BATCH_SIGNATURE_TIMES);
+
+        if verify_signature_sets(signature_sets.iter()) {
+            // Since all the signatures verified in a batch, there's no reason for them to be
+            // checked again later.
+            check_signatures = CheckSyncCommitteeSignature::No
+        } else {
+            metrics::inc_counter_vec(
+                &metrics::BATCH_SIGNATURE_VERIFICATION_FAILURES,
+                &["sync_contribution"],
+            );
+        }
+    }
+
+    // Complete the verification, potentially verifying all signatures independently.
+    let final_results = partial_results
+        .into_iter()
+        .map(|result| match result {
+            Ok(partial) => VerifiedSyncContribution::from_partial(partial, chain, check_signatures),
+            Err(e) => Err(e),
+        })
+        .collect();
+
+    Ok(final_results)
+}
+
+/// Verify sync committee messages using batch BLS signature verification.
+///
+/// See module-level docs for more info.
+pub fn batch_verify_sync_committee_messages<T, I>(
+    messages: I,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<Result<VerifiedSyncCommitteeMessage, Error>>, Error>
+where
+    T: BeaconChainTypes,
+    I: Iterator<Item = (SyncCommitteeMessage, SyncSubnetId)> + ExactSizeIterator,
+{
+    let mut num_partially_verified = 0;
+
+    // Perform partial verification of all messages, collecting the results.
+    let partial_results = messages
+        .map(|(sync_message, subnet_id)| {
+            let result =
+                PartiallyVerifiedSyncCommitteeMessage::verify(sync_message, subnet_id, chain);
+            if result.is_ok() {
+                num_partially_verified += 1;
+            }
+            result
+        })
+        .collect::<Vec<_>>();
+
+    // May be set to `No` if batch verification succeeds.
+    let mut check_signatures = CheckSyncCommitteeSignature::Yes;
+
+    // Perform batch BLS verification, if any message signatures are worth checking.
+    if num_partially_verified > 0 {
+        let signature_setup_timer =
+            metrics::start_timer(&metrics::SYNC_MESSAGE_PROCESSING_BATCH_SIGNATURE_SETUP_TIMES);
+
+        let pubkey_cache = chain
+            .validator_pubkey_cache
+            .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+            .ok_or(BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
+
+        // The signature sets borrow the aggregate signatures, so they must be collected first.
+        let signatures = partial_results
+            .iter()
+            .flatten()
+            .map(|partial| AggregateSignature::from(&partial.sync_message.signature))
+            .collect::<Vec<_>>();
+
+        let mut signature_sets = Vec::with_capacity(num_partially_verified);
+
+        // Iterate, flattening to get only the `Ok` values.
+        for (partial, signature) in partial_results.iter().flatten().zip(signatures.iter()) {
+            signature_sets.push(sync_committee_message_signature_set(
+                chain,
+                &pubkey_cache,
+                &partial.sync_message,
+                signature,
+                &partial.pubkey,
+            )?);
+        }
+
+        metrics::stop_timer(signature_setup_timer);
+
+        let _signature_verification_timer =
+            metrics::start_timer(&metrics::SYNC_MESSAGE_PROCESSING_BATCH_SIGNATURE_TIMES);
+
+        if verify_signature_sets(signature_sets.iter()) {
+            // Since all the signatures verified in a batch, there's no reason for them to be
+            // checked again later.
+            check_signatures = CheckSyncCommitteeSignature::No
+        } else {
+            metrics::inc_counter_vec(
+                &metrics::BATCH_SIGNATURE_VERIFICATION_FAILURES,
+                &["sync_message"],
+            );
+        }
+    }
+
+    // Complete the verification, potentially verifying all signatures independently.
+    let final_results = partial_results
+        .into_iter()
+        .map(|result| match result {
+            Ok(partial) => {
+                VerifiedSyncCommitteeMessage::from_partial(partial, chain, check_signatures)
+            }
+            Err(e) => Err(e),
+        })
+        .collect();
+
+    Ok(final_results)
+}
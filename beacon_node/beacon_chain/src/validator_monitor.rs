@@ -3,7 +3,8 @@
 //! This component should not affect consensus.
 
 use crate::metrics;
-use parking_lot::RwLock;
+use eth2::lighthouse::ValidatorMonitorEpochSummary;
+use parking_lot::{Mutex, RwLock};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use state_processing::per_epoch_processing::{
@@ -92,6 +93,10 @@ struct EpochSummary {
     pub proposer_slashings: usize,
     /// The number of attester slashings observed.
     pub attester_slashings: usize,
+    /// The balance of the validator in the first valid state observed during this epoch.
+    pub start_balance: Option<u64>,
+    /// Whether the validator was a member of the sync committee during this epoch.
+    pub sync_committee_member: bool,
 }
 
 impl EpochSummary {
@@ -160,6 +165,12 @@ impl EpochSummary {
     pub fn register_attester_slashing(&mut self) {
         self.attester_slashings += 1;
     }
+
+    pub fn register_start_balance(&mut self, balance: u64) {
+        if self.start_balance.is_none() {
+            self.start_balance = Some(balance);
+        }
+    }
 }
 
 type SummaryMap = HashMap<Epoch, EpochSummary>;
@@ -253,6 +264,9 @@ pub struct ValidatorMonitor<T> {
     indices: HashMap<u64, PublicKeyBytes>,
     /// If true, allow the automatic registration of validators.
     auto_register: bool,
+    /// Per-epoch summaries which have been finalized by `Self::process_validator_statuses` but not
+    /// yet persisted to disk.
+    pending_history: Mutex<Vec<ValidatorMonitorEpochSummary>>,
     log: Logger,
     _phantom: PhantomData<T>,
 }
@@ -263,6 +277,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             validators: <_>::default(),
             indices: <_>::default(),
             auto_register,
+            pending_history: <_>::default(),
             log,
             _phantom: PhantomData,
         };
@@ -316,6 +331,9 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 let id = &monitored_validator.id;
 
                 if let Some(balance) = state.balances().get(i) {
+                    monitored_validator.with_epoch_summary(current_epoch, |summary| {
+                        summary.register_start_balance(*balance)
+                    });
                     metrics::set_int_gauge(
                         &metrics::VALIDATOR_MONITOR_BALANCE_GWEI,
                         &[id],
@@ -535,6 +553,32 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                     );
                 }
 
+                // Record the performance of the validator in the previous epoch, to be persisted
+                // by the `BeaconChain`.
+                let history = {
+                    let summaries = monitored_validator.summaries.read();
+                    let prev_epoch_summary = summaries.get(&prev_epoch);
+                    ValidatorMonitorEpochSummary {
+                        epoch: prev_epoch,
+                        validator_index: i as u64,
+                        attestation_hit: previous_epoch_matched_any,
+                        attestation_head_hit: previous_epoch_matched_head,
+                        attestation_target_hit: previous_epoch_matched_target,
+                        attestation_min_inclusion_distance: min_inclusion_distance,
+                        blocks_proposed: prev_epoch_summary
+                            .map_or(0, |summary| summary.blocks as u64),
+                        sync_committee_member: prev_epoch_summary
+                            .map_or(false, |summary| summary.sync_committee_member),
+                        sync_signature_block_inclusions: prev_epoch_summary
+                            .map_or(0, |summary| summary.sync_signature_block_inclusions as u64),
+                        start_balance: prev_epoch_summary.and_then(|summary| summary.start_balance),
+                        end_balance: summaries
+                            .get(&(prev_epoch + 1))
+                            .and_then(|summary| summary.start_balance),
+                    }
+                };
+                self.pending_history.lock().push(history);
+
                 // Indicates the number of sync committee signatures that made it into
                 // a sync aggregate in the current_epoch (state.epoch - 1).
                 // Note: Unlike attestations, sync committee signatures must be included in the
@@ -543,6 +587,9 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 let current_epoch = epoch - 1;
                 if let Some(sync_committee) = summary.sync_committee() {
                     if sync_committee.contains(pubkey) {
+                        monitored_validator.with_epoch_summary(current_epoch, |summary| {
+                            summary.sync_committee_member = true
+                        });
                         metrics::set_int_gauge(
                             &metrics::VALIDATOR_MONITOR_VALIDATOR_IN_CURRENT_SYNC_COMMITTEE,
                             &[id],
@@ -638,6 +685,13 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             .and_then(|pubkey| self.validators.get(pubkey))
     }
 
+    /// Returns the per-epoch summaries generated since the last call to this function.
+    ///
+    /// The `BeaconChain` persists these summaries to disk so that they survive restarts.
+    pub fn take_pending_history(&self) -> Vec<ValidatorMonitorEpochSummary> {
+        std::mem::take(&mut *self.pending_history.lock())
+    }
+
     /// Returns the number of validators monitored by `self`.
     pub fn num_validators(&self) -> usize {
         self.validators.len()
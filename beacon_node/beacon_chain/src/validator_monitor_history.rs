@@ -0,0 +1,126 @@
+//! Persistence for the per-epoch validator summaries produced by the `ValidatorMonitor`.
+//!
+//! Each summary is stored in the `DBColumn::ValidatorMonitorHistory` column under a key which
+//! encodes its epoch followed by its validator index. Ordering the keys by epoch first allows old
+//! summaries to be pruned by iterating from the start of the column, whilst the summaries for a
+//! single validator can be read with one lookup per epoch.
+//!
+//! Summaries are retained for `ChainConfig::validator_monitor_history_epochs`.
+use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::ValidatorMonitorEpochSummary;
+use ssz::{Decode, Encode};
+use std::cmp;
+use store::{get_key_for_col, DBColumn, Error as StoreError, KeyValueStore, KeyValueStoreOp};
+use types::{Epoch, Hash256};
+
+/// Returns the database key for the summary of `validator_index` at `epoch`.
+fn history_key(epoch: Epoch, validator_index: u64) -> Hash256 {
+    let mut key = Hash256::zero();
+    key.as_bytes_mut()[0..8].copy_from_slice(&epoch.as_u64().to_be_bytes());
+    key.as_bytes_mut()[8..16].copy_from_slice(&validator_index.to_be_bytes());
+    key
+}
+
+/// Returns the epoch of a key produced by `history_key`.
+fn history_key_epoch(key: &Hash256) -> Epoch {
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&key.as_bytes()[0..8]);
+    Epoch::new(u64::from_be_bytes(bytes))
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Writes any summaries which are pending in the `ValidatorMonitor` to disk, pruning any
+    /// summaries which have fallen outside of the retention period.
+    pub fn persist_validator_monitor_history(&self) -> Result<(), BeaconChainError> {
+        let pending = self.validator_monitor.read().take_pending_history();
+        let retained_epochs = self.config.validator_monitor_history_epochs;
+        if pending.is_empty() || retained_epochs == 0 {
+            return Ok(());
+        }
+
+        let _timer = metrics::start_timer(&metrics::PERSIST_VALIDATOR_MONITOR_HISTORY);
+
+        let column = DBColumn::ValidatorMonitorHistory;
+        let mut ops = pending
+            .iter()
+            .map(|summary| {
+                let key = history_key(summary.epoch, summary.validator_index);
+                KeyValueStoreOp::PutKeyValue(
+                    get_key_for_col(column.into(), key.as_bytes()),
+                    summary.as_ssz_bytes(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(latest_epoch) = pending.iter().map(|summary| summary.epoch).max() {
+            let oldest_retained_epoch = latest_epoch.saturating_sub(retained_epochs - 1);
+            for key in self.store.hot_db.iter_column_keys(column) {
+                let key = key?;
+                if history_key_epoch(&key) >= oldest_retained_epoch {
+                    break;
+                }
+                ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    column.into(),
+                    key.as_bytes(),
+                )));
+            }
+        }
+
+        self.store.hot_db.do_atomically(ops)?;
+
+        Ok(())
+    }
+
+    /// Returns the persisted summaries of the validator with `validator_index` for each epoch in
+    /// the inclusive range `from_epoch..=to_epoch`, in ascending epoch order.
+    ///
+    /// Epochs prior to the retention period relative to `to_epoch` are ignored, as are epochs for
+    /// which no summary exists.
+    pub fn validator_monitor_history(
+        &self,
+        validator_index: u64,
+        from_epoch: Epoch,
+        to_epoch: Epoch,
+    ) -> Result<Vec<ValidatorMonitorEpochSummary>, BeaconChainError> {
+        let retained_epochs = self.config.validator_monitor_history_epochs;
+        if retained_epochs == 0 {
+            return Ok(vec![]);
+        }
+
+        let from_epoch = cmp::max(from_epoch, to_epoch.saturating_sub(retained_epochs - 1));
+        let column: &str = DBColumn::ValidatorMonitorHistory.into();
+
+        let mut summaries = vec![];
+        for epoch in from_epoch.as_u64()..=to_epoch.as_u64() {
+            let key = history_key(Epoch::new(epoch), validator_index);
+            if let Some(bytes) = self.store.hot_db.get_bytes(column, key.as_bytes())? {
+                summaries.push(
+                    ValidatorMonitorEpochSummary::from_ssz_bytes(&bytes)
+                        .map_err(StoreError::from)?,
+                );
+            }
+        }
+
+        Ok(summaries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_keys_are_ordered_by_epoch() {
+        let keys = [
+            history_key(Epoch::new(0), 0),
+            history_key(Epoch::new(0), u64::max_value()),
+            history_key(Epoch::new(1), 0),
+            history_key(Epoch::new(256), 1),
+        ];
+
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        for (key, epoch) in keys.iter().zip([0, 0, 1, 256]) {
+            assert_eq!(history_key_epoch(key), Epoch::new(epoch));
+        }
+    }
+}
@@ -162,6 +162,47 @@ fn proposer_slashing() {
     ));
 }
 
+#[test]
+fn proposer_slashing_batch() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), VALIDATOR_COUNT);
+
+    let slashing1 = harness.make_proposer_slashing(VALIDATOR_COUNT as u64 - 1);
+    let slashing3 = harness.make_proposer_slashing(VALIDATOR_COUNT as u64 - 3);
+
+    // A slashing with a signature from a different proposer.
+    let mut invalid_slashing = harness.make_proposer_slashing(VALIDATOR_COUNT as u64 - 2);
+    invalid_slashing.signed_header_1.signature = slashing3.signed_header_1.signature.clone();
+
+    let results = harness
+        .chain
+        .batch_verify_proposer_slashings_for_gossip(vec![
+            slashing1.clone(),
+            slashing1.clone(),
+            invalid_slashing,
+            slashing3.clone(),
+        ])
+        .unwrap();
+
+    // The invalid signature causes the batch to fail, but each slashing should still have the
+    // same result as if it were verified individually.
+    assert_eq!(results.len(), 4);
+    assert!(matches!(results[0], Ok(ObservationOutcome::New(_))));
+    assert!(matches!(results[1], Ok(ObservationOutcome::AlreadyKnown)));
+    assert!(results[2].is_err());
+    assert!(matches!(results[3], Ok(ObservationOutcome::New(_))));
+
+    // All valid slashings have been observed.
+    assert!(matches!(
+        harness
+            .chain
+            .verify_proposer_slashing_for_gossip(slashing3)
+            .unwrap(),
+        ObservationOutcome::AlreadyKnown
+    ));
+}
+
 #[test]
 fn attester_slashing() {
     let db_path = tempdir().unwrap();
@@ -232,3 +273,100 @@ fn attester_slashing() {
         ObservationOutcome::AlreadyKnown
     ));
 }
+
+#[tokio::test]
+async fn voluntary_exit_batch() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), VALIDATOR_COUNT);
+    let spec = &harness.chain.spec.clone();
+
+    harness
+        .extend_chain(
+            (E::slots_per_epoch() * (spec.shard_committee_period + 1)) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let epoch = Epoch::new(spec.shard_committee_period);
+    let exit1 = harness.make_voluntary_exit(VALIDATOR_COUNT as u64 - 1, epoch);
+    let exit3 = harness.make_voluntary_exit(VALIDATOR_COUNT as u64 - 3, epoch);
+
+    // An exit with a signature from a different validator.
+    let mut invalid_exit = harness.make_voluntary_exit(VALIDATOR_COUNT as u64 - 2, epoch);
+    invalid_exit.signature = exit3.signature.clone();
+
+    let results = harness
+        .chain
+        .batch_verify_voluntary_exits_for_gossip(vec![
+            exit1.clone(),
+            exit1.clone(),
+            invalid_exit,
+            exit3.clone(),
+        ])
+        .unwrap();
+
+    // The invalid signature causes the batch to fail, but each exit should still have the same
+    // result as if it were verified individually.
+    assert_eq!(results.len(), 4);
+    assert!(matches!(results[0], Ok(ObservationOutcome::New(_))));
+    assert!(matches!(results[1], Ok(ObservationOutcome::AlreadyKnown)));
+    assert!(results[2].is_err());
+    assert!(matches!(results[3], Ok(ObservationOutcome::New(_))));
+
+    // All valid exits have been observed.
+    assert!(matches!(
+        harness.chain.verify_voluntary_exit_for_gossip(exit3),
+        Ok(ObservationOutcome::AlreadyKnown)
+    ));
+}
+
+#[test]
+fn attester_slashing_batch() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), VALIDATOR_COUNT);
+
+    // First third of the validators
+    let first_third = (0..VALIDATOR_COUNT as u64 / 3).collect::<Vec<_>>();
+    // Second third of the validators
+    let second_third =
+        (VALIDATOR_COUNT as u64 / 3..2 * VALIDATOR_COUNT as u64 / 3).collect::<Vec<_>>();
+    // Last third of the validators
+    let last_third = (2 * VALIDATOR_COUNT as u64 / 3..VALIDATOR_COUNT as u64).collect::<Vec<_>>();
+
+    let slashing1 = harness.make_attester_slashing(first_third);
+    let slashing3 = harness.make_attester_slashing(last_third);
+
+    // A slashing with a signature from a different set of attesters.
+    let mut invalid_slashing = harness.make_attester_slashing(second_third);
+    invalid_slashing.attestation_1.signature = slashing3.attestation_1.signature.clone();
+
+    let results = harness
+        .chain
+        .batch_verify_attester_slashings_for_gossip(vec![
+            slashing1.clone(),
+            slashing1.clone(),
+            invalid_slashing,
+            slashing3.clone(),
+        ])
+        .unwrap();
+
+    // The invalid signature causes the batch to fail, but each slashing should still have the
+    // same result as if it were verified individually.
+    assert_eq!(results.len(), 4);
+    assert!(matches!(results[0], Ok(ObservationOutcome::New(_))));
+    assert!(matches!(results[1], Ok(ObservationOutcome::AlreadyKnown)));
+    assert!(results[2].is_err());
+    assert!(matches!(results[3], Ok(ObservationOutcome::New(_))));
+
+    // All valid slashings have been observed.
+    assert!(matches!(
+        harness
+            .chain
+            .verify_attester_slashing_for_gossip(slashing3)
+            .unwrap(),
+        ObservationOutcome::AlreadyKnown
+    ));
+}
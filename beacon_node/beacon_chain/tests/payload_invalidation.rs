@@ -6,9 +6,10 @@ use beacon_chain::otb_verification_service::{
 };
 use beacon_chain::{
     canonical_head::{CachedHead, CanonicalHead},
+    chain_config::DEFAULT_RE_ORG_THRESHOLD,
     test_utils::{BeaconChainHarness, EphemeralHarnessType},
-    BeaconChainError, BlockError, ExecutionPayloadError, StateSkipConfig, WhenSlotSkipped,
-    INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
+    BeaconChainError, BlockError, ChainConfig, ExecutionPayloadError, StateSkipConfig,
+    WhenSlotSkipped, INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
     INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
 };
 use execution_layer::{
@@ -53,12 +54,17 @@ impl InvalidPayloadRig {
         Self::new_with_spec(spec)
     }
 
-    fn new_with_spec(mut spec: ChainSpec) -> Self {
+    fn new_with_spec(spec: ChainSpec) -> Self {
+        Self::new_with_chain_config(spec, ChainConfig::default())
+    }
+
+    fn new_with_chain_config(mut spec: ChainSpec, chain_config: ChainConfig) -> Self {
         spec.altair_fork_epoch = Some(Epoch::new(0));
         spec.bellatrix_fork_epoch = Some(Epoch::new(0));
 
         let harness = BeaconChainHarness::builder(MainnetEthSpec)
             .spec(spec)
+            .chain_config(chain_config)
             .deterministic_keypairs(VALIDATOR_COUNT)
             .mock_execution_layer()
             .fresh_ephemeral_store()
@@ -1117,6 +1123,101 @@ async fn payload_preparation_before_transition_block() {
     assert_eq!(fork_choice_state.head_block_hash, latest_block_hash);
 }
 
+/// Tests that once a payload has been prepared upon the parent of a late, weak head, later
+/// `forkchoiceUpdated` messages during the head's slot continue to send the parent as the head.
+#[tokio::test]
+async fn re_org_forkchoice_update_keeps_parent_head() {
+    let chain_config = ChainConfig {
+        re_org_threshold: Some(DEFAULT_RE_ORG_THRESHOLD),
+        ..ChainConfig::default()
+    };
+    let mut rig = InvalidPayloadRig::new_with_chain_config(E::default_spec(), chain_config);
+    let el = rig.execution_layer();
+    el.watchdog_task().await;
+
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await; // Import a valid transition block.
+    let parent_root = rig.import_block(Payload::Valid).await;
+
+    // Provide preparation data to the EL for the proposer of the slot after the weak head.
+    let head = rig.harness.chain.head_snapshot();
+    let head_slot = head.beacon_block.slot() + 1;
+    let proposer = head
+        .beacon_state
+        .get_beacon_proposer_index(head_slot + 1, &rig.harness.chain.spec)
+        .unwrap();
+    el.update_proposer_preparation(
+        Epoch::new(0),
+        &[ProposerPreparationData {
+            validator_index: proposer as u64,
+            fee_recipient: Address::repeat_byte(99),
+        }],
+    )
+    .await;
+
+    // Import a weak head which was observed after the attestation deadline of its slot.
+    let state = head.beacon_state.clone_with_only_committee_caches();
+    let (block, _) = rig.harness.make_block(state, head_slot).await;
+    let slot_start = rig.harness.chain.slot_clock.start_of(head_slot).unwrap();
+    rig.harness
+        .chain
+        .block_times_cache
+        .write()
+        .set_time_observed(
+            block.canonical_root(),
+            head_slot,
+            slot_start + Duration::from_secs(8),
+            None,
+            None,
+        );
+    rig.harness
+        .mock_execution_layer
+        .as_ref()
+        .unwrap()
+        .server
+        .full_payload_verification();
+    let head_root: Hash256 = rig
+        .harness
+        .process_block(head_slot, block)
+        .await
+        .unwrap()
+        .into();
+    assert_eq!(rig.cached_head().head_block_root(), head_root);
+
+    // Prepare a payload which re-orgs the weak head.
+    rig.harness
+        .chain
+        .prepare_beacon_proposer(head_slot)
+        .await
+        .unwrap();
+    let (fork_choice_state, _) = rig.previous_forkchoice_update_params();
+    assert_eq!(
+        fork_choice_state.head_block_hash,
+        rig.block_hash(parent_root)
+    );
+
+    // A later update for the canonical head must not switch the execution layer back to the weak
+    // head.
+    let forkchoice_update_params = rig
+        .harness
+        .chain
+        .canonical_head
+        .fork_choice_read_lock()
+        .get_forkchoice_update_parameters();
+    assert_eq!(forkchoice_update_params.head_root, head_root);
+    rig.harness
+        .chain
+        .update_execution_engine_forkchoice(head_slot, forkchoice_update_params)
+        .await
+        .unwrap();
+
+    let (fork_choice_state, _) = rig.previous_forkchoice_update_params();
+    assert_eq!(
+        fork_choice_state.head_block_hash,
+        rig.block_hash(parent_root)
+    );
+}
+
 #[tokio::test]
 async fn attesting_to_optimistic_head() {
     let mut rig = InvalidPayloadRig::new();
@@ -6,9 +6,9 @@ use beacon_chain::test_utils::{
     test_spec, AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
 };
 use beacon_chain::{
-    historical_blocks::HistoricalBlockError, migrate::MigratorConfig, BeaconChain,
-    BeaconChainError, BeaconChainTypes, BeaconSnapshot, ChainConfig, ServerSentEventHandler,
-    WhenSlotSkipped,
+    historical_blocks::HistoricalBlockError, migrate::MigratorConfig, verify_head_tracker,
+    BeaconChain, BeaconChainError, BeaconChainTypes, BeaconSnapshot, ChainConfig,
+    ServerSentEventHandler, WhenSlotSkipped,
 };
 use fork_choice::CountUnrealized;
 use lazy_static::lazy_static;
@@ -22,8 +22,14 @@ use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
 use store::{
+    backup::{restore_backup, restored_manifest_path},
+    chunked_vector::{chunk_key, Chunk},
+    config::OnDiskStoreConfig,
     iter::{BlockRootsIterator, StateRootsIterator},
-    HotColdDB, LevelDB, StoreConfig,
+    metadata::{RestorePointChange, CONFIG_KEY, RESTORE_POINT_CHANGE_KEY},
+    verify::{CheckReport, Issue},
+    DBColumn, Error as StoreError, HotColdDB, ItemStore, KeyValueStore, LevelDB,
+    PartialBeaconState, StoreConfig, StoreOp,
 };
 use tempfile::{tempdir, TempDir};
 use tree_hash::TreeHash;
@@ -94,6 +100,584 @@ async fn full_participation_no_skips() {
     check_iterators(&harness);
 }
 
+#[tokio::test]
+async fn prune_execution_payloads() {
+    let num_blocks_produced = E::slots_per_epoch() * 6;
+    let db_path = tempdir().unwrap();
+    let mut spec = test_spec::<E>();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+    let store = get_store_with_spec(&db_path, spec.clone());
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .spec(spec)
+        .keypairs(KEYPAIRS[0..LOW_VALIDATOR_COUNT].to_vec())
+        .fresh_disk_store(store.clone())
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot > 0);
+
+    // Load all full blocks prior to pruning.
+    let mut blocks = vec![];
+    for snapshot in harness.chain.chain_dump().unwrap() {
+        let block_root = snapshot.beacon_block_root;
+        let block = harness.chain.get_block(&block_root).await.unwrap().unwrap();
+        assert!(store.execution_payload_exists(&block_root).unwrap());
+        blocks.push((block_root, block));
+    }
+
+    let num_pruned = store.try_prune_execution_payloads(false).unwrap();
+    assert_eq!(
+        num_pruned,
+        blocks
+            .iter()
+            .filter(|(_, block)| block.slot() < split_slot)
+            .count()
+    );
+
+    // Pruning again is a no-op.
+    assert_eq!(store.try_prune_execution_payloads(false).unwrap(), 0);
+
+    // Blocks with pruned payloads are reconstructed from the execution layer.
+    for (block_root, block) in blocks {
+        assert_eq!(
+            store.execution_payload_exists(&block_root).unwrap(),
+            block.slot() >= split_slot
+        );
+        assert_eq!(
+            harness.chain.get_block(&block_root).await.unwrap().unwrap(),
+            block
+        );
+    }
+}
+
+fn get_store_with_slots_per_restore_point(
+    db_path: &TempDir,
+    slots_per_restore_point: u64,
+) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+    let hot_path = db_path.path().join("hot_db");
+    let cold_path = db_path.path().join("cold_db");
+    let config = StoreConfig {
+        slots_per_restore_point,
+        ..StoreConfig::default()
+    };
+    let log = test_logger();
+
+    HotColdDB::open(
+        &hot_path,
+        &cold_path,
+        |_, _, _| Ok(()),
+        config,
+        test_spec::<E>(),
+        log,
+    )
+    .expect("disk store should initialize")
+}
+
+/// Return the root of every state in the freezer database.
+fn get_cold_state_roots(store: &HotColdDB<E, LevelDB<E>, LevelDB<E>>) -> Vec<Hash256> {
+    (0..store.get_split_slot().as_u64())
+        .map(|slot| {
+            store
+                .load_cold_state_by_slot(Slot::new(slot))
+                .unwrap()
+                .unwrap()
+                .canonical_root()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn change_slots_per_restore_point() {
+    let num_blocks_produced = E::slots_per_epoch() * 10;
+    let db_path = tempdir().unwrap();
+    let store = get_store_with_slots_per_restore_point(&db_path, 16);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let state_roots = get_cold_state_roots(&store);
+    assert!(state_roots.len() > 32);
+
+    store.change_slots_per_restore_point(32).unwrap();
+
+    // The database can only be re-opened with the new spacing.
+    drop(harness);
+    drop(store);
+    let store = get_store_with_slots_per_restore_point(&db_path, 32);
+    assert_eq!(get_cold_state_roots(&store), state_roots);
+}
+
+/// Return the on-disk slots-per-restore-point, and the pending change of it (if any).
+fn get_restore_point_config(
+    store: &HotColdDB<E, LevelDB<E>, LevelDB<E>>,
+) -> (u64, Option<RestorePointChange>) {
+    let disk_config = store
+        .hot_db
+        .get::<OnDiskStoreConfig>(&CONFIG_KEY)
+        .unwrap()
+        .unwrap();
+    let change = store
+        .cold_db
+        .get::<RestorePointChange>(&RESTORE_POINT_CHANGE_KEY)
+        .unwrap();
+    (disk_config.slots_per_restore_point, change)
+}
+
+#[tokio::test]
+async fn change_slots_per_restore_point_completed_after_interruption() {
+    let num_blocks_produced = E::slots_per_epoch() * 10;
+    let db_path = tempdir().unwrap();
+    let store = get_store_with_slots_per_restore_point(&db_path, 16);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let state_roots = get_cold_state_roots(&store);
+
+    // Interrupt the change between rewriting the freezer database and updating the hot database.
+    store.rewrite_restore_points(32).unwrap();
+    assert_eq!(
+        get_restore_point_config(&store),
+        (
+            16,
+            Some(RestorePointChange {
+                slots_per_restore_point: 32,
+                cold_db_rewritten: true,
+            })
+        )
+    );
+
+    // The change is completed when the database is re-opened.
+    drop(harness);
+    drop(store);
+    let store = get_store_with_slots_per_restore_point(&db_path, 32);
+    assert_eq!(get_restore_point_config(&store), (32, None));
+    assert_eq!(get_cold_state_roots(&store), state_roots);
+}
+
+#[tokio::test]
+async fn change_slots_per_restore_point_rolled_back_after_interruption() {
+    let num_blocks_produced = E::slots_per_epoch() * 10;
+    let db_path = tempdir().unwrap();
+    let store = get_store_with_slots_per_restore_point(&db_path, 16);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let state_roots = get_cold_state_roots(&store);
+
+    // Interrupt the change whilst storing the states for the new restore points.
+    store
+        .cold_db
+        .put_sync(
+            &RESTORE_POINT_CHANGE_KEY,
+            &RestorePointChange {
+                slots_per_restore_point: 8,
+                cold_db_rewritten: false,
+            },
+        )
+        .unwrap();
+    let slot = Slot::new(8);
+    let state_root = state_roots[slot.as_usize()];
+    let state = store.load_cold_state_by_slot(slot).unwrap().unwrap();
+    store
+        .cold_db
+        .do_atomically(vec![
+            PartialBeaconState::from_state_forgetful(&state).as_kv_store_op(state_root)
+        ])
+        .unwrap();
+
+    // The change is rolled back when the database is re-opened.
+    drop(harness);
+    drop(store);
+    let store = get_store_with_slots_per_restore_point(&db_path, 16);
+    assert_eq!(get_restore_point_config(&store), (16, None));
+    assert!(!store
+        .cold_db
+        .key_exists(DBColumn::BeaconState.into(), state_root.as_bytes())
+        .unwrap());
+    assert_eq!(get_cold_state_roots(&store), state_roots);
+}
+
+#[tokio::test]
+async fn prune_and_reconstruct_historic_states() {
+    let num_blocks_produced = E::slots_per_epoch() * 10;
+    let db_path = tempdir().unwrap();
+    let store = get_store_with_slots_per_restore_point(&db_path, 16);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let state_roots = get_cold_state_roots(&store);
+    let split_slot = store.get_split_slot();
+    let block_roots = harness
+        .chain
+        .chain_dump()
+        .unwrap()
+        .iter()
+        .map(|snapshot| snapshot.beacon_block_root)
+        .collect::<Vec<_>>();
+
+    store.prune_historic_states().unwrap();
+
+    // Only the genesis state remains available.
+    assert!(store
+        .load_cold_state_by_slot(Slot::new(0))
+        .unwrap()
+        .is_some());
+    for slot in 1..split_slot.as_u64() {
+        assert!(store
+            .load_cold_state_by_slot(Slot::new(slot))
+            .unwrap()
+            .is_none());
+    }
+
+    // Blocks are retained.
+    for block_root in &block_roots {
+        assert!(store.block_exists(block_root).unwrap());
+    }
+
+    store.reconstruct_historic_states().unwrap();
+    assert_eq!(store.get_anchor_info(), None);
+    assert_eq!(get_cold_state_roots(&store), state_roots);
+}
+
+#[tokio::test]
+async fn historic_state_cache() {
+    let num_blocks_produced = E::slots_per_epoch() * 10;
+    let db_path = tempdir().unwrap();
+    let store = get_store_with_slots_per_restore_point(&db_path, 32);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot > 64);
+    let state_roots = harness
+        .chain
+        .forwards_iter_state_roots_until(Slot::new(0), split_slot - 1)
+        .unwrap()
+        .map(|result| {
+            let (state_root, slot) = result.unwrap();
+            (slot, state_root)
+        })
+        .collect::<HashMap<_, _>>();
+    let check_state = |slot: u64| {
+        let state = store
+            .load_cold_state_by_slot(Slot::new(slot))
+            .unwrap()
+            .unwrap();
+        assert_eq!(state.slot(), slot);
+        assert_eq!(state.canonical_root(), state_roots[&Slot::new(slot)]);
+    };
+    let cache = store.historic_state_cache();
+    cache.clear();
+
+    // Loading an intermediate state also caches the epoch boundary state before it.
+    check_state(30);
+    assert_eq!(cache.slots(), vec![Slot::new(24), Slot::new(30)]);
+
+    // States are replayed from the closest cached state.
+    check_state(27);
+    check_state(31);
+    assert_eq!(
+        cache.slots(),
+        vec![Slot::new(24), Slot::new(27), Slot::new(30), Slot::new(31)]
+    );
+
+    // Concurrent loads of the same state all succeed.
+    let threads = (0..4)
+        .map(|_| {
+            let store = store.clone();
+            std::thread::spawn(move || store.load_cold_state_by_slot(Slot::new(45)))
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        let state = thread.join().unwrap().unwrap().unwrap();
+        assert_eq!(state.canonical_root(), state_roots[&Slot::new(45)]);
+    }
+
+    // Every state is still loaded correctly in any order.
+    for slot in (0..split_slot.as_u64()).rev() {
+        check_state(slot);
+    }
+
+    cache.clear();
+    assert!(cache.slots().is_empty());
+    check_state(50);
+}
+
+#[tokio::test]
+async fn backup_and_restore() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let backup_root = tempdir().unwrap();
+    let backup_dir = backup_root.path().join("backup");
+    let manifest = harness.chain.backup_database(&backup_dir).unwrap();
+    assert_eq!(manifest.split, store.get_split_info());
+
+    // Backups are never written over existing files.
+    assert!(store.backup(backup_root.path()).is_err());
+
+    // Restore the backup, and check that it is verified when opened.
+    let restore_path = tempdir().unwrap();
+    let hot_path = restore_path.path().join("hot_db");
+    let cold_path = restore_path.path().join("cold_db");
+    restore_backup::<E>(&backup_dir, &hot_path, &cold_path, &test_logger()).unwrap();
+    assert!(restored_manifest_path(&hot_path).exists());
+
+    let restored_store = get_store(&restore_path);
+    assert!(!restored_manifest_path(&hot_path).exists());
+    assert_eq!(restored_store.get_split_info(), store.get_split_info());
+    assert_eq!(
+        get_cold_state_roots(&restored_store),
+        get_cold_state_roots(&store)
+    );
+    let head_block_root = harness.head_block_root();
+    assert_eq!(
+        restored_store.get_blinded_block(&head_block_root).unwrap(),
+        store.get_blinded_block(&head_block_root).unwrap()
+    );
+
+    // Restoring over an existing database fails.
+    drop(restored_store);
+    assert!(restore_backup::<E>(&backup_dir, &hot_path, &cold_path, &test_logger()).is_err());
+}
+
+#[tokio::test]
+async fn restored_backup_modified_before_startup() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let backup_root = tempdir().unwrap();
+    let backup_dir = backup_root.path().join("backup");
+    harness.chain.backup_database(&backup_dir).unwrap();
+
+    let restore_path = tempdir().unwrap();
+    let hot_path = restore_path.path().join("hot_db");
+    let cold_path = restore_path.path().join("cold_db");
+    restore_backup::<E>(&backup_dir, &hot_path, &cold_path, &test_logger()).unwrap();
+
+    // Modify the restored database before it is opened.
+    LevelDB::<E>::open(&hot_path)
+        .unwrap()
+        .put_bytes("foo", b"bar", b"baz")
+        .unwrap();
+
+    let result = HotColdDB::open(
+        &hot_path,
+        &cold_path,
+        |_, _, _| Ok(()),
+        StoreConfig::default(),
+        test_spec::<E>(),
+        test_logger(),
+    );
+    assert!(matches!(
+        result,
+        Err(StoreError::BackupChecksumMismatch {
+            database: "hot",
+            ..
+        })
+    ));
+    assert!(restored_manifest_path(&hot_path).exists());
+}
+
+/// Run every consistency check against `store`.
+fn verify_store(store: &HotColdDB<E, LevelDB<E>, LevelDB<E>>, repair: bool) -> Vec<CheckReport> {
+    vec![
+        store.verify_orphaned_states(repair).unwrap(),
+        store.verify_hot_state_summaries(repair).unwrap(),
+        verify_head_tracker(store, repair).unwrap(),
+        store.verify_freezer_chunks().unwrap(),
+        store.verify_root_continuity(repair).unwrap(),
+    ]
+}
+
+#[tokio::test]
+async fn verify_and_repair_database() {
+    // Stop before an epoch boundary, so that the head state can be deleted on repair.
+    let num_blocks_produced = E::slots_per_epoch() * 6 - 1;
+    let db_path = tempdir().unwrap();
+    let store = get_store_with_slots_per_restore_point(&db_path, E::slots_per_epoch());
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    harness.chain.persist_head_and_fork_choice().unwrap();
+    assert!(store.get_split_slot() > E::slots_per_epoch());
+
+    for report in verify_store(&store, false) {
+        assert!(report.passed, "{:?}", report);
+        assert!(report.items_checked > 0, "{:?}", report);
+    }
+
+    // Leave a temporary state behind.
+    let temporary_state_root = Hash256::repeat_byte(0xaa);
+    store
+        .do_atomically(vec![StoreOp::PutStateTemporaryFlag(temporary_state_root)])
+        .unwrap();
+
+    // Delete the head block.
+    let head = harness.chain.head_snapshot();
+    let head_block_root = head.beacon_block_root;
+    store.delete_block(&head_block_root).unwrap();
+
+    // Corrupt a block root in the freezer.
+    let corrupt_slot = 5;
+    let key = chunk_key(0);
+    let mut chunk = Chunk::<Hash256>::load(&store.cold_db, DBColumn::BeaconBlockRoots, &key)
+        .unwrap()
+        .unwrap();
+    chunk.values[corrupt_slot] = Hash256::repeat_byte(0xbb);
+    let mut ops = vec![];
+    chunk
+        .store(DBColumn::BeaconBlockRoots, &key, &mut ops)
+        .unwrap();
+    store.cold_db.do_atomically(ops).unwrap();
+
+    // Verification reports the issues without changing the database.
+    let reports = verify_store(&store, false);
+    assert_eq!(verify_store(&store, false), reports);
+    let issues = reports
+        .iter()
+        .map(|report| {
+            assert!(report.issues.iter().all(|issue| !issue.repaired));
+            report
+                .issues
+                .iter()
+                .map(|issue| issue.issue.clone())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        issues[0],
+        vec![Issue::TemporaryState {
+            state_root: temporary_state_root
+        }]
+    );
+    assert_eq!(
+        issues[1],
+        vec![Issue::MissingLatestBlock {
+            state_root: head.beacon_state_root(),
+            slot: head.beacon_state.slot(),
+            latest_block_root: head_block_root,
+        }]
+    );
+    assert_eq!(
+        issues[2],
+        vec![Issue::MissingHeadBlock {
+            block_root: head_block_root,
+            slot: head.beacon_block.slot(),
+        }]
+    );
+    assert!(issues[3].is_empty());
+    assert_eq!(
+        issues[4],
+        vec![Issue::RootMismatch {
+            column: DBColumn::BeaconBlockRoots.into(),
+            slot: Slot::new(corrupt_slot as u64),
+            freezer_root: Hash256::repeat_byte(0xbb),
+            split_state_root: *head
+                .beacon_state
+                .get_block_root(Slot::new(corrupt_slot as u64))
+                .unwrap(),
+        }]
+    );
+
+    // Repair everything, after which the database is consistent again.
+    for report in verify_store(&store, true) {
+        assert!(report.passed, "{:?}", report);
+    }
+    for report in verify_store(&store, false) {
+        assert!(report.issues.is_empty(), "{:?}", report);
+    }
+    assert!(store
+        .load_hot_state_summary(&head.beacon_state_root())
+        .unwrap()
+        .is_none());
+
+    // Missing freezer chunks can't be repaired.
+    store
+        .cold_db
+        .key_delete(DBColumn::BeaconRandaoMixes.into(), &key)
+        .unwrap();
+    let report = store.verify_freezer_chunks().unwrap();
+    assert!(!report.passed);
+    assert_eq!(
+        report.issues[0].issue,
+        Issue::MissingChunk {
+            column: DBColumn::BeaconRandaoMixes.into(),
+            chunk_index: 0,
+        }
+    );
+}
+
 #[tokio::test]
 async fn randomised_skips() {
     let num_slots = E::slots_per_epoch() * 5;
@@ -2324,6 +2908,168 @@ async fn finalizes_after_resuming_from_db() {
     );
 }
 
+#[tokio::test]
+async fn gossip_caches_persist_across_restart() {
+    let validator_count = 16;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), validator_count);
+
+    harness
+        .extend_chain(
+            E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let head = harness.chain.head_snapshot();
+    let current_slot = harness.chain.slot().unwrap();
+    let current_epoch = current_slot.epoch(E::slots_per_epoch());
+
+    // Populate the naive aggregation pool and the observed caches at the current slot.
+    let attestation = harness
+        .get_unaggregated_attestations(
+            &AttestationStrategy::AllValidators,
+            &head.beacon_state,
+            head.beacon_state_root(),
+            head.beacon_block_root,
+            current_slot,
+        )
+        .into_iter()
+        .flatten()
+        .map(|(attestation, _subnet_id)| attestation)
+        .next()
+        .expect("should produce an attestation");
+    harness
+        .chain
+        .naive_aggregation_pool
+        .write()
+        .insert(&attestation)
+        .expect("should insert attestation");
+    harness
+        .chain
+        .observed_gossip_attesters
+        .write()
+        .observe_validator(current_epoch, 1)
+        .unwrap();
+    harness
+        .chain
+        .observed_aggregators
+        .write()
+        .observe_validator(current_epoch, 2)
+        .unwrap();
+
+    let num_pooled_attestations = harness.chain.naive_aggregation_pool.read().num_items();
+    let expected_aggregate = harness
+        .chain
+        .get_aggregated_attestation(&attestation.data)
+        .unwrap()
+        .expect("should have aggregate prior to restart");
+
+    harness
+        .chain
+        .persist_gossip_caches()
+        .expect("should persist the gossip caches");
+
+    let slot_clock = harness.chain.slot_clock.clone();
+    drop(harness);
+
+    let resumed_harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS[0..validator_count].to_vec())
+        .resumed_disk_store(store)
+        .mock_execution_layer()
+        .testing_slot_clock(slot_clock)
+        .build();
+    let chain = &resumed_harness.chain;
+
+    assert_eq!(
+        chain.naive_aggregation_pool.read().num_items(),
+        num_pooled_attestations,
+        "naive aggregation pool should be restored"
+    );
+    assert_eq!(
+        chain
+            .get_aggregated_attestation(&attestation.data)
+            .unwrap()
+            .expect("should have aggregate after restart"),
+        expected_aggregate,
+        "restored aggregate should match"
+    );
+    assert_eq!(
+        chain
+            .observed_gossip_attesters
+            .read()
+            .validator_has_been_observed(current_epoch, 1),
+        Ok(true),
+        "observed attester should be restored"
+    );
+    assert_eq!(
+        chain
+            .observed_aggregators
+            .read()
+            .validator_has_been_observed(current_epoch, 2),
+        Ok(true),
+        "observed aggregator should be restored"
+    );
+    assert_eq!(
+        chain
+            .observed_aggregators
+            .read()
+            .validator_has_been_observed(current_epoch, 3),
+        Ok(false),
+        "unobserved aggregator should not be known"
+    );
+}
+
+#[tokio::test]
+async fn gossip_caches_are_discarded_once_stale() {
+    let validator_count = 16;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), validator_count);
+
+    let current_epoch = harness.chain.epoch().unwrap();
+    harness
+        .chain
+        .observed_gossip_attesters
+        .write()
+        .observe_validator(current_epoch, 1)
+        .unwrap();
+    harness
+        .chain
+        .persist_gossip_caches()
+        .expect("should persist the gossip caches");
+
+    // Restart several epochs later, at which point the persisted entries are no longer relevant.
+    let slot_clock = harness.chain.slot_clock.clone();
+    drop(harness);
+    slot_clock.set_slot(
+        (current_epoch + 4)
+            .start_slot(E::slots_per_epoch())
+            .as_u64(),
+    );
+
+    let resumed_harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS[0..validator_count].to_vec())
+        .resumed_disk_store(store)
+        .mock_execution_layer()
+        .testing_slot_clock(slot_clock)
+        .build();
+
+    assert_eq!(
+        resumed_harness
+            .chain
+            .observed_gossip_attesters
+            .read()
+            .observed_validator_count(current_epoch),
+        None,
+        "stale observations should not be restored"
+    );
+}
+
 #[tokio::test]
 async fn revert_minority_fork_on_resume() {
     let validator_count = 16;
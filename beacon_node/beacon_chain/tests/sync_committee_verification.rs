@@ -657,3 +657,133 @@ async fn unaggregated_gossip_verification() {
         if received == subnet_id && !expected.contains(&subnet_id)
     );
 }
+
+#[tokio::test]
+async fn batch_gossip_verification() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    let state = harness.get_current_state();
+
+    harness
+        .add_attested_blocks_at_slots(
+            state,
+            Hash256::zero(),
+            &[Slot::new(1), Slot::new(2)],
+            (0..VALIDATOR_COUNT).collect::<Vec<_>>().as_slice(),
+        )
+        .await;
+
+    let current_slot = harness.chain.slot().expect("should get slot");
+    let head_state = harness.chain.head_beacon_state_cloned();
+    let head_block_root = harness.chain.head_snapshot().beacon_block_root;
+
+    /*
+     * Sync committee messages.
+     */
+
+    // Take the first message from each subnet, ensuring no validator is used twice on a subnet.
+    let messages = harness
+        .make_sync_committee_messages(
+            &head_state,
+            head_block_root,
+            current_slot,
+            RelativeSyncCommittee::Current,
+        )
+        .into_iter()
+        .enumerate()
+        .map(|(subnet_id, messages)| {
+            let (message, _) = messages
+                .first()
+                .expect("subnet should have messages")
+                .clone();
+            (message, SyncSubnetId::new(subnet_id as u64))
+        })
+        .collect::<Vec<_>>();
+    assert!(messages.len() >= 3);
+
+    // A message with the signature of a different validator.
+    let mut invalid_message = messages[1].clone();
+    let validator_index = invalid_message.0.validator_index;
+    invalid_message.0.signature = messages
+        .iter()
+        .find(|(message, _)| message.validator_index != validator_index)
+        .map(|(message, _)| message.signature.clone())
+        .expect("should find another validator");
+
+    let results = harness
+        .chain
+        .batch_verify_sync_committee_messages_for_gossip(
+            vec![messages[0].clone(), invalid_message].into_iter(),
+        )
+        .expect("should verify batch");
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok(), "valid message should be accepted");
+    assert!(
+        matches!(results[1], Err(SyncCommitteeError::InvalidSignature)),
+        "invalid message should be rejected after the batch fails"
+    );
+
+    let results = harness
+        .chain
+        .batch_verify_sync_committee_messages_for_gossip(messages[1..].to_vec().into_iter())
+        .expect("should verify batch");
+    assert!(results.iter().all(Result::is_ok));
+
+    // Every message has now been observed.
+    let results = harness
+        .chain
+        .batch_verify_sync_committee_messages_for_gossip(messages.clone().into_iter())
+        .expect("should verify batch");
+    assert!(results.iter().all(|result| matches!(
+        result,
+        Err(SyncCommitteeError::PriorSyncCommitteeMessageKnown { .. })
+    )));
+
+    /*
+     * Sync committee contributions.
+     */
+
+    let contributions = harness
+        .make_sync_contributions(
+            &head_state,
+            head_block_root,
+            head_state.slot(),
+            RelativeSyncCommittee::Current,
+        )
+        .into_iter()
+        .filter_map(|(_, contribution)| contribution)
+        .collect::<Vec<_>>();
+    assert!(contributions.len() >= 3);
+
+    // A contribution with the signature of a different aggregator.
+    let mut invalid_contribution = contributions[1].clone();
+    invalid_contribution.signature = contributions[2].signature.clone();
+
+    let results = harness
+        .chain
+        .batch_verify_sync_contributions_for_gossip(
+            vec![contributions[0].clone(), invalid_contribution].into_iter(),
+        )
+        .expect("should verify batch");
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok(), "valid contribution should be accepted");
+    assert!(
+        matches!(results[1], Err(SyncCommitteeError::InvalidSignature)),
+        "invalid contribution should be rejected after the batch fails"
+    );
+
+    let results = harness
+        .chain
+        .batch_verify_sync_contributions_for_gossip(contributions[1..].to_vec().into_iter())
+        .expect("should verify batch");
+    assert!(results.iter().all(Result::is_ok));
+
+    // Every contribution has now been observed.
+    let results = harness
+        .chain
+        .batch_verify_sync_contributions_for_gossip(contributions.into_iter())
+        .expect("should verify batch");
+    assert!(results.iter().all(|result| matches!(
+        result,
+        Err(SyncCommitteeError::SyncContributionAlreadyKnown(_))
+    )));
+}
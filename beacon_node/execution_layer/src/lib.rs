@@ -12,6 +12,7 @@ pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
 pub use engines::ForkChoiceState;
 use engines::{Engine, EngineError};
+pub use eth2::lighthouse::{PayloadSelectionReason, PayloadSource};
 use fork_choice::ForkchoiceUpdateParameters;
 use lru::LruCache;
 use payload_status::process_payload_status;
@@ -33,7 +34,7 @@ use tokio::{
 };
 use types::{
     BlindedPayload, BlockType, ChainSpec, Epoch, ExecPayload, ExecutionBlockHash, ForkName,
-    ProposerPreparationData, PublicKeyBytes, SignedBeaconBlock, Slot,
+    ProposerPreparationData, PublicKeyBytes, SignedBeaconBlock, Slot, Uint256,
 };
 
 mod engine_api;
@@ -118,6 +119,33 @@ pub enum ChainHealth {
     PreMerge,
 }
 
+/// Describes how a payload returned by `ExecutionLayer::get_payload_with_metadata` was obtained.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PayloadMetadata {
+    pub source: PayloadSource,
+    pub reason: PayloadSelectionReason,
+    /// The value of the bid received from the builder, if any, regardless of whether it was used.
+    pub builder_bid_value: Option<Uint256>,
+}
+
+impl PayloadMetadata {
+    fn local(reason: PayloadSelectionReason, builder_bid_value: Option<Uint256>) -> Self {
+        Self {
+            source: PayloadSource::Local,
+            reason,
+            builder_bid_value,
+        }
+    }
+
+    fn builder(reason: PayloadSelectionReason, builder_bid_value: Uint256) -> Self {
+        Self {
+            source: PayloadSource::Builder,
+            reason,
+            builder_bid_value: Some(builder_bid_value),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum FailedCondition {
     Skips,
@@ -525,6 +553,32 @@ impl<T: EthSpec> ExecutionLayer<T> {
         builder_params: BuilderParams,
         spec: &ChainSpec,
     ) -> Result<Payload, Error> {
+        self.get_payload_with_metadata(
+            parent_hash,
+            timestamp,
+            prev_randao,
+            proposer_index,
+            forkchoice_update_params,
+            builder_params,
+            spec,
+        )
+        .await
+        .map(|(payload, _)| payload)
+    }
+
+    /// Equivalent to `Self::get_payload`, but additionally returns a description of whether the
+    /// payload was obtained from the builder or the local execution engine, and why.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_payload_with_metadata<Payload: ExecPayload<T>>(
+        &self,
+        parent_hash: ExecutionBlockHash,
+        timestamp: u64,
+        prev_randao: Hash256,
+        proposer_index: u64,
+        forkchoice_update_params: ForkchoiceUpdateParameters,
+        builder_params: BuilderParams,
+        spec: &ChainSpec,
+    ) -> Result<(Payload, PayloadMetadata), Error> {
         let suggested_fee_recipient = self.get_suggested_fee_recipient(proposer_index).await;
 
         match Payload::block_type() {
@@ -557,6 +611,11 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     forkchoice_update_params,
                 )
                 .await
+                .map(|payload| {
+                    let metadata =
+                        PayloadMetadata::local(PayloadSelectionReason::FullBlockRequested, None);
+                    (payload, metadata)
+                })
             }
         }
     }
@@ -571,8 +630,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         forkchoice_update_params: ForkchoiceUpdateParameters,
         builder_params: BuilderParams,
         spec: &ChainSpec,
-    ) -> Result<Payload, Error> {
-        if let Some(builder) = self.builder() {
+    ) -> Result<(Payload, PayloadMetadata), Error> {
+        let local_reason = if let Some(builder) = self.builder() {
             let slot = builder_params.slot;
             let pubkey = builder_params.pubkey;
 
@@ -603,7 +662,11 @@ impl<T: EthSpec> ExecutionLayer<T> {
                                 "Unable to retrieve a payload from a connected \
                                 builder, falling back to the local execution client: {e:?}"
                             );
-                            Ok(local)
+                            let metadata = PayloadMetadata::local(
+                                PayloadSelectionReason::BuilderRequestFailed,
+                                None,
+                            );
+                            Ok((local, metadata))
                         }
                         (Ok(None), Ok(local)) => {
                             info!(
@@ -611,11 +674,16 @@ impl<T: EthSpec> ExecutionLayer<T> {
                                 "No payload provided by connected builder. \
                                 Attempting to propose through local execution engine"
                             );
-                            Ok(local)
+                            let metadata =
+                                PayloadMetadata::local(PayloadSelectionReason::BuilderNoBid, None);
+                            Ok((local, metadata))
                         }
                         (Ok(Some(relay)), Ok(local)) => {
                             let is_signature_valid = relay.data.verify_signature(spec);
+                            let value = relay.data.message.value;
                             let header = relay.data.message.header;
+                            let local_metadata =
+                                |reason| PayloadMetadata::local(reason, Some(value));
 
                             info!(
                                 self.log(),
@@ -629,28 +697,46 @@ impl<T: EthSpec> ExecutionLayer<T> {
                                     "Invalid parent hash from connected builder, \
                                     falling back to local execution engine."
                                 );
-                                Ok(local)
+                                Ok((
+                                    local,
+                                    local_metadata(
+                                        PayloadSelectionReason::InvalidBuilderParentHash,
+                                    ),
+                                ))
                             } else if header.prev_randao() != prev_randao {
                                 warn!(
                                     self.log(),
                                     "Invalid prev randao from connected builder, \
                                     falling back to local execution engine."
                                 );
-                                Ok(local)
+                                Ok((
+                                    local,
+                                    local_metadata(
+                                        PayloadSelectionReason::InvalidBuilderPrevRandao,
+                                    ),
+                                ))
                             } else if header.timestamp() != local.timestamp() {
                                 warn!(
                                     self.log(),
                                     "Invalid timestamp from connected builder, \
                                     falling back to local execution engine."
                                 );
-                                Ok(local)
+                                Ok((
+                                    local,
+                                    local_metadata(PayloadSelectionReason::InvalidBuilderTimestamp),
+                                ))
                             } else if header.block_number() != local.block_number() {
                                 warn!(
                                     self.log(),
                                     "Invalid block number from connected builder, \
                                     falling back to local execution engine."
                                 );
-                                Ok(local)
+                                Ok((
+                                    local,
+                                    local_metadata(
+                                        PayloadSelectionReason::InvalidBuilderBlockNumber,
+                                    ),
+                                ))
                             } else if !matches!(relay.version, Some(ForkName::Merge)) {
                                 // Once fork information is added to the payload, we will need to
                                 // check that the local and relay payloads match. At this point, if
@@ -661,12 +747,18 @@ impl<T: EthSpec> ExecutionLayer<T> {
                                     "Invalid fork from connected builder, falling \
                                     back to local execution engine."
                                 );
-                                Ok(local)
+                                Ok((
+                                    local,
+                                    local_metadata(PayloadSelectionReason::InvalidBuilderFork),
+                                ))
                             } else if !is_signature_valid {
                                 let pubkey_bytes = relay.data.message.pubkey;
                                 warn!(self.log(), "Invalid signature for pubkey {pubkey_bytes} on \
                                     bid from connected builder, falling back to local execution engine.");
-                                Ok(local)
+                                Ok((
+                                    local,
+                                    local_metadata(PayloadSelectionReason::InvalidBuilderSignature),
+                                ))
                             } else {
                                 if header.fee_recipient() != suggested_fee_recipient {
                                     info!(
@@ -675,7 +767,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
                                         not match, using it anyways."
                                     );
                                 }
-                                Ok(header)
+                                Ok((
+                                    header,
+                                    PayloadMetadata::builder(
+                                        PayloadSelectionReason::BuilderBidValid,
+                                        value,
+                                    ),
+                                ))
                             }
                         }
                         (relay_result, Err(local_error)) => {
@@ -684,7 +782,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
                             relay_result
                                 .map_err(Error::Builder)?
                                 .ok_or(Error::NoHeaderFromBuilder)
-                                .map(|d| d.data.message.header)
+                                .map(|d| {
+                                    let metadata = PayloadMetadata::builder(
+                                        PayloadSelectionReason::LocalEngineFailed,
+                                        d.data.message.value,
+                                    );
+                                    (d.data.message.header, metadata)
+                                })
                         }
                     };
                 }
@@ -692,15 +796,21 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     info!(self.log(), "Due to poor chain health the local execution engine will be used \
                                         for payload construction. To adjust chain health conditions \
                                         Use `builder-fallback` prefixed flags";
-                        "failed_condition" => ?condition)
+                        "failed_condition" => ?condition);
+                    PayloadSelectionReason::ChainUnhealthy
                 }
                 // Intentional no-op, so we never attempt builder API proposals pre-merge.
-                ChainHealth::PreMerge => (),
-                ChainHealth::Optimistic => info!(self.log(), "The local execution engine is syncing \
+                ChainHealth::PreMerge => PayloadSelectionReason::PreMerge,
+                ChainHealth::Optimistic => {
+                    info!(self.log(), "The local execution engine is syncing \
                                             so the builder network cannot safely be used. Attempting \
-                                            to build a block with the local execution engine"),
+                                            to build a block with the local execution engine");
+                    PayloadSelectionReason::ExecutionEngineOptimistic
+                }
             }
-        }
+        } else {
+            PayloadSelectionReason::NoBuilder
+        };
         self.get_full_payload_caching(
             parent_hash,
             timestamp,
@@ -709,6 +819,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
             forkchoice_update_params,
         )
         .await
+        .map(|payload| (payload, PayloadMetadata::local(local_reason, None)))
     }
 
     /// Get a full payload without caching its result in the execution layer's payload cache.
@@ -0,0 +1,287 @@
+//! Optional bearer-token authentication for the HTTP API.
+//!
+//! When enabled, every request must present an `Authorization: Bearer <token>` header and the
+//! token must grant a scope sufficient for the requested route. Tokens are configured with a
+//! YAML file with the following structure:
+//!
+//! ```yaml
+//! - token_path: /path/to/partner-token.txt
+//!   scope: read
+//! - token_path: /path/to/vc-token.txt
+//!   scope: publish
+//! - token_path: /path/to/admin-token.txt
+//!   scope: admin
+//! ```
+//!
+//! Each token file contains a single token, which is the same format expected by the
+//! `bearer_token_path` of the validator client's beacon node client configuration.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use warp::filters::path::FullPath;
+use warp::http::Method;
+use warp::Filter;
+
+/// The level of access granted by a token.
+///
+/// Scopes are ordered, and each scope grants access to all routes of the scopes below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// Read-only access to the standard `/eth` routes.
+    Read,
+    /// Access to the `/eth` routes which publish messages or alter the state of the node.
+    Publish,
+    /// Access to all routes, including the `/lighthouse` routes.
+    Admin,
+}
+
+/// `POST` routes which only read data, and therefore only require the `read` scope.
+const READ_ONLY_POST_PREFIXES: &[&str] = &["/eth/v1/beacon/rewards/", "/eth/v1/validator/duties/"];
+
+impl Scope {
+    /// Returns the scope required to access `path` with `method`.
+    pub fn required_for(method: &Method, path: &str) -> Self {
+        if path.starts_with("/lighthouse") {
+            Scope::Admin
+        } else if method == Method::GET
+            || READ_ONLY_POST_PREFIXES
+                .iter()
+                .any(|prefix| path.starts_with(prefix))
+        {
+            Scope::Read
+        } else {
+            Scope::Publish
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Read => write!(f, "read"),
+            Scope::Publish => write!(f, "publish"),
+            Scope::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// A single entry in the authentication config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiTokenConfig {
+    /// A file containing the token.
+    pub token_path: PathBuf,
+    /// The scope granted to the token.
+    pub scope: Scope,
+}
+
+/// The set of tokens which may be used to access the HTTP API.
+#[derive(Debug, Default)]
+pub struct ApiTokens {
+    tokens: HashMap<String, Scope>,
+}
+
+impl ApiTokens {
+    /// Load the authentication config at `path` and read each of the tokens it references.
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let file = File::open(path.as_ref()).map_err(|e| {
+            format!(
+                "Unable to open HTTP API auth config {:?}: {}",
+                path.as_ref(),
+                e
+            )
+        })?;
+        let configs: Vec<ApiTokenConfig> = serde_yaml::from_reader(file)
+            .map_err(|e| format!("Unable to parse HTTP API auth config: {:?}", e))?;
+
+        if configs.is_empty() {
+            return Err("HTTP API auth config must contain at least one token".to_string());
+        }
+
+        let mut tokens = HashMap::with_capacity(configs.len());
+        for config in configs {
+            let token = fs::read_to_string(&config.token_path)
+                .map_err(|e| format!("Unable to read API token {:?}: {}", config.token_path, e))?
+                .trim()
+                .to_string();
+
+            if token.is_empty() || token.contains(char::is_whitespace) {
+                return Err(format!("Invalid API token in {:?}", config.token_path));
+            }
+            if tokens.insert(token, config.scope).is_some() {
+                return Err(format!(
+                    "API token in {:?} is used more than once",
+                    config.token_path
+                ));
+            }
+        }
+
+        Ok(Self { tokens })
+    }
+
+    /// Returns the number of tokens with each scope, in scope order.
+    pub fn scope_counts(&self) -> Vec<(Scope, usize)> {
+        [Scope::Read, Scope::Publish, Scope::Admin]
+            .into_iter()
+            .map(|scope| {
+                let count = self.tokens.values().filter(|s| **s == scope).count();
+                (scope, count)
+            })
+            .collect()
+    }
+
+    /// Returns the scope granted by the value of an `Authorization` header, if any.
+    fn scope(&self, header: &str) -> Option<Scope> {
+        header
+            .strip_prefix("Bearer ")
+            .and_then(|token| self.tokens.get(token.trim()))
+            .copied()
+    }
+}
+
+/// Returns a `warp` filter which rejects requests which do not present a token with a scope
+/// sufficient for the requested route.
+///
+/// If `tokens` is `None` then all requests are permitted.
+pub fn authorization_filter(tokens: Option<Arc<ApiTokens>>) -> warp::filters::BoxedFilter<()> {
+    let tokens = match tokens {
+        Some(tokens) => tokens,
+        None => return warp::any().boxed(),
+    };
+
+    warp::any()
+        .map(move || tokens.clone())
+        .and(warp::method())
+        .and(warp::path::full())
+        .and(warp::header::header::<String>("Authorization"))
+        .and_then(
+            |tokens: Arc<ApiTokens>, method: Method, path: FullPath, header: String| async move {
+                let required = Scope::required_for(&method, path.as_str());
+                match tokens.scope(&header) {
+                    Some(scope) if scope >= required => Ok(()),
+                    Some(scope) => Err(warp_utils::reject::invalid_auth(format!(
+                        "token has {} scope but {} scope is required",
+                        scope, required
+                    ))),
+                    // Don't echo the header, it may contain a valid token for another service.
+                    None => Err(warp_utils::reject::invalid_auth(
+                        "unknown token".to_string(),
+                    )),
+                }
+            },
+        )
+        .untuple_one()
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_file(dir: &TempDir, name: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        File::create(&path)
+            .unwrap()
+            .write_all(contents.as_bytes())
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn required_scopes() {
+        for (method, path, scope) in [
+            (Method::GET, "/eth/v1/beacon/genesis", Scope::Read),
+            (Method::GET, "/eth/v1/events", Scope::Read),
+            (
+                Method::POST,
+                "/eth/v1/beacon/rewards/attestations/1",
+                Scope::Read,
+            ),
+            (
+                Method::POST,
+                "/eth/v1/validator/duties/attester/1",
+                Scope::Read,
+            ),
+            (Method::POST, "/eth/v1/beacon/blocks", Scope::Publish),
+            (
+                Method::POST,
+                "/eth/v1/beacon/pool/attestations",
+                Scope::Publish,
+            ),
+            (Method::GET, "/lighthouse/health", Scope::Admin),
+            (
+                Method::POST,
+                "/lighthouse/database/reconstruct",
+                Scope::Admin,
+            ),
+        ] {
+            assert_eq!(
+                Scope::required_for(&method, path),
+                scope,
+                "{} {}",
+                method,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn load_valid_config() {
+        let dir = TempDir::new().unwrap();
+        let read_path = write_file(&dir, "read.txt", "read-token\n");
+        let admin_path = write_file(&dir, "admin.txt", "admin-token");
+        let config_path = write_file(
+            &dir,
+            "auth.yaml",
+            &format!(
+                "- token_path: {:?}\n  scope: read\n- token_path: {:?}\n  scope: admin\n",
+                read_path, admin_path
+            ),
+        );
+
+        let tokens = ApiTokens::load_file(config_path).unwrap();
+        assert_eq!(tokens.scope("Bearer read-token"), Some(Scope::Read));
+        assert_eq!(tokens.scope("Bearer admin-token"), Some(Scope::Admin));
+        assert_eq!(tokens.scope("Basic admin-token"), None);
+        assert_eq!(tokens.scope("Bearer unknown"), None);
+        assert_eq!(
+            tokens.scope_counts(),
+            vec![(Scope::Read, 1), (Scope::Publish, 0), (Scope::Admin, 1)]
+        );
+    }
+
+    #[test]
+    fn reject_invalid_configs() {
+        let dir = TempDir::new().unwrap();
+        let token_path = write_file(&dir, "token.txt", "token");
+        let empty_path = write_file(&dir, "empty.txt", "\n");
+        for contents in [
+            // No tokens.
+            "[]".to_string(),
+            // Unknown scope.
+            format!("- token_path: {:?}\n  scope: root\n", token_path),
+            // Missing token file.
+            "- token_path: /does/not/exist\n  scope: read\n".to_string(),
+            // Empty token.
+            format!("- token_path: {:?}\n  scope: read\n", empty_path),
+            // Duplicate token.
+            format!(
+                "- token_path: {:?}\n  scope: read\n- token_path: {:?}\n  scope: admin\n",
+                token_path, token_path
+            ),
+        ] {
+            let path = write_file(&dir, "auth.yaml", &contents);
+            assert!(
+                ApiTokens::load_file(path).is_err(),
+                "{} should be invalid",
+                contents
+            );
+        }
+    }
+}
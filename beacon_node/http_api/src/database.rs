@@ -1,6 +1,13 @@
-use beacon_chain::store::{metadata::CURRENT_SCHEMA_VERSION, AnchorInfo};
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::lighthouse::DatabaseInfo;
+use beacon_chain::store::{metadata::CURRENT_SCHEMA_VERSION, AnchorInfo, Error as StoreError};
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{
+    DatabaseBackupRequest, DatabaseBackupState, DatabaseBackupStatus, DatabaseInfo,
+};
+use parking_lot::Mutex;
+use slog::{error, info, Logger};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use types::SignedBlindedBeaconBlock;
 
@@ -33,3 +40,156 @@ pub fn historical_blocks<T: BeaconChainTypes>(
     })?;
     Ok(anchor)
 }
+
+/// The status of the most recent backup started via the HTTP API, if any.
+pub type BackupStatus = Arc<Mutex<Option<DatabaseBackupStatus>>>;
+
+/// Start a backup of the database to `request.path` within `backup_dir` on a blocking thread.
+///
+/// Only one backup may run at a time. The returned status is also stored in `status`, which is
+/// updated once the backup completes or fails.
+pub fn start_backup<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    backup_dir: Option<&Path>,
+    request: DatabaseBackupRequest,
+    status: BackupStatus,
+    log: Logger,
+) -> Result<DatabaseBackupStatus, warp::Rejection> {
+    let backup_dir = backup_dir.ok_or_else(|| {
+        warp_utils::reject::custom_bad_request(
+            "backups are disabled, use --http-backup-dir to enable them".to_string(),
+        )
+    })?;
+    let path = backup_path(backup_dir, &request.path)?;
+
+    let mut status_lock = status.lock();
+    if let Some(DatabaseBackupStatus {
+        path: running_path,
+        state: DatabaseBackupState::InProgress,
+    }) = &*status_lock
+    {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "backup to {:?} is already in progress",
+            running_path
+        )));
+    }
+    ensure_empty_dir(&path)?;
+
+    let backup_status = DatabaseBackupStatus {
+        path: request.path.clone(),
+        state: DatabaseBackupState::InProgress,
+    };
+    *status_lock = Some(backup_status.clone());
+    drop(status_lock);
+
+    let executor = chain.task_executor.clone();
+    executor.spawn_blocking(
+        move || {
+            let state = match chain.backup_database(&path) {
+                Ok(manifest) => {
+                    info!(log, "Database backup complete"; "path" => ?path);
+                    DatabaseBackupState::Complete { manifest }
+                }
+                Err(e) => {
+                    error!(log, "Database backup failed"; "path" => ?path, "error" => ?e);
+                    let error = match e {
+                        BeaconChainError::DBError(StoreError::BackupError(msg)) => msg,
+                        e => format!("{:?}", e),
+                    };
+                    DatabaseBackupState::Failed { error }
+                }
+            };
+            *status.lock() = Some(DatabaseBackupStatus {
+                path: request.path,
+                state,
+            });
+        },
+        "database_backup",
+    );
+
+    Ok(backup_status)
+}
+
+pub fn backup_status(status: BackupStatus) -> Result<DatabaseBackupStatus, warp::Rejection> {
+    status.lock().clone().ok_or_else(|| {
+        warp_utils::reject::custom_not_found("no backup has been started".to_string())
+    })
+}
+
+/// Resolve `path` within `backup_dir`, rejecting paths which could escape it.
+fn backup_path(backup_dir: &Path, path: &Path) -> Result<PathBuf, warp::Rejection> {
+    let is_relative_descendant = path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+
+    if is_relative_descendant {
+        Ok(backup_dir.join(path))
+    } else {
+        Err(warp_utils::reject::custom_bad_request(format!(
+            "backup path {:?} must be a relative path without \"..\" or \".\" components",
+            path
+        )))
+    }
+}
+
+/// Reject the request immediately if the backup is certain to fail due to a non-empty `path`.
+fn ensure_empty_dir(path: &Path) -> Result<(), warp::Rejection> {
+    let is_empty = match fs::read_dir(path) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(e) if e.kind() == ErrorKind::NotFound => true,
+        Err(e) => {
+            return Err(warp_utils::reject::custom_server_error(format!(
+                "unable to read {:?}: {}",
+                path, e
+            )))
+        }
+    };
+
+    if is_empty {
+        Ok(())
+    } else {
+        Err(warp_utils::reject::custom_bad_request(format!(
+            "{:?} is not empty",
+            path
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_path_is_within_backup_dir() {
+        let backup_dir = Path::new("/var/lib/lighthouse/backups");
+
+        assert_eq!(
+            backup_path(backup_dir, Path::new("2022-10-19")).unwrap(),
+            backup_dir.join("2022-10-19")
+        );
+        assert_eq!(
+            backup_path(backup_dir, Path::new("daily/monday")).unwrap(),
+            backup_dir.join("daily/monday")
+        );
+    }
+
+    #[test]
+    fn backup_path_rejects_escapes() {
+        let backup_dir = Path::new("/var/lib/lighthouse/backups");
+
+        for path in [
+            "",
+            "/tmp/backup",
+            "../backup",
+            "daily/../../backup",
+            "./backup",
+        ] {
+            assert!(
+                backup_path(backup_dir, Path::new(path)).is_err(),
+                "{:?} should be rejected",
+                path
+            );
+        }
+    }
+}
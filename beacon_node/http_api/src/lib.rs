@@ -7,6 +7,7 @@
 
 mod attestation_performance;
 mod attester_duties;
+mod auth;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
@@ -14,15 +15,18 @@ mod database;
 mod metrics;
 mod proposer_duties;
 mod publish_blocks;
+mod standard_rewards;
 mod state_id;
 mod sync_committees;
 mod validator_inclusion;
+mod validator_monitor;
 mod version;
 
+use auth::ApiTokens;
 use beacon_chain::{
     attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
     validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
-    BeaconChainTypes, ProduceBlockVerification, WhenSlotSkipped,
+    BeaconChainTypes, ExecutionStatus, ForkChoiceStore, ProduceBlockVerification, WhenSlotSkipped,
 };
 pub use block_id::BlockId;
 use eth2::types::{self as api_types, EndpointVersion, ValidatorId};
@@ -46,15 +50,18 @@ use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use types::{
     Attestation, AttesterSlashing, BeaconStateError, BlindedPayload, CommitteeCache,
     ConfigAndPreset, Epoch, EthSpec, ForkName, FullPayload, ProposerPreparationData,
-    ProposerSlashing, RelativeEpoch, Signature, SignedAggregateAndProof, SignedBeaconBlock,
-    SignedBlindedBeaconBlock, SignedContributionAndProof, SignedValidatorRegistrationData,
-    SignedVoluntaryExit, Slot, SyncCommitteeMessage, SyncContributionData,
+    ProposerSlashing, PublicKeyBytes, RelativeEpoch, Signature, SignedAggregateAndProof,
+    SignedBeaconBlock, SignedBlindedBeaconBlock, SignedContributionAndProof,
+    SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncCommitteeMessage,
+    SyncContributionData,
 };
 use version::{
-    add_consensus_version_header, execution_optimistic_fork_versioned_response,
-    fork_versioned_response, inconsistent_fork_rejection, unsupported_version_rejection, V1, V2,
+    add_consensus_version_header, add_execution_optimistic_header,
+    execution_optimistic_fork_versioned_response, fork_versioned_response,
+    inconsistent_fork_rejection, unsupported_version_rejection, V1, V2,
 };
 use warp::http::StatusCode;
+use warp::hyper::body::Bytes;
 use warp::sse::Event;
 use warp::Reply;
 use warp::{http::Response, Filter};
@@ -83,6 +90,9 @@ pub type ExecutionOptimistic = bool;
 pub struct TlsConfig {
     pub cert: PathBuf,
     pub key: PathBuf,
+    /// If set, clients must present a certificate signed by one of the CAs in this PEM file.
+    #[serde(default)]
+    pub client_ca: Option<PathBuf>,
 }
 
 /// A wrapper around all the items required to spawn the HTTP server.
@@ -107,6 +117,12 @@ pub struct Config {
     pub tls_config: Option<TlsConfig>,
     pub allow_sync_stalled: bool,
     pub spec_fork_name: Option<ForkName>,
+    /// If set, requests must be authenticated with one of the tokens listed in this file.
+    #[serde(default)]
+    pub auth_config: Option<PathBuf>,
+    /// If set, `POST lighthouse/database/backup` may write backups within this directory.
+    #[serde(default)]
+    pub backup_dir: Option<PathBuf>,
 }
 
 impl Default for Config {
@@ -119,6 +135,8 @@ impl Default for Config {
             tls_config: None,
             allow_sync_stalled: false,
             spec_fork_name: None,
+            auth_config: None,
+            backup_dir: None,
         }
     }
 }
@@ -266,7 +284,7 @@ pub fn serve<T: BeaconChainTypes>(
     let cors_builder = {
         let builder = warp::cors()
             .allow_methods(vec!["GET", "POST"])
-            .allow_headers(vec!["Content-Type"]);
+            .allow_headers(vec!["Content-Type", "Authorization"]);
 
         warp_utils::cors::set_builder_origins(
             builder,
@@ -283,6 +301,25 @@ pub fn serve<T: BeaconChainTypes>(
         ));
     }
 
+    // Load the API tokens, if authentication is enabled.
+    let api_tokens = if let Some(auth_config) = &config.auth_config {
+        let tokens = ApiTokens::load_file(auth_config)?;
+        let counts = tokens.scope_counts();
+        info!(
+            log,
+            "HTTP API authentication enabled";
+            "tokens" => counts
+                .iter()
+                .map(|(scope, count)| format!("{}: {}", scope, count))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        Some(Arc::new(tokens))
+    } else {
+        None
+    };
+    let authorization_filter = auth::authorization_filter(api_tokens);
+
     // Create a filter that extracts the endpoint version.
     let any_version = warp::path(API_PREFIX).and(warp::path::param::<EndpointVersion>().or_else(
         |_| async move {
@@ -565,18 +602,23 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validators"))
         .and(warp::path::end())
         .and(multi_key_query::<api_types::ValidatorsQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .and_then(
             |state_id: StateId,
              chain: Arc<BeaconChain<T>>,
-             query_res: Result<api_types::ValidatorsQuery, warp::Rejection>| {
-                blocking_json_task(move || {
+             query_res: Result<api_types::ValidatorsQuery, warp::Rejection>,
+             accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
                     let query = query_res?;
-                    let (data, execution_optimistic) = state_id
+                    let (data, execution_optimistic, fork_name) = state_id
                         .map_state_and_execution_optimistic(
                             &chain,
                             |state, execution_optimistic| {
                                 let epoch = state.current_epoch();
                                 let far_future_epoch = chain.spec.far_future_epoch;
+                                let fork_name = state
+                                    .fork_name(&chain.spec)
+                                    .map_err(inconsistent_fork_rejection)?;
 
                                 Ok((
                                     state
@@ -624,14 +666,23 @@ pub fn serve<T: BeaconChainTypes>(
                                         })
                                         .collect::<Vec<_>>(),
                                     execution_optimistic,
+                                    fork_name,
                                 ))
                             },
                         )?;
 
-                    Ok(api_types::ExecutionOptimisticResponse {
-                        data,
-                        execution_optimistic: Some(execution_optimistic),
-                    })
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => ssz_response_with_metadata(
+                            data.as_ssz_bytes(),
+                            fork_name,
+                            execution_optimistic,
+                        ),
+                        _ => Ok(warp::reply::json(&api_types::ExecutionOptimisticResponse {
+                            data,
+                            execution_optimistic: Some(execution_optimistic),
+                        })
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -704,15 +755,22 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("committees"))
         .and(warp::query::<api_types::CommitteesQuery>())
         .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .and_then(
-            |state_id: StateId, chain: Arc<BeaconChain<T>>, query: api_types::CommitteesQuery| {
-                blocking_json_task(move || {
-                    let (data, execution_optimistic) = state_id
+            |state_id: StateId,
+             chain: Arc<BeaconChain<T>>,
+             query: api_types::CommitteesQuery,
+             accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
+                    let (data, execution_optimistic, fork_name) = state_id
                         .map_state_and_execution_optimistic(
                             &chain,
                             |state, execution_optimistic| {
                                 let current_epoch = state.current_epoch();
                                 let epoch = query.epoch.unwrap_or(current_epoch);
+                                let fork_name = state
+                                    .fork_name(&chain.spec)
+                                    .map_err(inconsistent_fork_rejection)?;
 
                                 let committee_cache =
                                     match RelativeEpoch::from_epoch(current_epoch, epoch) {
@@ -793,13 +851,21 @@ pub fn serve<T: BeaconChainTypes>(
                                     }
                                 }
 
-                                Ok((response, execution_optimistic))
+                                Ok((response, execution_optimistic, fork_name))
                             },
                         )?;
-                    Ok(api_types::ExecutionOptimisticResponse {
-                        data,
-                        execution_optimistic: Some(execution_optimistic),
-                    })
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => ssz_response_with_metadata(
+                            data.as_ssz_bytes(),
+                            fork_name,
+                            execution_optimistic,
+                        ),
+                        _ => Ok(warp::reply::json(&api_types::ExecutionOptimisticResponse {
+                            data,
+                            execution_optimistic: Some(execution_optimistic),
+                        })
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -1053,6 +1119,60 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST beacon/blocks (SSZ)
+    let post_beacon_blocks_ssz = eth_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("blocks"))
+        .and(warp::path::end())
+        .and(ssz_content_type_filter())
+        .and(warp::body::bytes())
+        .and(chain_filter.clone())
+        .and(network_tx_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |block_bytes: Bytes,
+             chain: Arc<BeaconChain<T>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
+             log: Logger| async move {
+                let block =
+                    SignedBeaconBlock::<T::EthSpec>::from_ssz_bytes(&block_bytes, &chain.spec)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!("invalid SSZ: {:?}", e))
+                        })?;
+                publish_blocks::publish_block(Arc::new(block), chain, &network_tx, log)
+                    .await
+                    .map(|()| warp::reply())
+            },
+        );
+
+    // POST beacon/blinded_blocks (SSZ)
+    let post_beacon_blinded_blocks_ssz = eth_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("blinded_blocks"))
+        .and(warp::path::end())
+        .and(ssz_content_type_filter())
+        .and(warp::body::bytes())
+        .and(chain_filter.clone())
+        .and(network_tx_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |block_bytes: Bytes,
+             chain: Arc<BeaconChain<T>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
+             log: Logger| async move {
+                let block = SignedBeaconBlock::<T::EthSpec, BlindedPayload<_>>::from_ssz_bytes(
+                    &block_bytes,
+                    &chain.spec,
+                )
+                .map_err(|e| {
+                    warp_utils::reject::custom_bad_request(format!("invalid SSZ: {:?}", e))
+                })?;
+                publish_blocks::publish_blinded_block(block, chain, &network_tx, log)
+                    .await
+                    .map(|()| warp::reply())
+            },
+        );
+
     let block_id_or_err = warp::path::param::<BlockId>().or_else(|_| async {
         Err(warp_utils::reject::custom_bad_request(
             "Invalid block ID".to_string(),
@@ -1088,25 +1208,22 @@ pub fn serve<T: BeaconChainTypes>(
                         .map_err(inconsistent_fork_rejection)?;
 
                     match accept_header {
-                        Some(api_types::Accept::Ssz) => Response::builder()
-                            .status(200)
-                            .header("Content-Type", "application/octet-stream")
-                            .body(block.as_ssz_bytes().into())
-                            .map_err(|e| {
-                                warp_utils::reject::custom_server_error(format!(
-                                    "failed to create response: {}",
-                                    e
-                                ))
-                            }),
+                        Some(api_types::Accept::Ssz) => ssz_response_with_metadata(
+                            block.as_ssz_bytes(),
+                            fork_name,
+                            execution_optimistic,
+                        ),
                         _ => execution_optimistic_fork_versioned_response(
                             endpoint_version,
                             fork_name,
                             execution_optimistic,
                             block,
                         )
-                        .map(|res| warp::reply::json(&res).into_response()),
+                        .map(|res| {
+                            add_consensus_version_header(warp::reply::json(&res), fork_name)
+                                .into_response()
+                        }),
                     }
-                    .map(|resp| add_consensus_version_header(resp, fork_name))
                 }
             },
         );
@@ -1143,6 +1260,72 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    /*
+     * beacon/rewards
+     */
+
+    let beacon_rewards_path = eth_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("rewards"))
+        .and(chain_filter.clone());
+
+    // GET beacon/rewards/blocks/{block_id}
+    let get_beacon_rewards_blocks = beacon_rewards_path
+        .clone()
+        .and(warp::path("blocks"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and_then(|chain: Arc<BeaconChain<T>>, block_id: BlockId| {
+            blocking_json_task(move || {
+                let (rewards, execution_optimistic) =
+                    standard_rewards::compute_beacon_block_rewards(chain, block_id)?;
+                Ok(api_types::GenericResponse::from(rewards)
+                    .add_execution_optimistic(execution_optimistic))
+            })
+        });
+
+    // POST beacon/rewards/sync_committee/{block_id}
+    let post_beacon_rewards_sync_committee = beacon_rewards_path
+        .clone()
+        .and(warp::path("sync_committee"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, block_id: BlockId, validators: Vec<String>| {
+                blocking_json_task(move || {
+                    let (rewards, execution_optimistic) =
+                        standard_rewards::compute_sync_committee_rewards(
+                            chain, block_id, validators,
+                        )?;
+                    Ok(api_types::GenericResponse::from(rewards)
+                        .add_execution_optimistic(execution_optimistic))
+                })
+            },
+        );
+
+    // POST beacon/rewards/attestations/{epoch}
+    let post_beacon_rewards_attestations = beacon_rewards_path
+        .clone()
+        .and(warp::path("attestations"))
+        .and(warp::path::param::<Epoch>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid epoch".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, epoch: Epoch, validators: Vec<String>| {
+                blocking_json_task(move || {
+                    let (rewards, execution_optimistic) =
+                        standard_rewards::compute_attestation_rewards(chain, epoch, validators)?;
+                    Ok(api_types::GenericResponse::from(rewards)
+                        .add_execution_optimistic(execution_optimistic))
+                })
+            },
+        );
+
     /*
      * beacon/pool
      */
@@ -1587,24 +1770,15 @@ pub fn serve<T: BeaconChainTypes>(
              chain: Arc<BeaconChain<T>>| {
                 blocking_task(move || match accept_header {
                     Some(api_types::Accept::Ssz) => {
-                        // We can ignore the optimistic status for the "fork" since it's a
-                        // specification constant that doesn't change across competing heads of the
-                        // beacon chain.
-                        let (state, _execution_optimistic) = state_id.state(&chain)?;
+                        let (state, execution_optimistic) = state_id.state(&chain)?;
                         let fork_name = state
                             .fork_name(&chain.spec)
                             .map_err(inconsistent_fork_rejection)?;
-                        Response::builder()
-                            .status(200)
-                            .header("Content-Type", "application/octet-stream")
-                            .body(state.as_ssz_bytes().into())
-                            .map(|resp| add_consensus_version_header(resp, fork_name))
-                            .map_err(|e| {
-                                warp_utils::reject::custom_server_error(format!(
-                                    "failed to create response: {}",
-                                    e
-                                ))
-                            })
+                        ssz_response_with_metadata(
+                            state.as_ssz_bytes(),
+                            fork_name,
+                            execution_optimistic,
+                        )
                     }
                     _ => state_id.map_state_and_execution_optimistic(
                         &chain,
@@ -1618,10 +1792,10 @@ pub fn serve<T: BeaconChainTypes>(
                                 execution_optimistic,
                                 &state,
                             )?;
-                            Ok(add_consensus_version_header(
-                                warp::reply::json(&res).into_response(),
-                                fork_name,
-                            ))
+                            Ok(
+                                add_consensus_version_header(warp::reply::json(&res), fork_name)
+                                    .into_response(),
+                            )
                         },
                     ),
                 })
@@ -1665,6 +1839,88 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET debug/fork_choice
+    let get_debug_fork_choice = eth_v1
+        .and(warp::path("debug"))
+        .and(warp::path("fork_choice"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                let fork_choice = chain.canonical_head.fork_choice_read_lock();
+                let proto_array = fork_choice.proto_array().core_proto_array();
+
+                let fork_choice_nodes = proto_array
+                    .nodes
+                    .iter()
+                    .map(|node| {
+                        let parent_root = node
+                            .parent
+                            .and_then(|index| proto_array.nodes.get(index))
+                            .map(|parent| parent.root);
+                        // Pre-merge blocks have no payload that could be invalidated.
+                        let validity = match node.execution_status {
+                            ExecutionStatus::Valid(_) | ExecutionStatus::Irrelevant(_) => {
+                                api_types::ForkChoiceNodeValidity::Valid
+                            }
+                            ExecutionStatus::Invalid(_) => {
+                                api_types::ForkChoiceNodeValidity::Invalid
+                            }
+                            ExecutionStatus::Optimistic(_) => {
+                                api_types::ForkChoiceNodeValidity::Optimistic
+                            }
+                        };
+
+                        // Only nodes from databases predating checkpoints in proto-array lack
+                        // them, and those nodes are long since finalized.
+                        api_types::ForkChoiceNode {
+                            slot: node.slot,
+                            block_root: node.root,
+                            parent_root,
+                            justified_epoch: node
+                                .justified_checkpoint
+                                .map_or_else(Epoch::default, |checkpoint| checkpoint.epoch),
+                            finalized_epoch: node
+                                .finalized_checkpoint
+                                .map_or_else(Epoch::default, |checkpoint| checkpoint.epoch),
+                            weight: node.weight,
+                            validity,
+                            execution_block_hash: node.execution_status.block_hash(),
+                            extra_data: api_types::ForkChoiceNodeExtraData {
+                                justified_checkpoint: node.justified_checkpoint,
+                                finalized_checkpoint: node.finalized_checkpoint,
+                                unrealized_justified_checkpoint: node
+                                    .unrealized_justified_checkpoint,
+                                unrealized_finalized_checkpoint: node
+                                    .unrealized_finalized_checkpoint,
+                            },
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let justified_balances = fork_choice.fc_store().justified_balances();
+
+                Ok(api_types::ForkChoice {
+                    justified_checkpoint: proto_array.justified_checkpoint,
+                    finalized_checkpoint: proto_array.finalized_checkpoint,
+                    fork_choice_nodes,
+                    extra_data: api_types::ForkChoiceExtraData {
+                        proposer_boost: api_types::ForkChoiceProposerBoost {
+                            root: proto_array.previous_proposer_boost.root,
+                            score: proto_array.previous_proposer_boost.score,
+                        },
+                        justified_balances: api_types::ForkChoiceJustifiedBalances {
+                            total_effective_balance: justified_balances.iter().sum(),
+                            num_active_validators: justified_balances
+                                .iter()
+                                .filter(|balance| **balance > 0)
+                                .count() as u64,
+                        },
+                    },
+                })
+            })
+        });
+
     /*
      * node
      */
@@ -1977,11 +2233,13 @@ pub fn serve<T: BeaconChainTypes>(
         .and(not_while_syncing_filter.clone())
         .and(warp::query::<api_types::ValidatorBlocksQuery>())
         .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .and_then(
             |endpoint_version: EndpointVersion,
              slot: Slot,
              query: api_types::ValidatorBlocksQuery,
-             chain: Arc<BeaconChain<T>>| async move {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| async move {
                 let randao_reveal = query.randao_reveal.as_ref().map_or_else(
                     || {
                         if query.verify_randao {
@@ -2022,8 +2280,12 @@ pub fn serve<T: BeaconChainTypes>(
                     .fork_name(&chain.spec)
                     .map_err(inconsistent_fork_rejection)?;
 
-                fork_versioned_response(endpoint_version, fork_name, block)
-                    .map(|response| warp::reply::json(&response))
+                match accept_header {
+                    Some(api_types::Accept::Ssz) => ssz_response(block.as_ssz_bytes()),
+                    _ => fork_versioned_response(endpoint_version, fork_name, block)
+                        .map(|response| warp::reply::json(&response).into_response()),
+                }
+                .map(|resp| add_consensus_version_header(resp, fork_name))
             },
         );
 
@@ -2040,10 +2302,12 @@ pub fn serve<T: BeaconChainTypes>(
         .and(not_while_syncing_filter.clone())
         .and(warp::query::<api_types::ValidatorBlocksQuery>())
         .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .and_then(
             |slot: Slot,
              query: api_types::ValidatorBlocksQuery,
-             chain: Arc<BeaconChain<T>>| async move {
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| async move {
                 let randao_reveal = query.randao_reveal.as_ref().map_or_else(
                     || {
                         if query.verify_randao {
@@ -2083,9 +2347,13 @@ pub fn serve<T: BeaconChainTypes>(
                     .to_ref()
                     .fork_name(&chain.spec)
                     .map_err(inconsistent_fork_rejection)?;
-                // Pose as a V2 endpoint so we return the fork `version`. 
-                fork_versioned_response(V2, fork_name, block)
-                    .map(|response| warp::reply::json(&response))
+                // Pose as a V2 endpoint so we return the fork `version`.
+                match accept_header {
+                    Some(api_types::Accept::Ssz) => ssz_response(block.as_ssz_bytes()),
+                    _ => fork_versioned_response(V2, fork_name, block)
+                        .map(|response| warp::reply::json(&response).into_response()),
+                }
+                .map(|resp| add_consensus_version_header(resp, fork_name))
             },
         );
 
@@ -2097,9 +2365,12 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::query::<api_types::ValidatorAttestationDataQuery>())
         .and(not_while_syncing_filter.clone())
         .and(chain_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .and_then(
-            |query: api_types::ValidatorAttestationDataQuery, chain: Arc<BeaconChain<T>>| {
-                blocking_json_task(move || {
+            |query: api_types::ValidatorAttestationDataQuery,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                blocking_task(move || {
                     let current_slot = chain
                         .slot()
                         .map_err(warp_utils::reject::beacon_chain_error)?;
@@ -2112,11 +2383,20 @@ pub fn serve<T: BeaconChainTypes>(
                         )));
                     }
 
-                    chain
+                    let attestation_data = chain
                         .produce_unaggregated_attestation(query.slot, query.committee_index)
                         .map(|attestation| attestation.data)
-                        .map(api_types::GenericResponse::from)
-                        .map_err(warp_utils::reject::beacon_chain_error)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+                    match accept_header {
+                        Some(api_types::Accept::Ssz) => {
+                            ssz_response(attestation_data.as_ssz_bytes())
+                        }
+                        _ => Ok(warp::reply::json(&api_types::GenericResponse::from(
+                            attestation_data,
+                        ))
+                        .into_response()),
+                    }
                 })
             },
         );
@@ -2718,6 +2998,158 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/fork_choice/votes/{block_id}
+    let get_lighthouse_fork_choice_votes = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path("votes"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|block_id: BlockId, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                let (block_root, _execution_optimistic) = block_id.root(&chain)?;
+                let fork_choice = chain.canonical_head.fork_choice_read_lock();
+                if !fork_choice.contains_block(&block_root) {
+                    return Err(warp_utils::reject::custom_not_found(format!(
+                        "block {:?} is not in fork choice",
+                        block_root
+                    )));
+                }
+
+                let votes = eth2::lighthouse::ForkChoiceVotes {
+                    block_root,
+                    supporting_validators: fork_choice.supporting_validators(block_root),
+                    equivocating_validators: fork_choice
+                        .equivocating_indices()
+                        .iter()
+                        .copied()
+                        .collect(),
+                    queued_attestations: fork_choice
+                        .queued_attestations()
+                        .iter()
+                        .map(
+                            |attestation| eth2::lighthouse::ForkChoiceQueuedAttestation {
+                                slot: attestation.slot(),
+                                block_root: attestation.block_root(),
+                                target_epoch: attestation.target_epoch(),
+                                attesting_indices: attestation.attesting_indices().to_vec(),
+                            },
+                        )
+                        .collect(),
+                };
+                Ok(api_types::GenericResponse::from(votes))
+            })
+        });
+
+    // GET lighthouse/fork_choice/votes/{block_id}/diff/{other_block_id}
+    let get_lighthouse_fork_choice_vote_diff = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path("votes"))
+        .and(block_id_or_err)
+        .and(warp::path("diff"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(
+            |block_id: BlockId, other_block_id: BlockId, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    let (first_root, _execution_optimistic) = block_id.root(&chain)?;
+                    let (second_root, _execution_optimistic) = other_block_id.root(&chain)?;
+                    let fork_choice = chain.canonical_head.fork_choice_read_lock();
+                    for block_root in [first_root, second_root] {
+                        if !fork_choice.contains_block(&block_root) {
+                            return Err(warp_utils::reject::custom_not_found(format!(
+                                "block {:?} is not in fork choice",
+                                block_root
+                            )));
+                        }
+                    }
+
+                    let diff = fork_choice.vote_diff(first_root, second_root);
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::ForkChoiceVoteDiff {
+                            first_root,
+                            second_root,
+                            first_only: diff.first_only,
+                            second_only: diff.second_only,
+                            both: diff.both,
+                        },
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/slasher/slashings
+    let get_lighthouse_slasher_slashings = warp::path("lighthouse")
+        .and(warp::path("slasher"))
+        .and(warp::path("slashings"))
+        .and(warp::query::<eth2::lighthouse::SlasherSlashingsQuery>())
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(
+            |query: eth2::lighthouse::SlasherSlashingsQuery, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    let slasher = chain.slasher.as_ref().ok_or_else(|| {
+                        warp_utils::reject::custom_not_found("slasher is not enabled".to_string())
+                    })?;
+                    let records = slasher.get_slashing_records().map_err(|e| {
+                        warp_utils::reject::custom_server_error(format!(
+                            "unable to read slashings: {:?}",
+                            e
+                        ))
+                    })?;
+
+                    let head = chain.canonical_head.cached_head();
+                    let head_state = &head.snapshot.beacon_state;
+
+                    let slashings = records
+                        .into_iter()
+                        .filter_map(|(id, record)| {
+                            let kind = if record.evidence.proposer_slashing().is_some() {
+                                eth2::lighthouse::SlashingKind::Proposer
+                            } else {
+                                eth2::lighthouse::SlashingKind::Attester
+                            };
+                            let validator_indices = record.evidence.slashable_indices();
+                            let included = validator_indices.iter().all(|&index| {
+                                head_state
+                                    .validators()
+                                    .get(index as usize)
+                                    .map_or(false, |validator| validator.slashed)
+                            });
+
+                            let matches = query
+                                .validator_index
+                                .map_or(true, |index| validator_indices.contains(&index))
+                                && query.kind.map_or(true, |k| k == kind)
+                                && query
+                                    .from_epoch
+                                    .map_or(true, |epoch| record.detected_epoch >= epoch)
+                                && query
+                                    .to_epoch
+                                    .map_or(true, |epoch| record.detected_epoch <= epoch)
+                                && query.included.map_or(true, |i| i == included);
+                            if !matches {
+                                return None;
+                            }
+
+                            Some(eth2::lighthouse::SlasherSlashing {
+                                id,
+                                kind,
+                                detected_epoch: record.detected_epoch,
+                                validator_indices,
+                                included,
+                                proposer_slashing: record.evidence.proposer_slashing().cloned(),
+                                attester_slashing: record.evidence.attester_slashing().cloned(),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    Ok(api_types::GenericResponse::from(slashings))
+                })
+            },
+        );
+
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
     let get_lighthouse_validator_inclusion_global = warp::path("lighthouse")
         .and(warp::path("validator_inclusion"))
@@ -2883,6 +3315,56 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // The status of the most recent backup, shared between the backup endpoints.
+    let backup_status = database::BackupStatus::default();
+    let backup_status_filter = warp::any().map(move || backup_status.clone());
+
+    // POST lighthouse/database/backup
+    let backup_dir = config.backup_dir.clone();
+    let post_lighthouse_database_backup = database_path
+        .and(warp::path("backup"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and(backup_status_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            move |request: eth2::lighthouse::DatabaseBackupRequest,
+                  chain: Arc<BeaconChain<T>>,
+                  backup_status: database::BackupStatus,
+                  log: Logger| {
+                let backup_dir = backup_dir.clone();
+                blocking_task(move || {
+                    info!(
+                        log,
+                        "Backing up database";
+                        "path" => ?request.path,
+                        "source" => "http_api"
+                    );
+                    let status = database::start_backup(
+                        chain,
+                        backup_dir.as_deref(),
+                        request,
+                        backup_status,
+                        log,
+                    )?;
+                    Ok(warp::reply::with_status(
+                        warp::reply::json(&status),
+                        StatusCode::ACCEPTED,
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/database/backup
+    let get_lighthouse_database_backup = database_path
+        .and(warp::path("backup"))
+        .and(warp::path::end())
+        .and(backup_status_filter)
+        .and_then(|backup_status: database::BackupStatus| {
+            blocking_json_task(move || database::backup_status(backup_status))
+        });
+
     // POST lighthouse/database/historical_blocks
     let post_lighthouse_database_historical_blocks = database_path
         .and(warp::path("historical_blocks"))
@@ -2955,6 +3437,38 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/validator_monitor/{pubkey}/history
+    let get_lighthouse_validator_monitor_history = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path::param::<PublicKeyBytes>())
+        .and(warp::path("history"))
+        .and(warp::query::<eth2::lighthouse::ValidatorMonitorHistoryQuery>())
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|pubkey, query, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                validator_monitor::get_validator_monitor_history(pubkey, query, chain)
+                    .map(api_types::GenericResponse::from)
+            })
+        });
+
+    // GET lighthouse/proposals/{slot}
+    let get_lighthouse_proposals = warp::path("lighthouse")
+        .and(warp::path("proposals"))
+        .and(warp::path::param::<Slot>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid slot".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|slot: Slot, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                let proposals = chain.proposal_diagnostics_cache.read().get(slot);
+                Ok(api_types::GenericResponse::from(proposals))
+            })
+        });
+
     // GET lighthouse/merge_readiness
     let get_lighthouse_merge_readiness = warp::path("lighthouse")
         .and(warp::path("merge_readiness"))
@@ -3006,6 +3520,21 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::BlockReward => {
                                     event_handler.subscribe_block_reward()
                                 }
+                                api_types::EventTopic::PayloadAttributes => {
+                                    event_handler.subscribe_payload_attributes()
+                                }
+                                api_types::EventTopic::ProposerSlashing => {
+                                    event_handler.subscribe_proposer_slashing()
+                                }
+                                api_types::EventTopic::AttesterSlashing => {
+                                    event_handler.subscribe_attester_slashing()
+                                }
+                                api_types::EventTopic::BlockGossip => {
+                                    event_handler.subscribe_block_gossip()
+                                }
+                                api_types::EventTopic::ProposalDiagnostics => {
+                                    event_handler.subscribe_proposal_diagnostics()
+                                }
                             };
 
                             receivers.push(BroadcastStream::new(receiver).map(|msg| {
@@ -3039,87 +3568,109 @@ pub fn serve<T: BeaconChainTypes>(
         );
 
     // Define the ultimate set of routes that will be provided to the server.
-    let routes = warp::get()
+    //
+    // The `authorization_filter` is applied to all routes, and is a no-op when authentication is
+    // disabled.
+    let routes = warp::any()
+        .and(authorization_filter)
         .and(
-            get_beacon_genesis
-                .boxed()
-                .or(get_beacon_state_root.boxed())
-                .or(get_beacon_state_fork.boxed())
-                .or(get_beacon_state_finality_checkpoints.boxed())
-                .or(get_beacon_state_validator_balances.boxed())
-                .or(get_beacon_state_validators_id.boxed())
-                .or(get_beacon_state_validators.boxed())
-                .or(get_beacon_state_committees.boxed())
-                .or(get_beacon_state_sync_committees.boxed())
-                .or(get_beacon_headers.boxed())
-                .or(get_beacon_headers_block_id.boxed())
-                .or(get_beacon_block.boxed())
-                .or(get_beacon_block_attestations.boxed())
-                .or(get_beacon_block_root.boxed())
-                .or(get_beacon_pool_attestations.boxed())
-                .or(get_beacon_pool_attester_slashings.boxed())
-                .or(get_beacon_pool_proposer_slashings.boxed())
-                .or(get_beacon_pool_voluntary_exits.boxed())
-                .or(get_config_fork_schedule.boxed())
-                .or(get_config_spec.boxed())
-                .or(get_config_deposit_contract.boxed())
-                .or(get_debug_beacon_states.boxed())
-                .or(get_debug_beacon_heads.boxed())
-                .or(get_node_identity.boxed())
-                .or(get_node_version.boxed())
-                .or(get_node_syncing.boxed())
-                .or(get_node_health.boxed())
-                .or(get_node_peers_by_id.boxed())
-                .or(get_node_peers.boxed())
-                .or(get_node_peer_count.boxed())
-                .or(get_validator_duties_proposer.boxed())
-                .or(get_validator_blocks.boxed())
-                .or(get_validator_blinded_blocks.boxed())
-                .or(get_validator_attestation_data.boxed())
-                .or(get_validator_aggregate_attestation.boxed())
-                .or(get_validator_sync_committee_contribution.boxed())
-                .or(get_lighthouse_health.boxed())
-                .or(get_lighthouse_syncing.boxed())
-                .or(get_lighthouse_nat.boxed())
-                .or(get_lighthouse_peers.boxed())
-                .or(get_lighthouse_peers_connected.boxed())
-                .or(get_lighthouse_proto_array.boxed())
-                .or(get_lighthouse_validator_inclusion_global.boxed())
-                .or(get_lighthouse_validator_inclusion.boxed())
-                .or(get_lighthouse_eth1_syncing.boxed())
-                .or(get_lighthouse_eth1_block_cache.boxed())
-                .or(get_lighthouse_eth1_deposit_cache.boxed())
-                .or(get_lighthouse_beacon_states_ssz.boxed())
-                .or(get_lighthouse_staking.boxed())
-                .or(get_lighthouse_database_info.boxed())
-                .or(get_lighthouse_block_rewards.boxed())
-                .or(get_lighthouse_attestation_performance.boxed())
-                .or(get_lighthouse_block_packing_efficiency.boxed())
-                .or(get_lighthouse_merge_readiness.boxed())
-                .or(get_events.boxed()),
+            warp::get()
+                .and(
+                    get_beacon_genesis
+                        .boxed()
+                        .or(get_beacon_state_root.boxed())
+                        .or(get_beacon_state_fork.boxed())
+                        .or(get_beacon_state_finality_checkpoints.boxed())
+                        .or(get_beacon_state_validator_balances.boxed())
+                        .or(get_beacon_state_validators_id.boxed())
+                        .or(get_beacon_state_validators.boxed())
+                        .or(get_beacon_state_committees.boxed())
+                        .or(get_beacon_state_sync_committees.boxed())
+                        .or(get_beacon_headers.boxed())
+                        .or(get_beacon_headers_block_id.boxed())
+                        .or(get_beacon_block.boxed())
+                        .or(get_beacon_block_attestations.boxed())
+                        .or(get_beacon_rewards_blocks.boxed())
+                        .or(get_beacon_block_root.boxed())
+                        .or(get_beacon_pool_attestations.boxed())
+                        .or(get_beacon_pool_attester_slashings.boxed())
+                        .or(get_beacon_pool_proposer_slashings.boxed())
+                        .or(get_beacon_pool_voluntary_exits.boxed())
+                        .or(get_config_fork_schedule.boxed())
+                        .or(get_config_spec.boxed())
+                        .or(get_config_deposit_contract.boxed())
+                        .or(get_debug_beacon_states.boxed())
+                        .or(get_debug_beacon_heads.boxed())
+                        .or(get_debug_fork_choice.boxed())
+                        .or(get_node_identity.boxed())
+                        .or(get_node_version.boxed())
+                        .or(get_node_syncing.boxed())
+                        .or(get_node_health.boxed())
+                        .or(get_node_peers_by_id.boxed())
+                        .or(get_node_peers.boxed())
+                        .or(get_node_peer_count.boxed())
+                        .or(get_validator_duties_proposer.boxed())
+                        .or(get_validator_blocks.boxed())
+                        .or(get_validator_blinded_blocks.boxed())
+                        .or(get_validator_attestation_data.boxed())
+                        .or(get_validator_aggregate_attestation.boxed())
+                        .or(get_validator_sync_committee_contribution.boxed())
+                        .or(get_lighthouse_health.boxed())
+                        .or(get_lighthouse_syncing.boxed())
+                        .or(get_lighthouse_nat.boxed())
+                        .or(get_lighthouse_peers.boxed())
+                        .or(get_lighthouse_peers_connected.boxed())
+                        .or(get_lighthouse_proto_array.boxed())
+                        .or(get_lighthouse_validator_inclusion_global.boxed())
+                        .or(get_lighthouse_validator_inclusion.boxed())
+                        .or(get_lighthouse_eth1_syncing.boxed())
+                        .or(get_lighthouse_eth1_block_cache.boxed())
+                        .or(get_lighthouse_eth1_deposit_cache.boxed())
+                        .or(get_lighthouse_beacon_states_ssz.boxed())
+                        .or(get_lighthouse_staking.boxed())
+                        .or(get_lighthouse_database_info.boxed())
+                        .or(get_lighthouse_database_backup.boxed())
+                        .or(get_lighthouse_block_rewards.boxed())
+                        .or(get_lighthouse_attestation_performance.boxed())
+                        .or(get_lighthouse_block_packing_efficiency.boxed())
+                        .or(get_lighthouse_validator_monitor_history.boxed())
+                        .or(get_lighthouse_proposals.boxed())
+                        .or(get_lighthouse_fork_choice_votes.boxed())
+                        .or(get_lighthouse_fork_choice_vote_diff.boxed())
+                        .or(get_lighthouse_slasher_slashings.boxed())
+                        .or(get_lighthouse_merge_readiness.boxed())
+                        .or(get_events.boxed()),
+                )
+                .or(warp::post().and(
+                    // The SSZ routes must be matched first, as they are distinguished from the JSON
+                    // routes only by their `Content-Type` header.
+                    post_beacon_blocks_ssz
+                        .boxed()
+                        .or(post_beacon_blinded_blocks_ssz.boxed())
+                        .or(post_beacon_blocks.boxed())
+                        .or(post_beacon_blinded_blocks.boxed())
+                        .or(post_beacon_pool_attestations.boxed())
+                        .or(post_beacon_rewards_sync_committee.boxed())
+                        .or(post_beacon_rewards_attestations.boxed())
+                        .or(post_beacon_pool_attester_slashings.boxed())
+                        .or(post_beacon_pool_proposer_slashings.boxed())
+                        .or(post_beacon_pool_voluntary_exits.boxed())
+                        .or(post_beacon_pool_sync_committees.boxed())
+                        .or(post_validator_duties_attester.boxed())
+                        .or(post_validator_duties_sync.boxed())
+                        .or(post_validator_aggregate_and_proofs.boxed())
+                        .or(post_validator_contribution_and_proofs.boxed())
+                        .or(post_validator_beacon_committee_subscriptions.boxed())
+                        .or(post_validator_sync_committee_subscriptions.boxed())
+                        .or(post_validator_prepare_beacon_proposer.boxed())
+                        .or(post_validator_register_validator.boxed())
+                        .or(post_lighthouse_liveness.boxed())
+                        .or(post_lighthouse_database_reconstruct.boxed())
+                        .or(post_lighthouse_database_backup.boxed())
+                        .or(post_lighthouse_database_historical_blocks.boxed())
+                        .or(post_lighthouse_block_rewards.boxed()),
+                )),
         )
-        .or(warp::post().and(
-            post_beacon_blocks
-                .boxed()
-                .or(post_beacon_blinded_blocks.boxed())
-                .or(post_beacon_pool_attestations.boxed())
-                .or(post_beacon_pool_attester_slashings.boxed())
-                .or(post_beacon_pool_proposer_slashings.boxed())
-                .or(post_beacon_pool_voluntary_exits.boxed())
-                .or(post_beacon_pool_sync_committees.boxed())
-                .or(post_validator_duties_attester.boxed())
-                .or(post_validator_duties_sync.boxed())
-                .or(post_validator_aggregate_and_proofs.boxed())
-                .or(post_validator_contribution_and_proofs.boxed())
-                .or(post_validator_beacon_committee_subscriptions.boxed())
-                .or(post_validator_sync_committee_subscriptions.boxed())
-                .or(post_validator_prepare_beacon_proposer.boxed())
-                .or(post_validator_register_validator.boxed())
-                .or(post_lighthouse_liveness.boxed())
-                .or(post_lighthouse_database_reconstruct.boxed())
-                .or(post_lighthouse_database_historical_blocks.boxed())
-                .or(post_lighthouse_block_rewards.boxed()),
-        ))
         .recover(warp_utils::reject::handle_rejection)
         .with(slog_logging(log.clone()))
         .with(prometheus_metrics())
@@ -3130,15 +3681,23 @@ pub fn serve<T: BeaconChainTypes>(
     let http_socket: SocketAddr = SocketAddr::new(config.listen_addr, config.listen_port);
     let http_server: HttpServer = match config.tls_config {
         Some(tls_config) => {
-            let (socket, server) = warp::serve(routes)
+            let mut tls_server = warp::serve(routes)
                 .tls()
                 .cert_path(tls_config.cert)
-                .key_path(tls_config.key)
-                .try_bind_with_graceful_shutdown(http_socket, async {
+                .key_path(tls_config.key);
+            if let Some(client_ca) = &tls_config.client_ca {
+                tls_server = tls_server.client_auth_required_path(client_ca);
+            }
+            let (socket, server) =
+                tls_server.try_bind_with_graceful_shutdown(http_socket, async {
                     shutdown.await;
                 })?;
 
-            info!(log, "HTTP API is being served over TLS";);
+            info!(
+                log,
+                "HTTP API is being served over TLS";
+                "client_auth_required" => tls_config.client_ca.is_some(),
+            );
 
             (socket, Box::pin(server))
         }
@@ -3160,6 +3719,38 @@ pub fn serve<T: BeaconChainTypes>(
     Ok(http_server)
 }
 
+/// Build a response with an SSZ-encoded body.
+fn ssz_response(bytes: Vec<u8>) -> Result<warp::reply::Response, warp::Rejection> {
+    Response::builder()
+        .status(200)
+        .header("Content-Type", "application/octet-stream")
+        .body(bytes.into())
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("failed to create response: {}", e))
+        })
+}
+
+/// Build a response with an SSZ-encoded body, giving the fork and the `execution_optimistic` status
+/// of the data in the `Eth-Consensus-Version` and `Eth-Execution-Optimistic` headers.
+fn ssz_response_with_metadata(
+    bytes: Vec<u8>,
+    fork_name: ForkName,
+    execution_optimistic: bool,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    ssz_response(bytes).map(|resp| {
+        add_execution_optimistic_header(
+            add_consensus_version_header(resp, fork_name),
+            execution_optimistic,
+        )
+        .into_response()
+    })
+}
+
+/// Match requests with an SSZ body.
+fn ssz_content_type_filter() -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::header::exact_ignore_case("content-type", "application/octet-stream")
+}
+
 /// Publish a message to the libp2p pubsub network.
 fn publish_pubsub_message<T: EthSpec>(
     network_tx: &UnboundedSender<NetworkMessage<T>>,
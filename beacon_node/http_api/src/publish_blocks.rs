@@ -27,6 +27,8 @@ pub async fn publish_block<T: BeaconChainTypes>(
     // specification is very clear that this is the desired behaviour.
     crate::publish_pubsub_message(network_tx, PubsubMessage::BeaconBlock(block.clone()))?;
 
+    chain.register_published_block(&block, seen_timestamp);
+
     // Determine the delay after the start of the slot, register it with metrics.
     let delay = get_block_delay_ms(seen_timestamp, block.message(), &chain.slot_clock);
     metrics::observe_duration(&metrics::HTTP_API_BLOCK_BROADCAST_DELAY_TIMES, delay);
@@ -127,6 +129,10 @@ pub async fn publish_blinded_block<T: BeaconChainTypes>(
     network_tx: &UnboundedSender<NetworkMessage<T::EthSpec>>,
     log: Logger,
 ) -> Result<(), Rejection> {
+    // Register the blinded block prior to reconstruction, so that the signing round-trip does not
+    // include the time taken to obtain the full payload.
+    chain.register_published_block(&block, timestamp_now());
+
     let full_block = reconstruct_block(chain.clone(), block, log.clone()).await?;
     publish_block::<T>(Arc::new(full_block), chain, network_tx, log).await
 }
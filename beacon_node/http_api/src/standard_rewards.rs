@@ -0,0 +1,165 @@
+//! Handlers for the standard `/eth/v1/beacon/rewards` endpoints.
+
+use crate::{BlockId, ExecutionOptimistic, StateId};
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::types::ValidatorId;
+use eth2::types::{StandardAttestationRewards, StandardBlockReward, SyncCommitteeReward};
+use state_processing::state_advance::complete_state_advance;
+use std::str::FromStr;
+use std::sync::Arc;
+use types::{BeaconState, Epoch, EthSpec, SignedBlindedBeaconBlock};
+use warp_utils::reject::{
+    beacon_chain_error, beacon_state_error, custom_bad_request, custom_not_found,
+    custom_server_error,
+};
+
+/// Compute the rewards earned by the proposer of the block identified by `block_id`.
+pub fn compute_beacon_block_rewards<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    block_id: BlockId,
+) -> Result<(StandardBlockReward, ExecutionOptimistic), warp::Rejection> {
+    let (block, execution_optimistic) = block_id.blinded_block(&chain)?;
+    let block_root = block.canonical_root();
+
+    let mut state = get_block_pre_state(&block, &chain)?;
+
+    let reward = chain
+        .compute_beacon_block_reward(block.message(), block_root, &mut state)
+        .map_err(beacon_chain_error)?;
+
+    Ok((reward, execution_optimistic))
+}
+
+/// Compute the sync committee rewards paid by the block identified by `block_id`.
+///
+/// If `validators` is empty then the rewards for the entire sync committee are returned.
+pub fn compute_sync_committee_rewards<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    block_id: BlockId,
+    validators: Vec<String>,
+) -> Result<(Vec<SyncCommitteeReward>, ExecutionOptimistic), warp::Rejection> {
+    let (block, execution_optimistic) = block_id.blinded_block(&chain)?;
+
+    if block.message().body().sync_aggregate().is_err() {
+        return Err(custom_bad_request(format!(
+            "block at slot {} does not contain a sync aggregate",
+            block.slot()
+        )));
+    }
+
+    let validators = resolve_validator_indices(&chain, &validators)?;
+
+    let mut state = get_block_pre_state(&block, &chain)?;
+
+    let rewards = chain
+        .compute_sync_committee_rewards(block.message(), &mut state)
+        .map_err(beacon_chain_error)?
+        .into_iter()
+        .filter(|reward| {
+            validators.is_empty() || validators.contains(&(reward.validator_index as usize))
+        })
+        .collect();
+
+    Ok((rewards, execution_optimistic))
+}
+
+/// Compute the rewards and penalties for attestations made during `epoch`.
+///
+/// If `validators` is empty then the rewards for all eligible validators are returned.
+pub fn compute_attestation_rewards<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    epoch: Epoch,
+    validators: Vec<String>,
+) -> Result<(StandardAttestationRewards, ExecutionOptimistic), warp::Rejection> {
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+
+    // Rewards for attestations in `epoch` are applied at the end of the following epoch.
+    let end_slot = (epoch + 1).end_slot(slots_per_epoch);
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+    if end_slot > head_slot {
+        return Err(custom_not_found(format!(
+            "rewards for epoch {} are not available until slot {}",
+            epoch, end_slot
+        )));
+    }
+
+    let validators = resolve_validator_indices(&chain, &validators)?;
+
+    let (state, execution_optimistic) = StateId::from_slot(end_slot).state(&chain)?;
+
+    let rewards = chain
+        .compute_attestation_rewards(epoch, state, &validators)
+        .map_err(|e| match e {
+            BeaconChainError::ValidatorIndexUnknown(index) => {
+                custom_bad_request(format!("unknown validator index {}", index))
+            }
+            e => beacon_chain_error(e),
+        })?;
+
+    Ok((rewards, execution_optimistic))
+}
+
+/// Load the parent state of `block` and advance it to the slot of `block`.
+fn get_block_pre_state<T: BeaconChainTypes>(
+    block: &SignedBlindedBeaconBlock<T::EthSpec>,
+    chain: &BeaconChain<T>,
+) -> Result<BeaconState<T::EthSpec>, warp::Rejection> {
+    if block.slot() == 0 {
+        return Err(custom_bad_request(
+            "rewards are not defined for the genesis block".to_string(),
+        ));
+    }
+
+    let parent_root = block.parent_root();
+    let parent_block = chain
+        .get_blinded_block(&parent_root)
+        .and_then(|maybe_block| {
+            maybe_block.ok_or(BeaconChainError::MissingBeaconBlock(parent_root))
+        })
+        .map_err(beacon_chain_error)?;
+
+    let parent_state_root = parent_block.state_root();
+    let mut state = chain
+        .get_state(&parent_state_root, Some(parent_block.slot()))
+        .and_then(|maybe_state| {
+            maybe_state.ok_or(BeaconChainError::MissingBeaconState(parent_state_root))
+        })
+        .map_err(beacon_chain_error)?;
+
+    complete_state_advance(
+        &mut state,
+        Some(parent_state_root),
+        block.slot(),
+        &chain.spec,
+    )
+    .map_err(|e| custom_server_error(format!("unable to advance pre-state: {:?}", e)))?;
+
+    state
+        .build_all_caches(&chain.spec)
+        .map_err(beacon_state_error)?;
+
+    Ok(state)
+}
+
+/// Convert the pubkeys or indices in `validators` into validator indices.
+fn resolve_validator_indices<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    validators: &[String],
+) -> Result<Vec<usize>, warp::Rejection> {
+    validators
+        .iter()
+        .map(|validator| {
+            match ValidatorId::from_str(validator)
+                .map_err(|e| custom_bad_request(format!("invalid validator id: {}", e)))?
+            {
+                ValidatorId::Index(index) => Ok(index as usize),
+                ValidatorId::PublicKey(pubkey) => chain
+                    .validator_index(&pubkey)
+                    .map_err(beacon_chain_error)?
+                    .ok_or_else(|| {
+                        custom_bad_request(format!("unknown validator pubkey {:?}", pubkey))
+                    }),
+            }
+        })
+        .collect()
+}
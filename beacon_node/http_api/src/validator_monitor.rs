@@ -0,0 +1,39 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{ValidatorMonitorEpochSummary, ValidatorMonitorHistoryQuery};
+use std::sync::Arc;
+use types::PublicKeyBytes;
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_not_found};
+
+/// Returns the persisted validator monitor summaries for the validator with `pubkey`.
+///
+/// `to_epoch` defaults to the current epoch and `from_epoch` defaults to the start of the retention
+/// period relative to `to_epoch`.
+pub fn get_validator_monitor_history<T: BeaconChainTypes>(
+    pubkey: PublicKeyBytes,
+    query: ValidatorMonitorHistoryQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<ValidatorMonitorEpochSummary>, warp::Rejection> {
+    let validator_index = chain
+        .validator_index(&pubkey)
+        .map_err(beacon_chain_error)?
+        .ok_or_else(|| custom_not_found(format!("unknown validator: {}", pubkey)))?;
+
+    let to_epoch = match query.to_epoch {
+        Some(epoch) => epoch,
+        None => chain.epoch().map_err(beacon_chain_error)?,
+    };
+    let from_epoch = query
+        .from_epoch
+        .unwrap_or_else(|| to_epoch.saturating_sub(chain.config.validator_monitor_history_epochs));
+
+    if from_epoch > to_epoch {
+        return Err(custom_bad_request(format!(
+            "from_epoch must not be larger than to_epoch. from: {}, to: {}",
+            from_epoch, to_epoch
+        )));
+    }
+
+    chain
+        .validator_monitor_history(validator_index as u64, from_epoch, to_epoch)
+        .map_err(beacon_chain_error)
+}
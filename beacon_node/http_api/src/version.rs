@@ -1,7 +1,7 @@
 use crate::api_types::{
     EndpointVersion, ExecutionOptimisticForkVersionedResponse, ForkVersionedResponse,
 };
-use eth2::CONSENSUS_VERSION_HEADER;
+use eth2::{CONSENSUS_VERSION_HEADER, EXECUTION_OPTIMISTIC_HEADER};
 use serde::Serialize;
 use types::{ForkName, InconsistentFork};
 use warp::reply::{self, Reply, WithHeader};
@@ -52,6 +52,18 @@ pub fn add_consensus_version_header<T: Reply>(reply: T, fork_name: ForkName) ->
     reply::with_header(reply, CONSENSUS_VERSION_HEADER, fork_name.to_string())
 }
 
+/// Add the `Eth-Execution-Optimistic` header to a response.
+pub fn add_execution_optimistic_header<T: Reply>(
+    reply: T,
+    execution_optimistic: bool,
+) -> WithHeader<T> {
+    reply::with_header(
+        reply,
+        EXECUTION_OPTIMISTIC_HEADER,
+        execution_optimistic.to_string(),
+    )
+}
+
 pub fn inconsistent_fork_rejection(error: InconsistentFork) -> warp::reject::Rejection {
     warp_utils::reject::custom_server_error(format!("wrong fork: {:?}", error))
 }
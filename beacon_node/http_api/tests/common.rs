@@ -1,6 +1,6 @@
 use beacon_chain::{
     test_utils::{BeaconChainHarness, EphemeralHarnessType},
-    BeaconChain, BeaconChainTypes,
+    BeaconChain, BeaconChainTypes, ChainConfig,
 };
 use eth2::{BeaconNodeHttpClient, Timeouts};
 use http_api::{Config, Context};
@@ -48,8 +48,17 @@ pub struct ApiServer<E: EthSpec, SFut: Future<Output = ()>> {
 
 impl<E: EthSpec> InteractiveTester<E> {
     pub async fn new(spec: Option<ChainSpec>, validator_count: usize) -> Self {
+        Self::new_with_chain_config(spec, validator_count, ChainConfig::default()).await
+    }
+
+    pub async fn new_with_chain_config(
+        spec: Option<ChainSpec>,
+        validator_count: usize,
+        chain_config: ChainConfig,
+    ) -> Self {
         let harness = BeaconChainHarness::builder(E::default())
             .spec_or_default(spec)
+            .chain_config(chain_config)
             .deterministic_keypairs(validator_count)
             .fresh_ephemeral_store()
             .build();
@@ -96,6 +105,25 @@ pub async fn create_api_server_on_port<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     log: Logger,
     port: u16,
+) -> ApiServer<T::EthSpec, impl Future<Output = ()>> {
+    let config = Config {
+        enabled: true,
+        listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+        listen_port: port,
+        allow_origin: None,
+        tls_config: None,
+        allow_sync_stalled: false,
+        spec_fork_name: None,
+        auth_config: None,
+        backup_dir: Some(std::env::temp_dir()),
+    };
+    create_api_server_with_config(chain, log, config).await
+}
+
+pub async fn create_api_server_with_config<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    log: Logger,
+    config: Config,
 ) -> ApiServer<T::EthSpec, impl Future<Output = ()>> {
     let (network_tx, network_rx) = mpsc::unbounded_channel();
 
@@ -136,15 +164,7 @@ pub async fn create_api_server_on_port<T: BeaconChainTypes>(
     let eth1_service = eth1::Service::new(eth1::Config::default(), log.clone(), chain.spec.clone());
 
     let context = Arc::new(Context {
-        config: Config {
-            enabled: true,
-            listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
-            listen_port: port,
-            allow_origin: None,
-            tls_config: None,
-            allow_sync_stalled: false,
-            spec_fork_name: None,
-        },
+        config,
         chain: Some(chain.clone()),
         network_tx: Some(network_tx),
         network_globals: Some(network_globals),
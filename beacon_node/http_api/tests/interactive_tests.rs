@@ -1,7 +1,24 @@
 //! Generic tests that make use of the (newer) `InteractiveApiTester`
 use crate::common::*;
-use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
-use eth2::types::DepositContractData;
+use beacon_chain::{
+    chain_config::DEFAULT_RE_ORG_THRESHOLD,
+    slot_clock::SlotClock,
+    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy},
+    ChainConfig,
+};
+use eth2::{
+    reqwest::{
+        self,
+        header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    },
+    types::DepositContractData,
+    BeaconNodeHttpClient, StatusCode, Timeouts,
+};
+use http_api::Config;
+use sensitive_url::SensitiveUrl;
+use std::fs;
+use std::time::Duration;
+use tempfile::TempDir;
 use tree_hash::TreeHash;
 use types::{EthSpec, FullPayload, MainnetEthSpec, Slot};
 
@@ -130,3 +147,251 @@ pub async fn fork_choice_before_proposal() {
     // D's parent is B.
     assert_eq!(block_d.parent_root(), block_root_b.into());
 }
+
+// Test that a proposer with re-orgs enabled builds upon the parent of a late head block which has
+// not received any attestations.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn proposer_re_org_late_weak_head() {
+    proposer_re_org_test(Duration::from_secs(8), true).await;
+}
+
+// Test that a proposer with re-orgs enabled builds upon a weak head block which arrived on time.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn proposer_re_org_timely_weak_head() {
+    proposer_re_org_test(Duration::from_secs(1), false).await;
+}
+
+/// Produce a block in the slot after a head block `B` which was observed `head_delay` into its
+/// slot and received no attestations, and check whether it re-orgs `B`.
+async fn proposer_re_org_test(head_delay: Duration, should_re_org: bool) {
+    // Validator count needs to be at least 32 or the committee weight is 0 when computing
+    // `validator_count // 32`.
+    let validator_count = 32;
+    let num_initial: u64 = 31;
+
+    let chain_config = ChainConfig {
+        re_org_threshold: Some(DEFAULT_RE_ORG_THRESHOLD),
+        ..ChainConfig::default()
+    };
+    let tester =
+        InteractiveTester::<E>::new_with_chain_config(None, validator_count, chain_config).await;
+    let harness = &tester.harness;
+
+    // Create some chain depth.
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            num_initial as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // We set up the following block graph, where C may re-org B by building upon A.
+    //
+    // A | B | - |
+    // ^ | - | C |
+    let slot_a = Slot::new(num_initial);
+    let slot_b = slot_a + 1;
+    let slot_c = slot_a + 2;
+    let block_root_a = harness.head_block_root();
+
+    harness.advance_slot();
+    let state_a = harness.get_current_state();
+    let (block_b, state_b) = harness.make_block(state_a, slot_b).await;
+
+    // Record the time at which B was observed, as the gossip handler would.
+    let slot_start_b = harness.chain.slot_clock.start_of(slot_b).unwrap();
+    harness.chain.block_times_cache.write().set_time_observed(
+        block_b.canonical_root(),
+        slot_b,
+        slot_start_b + head_delay,
+        None,
+        None,
+    );
+    let block_root_b = harness.process_block(slot_b, block_b).await.unwrap();
+    assert_eq!(
+        harness.chain.canonical_head.cached_head().head_block_root(),
+        block_root_b.into()
+    );
+
+    // Manually prod the per-slot task, because the slot timer doesn't run in the background in
+    // these tests. This runs fork choice and expires the proposer boost of B.
+    harness.advance_slot();
+    harness.chain.per_slot_task().await;
+
+    let proposer_index = state_b
+        .get_beacon_proposer_index(slot_c, &harness.chain.spec)
+        .unwrap();
+    let randao_reveal = harness
+        .sign_randao_reveal(&state_b, proposer_index, slot_c)
+        .into();
+    let block_c = tester
+        .client
+        .get_validator_blocks::<E, FullPayload<E>>(slot_c, &randao_reveal, None)
+        .await
+        .unwrap()
+        .data;
+
+    let expected_parent = if should_re_org {
+        block_root_a
+    } else {
+        block_root_b.into()
+    };
+    assert_eq!(block_c.parent_root(), expected_parent);
+}
+
+// Test that bearer-token authentication restricts each route to tokens with a sufficient scope.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn http_api_authentication() {
+    let validator_count = 24;
+    let harness = BeaconChainHarness::builder(E::default())
+        .default_spec()
+        .deterministic_keypairs(validator_count)
+        .fresh_ephemeral_store()
+        .build();
+
+    let dir = TempDir::new().unwrap();
+    let scopes = ["read", "publish", "admin"];
+    let mut auth_config = String::new();
+    for scope in scopes {
+        let token_path = dir.path().join(format!("{}.txt", scope));
+        fs::write(&token_path, format!("{}-token\n", scope)).unwrap();
+        auth_config.push_str(&format!(
+            "- token_path: {:?}\n  scope: {}\n",
+            token_path, scope
+        ));
+    }
+    let auth_config_path = dir.path().join("auth.yaml");
+    fs::write(&auth_config_path, auth_config).unwrap();
+
+    let config = Config {
+        enabled: true,
+        listen_port: unused_port::unused_tcp_port().unwrap(),
+        auth_config: Some(auth_config_path),
+        ..Config::default()
+    };
+    let ApiServer {
+        server,
+        listening_socket,
+        shutdown_tx: _server_shutdown,
+        ..
+    } = create_api_server_with_config(harness.chain.clone(), harness.logger().clone(), config)
+        .await;
+    tokio::spawn(server);
+
+    let client_with_token = |token: Option<&str>| {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = token {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+            );
+        }
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap();
+        BeaconNodeHttpClient::from_components(
+            SensitiveUrl::parse(&format!("http://{}", listening_socket)).unwrap(),
+            client,
+            Timeouts::set_all(Duration::from_secs(1)),
+        )
+    };
+
+    // Requests without a token are unauthorized.
+    let client = client_with_token(None);
+    let err = client.get_beacon_genesis().await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+
+    // Requests with an unknown token are forbidden.
+    let client = client_with_token(Some("unknown-token"));
+    let err = client.get_beacon_genesis().await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+
+    // Each scope grants access to its own routes and the routes of lower scopes.
+    for (i, scope) in scopes.iter().enumerate() {
+        let client = client_with_token(Some(&format!("{}-token", scope)));
+
+        client.get_beacon_genesis().await.unwrap();
+
+        let publish = client.post_beacon_pool_attestations::<E>(&[]).await;
+        let admin = client.get_lighthouse_syncing().await;
+        if i >= 1 {
+            publish.unwrap();
+        } else {
+            assert_eq!(publish.unwrap_err().status(), Some(StatusCode::FORBIDDEN));
+        }
+        if i >= 2 {
+            admin.unwrap();
+        } else {
+            assert_eq!(admin.unwrap_err().status(), Some(StatusCode::FORBIDDEN));
+        }
+    }
+}
+
+// Test that the per-epoch summaries of monitored validators are persisted and served by the API.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn validator_monitor_history() {
+    let validator_count = 32;
+    let num_epochs = 4;
+    let monitored_index = 0;
+
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    harness
+        .chain
+        .validator_monitor
+        .write()
+        .auto_register_local_validator(monitored_index);
+
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            num_epochs * E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    harness.chain.persist_validator_monitor_history().unwrap();
+
+    let pubkey = harness.validator_keypairs[monitored_index as usize]
+        .pk
+        .compress();
+    let history = client
+        .get_lighthouse_validator_monitor_history(&pubkey, None, None)
+        .await
+        .unwrap()
+        .data;
+
+    assert!(!history.is_empty(), "history should be recorded");
+    assert!(history.windows(2).all(|pair| pair[0].epoch < pair[1].epoch));
+    for summary in &history {
+        assert_eq!(summary.validator_index, monitored_index);
+        // Attestations in the genesis epoch may not be included, since there is no block at the
+        // genesis slot.
+        if summary.epoch > 0 {
+            assert!(summary.attestation_hit, "{:?}", summary);
+            assert!(summary.attestation_head_hit, "{:?}", summary);
+            assert!(summary.attestation_target_hit, "{:?}", summary);
+        }
+    }
+
+    // Query a single epoch.
+    let first = &history[0];
+    let single = client
+        .get_lighthouse_validator_monitor_history(&pubkey, Some(first.epoch), Some(first.epoch))
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(single, vec![first.clone()]);
+
+    // An inverted range is rejected.
+    let err = client
+        .get_lighthouse_validator_monitor_history(&pubkey, Some(first.epoch + 1), Some(first.epoch))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+}
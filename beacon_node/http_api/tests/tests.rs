@@ -6,6 +6,7 @@ use beacon_chain::{
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{DatabaseBackupRequest, DatabaseBackupState},
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{BlockId as CoreBlockId, StateId as CoreStateId, *},
@@ -21,8 +22,9 @@ use network::NetworkMessage;
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
-use state_processing::per_slot_processing;
+use state_processing::{common::base::get_base_reward, per_slot_processing};
 use std::convert::TryInto;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{mpsc, oneshot};
 use tokio::time::Duration;
@@ -623,8 +625,31 @@ impl ApiTester {
                         validators
                     });
 
+                    let result_ssz = self
+                        .client
+                        .get_beacon_states_validators_ssz(
+                            state_id.0,
+                            Some(validator_index_ids.as_slice()),
+                            None,
+                        )
+                        .await
+                        .unwrap();
+                    let result_json = self
+                        .client
+                        .get_beacon_states_validators(
+                            state_id.0,
+                            Some(validator_index_ids.as_slice()),
+                            None,
+                        )
+                        .await
+                        .unwrap();
+                    // The SSZ response must include the `execution_optimistic` status given in the
+                    // JSON response.
+                    assert_eq!(result_ssz, result_json, "{:?}", state_id);
+
                     assert_eq!(result_index_ids, expected, "{:?}", state_id);
                     assert_eq!(result_pubkey_ids, expected, "{:?}", state_id);
+                    assert_eq!(result_ssz.map(|res| res.data), expected, "{:?}", state_id);
                 }
             }
         }
@@ -702,6 +727,18 @@ impl ApiTester {
                 .unwrap()
                 .map(|res| res.data);
 
+            let results_ssz = self
+                .client
+                .get_beacon_states_committees_ssz(state_id.0, None, None, epoch_opt)
+                .await
+                .unwrap();
+            let results_json = self
+                .client
+                .get_beacon_states_committees(state_id.0, None, None, epoch_opt)
+                .await
+                .unwrap();
+            assert_eq!(results_ssz, results_json, "{}", state_id);
+
             if results.is_none() && state_opt.is_none() {
                 continue;
             }
@@ -906,6 +943,22 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_beacon_blocks_ssz_valid(mut self) -> Self {
+        let next_block = &self.next_block;
+
+        self.client
+            .post_beacon_blocks_ssz(next_block)
+            .await
+            .unwrap();
+
+        assert!(
+            self.network_rx.recv().await.is_some(),
+            "valid blocks should be sent to network"
+        );
+
+        self
+    }
+
     pub async fn test_post_beacon_blocks_invalid(mut self) -> Self {
         let mut next_block = self.next_block.clone();
         *next_block.message_mut().proposer_index_mut() += 1;
@@ -1034,6 +1087,266 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_beacon_rewards_blocks_altair(self) -> Self {
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+
+        for slot in (head_slot.as_u64() - 40)..=head_slot.as_u64() {
+            if SKIPPED_SLOTS.contains(&slot) {
+                continue;
+            }
+            let slot = Slot::new(slot);
+            let block_id = CoreBlockId::Slot(slot);
+
+            let block_reward = self
+                .client
+                .get_beacon_rewards_blocks(block_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            let sync_rewards = self
+                .client
+                .post_beacon_rewards_sync_committee(block_id, &[])
+                .await
+                .unwrap()
+                .data;
+
+            let mut pre_state = self
+                .chain
+                .state_at_slot(slot - 1, StateSkipConfig::WithStateRoots)
+                .unwrap();
+            per_slot_processing(&mut pre_state, None, &self.chain.spec).unwrap();
+            let post_state = self
+                .chain
+                .state_at_slot(slot, StateSkipConfig::WithStateRoots)
+                .unwrap();
+            let balance_diff = |index: usize| {
+                post_state.balances()[index] as i64 - pre_state.balances()[index] as i64
+            };
+
+            assert!(!sync_rewards.is_empty());
+            assert_eq!(
+                block_reward.total,
+                block_reward.attestations
+                    + block_reward.sync_aggregate
+                    + block_reward.proposer_slashings
+                    + block_reward.attester_slashings
+            );
+
+            // The proposer's balance also includes its own reward as a sync committee member.
+            let proposer_index = block_reward.proposer_index as usize;
+            let proposer_sync_reward = sync_rewards
+                .iter()
+                .find(|reward| reward.validator_index as usize == proposer_index)
+                .map_or(0, |reward| reward.reward);
+            assert_eq!(
+                balance_diff(proposer_index),
+                block_reward.total as i64 + proposer_sync_reward,
+                "slot {}",
+                slot
+            );
+
+            for reward in &sync_rewards {
+                let index = reward.validator_index as usize;
+                if index != proposer_index {
+                    assert_eq!(balance_diff(index), reward.reward, "slot {}", slot);
+                }
+            }
+
+            // Requesting a single validator filters the response.
+            let filtered = self
+                .client
+                .post_beacon_rewards_sync_committee(block_id, &[ValidatorId::Index(0)])
+                .await
+                .unwrap()
+                .data;
+            assert_eq!(
+                filtered,
+                sync_rewards
+                    .iter()
+                    .filter(|reward| reward.validator_index == 0)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        self
+    }
+
+    pub async fn test_beacon_rewards_attestations_altair(self) -> Self {
+        let spec = &self.chain.spec;
+
+        for epoch in 1..=2 {
+            let epoch = Epoch::new(epoch);
+            let rewards = self
+                .client
+                .post_beacon_rewards_attestations(epoch, &[])
+                .await
+                .unwrap()
+                .data;
+
+            let pre_state = self
+                .chain
+                .state_at_slot(
+                    (epoch + 1).end_slot(E::slots_per_epoch()),
+                    StateSkipConfig::WithStateRoots,
+                )
+                .unwrap();
+            let mut post_state = pre_state.clone();
+            per_slot_processing(&mut post_state, None, spec).unwrap();
+
+            assert_eq!(
+                rewards.ideal_rewards.len() as u64,
+                spec.max_effective_balance / spec.effective_balance_increment
+            );
+            assert_eq!(rewards.total_rewards.len(), VALIDATOR_COUNT);
+
+            for reward in &rewards.total_rewards {
+                let index = reward.validator_index as usize;
+                assert_eq!(reward.inclusion_delay, None);
+                assert_eq!(
+                    post_state.balances()[index] as i64 - pre_state.balances()[index] as i64,
+                    reward.head + reward.target + reward.source + reward.inactivity,
+                    "epoch {}, validator {}",
+                    epoch,
+                    index
+                );
+            }
+        }
+
+        // Requesting a single validator filters the response.
+        let pubkey = self.validator_keypairs()[3].pk.compress();
+        let rewards = self
+            .client
+            .post_beacon_rewards_attestations(Epoch::new(2), &[ValidatorId::PublicKey(pubkey)])
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(rewards.total_rewards.len(), 1);
+        assert_eq!(rewards.total_rewards[0].validator_index, 3);
+
+        // Rewards for an epoch that has not been processed are unavailable.
+        let head_epoch = self.chain.epoch().unwrap();
+        let result = self
+            .client
+            .post_beacon_rewards_attestations(head_epoch, &[])
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
+    pub async fn test_beacon_rewards_base(self) -> Self {
+        let spec = &self.chain.spec;
+        let head = self.chain.head_snapshot();
+        let head_slot = head.beacon_block.slot();
+
+        // All validators have the same effective balance, so every attester earns the proposer
+        // the same reward.
+        let head_total_active_balance = head.beacon_state.get_total_active_balance().unwrap();
+        let head_proposer_reward =
+            get_base_reward(&head.beacon_state, 0, head_total_active_balance, spec).unwrap()
+                / spec.proposer_reward_quotient;
+        let num_attesters = head
+            .beacon_block
+            .message()
+            .body()
+            .attestations()
+            .iter()
+            .map(|attestation| attestation.aggregation_bits.num_set_bits() as u64)
+            .sum::<u64>();
+        assert!(
+            num_attesters > 0,
+            "precondition: head block has attestations"
+        );
+
+        let block_reward = self
+            .client
+            .get_beacon_rewards_blocks(CoreBlockId::Slot(head_slot))
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(
+            block_reward.proposer_index,
+            head.beacon_block.message().proposer_index()
+        );
+        assert_eq!(
+            block_reward.attestations,
+            num_attesters * head_proposer_reward
+        );
+        assert_eq!(block_reward.sync_aggregate, 0);
+        assert_eq!(block_reward.proposer_slashings, 0);
+        assert_eq!(block_reward.attester_slashings, 0);
+        assert_eq!(block_reward.total, block_reward.attestations);
+
+        let result = self
+            .client
+            .post_beacon_rewards_sync_committee(CoreBlockId::Slot(head_slot), &[])
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+
+        // Every validator attested correctly and was included with the minimum delay during
+        // epoch 1, so each should receive exactly the ideal reward for its effective balance.
+        let epoch = Epoch::new(1);
+        let rewards = self
+            .client
+            .post_beacon_rewards_attestations(epoch, &[])
+            .await
+            .unwrap()
+            .data;
+
+        let pre_state = self
+            .chain
+            .state_at_slot(
+                (epoch + 1).end_slot(E::slots_per_epoch()),
+                StateSkipConfig::WithStateRoots,
+            )
+            .unwrap();
+        let mut post_state = pre_state.clone();
+        per_slot_processing(&mut post_state, None, spec).unwrap();
+
+        let total_active_balance = pre_state.get_total_active_balance().unwrap();
+        let effective_balance = pre_state.validators()[0].effective_balance;
+        let ideal = rewards
+            .ideal_rewards
+            .iter()
+            .find(|ideal| ideal.effective_balance == effective_balance)
+            .unwrap();
+        let base_reward = get_base_reward(&pre_state, 0, total_active_balance, spec).unwrap();
+        let proposer_reward = base_reward / spec.proposer_reward_quotient;
+        assert_eq!(
+            ideal.inclusion_delay.as_ref().unwrap().value,
+            base_reward - proposer_reward
+        );
+
+        assert_eq!(rewards.total_rewards.len(), VALIDATOR_COUNT);
+        let mut total_delta = 0;
+        for reward in &rewards.total_rewards {
+            assert_eq!(reward.head, ideal.head as i64);
+            assert_eq!(reward.target, ideal.target as i64);
+            assert_eq!(reward.source, ideal.source as i64);
+            assert_eq!(reward.inclusion_delay, ideal.inclusion_delay);
+            assert_eq!(reward.inactivity, 0);
+            total_delta += reward.head
+                + reward.target
+                + reward.source
+                + reward.inclusion_delay.as_ref().unwrap().value as i64
+                + reward.inactivity;
+        }
+
+        // The remainder of the change in balances is the proposers' share of the inclusion
+        // rewards.
+        let total_balance =
+            |state: &BeaconState<E>| state.balances().iter().map(|b| *b as i64).sum::<i64>();
+        assert_eq!(
+            total_balance(&post_state) - total_balance(&pre_state),
+            total_delta + VALIDATOR_COUNT as i64 * proposer_reward as i64
+        );
+
+        self
+    }
+
     pub async fn test_post_beacon_pool_attestations_valid(mut self) -> Self {
         self.client
             .post_beacon_pool_attestations(self.attestations.as_slice())
@@ -1522,6 +1835,71 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_debug_fork_choice(self) -> Self {
+        let result = self.client.get_debug_fork_choice().await.unwrap();
+
+        let fork_choice = self.chain.canonical_head.fork_choice_read_lock();
+        let proto_array = fork_choice.proto_array().core_proto_array();
+
+        assert_eq!(
+            result.justified_checkpoint,
+            proto_array.justified_checkpoint
+        );
+        assert_eq!(
+            result.finalized_checkpoint,
+            proto_array.finalized_checkpoint
+        );
+        assert_eq!(
+            result.extra_data.proposer_boost.root,
+            proto_array.previous_proposer_boost.root
+        );
+        assert_eq!(
+            result.extra_data.justified_balances.num_active_validators,
+            VALIDATOR_COUNT as u64
+        );
+        assert_eq!(result.fork_choice_nodes.len(), proto_array.nodes.len());
+
+        for (result_node, node) in result.fork_choice_nodes.iter().zip(&proto_array.nodes) {
+            assert_eq!(result_node.slot, node.slot);
+            assert_eq!(result_node.block_root, node.root);
+            assert_eq!(
+                result_node.parent_root,
+                node.parent.map(|index| proto_array.nodes[index].root)
+            );
+            assert_eq!(
+                Some(result_node.justified_epoch),
+                node.justified_checkpoint.map(|checkpoint| checkpoint.epoch)
+            );
+            assert_eq!(
+                Some(result_node.finalized_epoch),
+                node.finalized_checkpoint.map(|checkpoint| checkpoint.epoch)
+            );
+            assert_eq!(
+                result_node.extra_data.justified_checkpoint,
+                node.justified_checkpoint
+            );
+            assert_eq!(
+                result_node.extra_data.finalized_checkpoint,
+                node.finalized_checkpoint
+            );
+            assert_eq!(result_node.weight, node.weight);
+            assert_eq!(
+                result_node.execution_block_hash,
+                node.execution_status.block_hash()
+            );
+        }
+
+        let head_root = self.chain.head_beacon_block_root();
+        assert!(result
+            .fork_choice_nodes
+            .iter()
+            .any(|node| node.block_root == head_root));
+
+        drop(fork_choice);
+
+        self
+    }
+
     fn validator_count(&self) -> usize {
         self.chain.head_snapshot().beacon_state.validators().len()
     }
@@ -1922,6 +2300,151 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_proposals(self) -> Self {
+        let fork = self.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = self.chain.genesis_validators_root;
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        // Blocks may already have been produced at `slot` whilst setting up the tester.
+        let num_existing = self
+            .client
+            .get_lighthouse_proposals(slot)
+            .await
+            .unwrap()
+            .data
+            .len();
+
+        let proposer_pubkey_bytes = self
+            .client
+            .get_validator_duties_proposer(epoch)
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .find(|duty| duty.slot == slot)
+            .map(|duty| duty.pubkey)
+            .unwrap();
+        let proposer_pubkey = (&proposer_pubkey_bytes).try_into().unwrap();
+
+        let sk = self
+            .validator_keypairs()
+            .iter()
+            .find(|kp| kp.pk == proposer_pubkey)
+            .map(|kp| kp.sk.clone())
+            .unwrap();
+
+        let randao_reveal = {
+            let domain =
+                self.chain
+                    .spec
+                    .get_domain(epoch, Domain::Randao, &fork, genesis_validators_root);
+            let message = epoch.signing_root(domain);
+            sk.sign(message).into()
+        };
+
+        let block = self
+            .client
+            .get_validator_blocks::<E, FullPayload<E>>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data;
+
+        let proposals = self
+            .client
+            .get_lighthouse_proposals(slot)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(proposals.len(), num_existing + 1);
+        let proposal = proposals.last().unwrap();
+        assert_eq!(proposal.slot, slot);
+        assert_eq!(proposal.proposer_index, block.proposer_index());
+        assert_eq!(proposal.block_root, block.canonical_root());
+        assert!(!proposal.blinded);
+        assert!(proposal.state_load_time_ms.is_some());
+        assert!(proposal.total_time_ms >= proposal.block_completion_time_ms);
+        assert_eq!(proposal.signing_round_trip_ms, None);
+
+        let signed_block = block.sign(&sk, &fork, genesis_validators_root, &self.chain.spec);
+        self.client.post_beacon_blocks(&signed_block).await.unwrap();
+
+        let proposals = self
+            .client
+            .get_lighthouse_proposals(slot)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(proposals.len(), num_existing + 1);
+        assert!(proposals.last().unwrap().signing_round_trip_ms.is_some());
+
+        self
+    }
+
+    pub async fn test_block_production_ssz(self) -> Self {
+        let fork = self.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = self.chain.genesis_validators_root;
+
+        for _ in 0..E::slots_per_epoch() {
+            let slot = self.chain.slot().unwrap();
+            let epoch = self.chain.epoch().unwrap();
+
+            let proposer_pubkey_bytes = self
+                .client
+                .get_validator_duties_proposer(epoch)
+                .await
+                .unwrap()
+                .data
+                .into_iter()
+                .find(|duty| duty.slot == slot)
+                .map(|duty| duty.pubkey)
+                .unwrap();
+            let proposer_pubkey = (&proposer_pubkey_bytes).try_into().unwrap();
+
+            let sk = self
+                .validator_keypairs()
+                .iter()
+                .find(|kp| kp.pk == proposer_pubkey)
+                .map(|kp| kp.sk.clone())
+                .unwrap();
+
+            let randao_reveal = {
+                let domain = self.chain.spec.get_domain(
+                    epoch,
+                    Domain::Randao,
+                    &fork,
+                    genesis_validators_root,
+                );
+                let message = epoch.signing_root(domain);
+                sk.sign(message).into()
+            };
+
+            let block = self
+                .client
+                .get_validator_blocks_ssz::<E, FullPayload<E>>(
+                    slot,
+                    &randao_reveal,
+                    None,
+                    &self.chain.spec,
+                )
+                .await
+                .unwrap();
+
+            let signed_block = block.sign(&sk, &fork, genesis_validators_root, &self.chain.spec);
+
+            self.client
+                .post_beacon_blocks_ssz(&signed_block)
+                .await
+                .unwrap();
+
+            assert_eq!(self.chain.head_beacon_block().as_ref(), &signed_block);
+
+            self.chain.slot_clock.set_slot(slot.as_u64() + 1);
+        }
+
+        self
+    }
+
     pub async fn test_block_production_no_verify_randao(self) -> Self {
         for _ in 0..E::slots_per_epoch() {
             let slot = self.chain.slot().unwrap();
@@ -2211,6 +2734,12 @@ impl ApiTester {
                 .unwrap()
                 .data;
 
+            let result_ssz = self
+                .client
+                .get_validator_attestation_data_ssz(slot, index)
+                .await
+                .unwrap();
+
             let expected = self
                 .chain
                 .produce_unaggregated_attestation(slot, index)
@@ -2218,6 +2747,7 @@ impl ApiTester {
                 .data;
 
             assert_eq!(result, expected);
+            assert_eq!(result_ssz, expected);
         }
 
         self
@@ -3122,6 +3652,49 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_fork_choice_votes(self) -> Self {
+        let head_root = self.chain.canonical_head.cached_head().head_block_root();
+
+        let votes = self
+            .client
+            .get_lighthouse_fork_choice_votes(CoreBlockId::Head)
+            .await
+            .unwrap()
+            .data;
+        {
+            let fork_choice = self.chain.canonical_head.fork_choice_read_lock();
+            assert_eq!(votes.block_root, head_root);
+            assert_eq!(
+                votes.supporting_validators,
+                fork_choice.supporting_validators(head_root)
+            );
+            assert!(votes.equivocating_validators.is_empty());
+            assert_eq!(
+                votes.queued_attestations.len(),
+                fork_choice.queued_attestations().len()
+            );
+        }
+
+        let diff = self
+            .client
+            .get_lighthouse_fork_choice_vote_diff(CoreBlockId::Head, CoreBlockId::Genesis)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(diff.first_root, head_root);
+        assert_eq!(diff.second_root, self.chain.genesis_block_root);
+        assert!(diff.first_only.is_empty());
+        assert_eq!(diff.both, votes.supporting_validators);
+
+        let result = self
+            .client
+            .get_lighthouse_fork_choice_votes(CoreBlockId::Root(Hash256::repeat_byte(42)))
+            .await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
     pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
         let epoch = self.chain.epoch().unwrap() - 1;
         self.client
@@ -3219,6 +3792,63 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_database_backup(self) -> Self {
+        // The server's backup directory is the system temp dir, which contains `backup_root`.
+        let backup_root = tempfile::tempdir().unwrap();
+        let request = DatabaseBackupRequest {
+            path: PathBuf::from(backup_root.path().file_name().unwrap()).join("backup"),
+        };
+
+        let result = self.client.get_lighthouse_database_backup().await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::NOT_FOUND));
+
+        let status = self
+            .client
+            .post_lighthouse_database_backup(&request)
+            .await
+            .unwrap();
+        assert_eq!(status.path, request.path);
+        assert_eq!(status.state, DatabaseBackupState::InProgress);
+
+        let mut status = self.client.get_lighthouse_database_backup().await.unwrap();
+        for _ in 0..100 {
+            if status.state != DatabaseBackupState::InProgress {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            status = self.client.get_lighthouse_database_backup().await.unwrap();
+        }
+        match status.state {
+            DatabaseBackupState::Complete { manifest } => {
+                assert_eq!(manifest.split, self.chain.store.get_split_info())
+            }
+            state => panic!("backup did not complete: {:?}", state),
+        }
+        assert!(backup_root
+            .path()
+            .join("backup")
+            .join(store::backup::MANIFEST_FILENAME)
+            .exists());
+
+        // The backup directory must be empty.
+        let result = self.client.post_lighthouse_database_backup(&request).await;
+        assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+
+        // The backup directory must be within the server's backup directory.
+        for path in [
+            backup_root.path().join("absolute"),
+            PathBuf::from("../escape"),
+        ] {
+            let result = self
+                .client
+                .post_lighthouse_database_backup(&DatabaseBackupRequest { path })
+                .await;
+            assert_eq!(result.unwrap_err().status(), Some(StatusCode::BAD_REQUEST));
+        }
+
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -3427,6 +4057,107 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_events_slashings(self) -> Self {
+        let topics = vec![EventTopic::ProposerSlashing, EventTopic::AttesterSlashing];
+        let mut events_future = self
+            .client
+            .get_events::<E>(topics.as_slice())
+            .await
+            .unwrap();
+
+        self.client
+            .post_beacon_pool_proposer_slashings(&self.proposer_slashing)
+            .await
+            .unwrap();
+        let proposer_slashing_events =
+            poll_events(&mut events_future, 1, Duration::from_millis(10000)).await;
+        assert_eq!(
+            proposer_slashing_events.as_slice(),
+            &[EventKind::ProposerSlashing(Box::new(
+                self.proposer_slashing.clone()
+            ))]
+        );
+
+        self.client
+            .post_beacon_pool_attester_slashings(&self.attester_slashing)
+            .await
+            .unwrap();
+        let attester_slashing_events =
+            poll_events(&mut events_future, 1, Duration::from_millis(10000)).await;
+        assert_eq!(
+            attester_slashing_events.as_slice(),
+            &[EventKind::AttesterSlashing(Box::new(
+                self.attester_slashing.clone()
+            ))]
+        );
+
+        self
+    }
+
+    pub async fn test_get_events_payload_attributes(self) -> Self {
+        let topics = vec![EventTopic::PayloadAttributes];
+        let mut events_future = self
+            .client
+            .get_events::<E>(topics.as_slice())
+            .await
+            .unwrap();
+
+        let current_slot = self.chain.slot().unwrap();
+        let prepare_slot = current_slot + 1;
+        let head = self.chain.canonical_head.cached_head();
+        let proposer_index = self
+            .chain
+            .state_at_slot(prepare_slot, StateSkipConfig::WithoutStateRoots)
+            .unwrap()
+            .get_beacon_proposer_index(prepare_slot, &self.chain.spec)
+            .unwrap() as u64;
+        let suggested_fee_recipient = self
+            .chain
+            .execution_layer
+            .as_ref()
+            .unwrap()
+            .get_suggested_fee_recipient(proposer_index)
+            .await;
+
+        let expected = EventKind::PayloadAttributes(ForkVersionedResponse {
+            version: Some(ForkName::Bellatrix),
+            data: SseExtendedPayloadAttributes {
+                proposal_slot: prepare_slot,
+                proposer_index,
+                parent_block_root: head.head_block_root(),
+                parent_block_number: head
+                    .snapshot
+                    .beacon_block
+                    .message()
+                    .execution_payload()
+                    .unwrap()
+                    .block_number(),
+                parent_block_hash: head.forkchoice_update_parameters().head_hash.unwrap(),
+                payload_attributes: SsePayloadAttributes {
+                    timestamp: self
+                        .chain
+                        .slot_clock
+                        .start_of(prepare_slot)
+                        .unwrap()
+                        .as_secs(),
+                    prev_randao: head.head_random().unwrap(),
+                    suggested_fee_recipient,
+                },
+            },
+        });
+
+        self.chain
+            .prepare_beacon_proposer(current_slot)
+            .await
+            .unwrap();
+
+        let payload_attributes_events =
+            poll_events(&mut events_future, 1, Duration::from_millis(10000)).await;
+        assert_eq!(payload_attributes_events.as_slice(), &[expected]);
+
+        self
+    }
+
     pub async fn test_get_events_altair(self) -> Self {
         let topics = vec![EventTopic::ContributionAndProof];
         let mut events_future = self
@@ -3563,6 +4294,19 @@ async fn get_events() {
     ApiTester::new().await.test_get_events().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_events_slashings() {
+    ApiTester::new().await.test_get_events_slashings().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_events_payload_attributes() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_get_events_payload_attributes()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_events_altair() {
     let mut spec = E::default_spec();
@@ -3581,6 +4325,21 @@ async fn get_events_from_genesis() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_rewards_altair() {
+    ApiTester::new_with_hard_forks(true, false)
+        .await
+        .test_beacon_rewards_blocks_altair()
+        .await
+        .test_beacon_rewards_attestations_altair()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_rewards_base() {
+    ApiTester::new().await.test_beacon_rewards_base().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn beacon_get() {
     ApiTester::new()
@@ -3628,6 +4387,14 @@ async fn post_beacon_blocks_valid() {
     ApiTester::new().await.test_post_beacon_blocks_valid().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_beacon_blocks_ssz_valid() {
+    ApiTester::new()
+        .await
+        .test_post_beacon_blocks_ssz_valid()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn post_beacon_blocks_invalid() {
     ApiTester::new()
@@ -3719,6 +4486,8 @@ async fn debug_get() {
         .test_get_debug_beacon_states()
         .await
         .test_get_debug_beacon_heads()
+        .await
+        .test_get_debug_fork_choice()
         .await;
 }
 
@@ -3789,6 +4558,11 @@ async fn block_production() {
     ApiTester::new().await.test_block_production().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn block_production_ssz() {
+    ApiTester::new().await.test_block_production_ssz().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn block_production_with_skip_slots() {
     ApiTester::new()
@@ -4070,6 +4844,8 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_proto_array()
         .await
+        .test_get_lighthouse_fork_choice_votes()
+        .await
         .test_get_lighthouse_validator_inclusion()
         .await
         .test_get_lighthouse_validator_inclusion_global()
@@ -4088,7 +4864,11 @@ async fn lighthouse_endpoints() {
         .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_database_backup()
+        .await
         .test_post_lighthouse_liveness()
+        .await
+        .test_get_lighthouse_proposals()
         .await;
 }
 
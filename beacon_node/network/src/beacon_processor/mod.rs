@@ -76,7 +76,10 @@ mod work_reprocessing_queue;
 mod worker;
 
 use crate::beacon_processor::work_reprocessing_queue::QueuedGossipBlock;
-pub use worker::{ChainSegmentProcessId, GossipAggregatePackage, GossipAttestationPackage};
+pub use worker::{
+    ChainSegmentProcessId, GossipAggregatePackage, GossipAttestationPackage,
+    GossipOperationPackage, GossipSyncContributionPackage, GossipSyncSignaturePackage,
+};
 
 /// The maximum size of the channel for work events to the `BeaconProcessor`.
 ///
@@ -162,19 +165,22 @@ const MANAGER_TASK_NAME: &str = "beacon_processor_manager";
 /// The name of the worker tokio tasks.
 const WORKER_TASK_NAME: &str = "beacon_processor_worker";
 
-/// The `MAX_..._BATCH_SIZE` variables define how many attestations can be included in a single
-/// batch.
+/// The `MAX_..._BATCH_SIZE` variables define how many attestations, sync committee messages, exits
+/// or slashings can be included in a single batch.
 ///
 /// Choosing these values is difficult since there is a trade-off between:
 ///
 /// - It is faster to verify one large batch than multiple smaller batches.
 /// - "Poisoning" attacks have a larger impact as the batch size increases.
 ///
-/// Poisoning occurs when an invalid signature is included in a batch of attestations. A single
+/// Poisoning occurs when an invalid signature is included in a batch of messages. A single
 /// invalid signature causes the entire batch to fail. When a batch fails, we fall-back to
-/// individually verifying each attestation signature.
+/// individually verifying each signature.
 const MAX_GOSSIP_ATTESTATION_BATCH_SIZE: usize = 64;
 const MAX_GOSSIP_AGGREGATE_BATCH_SIZE: usize = 64;
+const MAX_GOSSIP_SYNC_SIGNATURE_BATCH_SIZE: usize = 64;
+const MAX_GOSSIP_SYNC_CONTRIBUTION_BATCH_SIZE: usize = 64;
+const MAX_GOSSIP_OPERATION_BATCH_SIZE: usize = 64;
 
 /// Unique IDs used for metrics and testing.
 pub const WORKER_FREED: &str = "worker_freed";
@@ -186,10 +192,15 @@ pub const GOSSIP_AGGREGATE_BATCH: &str = "gossip_aggregate_batch";
 pub const GOSSIP_BLOCK: &str = "gossip_block";
 pub const DELAYED_IMPORT_BLOCK: &str = "delayed_import_block";
 pub const GOSSIP_VOLUNTARY_EXIT: &str = "gossip_voluntary_exit";
+pub const GOSSIP_VOLUNTARY_EXIT_BATCH: &str = "gossip_voluntary_exit_batch";
 pub const GOSSIP_PROPOSER_SLASHING: &str = "gossip_proposer_slashing";
+pub const GOSSIP_PROPOSER_SLASHING_BATCH: &str = "gossip_proposer_slashing_batch";
 pub const GOSSIP_ATTESTER_SLASHING: &str = "gossip_attester_slashing";
+pub const GOSSIP_ATTESTER_SLASHING_BATCH: &str = "gossip_attester_slashing_batch";
 pub const GOSSIP_SYNC_SIGNATURE: &str = "gossip_sync_signature";
+pub const GOSSIP_SYNC_SIGNATURE_BATCH: &str = "gossip_sync_signature_batch";
 pub const GOSSIP_SYNC_CONTRIBUTION: &str = "gossip_sync_contribution";
+pub const GOSSIP_SYNC_CONTRIBUTION_BATCH: &str = "gossip_sync_contribution_batch";
 pub const RPC_BLOCK: &str = "rpc_block";
 pub const CHAIN_SEGMENT: &str = "chain_segment";
 pub const STATUS_PROCESSING: &str = "status_processing";
@@ -681,16 +692,25 @@ pub enum Work<T: BeaconChainTypes> {
         peer_id: PeerId,
         voluntary_exit: Box<SignedVoluntaryExit>,
     },
+    GossipVoluntaryExitBatch {
+        packages: Vec<GossipOperationPackage<SignedVoluntaryExit>>,
+    },
     GossipProposerSlashing {
         message_id: MessageId,
         peer_id: PeerId,
         proposer_slashing: Box<ProposerSlashing>,
     },
+    GossipProposerSlashingBatch {
+        packages: Vec<GossipOperationPackage<ProposerSlashing>>,
+    },
     GossipAttesterSlashing {
         message_id: MessageId,
         peer_id: PeerId,
         attester_slashing: Box<AttesterSlashing<T::EthSpec>>,
     },
+    GossipAttesterSlashingBatch {
+        packages: Vec<GossipOperationPackage<AttesterSlashing<T::EthSpec>>>,
+    },
     GossipSyncSignature {
         message_id: MessageId,
         peer_id: PeerId,
@@ -698,12 +718,18 @@ pub enum Work<T: BeaconChainTypes> {
         subnet_id: SyncSubnetId,
         seen_timestamp: Duration,
     },
+    GossipSyncSignatureBatch {
+        packages: Vec<GossipSyncSignaturePackage>,
+    },
     GossipSyncContribution {
         message_id: MessageId,
         peer_id: PeerId,
         sync_contribution: Box<SignedContributionAndProof<T::EthSpec>>,
         seen_timestamp: Duration,
     },
+    GossipSyncContributionBatch {
+        packages: Vec<GossipSyncContributionPackage<T::EthSpec>>,
+    },
     RpcBlock {
         block: Arc<SignedBeaconBlock<T::EthSpec>>,
         seen_timestamp: Duration,
@@ -741,10 +767,15 @@ impl<T: BeaconChainTypes> Work<T> {
             Work::GossipBlock { .. } => GOSSIP_BLOCK,
             Work::DelayedImportBlock { .. } => DELAYED_IMPORT_BLOCK,
             Work::GossipVoluntaryExit { .. } => GOSSIP_VOLUNTARY_EXIT,
+            Work::GossipVoluntaryExitBatch { .. } => GOSSIP_VOLUNTARY_EXIT_BATCH,
             Work::GossipProposerSlashing { .. } => GOSSIP_PROPOSER_SLASHING,
+            Work::GossipProposerSlashingBatch { .. } => GOSSIP_PROPOSER_SLASHING_BATCH,
             Work::GossipAttesterSlashing { .. } => GOSSIP_ATTESTER_SLASHING,
+            Work::GossipAttesterSlashingBatch { .. } => GOSSIP_ATTESTER_SLASHING_BATCH,
             Work::GossipSyncSignature { .. } => GOSSIP_SYNC_SIGNATURE,
+            Work::GossipSyncSignatureBatch { .. } => GOSSIP_SYNC_SIGNATURE_BATCH,
             Work::GossipSyncContribution { .. } => GOSSIP_SYNC_CONTRIBUTION,
+            Work::GossipSyncContributionBatch { .. } => GOSSIP_SYNC_CONTRIBUTION_BATCH,
             Work::RpcBlock { .. } => RPC_BLOCK,
             Work::ChainSegment { .. } => CHAIN_SEGMENT,
             Work::Status { .. } => STATUS_PROCESSING,
@@ -1097,10 +1128,58 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                             }
                         // Check sync committee messages after attestations as their rewards are lesser
                         // and they don't influence fork choice.
-                        } else if let Some(item) = sync_contribution_queue.pop() {
-                            self.spawn_worker(item, toolbox);
-                        } else if let Some(item) = sync_message_queue.pop() {
-                            self.spawn_worker(item, toolbox);
+                        //
+                        // Potentially use batching.
+                        } else if sync_contribution_queue.len() > 0 {
+                            if let Some(work) = self.next_batch_or_single(
+                                sync_contribution_queue.len(),
+                                MAX_GOSSIP_SYNC_CONTRIBUTION_BATCH_SIZE,
+                                || sync_contribution_queue.pop(),
+                                |item| match item {
+                                    Work::GossipSyncContribution {
+                                        message_id,
+                                        peer_id,
+                                        sync_contribution,
+                                        seen_timestamp,
+                                    } => Some(GossipSyncContributionPackage::new(
+                                        message_id,
+                                        peer_id,
+                                        sync_contribution,
+                                        seen_timestamp,
+                                    )),
+                                    _ => None,
+                                },
+                                |packages| Work::GossipSyncContributionBatch { packages },
+                                "sync contribution",
+                            ) {
+                                self.spawn_worker(work, toolbox);
+                            }
+                        } else if sync_message_queue.len() > 0 {
+                            if let Some(work) = self.next_batch_or_single(
+                                sync_message_queue.len(),
+                                MAX_GOSSIP_SYNC_SIGNATURE_BATCH_SIZE,
+                                || sync_message_queue.pop(),
+                                |item| match item {
+                                    Work::GossipSyncSignature {
+                                        message_id,
+                                        peer_id,
+                                        sync_signature,
+                                        subnet_id,
+                                        seen_timestamp,
+                                    } => Some(GossipSyncSignaturePackage::new(
+                                        message_id,
+                                        peer_id,
+                                        sync_signature,
+                                        subnet_id,
+                                        seen_timestamp,
+                                    )),
+                                    _ => None,
+                                },
+                                |packages| Work::GossipSyncSignatureBatch { packages },
+                                "sync message",
+                            ) {
+                                self.spawn_worker(work, toolbox);
+                            }
                         // Aggregates and unaggregates queued for re-processing are older and we
                         // care about fresher ones, so check those first.
                         } else if let Some(item) = unknown_block_aggregate_queue.pop() {
@@ -1121,13 +1200,73 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                         //
                         // Check attester slashings before proposer slashings since they have the
                         // potential to slash multiple validators at once.
-                        } else if let Some(item) = gossip_attester_slashing_queue.pop() {
-                            self.spawn_worker(item, toolbox);
-                        } else if let Some(item) = gossip_proposer_slashing_queue.pop() {
-                            self.spawn_worker(item, toolbox);
+                        } else if gossip_attester_slashing_queue.len() > 0 {
+                            if let Some(work) = self.next_batch_or_single(
+                                gossip_attester_slashing_queue.len(),
+                                MAX_GOSSIP_OPERATION_BATCH_SIZE,
+                                || gossip_attester_slashing_queue.pop(),
+                                |item| match item {
+                                    Work::GossipAttesterSlashing {
+                                        message_id,
+                                        peer_id,
+                                        attester_slashing,
+                                    } => Some(GossipOperationPackage::new(
+                                        message_id,
+                                        peer_id,
+                                        attester_slashing,
+                                    )),
+                                    _ => None,
+                                },
+                                |packages| Work::GossipAttesterSlashingBatch { packages },
+                                "attester slashings",
+                            ) {
+                                self.spawn_worker(work, toolbox);
+                            }
+                        } else if gossip_proposer_slashing_queue.len() > 0 {
+                            if let Some(work) = self.next_batch_or_single(
+                                gossip_proposer_slashing_queue.len(),
+                                MAX_GOSSIP_OPERATION_BATCH_SIZE,
+                                || gossip_proposer_slashing_queue.pop(),
+                                |item| match item {
+                                    Work::GossipProposerSlashing {
+                                        message_id,
+                                        peer_id,
+                                        proposer_slashing,
+                                    } => Some(GossipOperationPackage::new(
+                                        message_id,
+                                        peer_id,
+                                        proposer_slashing,
+                                    )),
+                                    _ => None,
+                                },
+                                |packages| Work::GossipProposerSlashingBatch { packages },
+                                "proposer slashings",
+                            ) {
+                                self.spawn_worker(work, toolbox);
+                            }
                         // Check exits last since our validators don't get rewards from them.
-                        } else if let Some(item) = gossip_voluntary_exit_queue.pop() {
-                            self.spawn_worker(item, toolbox);
+                        } else if gossip_voluntary_exit_queue.len() > 0 {
+                            if let Some(work) = self.next_batch_or_single(
+                                gossip_voluntary_exit_queue.len(),
+                                MAX_GOSSIP_OPERATION_BATCH_SIZE,
+                                || gossip_voluntary_exit_queue.pop(),
+                                |item| match item {
+                                    Work::GossipVoluntaryExit {
+                                        message_id,
+                                        peer_id,
+                                        voluntary_exit,
+                                    } => Some(GossipOperationPackage::new(
+                                        message_id,
+                                        peer_id,
+                                        voluntary_exit,
+                                    )),
+                                    _ => None,
+                                },
+                                |packages| Work::GossipVoluntaryExitBatch { packages },
+                                "voluntary exits",
+                            ) {
+                                self.spawn_worker(work, toolbox);
+                            }
                         // Handle backfill sync chain segments.
                         } else if let Some(item) = backfill_chain_segment.pop() {
                             self.spawn_worker(item, toolbox);
@@ -1215,6 +1354,18 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                             Work::GossipSyncContribution { .. } => {
                                 sync_contribution_queue.push(work)
                             }
+                            // Exit, slashing and sync committee batches are formed internally
+                            // within the `BeaconProcessor`, they are not sent from external
+                            // services.
+                            Work::GossipVoluntaryExitBatch { .. }
+                            | Work::GossipProposerSlashingBatch { .. }
+                            | Work::GossipAttesterSlashingBatch { .. }
+                            | Work::GossipSyncSignatureBatch { .. }
+                            | Work::GossipSyncContributionBatch { .. } => crit!(
+                                    self.log,
+                                    "Unsupported inbound event";
+                                    "type" => work_id
+                            ),
                             Work::RpcBlock { .. } => rpc_block_queue.push(work, work_id, &self.log),
                             Work::ChainSegment { ref process_id, .. } => match process_id {
                                 ChainSegmentProcessId::RangeBatchId { .. }
@@ -1315,6 +1466,39 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
         executor.spawn(manager_future, MANAGER_TASK_NAME);
     }
 
+    /// Pops up to `max_batch_size` items from a queue of length `queue_len` using `pop`.
+    ///
+    /// A single item is returned unchanged so it can be processed individually. Two or more items
+    /// are converted into packages with `to_package` and returned as the batch built by
+    /// `to_batch`, so they can take advantage of batch signature verification.
+    fn next_batch_or_single<P>(
+        &self,
+        queue_len: usize,
+        max_batch_size: usize,
+        mut pop: impl FnMut() -> Option<Work<T>>,
+        to_package: fn(Work<T>) -> Option<P>,
+        to_batch: fn(Vec<P>) -> Work<T>,
+        queue_name: &str,
+    ) -> Option<Work<T>> {
+        let batch_size = cmp::min(queue_len, max_batch_size);
+
+        if batch_size < 2 {
+            return pop();
+        }
+
+        let mut packages = Vec::with_capacity(batch_size);
+        for _ in 0..batch_size {
+            if let Some(item) = pop() {
+                match to_package(item) {
+                    Some(package) => packages.push(package),
+                    None => error!(self.log, "Invalid item in queue"; "queue" => queue_name),
+                }
+            }
+        }
+
+        Some(to_batch(packages))
+    }
+
     /// Spawns a blocking worker thread to process some `Work`.
     ///
     /// Sends an message on `idle_tx` when the work is complete and the task is stopping.
@@ -1474,6 +1658,11 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
             } => task_spawner.spawn_blocking(move || {
                 worker.process_gossip_voluntary_exit(message_id, peer_id, *voluntary_exit)
             }),
+            /*
+             * Batched voluntary exit verification.
+             */
+            Work::GossipVoluntaryExitBatch { packages } => task_spawner
+                .spawn_blocking(move || worker.process_gossip_voluntary_exit_batch(packages)),
             /*
              * Proposer slashings received on gossip.
              */
@@ -1484,6 +1673,11 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
             } => task_spawner.spawn_blocking(move || {
                 worker.process_gossip_proposer_slashing(message_id, peer_id, *proposer_slashing)
             }),
+            /*
+             * Batched proposer slashing verification.
+             */
+            Work::GossipProposerSlashingBatch { packages } => task_spawner
+                .spawn_blocking(move || worker.process_gossip_proposer_slashing_batch(packages)),
             /*
              * Attester slashings received on gossip.
              */
@@ -1494,6 +1688,11 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
             } => task_spawner.spawn_blocking(move || {
                 worker.process_gossip_attester_slashing(message_id, peer_id, *attester_slashing)
             }),
+            /*
+             * Batched attester slashing verification.
+             */
+            Work::GossipAttesterSlashingBatch { packages } => task_spawner
+                .spawn_blocking(move || worker.process_gossip_attester_slashing_batch(packages)),
             /*
              * Sync committee message verification.
              */
@@ -1512,6 +1711,12 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                     seen_timestamp,
                 )
             }),
+            /*
+             * Batched sync committee message verification.
+             */
+            Work::GossipSyncSignatureBatch { packages } => task_spawner.spawn_blocking(move || {
+                worker.process_gossip_sync_committee_signature_batch(packages)
+            }),
             /*
              * Syn contribution verification.
              */
@@ -1528,6 +1733,11 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                     seen_timestamp,
                 )
             }),
+            /*
+             * Batched sync contribution verification.
+             */
+            Work::GossipSyncContributionBatch { packages } => task_spawner
+                .spawn_blocking(move || worker.process_sync_committee_contribution_batch(packages)),
             /*
              * Verification for beacon blocks received during syncing via RPC.
              */
@@ -9,7 +9,7 @@ use crate::{service::NetworkMessage, sync::SyncMessage};
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
 };
-use beacon_chain::{BeaconChain, MAXIMUM_GOSSIP_CLOCK_DISPARITY};
+use beacon_chain::{events::EventKind, BeaconChain, MAXIMUM_GOSSIP_CLOCK_DISPARITY};
 use lighthouse_network::{
     discv5::enr::{CombinedKey, EnrBuilder},
     rpc::methods::{MetaData, MetaDataV2},
@@ -557,6 +557,37 @@ async fn import_gossip_block_at_current_slot() {
     );
 }
 
+/// Blocks which pass gossip verification should be emitted on the `block_gossip` event stream.
+#[tokio::test]
+async fn import_gossip_block_emits_block_gossip_event() {
+    let mut rig = TestRig::new(SMALL_CHAIN).await;
+
+    let mut block_gossip_rx = rig
+        .chain
+        .event_handler
+        .as_ref()
+        .expect("harness should have an event handler")
+        .subscribe_block_gossip();
+
+    rig.enqueue_gossip_block();
+
+    rig.assert_event_journal(&[GOSSIP_BLOCK, WORKER_FREED, NOTHING_TO_DO])
+        .await;
+
+    match block_gossip_rx.try_recv() {
+        Ok(EventKind::BlockGossip(block_gossip)) => {
+            assert_eq!(block_gossip.slot, rig.next_block.slot());
+            assert_eq!(block_gossip.block, rig.next_block.canonical_root());
+            assert_eq!(
+                block_gossip.proposer_index,
+                rig.next_block.message().proposer_index()
+            );
+            assert_eq!(block_gossip.seen_timestamp, Duration::from_secs(0));
+        }
+        other => panic!("expected a block gossip event, got {:?}", other),
+    }
+}
+
 /// Ensure a valid attestation can be imported.
 #[tokio::test]
 async fn import_gossip_attestation() {
@@ -3,8 +3,11 @@ use crate::{metrics, service::NetworkMessage, sync::SyncMessage};
 use beacon_chain::store::Error;
 use beacon_chain::{
     attestation_verification::{self, Error as AttnError, VerifiedAttestation},
+    events::{EventKind, SseBlockGossip},
     observed_operations::ObservationOutcome,
-    sync_committee_verification::{self, Error as SyncCommitteeError},
+    sync_committee_verification::{
+        self, Error as SyncCommitteeError, VerifiedSyncCommitteeMessage, VerifiedSyncContribution,
+    },
     validator_monitor::get_block_delay_ms,
     BeaconChainError, BeaconChainTypes, BlockError, CountUnrealized, ForkChoiceError,
     GossipVerifiedBlock,
@@ -181,6 +184,78 @@ impl<E: EthSpec> GossipAggregatePackage<E> {
     }
 }
 
+/// Items required to verify a batch of gossip sync committee messages.
+#[derive(Debug)]
+pub struct GossipSyncSignaturePackage {
+    message_id: MessageId,
+    peer_id: PeerId,
+    sync_signature: Box<SyncCommitteeMessage>,
+    subnet_id: SyncSubnetId,
+    seen_timestamp: Duration,
+}
+
+impl GossipSyncSignaturePackage {
+    pub fn new(
+        message_id: MessageId,
+        peer_id: PeerId,
+        sync_signature: Box<SyncCommitteeMessage>,
+        subnet_id: SyncSubnetId,
+        seen_timestamp: Duration,
+    ) -> Self {
+        Self {
+            message_id,
+            peer_id,
+            sync_signature,
+            subnet_id,
+            seen_timestamp,
+        }
+    }
+}
+
+/// Items required to verify a batch of gossip sync committee contributions.
+#[derive(Debug)]
+pub struct GossipSyncContributionPackage<E: EthSpec> {
+    message_id: MessageId,
+    peer_id: PeerId,
+    sync_contribution: Box<SignedContributionAndProof<E>>,
+    seen_timestamp: Duration,
+}
+
+impl<E: EthSpec> GossipSyncContributionPackage<E> {
+    pub fn new(
+        message_id: MessageId,
+        peer_id: PeerId,
+        sync_contribution: Box<SignedContributionAndProof<E>>,
+        seen_timestamp: Duration,
+    ) -> Self {
+        Self {
+            message_id,
+            peer_id,
+            sync_contribution,
+            seen_timestamp,
+        }
+    }
+}
+
+/// Items required to verify a batch of gossip voluntary exits, proposer slashings or attester
+/// slashings.
+#[derive(Debug)]
+pub struct GossipOperationPackage<T> {
+    message_id: MessageId,
+    peer_id: PeerId,
+    operation: Box<T>,
+}
+
+impl<T> GossipOperationPackage<T> {
+    pub fn new(message_id: MessageId, peer_id: PeerId, operation: Box<T>) -> Self {
+        Self {
+            message_id,
+            peer_id,
+            operation,
+        }
+    }
+}
+
 impl<T: BeaconChainTypes> Worker<T> {
     /* Auxiliary functions */
 
@@ -731,6 +806,19 @@ impl<T: BeaconChainTypes> Worker<T> {
                 );
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
 
+                // Notify subscribers of the block before it is fully verified and imported.
+                if let Some(event_handler) = self.chain.event_handler.as_ref() {
+                    if event_handler.has_block_gossip_subscribers() {
+                        event_handler.register(EventKind::BlockGossip(Box::new(SseBlockGossip {
+                            slot: verified_block.block.slot(),
+                            block: verified_block.block_root,
+                            proposer_index: verified_block.block.message().proposer_index(),
+                            seen_timestamp: seen_duration,
+                            block_delay,
+                        })));
+                    }
+                }
+
                 // Log metrics to keep track of propagation delay times.
                 if let Some(duration) = SystemTime::now()
                     .duration_since(UNIX_EPOCH)
@@ -981,8 +1069,55 @@ impl<T: BeaconChainTypes> Worker<T> {
         voluntary_exit: SignedVoluntaryExit,
     ) {
         let validator_index = voluntary_exit.message.validator_index;
+        let result = self.chain.verify_voluntary_exit_for_gossip(voluntary_exit);
+        self.process_gossip_voluntary_exit_result(result, message_id, peer_id, validator_index);
+    }
 
-        let exit = match self.chain.verify_voluntary_exit_for_gossip(voluntary_exit) {
+    pub fn process_gossip_voluntary_exit_batch(
+        self,
+        packages: Vec<GossipOperationPackage<SignedVoluntaryExit>>,
+    ) {
+        let (metadata, exits): (Vec<_>, Vec<_>) = packages
+            .into_iter()
+            .map(|package| {
+                (
+                    (
+                        package.message_id,
+                        package.peer_id,
+                        package.operation.message.validator_index,
+                    ),
+                    *package.operation,
+                )
+            })
+            .unzip();
+
+        let results = match self.chain.batch_verify_voluntary_exits_for_gossip(exits) {
+            Ok(results) => results,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Batch exit verification failed";
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+
+        for (result, (message_id, peer_id, validator_index)) in
+            results.into_iter().zip(metadata.into_iter())
+        {
+            self.process_gossip_voluntary_exit_result(result, message_id, peer_id, validator_index);
+        }
+    }
+
+    fn process_gossip_voluntary_exit_result(
+        &self,
+        result: Result<ObservationOutcome<SignedVoluntaryExit>, BeaconChainError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+        validator_index: u64,
+    ) {
+        let exit = match result {
             Ok(ObservationOutcome::New(exit)) => exit,
             Ok(ObservationOutcome::AlreadyKnown) => {
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
@@ -1039,11 +1174,65 @@ impl<T: BeaconChainTypes> Worker<T> {
         proposer_slashing: ProposerSlashing,
     ) {
         let validator_index = proposer_slashing.signed_header_1.message.proposer_index;
+        let result = self
+            .chain
+            .verify_proposer_slashing_for_gossip(proposer_slashing);
+        self.process_gossip_proposer_slashing_result(result, message_id, peer_id, validator_index);
+    }
+
+    pub fn process_gossip_proposer_slashing_batch(
+        self,
+        packages: Vec<GossipOperationPackage<ProposerSlashing>>,
+    ) {
+        let (metadata, slashings): (Vec<_>, Vec<_>) = packages
+            .into_iter()
+            .map(|package| {
+                (
+                    (
+                        package.message_id,
+                        package.peer_id,
+                        package.operation.signed_header_1.message.proposer_index,
+                    ),
+                    *package.operation,
+                )
+            })
+            .unzip();
 
-        let slashing = match self
+        let results = match self
             .chain
-            .verify_proposer_slashing_for_gossip(proposer_slashing)
+            .batch_verify_proposer_slashings_for_gossip(slashings)
+        {
+            Ok(results) => results,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Batch proposer slashing verification failed";
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+
+        for (result, (message_id, peer_id, validator_index)) in
+            results.into_iter().zip(metadata.into_iter())
         {
+            self.process_gossip_proposer_slashing_result(
+                result,
+                message_id,
+                peer_id,
+                validator_index,
+            );
+        }
+    }
+
+    fn process_gossip_proposer_slashing_result(
+        &self,
+        result: Result<ObservationOutcome<ProposerSlashing>, BeaconChainError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+        validator_index: u64,
+    ) {
+        let slashing = match result {
             Ok(ObservationOutcome::New(slashing)) => slashing,
             Ok(ObservationOutcome::AlreadyKnown) => {
                 debug!(
@@ -1100,10 +1289,48 @@ impl<T: BeaconChainTypes> Worker<T> {
         peer_id: PeerId,
         attester_slashing: AttesterSlashing<T::EthSpec>,
     ) {
-        let slashing = match self
+        let result = self
+            .chain
+            .verify_attester_slashing_for_gossip(attester_slashing);
+        self.process_gossip_attester_slashing_result(result, message_id, peer_id);
+    }
+
+    pub fn process_gossip_attester_slashing_batch(
+        self,
+        packages: Vec<GossipOperationPackage<AttesterSlashing<T::EthSpec>>>,
+    ) {
+        let (metadata, slashings): (Vec<_>, Vec<_>) = packages
+            .into_iter()
+            .map(|package| ((package.message_id, package.peer_id), *package.operation))
+            .unzip();
+
+        let results = match self
             .chain
-            .verify_attester_slashing_for_gossip(attester_slashing)
+            .batch_verify_attester_slashings_for_gossip(slashings)
         {
+            Ok(results) => results,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Batch attester slashing verification failed";
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+
+        for (result, (message_id, peer_id)) in results.into_iter().zip(metadata.into_iter()) {
+            self.process_gossip_attester_slashing_result(result, message_id, peer_id);
+        }
+    }
+
+    fn process_gossip_attester_slashing_result(
+        &self,
+        result: Result<ObservationOutcome<AttesterSlashing<T::EthSpec>>, BeaconChainError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+    ) {
+        let slashing = match result {
             Ok(ObservationOutcome::New(slashing)) => slashing,
             Ok(ObservationOutcome::AlreadyKnown) => {
                 debug!(
@@ -1163,10 +1390,86 @@ impl<T: BeaconChainTypes> Worker<T> {
         seen_timestamp: Duration,
     ) {
         let message_slot = sync_signature.slot;
-        let sync_signature = match self
+        let result = self
+            .chain
+            .verify_sync_committee_message_for_gossip(sync_signature, subnet_id);
+        self.process_gossip_sync_committee_signature_result(
+            result,
+            message_id,
+            peer_id,
+            message_slot,
+            seen_timestamp,
+        );
+    }
+
+    pub fn process_gossip_sync_committee_signature_batch(
+        self,
+        packages: Vec<GossipSyncSignaturePackage>,
+    ) {
+        let (metadata, messages): (Vec<_>, Vec<_>) = packages
+            .into_iter()
+            .map(|package| {
+                (
+                    (
+                        package.message_id,
+                        package.peer_id,
+                        package.sync_signature.slot,
+                        package.seen_timestamp,
+                    ),
+                    (*package.sync_signature, package.subnet_id),
+                )
+            })
+            .unzip();
+
+        let results = match self
             .chain
-            .verify_sync_committee_message_for_gossip(sync_signature, subnet_id)
+            .batch_verify_sync_committee_messages_for_gossip(messages.into_iter())
         {
+            Ok(results) => results,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Batch sync message verification failed";
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+
+        // Sanity check.
+        if results.len() != metadata.len() {
+            // The log is `crit` since in this scenario we might be penalizing/rewarding the wrong
+            // peer.
+            crit!(
+                self.log,
+                "Batch sync message result mismatch";
+                "results" => results.len(),
+                "packages" => metadata.len(),
+            )
+        }
+
+        for (result, (message_id, peer_id, message_slot, seen_timestamp)) in
+            results.into_iter().zip(metadata.into_iter())
+        {
+            self.process_gossip_sync_committee_signature_result(
+                result,
+                message_id,
+                peer_id,
+                message_slot,
+                seen_timestamp,
+            );
+        }
+    }
+
+    fn process_gossip_sync_committee_signature_result(
+        &self,
+        result: Result<VerifiedSyncCommitteeMessage, SyncCommitteeError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+        message_slot: Slot,
+        seen_timestamp: Duration,
+    ) {
+        let sync_signature = match result {
             Ok(sync_signature) => sync_signature,
             Err(e) => {
                 self.handle_sync_committee_message_failure(
@@ -1225,10 +1528,86 @@ impl<T: BeaconChainTypes> Worker<T> {
         seen_timestamp: Duration,
     ) {
         let contribution_slot = sync_contribution.message.contribution.slot;
-        let sync_contribution = match self
+        let result = self
             .chain
-            .verify_sync_contribution_for_gossip(sync_contribution)
+            .verify_sync_contribution_for_gossip(sync_contribution);
+        self.process_sync_committee_contribution_result(
+            result,
+            message_id,
+            peer_id,
+            contribution_slot,
+            seen_timestamp,
+        );
+    }
+
+    pub fn process_sync_committee_contribution_batch(
+        self,
+        packages: Vec<GossipSyncContributionPackage<T::EthSpec>>,
+    ) {
+        let (metadata, contributions): (Vec<_>, Vec<_>) = packages
+            .into_iter()
+            .map(|package| {
+                (
+                    (
+                        package.message_id,
+                        package.peer_id,
+                        package.sync_contribution.message.contribution.slot,
+                        package.seen_timestamp,
+                    ),
+                    *package.sync_contribution,
+                )
+            })
+            .unzip();
+
+        let results = match self
+            .chain
+            .batch_verify_sync_contributions_for_gossip(contributions.into_iter())
         {
+            Ok(results) => results,
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Batch sync contribution verification failed";
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+
+        // Sanity check.
+        if results.len() != metadata.len() {
+            // The log is `crit` since in this scenario we might be penalizing/rewarding the wrong
+            // peer.
+            crit!(
+                self.log,
+                "Batch sync contribution result mismatch";
+                "results" => results.len(),
+                "packages" => metadata.len(),
+            )
+        }
+
+        for (result, (message_id, peer_id, contribution_slot, seen_timestamp)) in
+            results.into_iter().zip(metadata.into_iter())
+        {
+            self.process_sync_committee_contribution_result(
+                result,
+                message_id,
+                peer_id,
+                contribution_slot,
+                seen_timestamp,
+            );
+        }
+    }
+
+    fn process_sync_committee_contribution_result(
+        &self,
+        result: Result<VerifiedSyncContribution<T>, SyncCommitteeError>,
+        message_id: MessageId,
+        peer_id: PeerId,
+        contribution_slot: Slot,
+        seen_timestamp: Duration,
+    ) {
+        let sync_contribution = match result {
             Ok(sync_contribution) => sync_contribution,
             Err(e) => {
                 // Report the failure to gossipsub
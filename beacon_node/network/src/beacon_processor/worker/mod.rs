@@ -9,7 +9,10 @@ mod gossip_methods;
 mod rpc_methods;
 mod sync_methods;
 
-pub use gossip_methods::{GossipAggregatePackage, GossipAttestationPackage};
+pub use gossip_methods::{
+    GossipAggregatePackage, GossipAttestationPackage, GossipOperationPackage,
+    GossipSyncContributionPackage, GossipSyncSignaturePackage,
+};
 pub use sync_methods::ChainSegmentProcessId;
 
 pub(crate) const FUTURE_SLOT_TOLERANCE: u64 = 1;
@@ -0,0 +1,158 @@
+use std::collections::BTreeSet;
+use std::time::Instant;
+
+/// Compute all maximal cliques of the graph whose vertices are `vertices` and whose edges join
+/// each pair of vertices for which `is_compatible` returns `true`.
+///
+/// Each clique is returned as a sorted list of indices into `vertices`. Returns `None` if the
+/// `deadline` passes before the search is complete.
+///
+/// Uses the Bron–Kerbosch algorithm with pivoting, see:
+/// https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm
+pub fn bron_kerbosch<T, F>(
+    vertices: &[T],
+    is_compatible: F,
+    deadline: Instant,
+) -> Option<Vec<Vec<usize>>>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let neighbours = (0..vertices.len())
+        .map(|i| {
+            (0..vertices.len())
+                .filter(|&j| i != j && is_compatible(&vertices[i], &vertices[j]))
+                .collect::<BTreeSet<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut cliques = vec![];
+    expand(
+        &neighbours,
+        &mut vec![],
+        (0..vertices.len()).collect(),
+        BTreeSet::new(),
+        &mut cliques,
+        deadline,
+    )?;
+
+    for clique in &mut cliques {
+        clique.sort_unstable();
+    }
+    cliques.sort();
+
+    Some(cliques)
+}
+
+/// Extend the clique `r` with vertices from `p`, excluding the vertices in `x` which have already
+/// been used to extend `r`.
+fn expand(
+    neighbours: &[BTreeSet<usize>],
+    r: &mut Vec<usize>,
+    mut p: BTreeSet<usize>,
+    mut x: BTreeSet<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+    deadline: Instant,
+) -> Option<()> {
+    if Instant::now() >= deadline {
+        return None;
+    }
+
+    // Choose the pivot with the most neighbours in `p` to minimise the number of branches.
+    let pivot = match p
+        .union(&x)
+        .max_by_key(|&&u| neighbours[u].intersection(&p).count())
+    {
+        Some(&pivot) => pivot,
+        // If `p` and `x` are both empty then `r` is maximal. It is only empty if the graph has no
+        // vertices, in which case there are no cliques.
+        None => {
+            if !r.is_empty() {
+                cliques.push(r.clone());
+            }
+            return Some(());
+        }
+    };
+
+    let candidates = p
+        .difference(&neighbours[pivot])
+        .copied()
+        .collect::<Vec<_>>();
+
+    for v in candidates {
+        r.push(v);
+        expand(
+            neighbours,
+            r,
+            p.intersection(&neighbours[v]).copied().collect(),
+            x.intersection(&neighbours[v]).copied().collect(),
+            cliques,
+            deadline,
+        )?;
+        r.pop();
+        p.remove(&v);
+        x.insert(v);
+    }
+
+    Some(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    fn deadline() -> Instant {
+        Instant::now() + Duration::from_secs(60)
+    }
+
+    /// Find the maximal cliques of a graph given as a list of edges between `n` vertices.
+    fn cliques_of(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let vertices = (0..n).collect::<Vec<_>>();
+        bron_kerbosch(
+            &vertices,
+            |a, b| edges.contains(&(*a, *b)) || edges.contains(&(*b, *a)),
+            deadline(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn no_vertices() {
+        assert_eq!(cliques_of(0, &[]), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn no_edges() {
+        assert_eq!(cliques_of(3, &[]), vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn complete_graph() {
+        assert_eq!(
+            cliques_of(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]),
+            vec![vec![0, 1, 2, 3]]
+        );
+    }
+
+    // The example graph from Wikipedia, with vertices numbered from 0 rather than 1.
+    #[test]
+    fn wikipedia_example() {
+        let edges = [(0, 1), (0, 4), (1, 2), (1, 4), (2, 3), (3, 4), (3, 5)];
+        assert_eq!(
+            cliques_of(6, &edges),
+            vec![
+                vec![0, 1, 4],
+                vec![1, 2],
+                vec![2, 3],
+                vec![3, 4],
+                vec![3, 5]
+            ]
+        );
+    }
+
+    #[test]
+    fn deadline_exceeded() {
+        let vertices = (0..4).collect::<Vec<_>>();
+        assert_eq!(bron_kerbosch(&vertices, |_, _| true, Instant::now()), None);
+    }
+}
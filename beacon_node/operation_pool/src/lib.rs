@@ -1,6 +1,7 @@
 mod attestation;
 mod attestation_id;
 mod attester_slashing;
+mod bron_kerbosch;
 mod max_cover;
 mod metrics;
 mod persistence;
@@ -13,7 +14,8 @@ pub use persistence::{PersistedOperationPool, PersistedOperationPoolAltair};
 use crate::sync_aggregate_id::SyncAggregateId;
 use attestation_id::AttestationId;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::maximum_cover;
+use bron_kerbosch::bron_kerbosch;
+use max_cover::{maximum_cover, order_by_marginal_score, GroupedCover};
 use parking_lot::RwLock;
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
@@ -21,9 +23,10 @@ use state_processing::per_block_processing::{
     VerifySignatures,
 };
 use state_processing::SigVerifiedOp;
-use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::{Duration, Instant};
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, Attestation, AttesterSlashing,
     BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec, Fork, ForkVersion, Hash256,
@@ -33,10 +36,23 @@ use types::{
 
 type SyncContributions<T> = RwLock<HashMap<SyncAggregateId, Vec<SyncCommitteeContribution<T>>>>;
 
+/// The maximum time to spend re-aggregating and selecting attestations for a block, after which
+/// we fall back to the greedy selection of the attestations aggregated on insertion.
+pub const MAX_ATTESTATION_PACKING_TIME: Duration = Duration::from_millis(100);
+
+/// The maximum number of attestations to retain as they were received for each `AttestationId`.
+pub const MAX_RECEIVED_ATTESTATIONS_PER_DATA: usize = 64;
+
 #[derive(Default, Debug)]
 pub struct OperationPool<T: EthSpec + Default> {
     /// Map from attestation ID (see below) to vectors of attestations.
     attestations: RwLock<HashMap<AttestationId, Vec<Attestation<T>>>>,
+    /// Map from attestation ID to the attestations inserted for that ID, prior to aggregation.
+    ///
+    /// The attestations in `attestations` are aggregated greedily as they are inserted, which can
+    /// prevent compatible attestations from being combined. These are retained so that they can be
+    /// re-aggregated into maximal cliques when packing a block. They are not persisted.
+    received_attestations: RwLock<HashMap<AttestationId, Vec<Attestation<T>>>>,
     /// Map from sync aggregate ID to the best `SyncCommitteeContribution`s seen for that ID.
     sync_contributions: SyncContributions<T>,
     /// Set of attester slashings, and the fork version they were verified against.
@@ -185,6 +201,15 @@ impl<T: EthSpec> OperationPool<T> {
         // Take a write lock on the attestations map.
         let mut attestations = self.attestations.write();
 
+        // Retain a copy of the attestation prior to aggregation. The lock must be taken after the
+        // lock on `attestations` to match `get_attestations`.
+        let mut received_attestations = self.received_attestations.write();
+        let received = received_attestations.entry(id.clone()).or_default();
+        if received.len() < MAX_RECEIVED_ATTESTATIONS_PER_DATA && !received.contains(&attestation) {
+            received.push(attestation.clone());
+        }
+        drop(received_attestations);
+
         let existing_attestations = match attestations.entry(id) {
             Entry::Vacant(entry) => {
                 entry.insert(vec![attestation]);
@@ -232,16 +257,65 @@ impl<T: EthSpec> OperationPool<T> {
         }
     }
 
+    /// Aggregate the received attestations for each `AttestationId` into maximal cliques of
+    /// attestations with disjoint signers.
+    ///
+    /// Only aggregates which are not already present in `all_attestations` are returned. Returns
+    /// `None` if the `deadline` passes first.
+    fn get_clique_aggregates(
+        &self,
+        all_attestations: &HashMap<AttestationId, Vec<Attestation<T>>>,
+        deadline: Instant,
+    ) -> Option<HashMap<AttestationId, Vec<Attestation<T>>>> {
+        let _timer = metrics::start_timer(&metrics::ATTESTATION_CLIQUE_AGGREGATION_TIME);
+        let received_attestations = self.received_attestations.read();
+
+        let mut clique_aggregates = HashMap::new();
+        for (id, received) in received_attestations.iter() {
+            let cliques = bron_kerbosch(received, |a, b| a.signers_disjoint_from(b), deadline)?;
+
+            let existing = all_attestations.get(id);
+            let aggregates = cliques
+                .into_iter()
+                // A clique of a single attestation was aggregated into `all_attestations` on
+                // insertion, so it can't improve on the existing aggregates.
+                .filter(|clique| clique.len() > 1)
+                .filter_map(|clique| {
+                    let mut clique_attestations = clique.into_iter().map(|i| &received[i]);
+                    let mut aggregate = clique_attestations.next()?.clone();
+                    for attestation in clique_attestations {
+                        aggregate.aggregate(attestation);
+                    }
+                    let is_new = existing.map_or(true, |existing| !existing.contains(&aggregate));
+                    if is_new {
+                        Some(aggregate)
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            if !aggregates.is_empty() {
+                clique_aggregates.insert(id.clone(), aggregates);
+            }
+        }
+
+        Some(clique_aggregates)
+    }
+
     /// Return all valid attestations for the given epoch, for use in max cover.
-    fn get_valid_attestations_for_epoch<'a>(
+    fn get_valid_attestations_for_epoch<'a, I>(
         &'a self,
         epoch: Epoch,
-        all_attestations: &'a HashMap<AttestationId, Vec<Attestation<T>>>,
+        all_attestations: I,
         state: &'a BeaconState<T>,
         total_active_balance: u64,
         validity_filter: impl FnMut(&&Attestation<T>) -> bool + Send,
         spec: &'a ChainSpec,
-    ) -> impl Iterator<Item = AttMaxCover<'a, T>> + Send {
+    ) -> impl Iterator<Item = AttMaxCover<'a, T>> + Send
+    where
+        I: Iterator<Item = (&'a AttestationId, &'a Vec<Attestation<T>>)> + Send + 'a,
+    {
         let domain_bytes = AttestationId::compute_domain_bytes(
             epoch,
             &state.fork(),
@@ -249,7 +323,6 @@ impl<T: EthSpec> OperationPool<T> {
             spec,
         );
         all_attestations
-            .iter()
             .filter(move |(key, _)| key.domain_bytes_match(&domain_bytes))
             .flat_map(|(_, attestations)| attestations)
             .filter(move |attestation| attestation.data.target.epoch == epoch)
@@ -270,9 +343,14 @@ impl<T: EthSpec> OperationPool<T> {
     /// Get a list of attestations for inclusion in a block.
     ///
     /// The `validity_filter` is a closure that provides extra filtering of the attestations
-    /// before an approximately optimal bundle is constructed. We use it to provide access
-    /// to the fork choice data from the `BeaconChain` struct that doesn't logically belong
-    /// in the operation pool.
+    /// before an optimal bundle is constructed. We use it to provide access to the fork choice
+    /// data from the `BeaconChain` struct that doesn't logically belong in the operation pool.
+    ///
+    /// The received attestations are re-aggregated into maximal cliques and grouped by committee.
+    /// Committees with at most `MAX_EXACT_GROUP_SIZE` attestations are solved exactly and larger
+    /// committees greedily, and the committee solutions are then combined to maximise the
+    /// proposer's reward. If this takes longer than `MAX_ATTESTATION_PACKING_TIME` then all of the
+    /// attestations are selected greedily instead.
     pub fn get_attestations(
         &self,
         state: &BeaconState<T>,
@@ -280,6 +358,8 @@ impl<T: EthSpec> OperationPool<T> {
         curr_epoch_validity_filter: impl FnMut(&&Attestation<T>) -> bool + Send,
         spec: &ChainSpec,
     ) -> Result<Vec<Attestation<T>>, OpPoolError> {
+        let deadline = Instant::now() + MAX_ATTESTATION_PACKING_TIME;
+
         // Attestations for the current fork, which may be from the current or previous epoch.
         let prev_epoch = state.previous_epoch();
         let current_epoch = state.current_epoch();
@@ -288,82 +368,128 @@ impl<T: EthSpec> OperationPool<T> {
             .get_total_active_balance()
             .map_err(OpPoolError::GetAttestationsTotalBalanceError)?;
 
+        let clique_aggregates = self
+            .get_clique_aggregates(&*all_attestations, deadline)
+            .unwrap_or_else(|| {
+                metrics::inc_counter_vec(
+                    &metrics::ATTESTATION_PACKING_TIMEOUTS,
+                    &["clique_aggregation"],
+                );
+                HashMap::new()
+            });
+
         // Split attestations for the previous & current epochs, so that we
         // can optimise them individually in parallel.
-        let mut num_prev_valid = 0_i64;
-        let mut num_curr_valid = 0_i64;
-
-        let prev_epoch_att = self
-            .get_valid_attestations_for_epoch(
+        let prev_epoch_att = if prev_epoch == current_epoch {
+            // If we're in the genesis epoch, just use the current epoch attestations.
+            vec![]
+        } else {
+            self.get_valid_attestations_for_epoch(
                 prev_epoch,
-                &*all_attestations,
+                all_attestations.iter().chain(clique_aggregates.iter()),
                 state,
                 total_active_balance,
                 prev_epoch_validity_filter,
                 spec,
             )
-            .inspect(|_| num_prev_valid += 1);
+            .collect::<Vec<_>>()
+        };
         let curr_epoch_att = self
             .get_valid_attestations_for_epoch(
                 current_epoch,
-                &*all_attestations,
+                all_attestations.iter().chain(clique_aggregates.iter()),
                 state,
                 total_active_balance,
                 curr_epoch_validity_filter,
                 spec,
             )
-            .inspect(|_| num_curr_valid += 1);
+            .collect::<Vec<_>>();
+
+        metrics::set_gauge(
+            &metrics::NUM_PREV_EPOCH_ATTESTATIONS,
+            prev_epoch_att.len() as i64,
+        );
+        metrics::set_gauge(
+            &metrics::NUM_CURR_EPOCH_ATTESTATIONS,
+            curr_epoch_att.len() as i64,
+        );
 
+        let max_attestations = T::MaxAttestations::to_usize();
         let prev_epoch_limit = if let BeaconState::Base(base_state) = state {
             std::cmp::min(
                 T::MaxPendingAttestations::to_usize()
                     .saturating_sub(base_state.previous_epoch_attestations.len()),
-                T::MaxAttestations::to_usize(),
+                max_attestations,
             )
         } else {
-            T::MaxAttestations::to_usize()
+            max_attestations
         };
 
-        let (prev_cover, curr_cover) = rayon::join(
+        let (prev_packed, curr_packed) = rayon::join(
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_PREV_EPOCH_PACKING_TIME);
-                // If we're in the genesis epoch, just use the current epoch attestations.
-                if prev_epoch == current_epoch {
-                    vec![]
-                } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
-                }
+                pack_attestations(
+                    prev_epoch_att,
+                    prev_epoch_limit,
+                    "prev_epoch_attestations",
+                    deadline,
+                )
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(
+                pack_attestations(
                     curr_epoch_att,
-                    T::MaxAttestations::to_usize(),
+                    max_attestations,
                     "curr_epoch_attestations",
+                    deadline,
                 )
             },
         );
 
-        metrics::set_gauge(&metrics::NUM_PREV_EPOCH_ATTESTATIONS, num_prev_valid);
-        metrics::set_gauge(&metrics::NUM_CURR_EPOCH_ATTESTATIONS, num_curr_valid);
+        match (prev_packed, curr_packed) {
+            (PackedAttestations::Exact(prev_exact), PackedAttestations::Exact(curr_exact)) => {
+                // Split the attestations between the epochs so as to maximise the total reward.
+                let (prev_limit, curr_limit) = (0..=prev_epoch_limit)
+                    .map(|prev_limit| (prev_limit, max_attestations - prev_limit))
+                    .max_by_key(|(prev_limit, curr_limit)| {
+                        prev_exact
+                            .value(*prev_limit)
+                            .saturating_add(curr_exact.value(*curr_limit))
+                    })
+                    .unwrap_or((0, max_attestations));
+
+                let mut packed = prev_exact.into_solution(prev_limit);
+                packed.extend(curr_exact.into_solution(curr_limit));
 
-        Ok(max_cover::merge_solutions(
-            curr_cover,
-            prev_cover,
-            T::MaxAttestations::to_usize(),
-        ))
+                // Order the attestations by reward, as for the greedy solution.
+                Ok(order_by_marginal_score(packed))
+            }
+            (prev_packed, curr_packed) => {
+                metrics::inc_counter_vec(
+                    &metrics::ATTESTATION_PACKING_TIMEOUTS,
+                    &["exact_selection"],
+                );
+                Ok(max_cover::merge_solutions(
+                    curr_packed.into_cover(max_attestations),
+                    prev_packed.into_cover(prev_epoch_limit),
+                    max_attestations,
+                ))
+            }
+        }
     }
 
     /// Remove attestations which are too old to be included in a block.
     pub fn prune_attestations(&self, current_epoch: Epoch) {
         // Prune attestations that are from before the previous epoch.
-        self.attestations.write().retain(|_, attestations| {
+        let retain = |_: &AttestationId, attestations: &mut Vec<Attestation<T>>| {
             // All the attestations in this bucket have the same data, so we only need to
             // check the first one.
             attestations
                 .first()
                 .map_or(false, |att| current_epoch <= att.data.target.epoch + 1)
-        });
+        };
+        self.attestations.write().retain(retain);
+        self.received_attestations.write().retain(retain);
     }
 
     /// Insert a proposer slashing into the pool.
@@ -613,6 +739,50 @@ impl<T: EthSpec> OperationPool<T> {
     }
 }
 
+/// The attestations selected for a single epoch.
+enum PackedAttestations<'a, T: EthSpec> {
+    /// The best selection for every limit, combined from the solution for each committee.
+    Exact(GroupedCover<AttMaxCover<'a, T>>),
+    /// The greedy selection, used if the exact selection could not be made before the deadline.
+    Greedy(Vec<AttMaxCover<'a, T>>),
+}
+
+impl<'a, T: EthSpec> PackedAttestations<'a, T> {
+    /// The selected attestations, using at most `limit` attestations for an exact selection.
+    fn into_cover(self, limit: usize) -> Vec<AttMaxCover<'a, T>> {
+        match self {
+            PackedAttestations::Exact(exact) => exact.into_solution(limit),
+            PackedAttestations::Greedy(greedy) => greedy,
+        }
+    }
+}
+
+/// Select up to `limit` of the `attestations` for a single epoch.
+///
+/// The attestations are selected greedily only if the exact selection could not be made before
+/// the `deadline`.
+fn pack_attestations<'a, T: EthSpec>(
+    attestations: Vec<AttMaxCover<'a, T>>,
+    limit: usize,
+    label: &str,
+    deadline: Instant,
+) -> PackedAttestations<'a, T> {
+    // Validators attest in a single committee per epoch, so attestations from different
+    // committees cover disjoint sets of validators.
+    let mut committees = BTreeMap::<_, Vec<_>>::new();
+    for attestation in &attestations {
+        committees
+            .entry((attestation.att.data.slot, attestation.att.data.index))
+            .or_default()
+            .push(attestation.clone());
+    }
+
+    match GroupedCover::new(committees.into_values().collect(), limit, deadline) {
+        Some(exact) => PackedAttestations::Exact(exact),
+        None => PackedAttestations::Greedy(maximum_cover(attestations, limit, label)),
+    }
+}
+
 /// Filter up to a maximum number of operations out of an iterator.
 fn filter_limit_operations<'a, T: 'a, I, F>(operations: I, filter: F, limit: usize) -> Vec<T>
 where
@@ -654,6 +824,7 @@ impl<T: EthSpec + Default> PartialEq for OperationPool<T> {
         if ptr::eq(self, other) {
             return true;
         }
+        // The `received_attestations` are not persisted, so they are excluded from comparison.
         *self.attestations.read() == *other.attestations.read()
             && *self.attester_slashings.read() == *other.attester_slashings.read()
             && *self.proposer_slashings.read() == *other.proposer_slashings.read()
@@ -1019,6 +1190,61 @@ mod release_tests {
         assert_eq!(op_pool.num_attestations(), 2 * committees.len());
     }
 
+    /// Attestations which were aggregated greedily on insertion should be re-aggregated into the
+    /// best possible aggregate when packing a block.
+    #[test]
+    fn attestation_clique_aggregation() {
+        let (harness, ref spec) = attestation_test_state::<MainnetEthSpec>(1);
+
+        let mut state = harness.get_current_state();
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let slot = state.slot();
+        let num_validators =
+            MainnetEthSpec::slots_per_epoch() as usize * spec.target_committee_size;
+        let attestations = harness.make_attestations(
+            (0..num_validators).collect::<Vec<_>>().as_slice(),
+            &state,
+            Hash256::zero(),
+            SignedBeaconBlockHash::from(Hash256::zero()),
+            slot,
+        );
+        let (atts, _) = attestations.into_iter().next().unwrap();
+
+        let aggregate = |positions: &[usize]| {
+            let mut agg = atts[positions[0]].0.clone();
+            for &i in &positions[1..] {
+                agg.aggregate(&atts[i].0);
+            }
+            agg
+        };
+
+        // Inserting in this order results in the aggregates {0, 1, 2} and {1, 2, 3, 4}, although
+        // {0}, {1, 3} and {2, 4} can be aggregated to cover all five validators.
+        for positions in [&[0][..], &[1, 2], &[1, 3], &[2, 4]] {
+            op_pool
+                .insert_attestation(
+                    aggregate(positions),
+                    &state.fork(),
+                    state.genesis_validators_root(),
+                    spec,
+                )
+                .unwrap();
+        }
+        assert_eq!(op_pool.num_attestations(), 2);
+
+        *state.slot_mut() += spec.min_attestation_inclusion_delay;
+
+        let block_attestations = op_pool
+            .get_attestations(&state, |_| true, |_| true, spec)
+            .expect("should have block attestations");
+        assert_eq!(block_attestations.len(), 1);
+        assert_eq!(
+            block_attestations[0].aggregation_bits,
+            aggregate(&[0, 1, 2, 3, 4]).aggregation_bits
+        );
+    }
+
     /// Create a bunch of attestations signed by a small number of validators, and another
     /// bunch signed by a larger number, such that there are at least `max_attestations`
     /// signed by the larger number. Then, check that `get_attestations` only returns the
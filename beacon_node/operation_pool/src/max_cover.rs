@@ -1,5 +1,15 @@
 use crate::metrics;
 use itertools::Itertools;
+use std::cmp;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// The maximum number of items in a group for which `GroupedCover` will compute an exact
+/// solution. Larger groups are solved greedily.
+pub const MAX_EXACT_GROUP_SIZE: usize = 12;
+
+/// The number of search nodes visited between checks of the deadline.
+const DEADLINE_CHECK_INTERVAL: usize = 1024;
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -110,10 +120,300 @@ where
         .collect()
 }
 
+/// Order `items` such that each item has the maximum score given the items before it.
+///
+/// This is the order in which `maximum_cover` would select the items, except that items with a
+/// score of zero are retained.
+pub fn order_by_marginal_score<T: MaxCover>(mut items: Vec<T>) -> Vec<T::Object> {
+    let mut result = Vec::with_capacity(items.len());
+
+    while let Some(best_index) = items
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, item)| item.score())
+        .map(|(i, _)| i)
+    {
+        let best = items.swap_remove(best_index);
+        items
+            .iter_mut()
+            .for_each(|item| item.update_covering_set(best.object(), best.covering_set()));
+        result.push(best.object().clone());
+    }
+
+    result
+}
+
+/// The best solutions for a single group, indexed by the number of items they contain.
+///
+/// Each solution is a pair of its value and the indices of its items within the group.
+type GroupSolutions = Vec<(u64, Vec<usize>)>;
+
+/// A maximum cover of items which are partitioned into groups, such that the covering sets of
+/// items in different groups are disjoint.
+///
+/// The value of a solution is the sum of the values of the elements it covers, where each element
+/// is valued at the maximum value assigned to it by the items which cover it.
+///
+/// Groups with at most `MAX_EXACT_GROUP_SIZE` items are solved exactly by searching all of their
+/// subsets, and larger groups are solved greedily. The solutions for each group are then combined
+/// by dynamic programming, which yields the best solution for every limit up to `max_limit`. The
+/// result is optimal if every group is solved exactly.
+pub struct GroupedCover<T> {
+    max_limit: usize,
+    groups: Vec<Vec<T>>,
+    group_solutions: Vec<GroupSolutions>,
+    /// `values[g][c]` is the value of the best solution using at most `c` items from the first
+    /// `g` groups.
+    values: Vec<Vec<u64>>,
+    /// `choices[g][c]` is the number of items from group `g` in the solution of
+    /// `values[g + 1][c]`.
+    choices: Vec<Vec<usize>>,
+}
+
+impl<T> GroupedCover<T>
+where
+    T: MaxCover<Set = HashMap<u64, u64>>,
+{
+    /// Solve the maximum cover of `groups` for every limit up to `max_limit`.
+    ///
+    /// Returns `None` if the `deadline` passes before a solution is found.
+    pub fn new(groups: Vec<Vec<T>>, max_limit: usize, deadline: Instant) -> Option<Self> {
+        let groups = groups
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .filter(|item| item.score() != 0)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|group| !group.is_empty())
+            .collect::<Vec<_>>();
+
+        let group_solutions = groups
+            .iter()
+            .map(|group| solve_group(group, max_limit, deadline))
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut values = vec![vec![0; max_limit + 1]];
+        let mut choices = Vec::with_capacity(group_solutions.len());
+
+        for solutions in &group_solutions {
+            if Instant::now() >= deadline {
+                return None;
+            }
+
+            let prev_values = values.last()?;
+            let mut next_values = prev_values.clone();
+            let mut next_choices = vec![0; max_limit + 1];
+
+            for limit in 0..=max_limit {
+                for (num_items, (value, _)) in solutions.iter().enumerate().take(limit + 1) {
+                    let candidate = prev_values[limit - num_items].saturating_add(*value);
+                    if candidate > next_values[limit] {
+                        next_values[limit] = candidate;
+                        next_choices[limit] = num_items;
+                    }
+                }
+            }
+
+            values.push(next_values);
+            choices.push(next_choices);
+        }
+
+        Some(Self {
+            max_limit,
+            groups,
+            group_solutions,
+            values,
+            choices,
+        })
+    }
+
+    /// The value of the best solution using at most `limit` items.
+    pub fn value(&self, limit: usize) -> u64 {
+        let limit = cmp::min(limit, self.max_limit);
+        self.values
+            .last()
+            .and_then(|values| values.get(limit))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The items of the best solution using at most `limit` items.
+    pub fn into_solution(self, limit: usize) -> Vec<T> {
+        let mut limit = cmp::min(limit, self.max_limit);
+        let mut solution = vec![];
+
+        for ((group, solutions), choices) in self
+            .groups
+            .into_iter()
+            .zip(self.group_solutions)
+            .zip(self.choices)
+            .rev()
+        {
+            let num_items = choices[limit];
+            if let Some((_, indices)) = solutions.get(num_items) {
+                solution.extend(indices.iter().filter_map(|&i| group.get(i).cloned()));
+            }
+            limit -= num_items;
+        }
+
+        solution
+    }
+}
+
+/// Compute the best solution for each number of items from `group`, up to `max_limit`.
+fn solve_group<T>(group: &[T], max_limit: usize, deadline: Instant) -> Option<GroupSolutions>
+where
+    T: MaxCover<Set = HashMap<u64, u64>>,
+{
+    // Map the elements of the group to a dense range of indices.
+    let mut element_indices = HashMap::new();
+    let items = group
+        .iter()
+        .map(|item| {
+            item.covering_set()
+                .iter()
+                .map(|(element, value)| {
+                    let next_index = element_indices.len();
+                    (
+                        *element_indices.entry(*element).or_insert(next_index),
+                        *value,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let max_items = cmp::min(items.len(), max_limit);
+    let mut search = GroupSearch {
+        items: &items,
+        covered: vec![0; element_indices.len()],
+        chosen: vec![],
+        best: vec![(0, vec![]); max_items + 1],
+        max_items,
+        nodes: 0,
+        deadline,
+    };
+
+    if items.len() <= MAX_EXACT_GROUP_SIZE {
+        search.exact(0, 0)?;
+    } else {
+        metrics::inc_counter(&metrics::ATTESTATION_PACKING_GREEDY_GROUPS);
+        search.greedy()?;
+    }
+
+    Some(search.best)
+}
+
+/// State for the search for the best solutions within a single group.
+struct GroupSearch<'a> {
+    /// The `(element, value)` pairs covered by each item.
+    items: &'a [Vec<(usize, u64)>],
+    /// The value of each element covered by the chosen items.
+    covered: Vec<u64>,
+    /// The indices of the chosen items.
+    chosen: Vec<usize>,
+    best: GroupSolutions,
+    max_items: usize,
+    nodes: usize,
+    deadline: Instant,
+}
+
+impl GroupSearch<'_> {
+    /// Add the item at `index` to the solution, returning its marginal value and the previous
+    /// values of the elements it changed.
+    fn choose(&mut self, index: usize) -> (u64, Vec<(usize, u64)>) {
+        let items = self.items;
+        let mut gain = 0;
+        let mut changes = vec![];
+        for &(element, value) in &items[index] {
+            let covered = &mut self.covered[element];
+            if value > *covered {
+                gain += value - *covered;
+                changes.push((element, *covered));
+                *covered = value;
+            }
+        }
+        self.chosen.push(index);
+        (gain, changes)
+    }
+
+    /// Remove the most recently chosen item from the solution.
+    fn unchoose(&mut self, changes: Vec<(usize, u64)>) {
+        for (element, value) in changes {
+            self.covered[element] = value;
+        }
+        self.chosen.pop();
+    }
+
+    fn record(&mut self, value: u64) {
+        let best = &mut self.best[self.chosen.len()];
+        if value > best.0 {
+            *best = (value, self.chosen.clone());
+        }
+    }
+
+    /// Search every subset of the items from `index` onwards.
+    fn exact(&mut self, index: usize, value: u64) -> Option<()> {
+        self.nodes += 1;
+        if self.nodes % DEADLINE_CHECK_INTERVAL == 0 && Instant::now() >= self.deadline {
+            return None;
+        }
+
+        self.record(value);
+
+        if index == self.items.len() {
+            return Some(());
+        }
+
+        if self.chosen.len() < self.max_items {
+            let (gain, changes) = self.choose(index);
+            self.exact(index + 1, value + gain)?;
+            self.unchoose(changes);
+        }
+
+        self.exact(index + 1, value)
+    }
+
+    /// Repeatedly choose the item with the greatest marginal value.
+    fn greedy(&mut self) -> Option<()> {
+        let mut value = 0;
+        while self.chosen.len() < self.max_items {
+            if Instant::now() >= self.deadline {
+                return None;
+            }
+
+            let best = (0..self.items.len())
+                .filter(|index| !self.chosen.contains(index))
+                .map(|index| {
+                    let gain = self.items[index]
+                        .iter()
+                        .map(|&(element, value)| value.saturating_sub(self.covered[element]))
+                        .sum::<u64>();
+                    (index, gain)
+                })
+                .max_by_key(|(_, gain)| *gain);
+
+            match best {
+                Some((index, gain)) if gain > 0 => {
+                    self.choose(index);
+                    value += gain;
+                    self.record(value);
+                }
+                _ => break,
+            }
+        }
+
+        Some(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use std::iter::FromIterator;
+    use std::time::Duration;
     use std::{collections::HashSet, hash::Hash};
 
     impl<T> MaxCover for HashSet<T>
@@ -213,4 +513,121 @@ mod test {
         assert_eq!(quality(&cover), 19);
         assert_eq!(cover.len(), 5);
     }
+
+    #[test]
+    fn order_by_marginal_score_keeps_zero_scores() {
+        let sets = example_system();
+        let order = order_by_marginal_score(sets.clone());
+        assert_eq!(order.len(), sets.len());
+        assert_eq!(order[0], sets[1]);
+        assert_eq!(order[1], sets[0]);
+    }
+
+    /// A covering set with a value for each element.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Rewards(HashMap<u64, u64>);
+
+    impl Rewards {
+        fn new(rewards: &[(u64, u64)]) -> Self {
+            Rewards(rewards.iter().copied().collect())
+        }
+    }
+
+    impl MaxCover for Rewards {
+        type Object = Self;
+        type Set = HashMap<u64, u64>;
+
+        fn object(&self) -> &Self {
+            self
+        }
+
+        fn covering_set(&self) -> &HashMap<u64, u64> {
+            &self.0
+        }
+
+        fn update_covering_set(&mut self, _: &Self, covered: &HashMap<u64, u64>) {
+            self.0.retain(|k, _| !covered.contains_key(k));
+        }
+
+        fn score(&self) -> usize {
+            self.0.values().sum::<u64>() as usize
+        }
+    }
+
+    fn grouped_cover(groups: Vec<Vec<Rewards>>, max_limit: usize) -> GroupedCover<Rewards> {
+        GroupedCover::new(groups, max_limit, Instant::now() + Duration::from_secs(60)).unwrap()
+    }
+
+    // The same system as `suboptimal`, for which the exact solution should be found.
+    #[test]
+    fn grouped_cover_optimal() {
+        let sets = [
+            vec![0, 1, 8, 11, 14],
+            vec![2, 3, 7, 9, 10],
+            vec![4, 5, 6, 12, 13],
+            vec![9, 10],
+            vec![5, 6, 7, 8],
+            vec![0, 1, 2, 3, 4],
+        ]
+        .iter()
+        .map(|set| Rewards::new(&set.iter().map(|&i| (i, 1)).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+
+        let cover = grouped_cover(vec![sets.clone()], 3);
+        assert_eq!(cover.value(3), 15);
+
+        let solution = cover.into_solution(3);
+        assert_eq!(solution.len(), 3);
+        for set in &sets[0..3] {
+            assert!(solution.contains(set));
+        }
+    }
+
+    #[test]
+    fn grouped_cover_splits_limit_between_groups() {
+        let group_a = vec![Rewards::new(&[(1, 10)]), Rewards::new(&[(2, 9)])];
+        let group_b = vec![Rewards::new(&[(3, 20)]), Rewards::new(&[(4, 1)])];
+        let cover = grouped_cover(vec![group_a, group_b], 4);
+
+        assert_eq!(cover.value(0), 0);
+        assert_eq!(cover.value(1), 20);
+        assert_eq!(cover.value(2), 30);
+        assert_eq!(cover.value(3), 39);
+        assert_eq!(cover.value(4), 40);
+        // Limits greater than the maximum are clamped.
+        assert_eq!(cover.value(5), 40);
+
+        let mut solution = cover.into_solution(2);
+        solution.sort_by_key(|rewards| rewards.score());
+        assert_eq!(
+            solution,
+            vec![Rewards::new(&[(1, 10)]), Rewards::new(&[(3, 20)])]
+        );
+    }
+
+    // Elements covered by several items are valued at the maximum value assigned by those items.
+    #[test]
+    fn grouped_cover_uses_maximum_element_value() {
+        let group = vec![Rewards::new(&[(1, 5)]), Rewards::new(&[(1, 7), (2, 1)])];
+        let cover = grouped_cover(vec![group], 2);
+        assert_eq!(cover.value(1), 8);
+        assert_eq!(cover.value(2), 8);
+    }
+
+    // Large groups are solved greedily, which finds the same sub-optimal solution as
+    // `maximum_cover`.
+    #[test]
+    fn grouped_cover_large_group() {
+        let group = (0..MAX_EXACT_GROUP_SIZE as u64 + 1)
+            .map(|i| Rewards::new(&[(i, i + 1)]))
+            .collect::<Vec<_>>();
+        let cover = grouped_cover(vec![group], 2);
+        assert_eq!(cover.value(2), 13 + 12);
+    }
+
+    #[test]
+    fn grouped_cover_deadline_exceeded() {
+        let group = vec![Rewards::new(&[(1, 1)])];
+        assert!(GroupedCover::new(vec![group], 1, Instant::now()).is_none());
+    }
 }
@@ -11,6 +11,19 @@ lazy_static! {
         "op_pool_attestation_curr_epoch_packing_time",
         "Time to pack current epoch attestations"
     );
+    pub static ref ATTESTATION_CLIQUE_AGGREGATION_TIME: Result<Histogram> = try_create_histogram(
+        "op_pool_attestation_clique_aggregation_time",
+        "Time to aggregate attestations into maximal cliques before packing"
+    );
+    pub static ref ATTESTATION_PACKING_TIMEOUTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "op_pool_attestation_packing_timeouts_total",
+        "Number of times attestation packing exceeded its time budget and fell back",
+        &["stage"]
+    );
+    pub static ref ATTESTATION_PACKING_GREEDY_GROUPS: Result<IntCounter> = try_create_int_counter(
+        "op_pool_attestation_packing_greedy_groups_total",
+        "Number of committees with too many attestations to be packed exactly"
+    );
     pub static ref NUM_PREV_EPOCH_ATTESTATIONS: Result<IntGauge> = try_create_int_gauge(
         "op_pool_prev_epoch_attestations",
         "Number of valid attestations considered for packing from the previous epoch"
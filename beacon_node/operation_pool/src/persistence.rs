@@ -118,6 +118,7 @@ impl<T: EthSpec> PersistedOperationPool<T> {
 
                 OperationPool {
                     attestations,
+                    received_attestations: Default::default(),
                     sync_contributions,
                     attester_slashings,
                     proposer_slashings,
@@ -262,6 +262,34 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     over TLS. Must not be password-protected.")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("http-tls-client-ca")
+                .long("http-tls-client-ca")
+                .help("The path of a PEM file containing the certificate authorities used to \
+                    verify client certificates. If provided, clients must present a certificate \
+                    signed by one of these authorities when connecting to the HTTP API server \
+                    over TLS.")
+                .takes_value(true)
+                .requires("http-enable-tls")
+        )
+        .arg(
+            Arg::with_name("http-auth-config")
+                .long("http-auth-config")
+                .value_name("PATH")
+                .help("Path to a YAML file listing the bearer tokens which may be used to access \
+                    the HTTP API and the scope granted to each token: read, publish or admin. If \
+                    provided, requests without a valid token are rejected.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-backup-dir")
+                .long("http-backup-dir")
+                .value_name("DIR")
+                .help("Directory within which database backups may be written via the HTTP API. \
+                    Backup requests must give a path relative to this directory. If not provided, \
+                    backups via the HTTP API are disabled.")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("http-allow-sync-stalled")
                 .long("http-allow-sync-stalled")
@@ -410,6 +438,14 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Specifies how many blocks the database should cache in memory [default: 5]")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("historic-state-cache-size")
+                .long("historic-state-cache-size")
+                .value_name("MEGABYTES")
+                .help("Specifies how much memory the database may use to cache historic states \
+                       reconstructed from the freezer database [default: 512]")
+                .takes_value(true)
+        )
         /*
          * Execution Layer Integration
          */
@@ -503,6 +539,14 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .default_value("true")
         )
+        .arg(
+            Arg::with_name("prune-payloads")
+                .long("prune-payloads")
+                .help("If present, delete the execution payloads of finalized blocks from the \
+                       database. Pruned payloads are reconstructed from the execution layer \
+                       when required, e.g. to serve blocks over the HTTP API or to peers.")
+                .takes_value(false)
+        )
 
         /*
          * Misc.
@@ -706,6 +750,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("PATH")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("validator-monitor-history-epochs")
+                .long("validator-monitor-history-epochs")
+                .help("The number of epochs of per-validator performance summaries from the \
+                    validator monitor to retain on disk. These summaries are served by the \
+                    /lighthouse/validator_monitor/{pubkey}/history endpoint. Set to 0 to disable \
+                    storing summaries.")
+                .value_name("EPOCHS")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("disable-lock-timeouts")
                 .long("disable-lock-timeouts")
@@ -714,6 +768,34 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        experimental as it may obscure performance issues.")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("enable-proposer-re-orgs")
+                .long("enable-proposer-re-orgs")
+                .help("Attempt to re-org out weak head blocks which were received late, by \
+                       proposing upon their parent instead. The head must be in the slot \
+                       immediately prior to the proposal and its parent in the slot before that.")
+                .takes_value(false)
+        )
+        .arg(
+            Arg::with_name("proposer-re-org-threshold")
+                .long("proposer-re-org-threshold")
+                .value_name("PERCENT")
+                .help("Percentage of the weight of a single committee below which a late head \
+                       block may be re-orged. Only has an effect with --enable-proposer-re-orgs. \
+                       [default: 20]")
+                .requires("enable-proposer-re-orgs")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("proposer-re-org-epochs-since-finalization")
+                .long("proposer-re-org-epochs-since-finalization")
+                .value_name("EPOCHS")
+                .help("Maximum number of epochs since finalization at which proposer re-orgs \
+                       are attempted. Only has an effect with --enable-proposer-re-orgs. \
+                       [default: 2]")
+                .requires("enable-proposer-re-orgs")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("fork-choice-before-proposal-timeout")
                 .long("fork-choice-before-proposal-timeout")
@@ -1,3 +1,6 @@
+use beacon_chain::chain_config::{
+    ReOrgThreshold, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_THRESHOLD,
+};
 use clap::ArgMatches;
 use clap_utils::flags::DISABLE_MALLOC_TUNING_FLAG;
 use client::{ClientConfig, ClientGenesis};
@@ -138,9 +141,14 @@ pub fn get_config<E: EthSpec>(
                 .ok_or("--http-tls-key was not provided.")?
                 .parse::<PathBuf>()
                 .map_err(|_| "http-tls-key is not a valid path name.")?,
+            client_ca: clap_utils::parse_optional(cli_args, "http-tls-client-ca")?,
         });
     }
 
+    client_config.http_api.auth_config = clap_utils::parse_optional(cli_args, "http-auth-config")?;
+
+    client_config.http_api.backup_dir = clap_utils::parse_optional(cli_args, "http-backup-dir")?;
+
     if cli_args.is_present("http-allow-sync-stalled") {
         client_config.http_api.allow_sync_stalled = true;
     }
@@ -345,12 +353,19 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "block-cache-size is not a valid integer".to_string())?;
     }
 
+    if let Some(historic_state_cache_size) = cli_args.value_of("historic-state-cache-size") {
+        client_config.store.historic_state_cache_size_mb = historic_state_cache_size
+            .parse()
+            .map_err(|_| "historic-state-cache-size is not a valid integer".to_string())?;
+    }
+
     client_config.store.compact_on_init = cli_args.is_present("compact-db");
     if let Some(compact_on_prune) = cli_args.value_of("auto-compact-db") {
         client_config.store.compact_on_prune = compact_on_prune
             .parse()
             .map_err(|_| "auto-compact-db takes a boolean".to_string())?;
     }
+    client_config.store.prune_payloads = cli_args.is_present("prune-payloads");
 
     /*
      * Zero-ports
@@ -631,6 +646,11 @@ pub fn get_config<E: EthSpec>(
             .extend_from_slice(&pubkeys);
     }
 
+    if let Some(epochs) = clap_utils::parse_optional(cli_args, "validator-monitor-history-epochs")?
+    {
+        client_config.chain.validator_monitor_history_epochs = epochs;
+    }
+
     if cli_args.is_present("disable-lock-timeouts") {
         client_config.chain.enable_lock_timeouts = false;
     }
@@ -644,6 +664,17 @@ pub fn get_config<E: EthSpec>(
     client_config.chain.count_unrealized =
         clap_utils::parse_required(cli_args, "count-unrealized")?;
 
+    if cli_args.is_present("enable-proposer-re-orgs") {
+        client_config.chain.re_org_threshold = Some(
+            clap_utils::parse_optional(cli_args, "proposer-re-org-threshold")?
+                .map(ReOrgThreshold)
+                .unwrap_or(DEFAULT_RE_ORG_THRESHOLD),
+        );
+        client_config.chain.re_org_max_epochs_since_finalization =
+            clap_utils::parse_optional(cli_args, "proposer-re-org-epochs-since-finalization")?
+                .unwrap_or(DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION);
+    }
+
     /*
      * Builder fallback configs.
      */
@@ -0,0 +1,335 @@
+//! Consistent backups of the hot and cold databases.
+//!
+//! A backup is a directory containing a copy of each database as a LevelDB database, along with a
+//! manifest recording the split point and a checksum of each database:
+//!
+//! ```text
+//! backup/
+//! ├── chain_db/
+//! ├── freezer_db/
+//! └── manifest.json
+//! ```
+//!
+//! The manifest is written last, so a directory without a manifest is an incomplete backup.
+//!
+//! When a backup is restored, a copy of its manifest is written alongside the hot database. The
+//! next time the database is opened both databases are checked against the manifest before they
+//! are used, and the copy is removed.
+use crate::hot_cold_store::{HotColdDB, Split};
+use crate::metadata::CURRENT_SCHEMA_VERSION;
+use crate::{Error, ItemStore, KeyValueStore, KeyValueStoreOp, LevelDB, RawEntryIter};
+use eth2_hashing::{Context, Sha256Context};
+use serde_derive::{Deserialize, Serialize};
+use slog::{info, Logger};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use types::{EthSpec, Hash256};
+
+/// The version of the backup format, incremented whenever the layout of a backup changes.
+pub const BACKUP_VERSION: u64 = 1;
+pub const MANIFEST_FILENAME: &str = "manifest.json";
+pub const HOT_DB_DIR: &str = "chain_db";
+pub const COLD_DB_DIR: &str = "freezer_db";
+/// The name of the manifest copy written alongside a restored hot database.
+pub const RESTORED_MANIFEST_FILENAME: &str = "restored_backup.json";
+
+/// The number of entries written to a database in each batch.
+const WRITE_BATCH_SIZE: usize = 1024;
+
+/// The number of entries in a database, and a SHA256 hash of all its keys and values.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseChecksum {
+    pub num_entries: u64,
+    pub checksum: Hash256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub version: u64,
+    pub schema_version: u64,
+    pub split: Split,
+    pub hot_db: DatabaseChecksum,
+    pub cold_db: DatabaseChecksum,
+}
+
+impl BackupManifest {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let file = File::open(path)
+            .map_err(|e| Error::BackupError(format!("Unable to open {:?}: {}", path, e)))?;
+        let manifest: Self = serde_json::from_reader(file)
+            .map_err(|e| Error::BackupError(format!("Unable to parse {:?}: {}", path, e)))?;
+
+        if manifest.version != BACKUP_VERSION {
+            return Err(Error::BackupError(format!(
+                "Unsupported backup version {}, expected {}",
+                manifest.version, BACKUP_VERSION
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// Write the manifest to a temporary file and rename it into place, so that `path` never
+    /// contains a partially written manifest.
+    pub fn store(&self, path: &Path) -> Result<(), Error> {
+        let tmp_path = path.with_extension("tmp");
+        let file = File::create(&tmp_path)
+            .map_err(|e| Error::BackupError(format!("Unable to create {:?}: {}", tmp_path, e)))?;
+        serde_json::to_writer_pretty(&file, self)
+            .map_err(|e| Error::BackupError(format!("Unable to write {:?}: {}", tmp_path, e)))?;
+        file.sync_all()
+            .map_err(|e| Error::BackupError(format!("Unable to sync {:?}: {}", tmp_path, e)))?;
+        fs::rename(&tmp_path, path)
+            .map_err(|e| Error::BackupError(format!("Unable to rename {:?}: {}", tmp_path, e)))
+    }
+}
+
+/// Incrementally computes a `DatabaseChecksum` from entries in key order.
+struct ChecksumBuilder {
+    context: Context,
+    num_entries: u64,
+}
+
+impl ChecksumBuilder {
+    fn new() -> Self {
+        Self {
+            context: Context::new(),
+            num_entries: 0,
+        }
+    }
+
+    fn update(&mut self, key: &[u8], value: &[u8]) {
+        // Length-prefix the key and value so that the boundary between them is unambiguous.
+        self.context.update(&(key.len() as u64).to_le_bytes());
+        self.context.update(key);
+        self.context.update(&(value.len() as u64).to_le_bytes());
+        self.context.update(value);
+        self.num_entries += 1;
+    }
+
+    fn finalize(self) -> DatabaseChecksum {
+        DatabaseChecksum {
+            num_entries: self.num_entries,
+            checksum: Hash256::from(self.context.finalize()),
+        }
+    }
+}
+
+/// Compute the checksum of all `entries`.
+pub fn checksum_entries(entries: RawEntryIter) -> Result<DatabaseChecksum, Error> {
+    let mut checksum = ChecksumBuilder::new();
+    for res in entries {
+        let (key, value) = res?;
+        checksum.update(&key, &value);
+    }
+    Ok(checksum.finalize())
+}
+
+/// Copy all `entries` into `dest`, returning their checksum.
+fn copy_entries<E: EthSpec>(
+    entries: RawEntryIter,
+    dest: &LevelDB<E>,
+) -> Result<DatabaseChecksum, Error> {
+    let mut checksum = ChecksumBuilder::new();
+    let mut ops = Vec::with_capacity(WRITE_BATCH_SIZE);
+    for res in entries {
+        let (key, value) = res?;
+        checksum.update(&key, &value);
+        ops.push(KeyValueStoreOp::PutKeyValue(key, value));
+
+        if ops.len() >= WRITE_BATCH_SIZE {
+            dest.do_atomically(std::mem::replace(
+                &mut ops,
+                Vec::with_capacity(WRITE_BATCH_SIZE),
+            ))?;
+        }
+    }
+    dest.do_atomically(ops)?;
+    Ok(checksum.finalize())
+}
+
+fn check_checksum(
+    database: &'static str,
+    expected: &DatabaseChecksum,
+    computed: &DatabaseChecksum,
+) -> Result<(), Error> {
+    if expected != computed {
+        return Err(Error::BackupChecksumMismatch {
+            database,
+            expected: expected.checksum,
+            computed: computed.checksum,
+        });
+    }
+    Ok(())
+}
+
+/// Return an error if `path` exists and is not an empty directory.
+fn ensure_empty_dir(path: &Path) -> Result<(), Error> {
+    if path.exists() {
+        let mut entries = fs::read_dir(path)
+            .map_err(|e| Error::BackupError(format!("Unable to read {:?}: {}", path, e)))?;
+        if entries.next().is_some() {
+            return Err(Error::BackupError(format!("{:?} is not empty", path)));
+        }
+    }
+    Ok(())
+}
+
+/// The path of the manifest copy written alongside the hot database at `hot_path`.
+pub fn restored_manifest_path(hot_path: &Path) -> PathBuf {
+    hot_path
+        .parent()
+        .unwrap_or(hot_path)
+        .join(RESTORED_MANIFEST_FILENAME)
+}
+
+impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
+where
+    E: EthSpec,
+    Hot: KeyValueStore<E> + ItemStore<E>,
+    Cold: KeyValueStore<E> + ItemStore<E>,
+{
+    /// Write a consistent backup of the hot and cold databases to `backup_dir`, which must not
+    /// exist or be empty.
+    ///
+    /// The database remains usable while the backup is taken.
+    pub fn backup(&self, backup_dir: &Path) -> Result<BackupManifest, Error> {
+        ensure_empty_dir(backup_dir)?;
+        fs::create_dir_all(backup_dir)
+            .map_err(|e| Error::BackupError(format!("Unable to create {:?}: {}", backup_dir, e)))?;
+
+        let hot_backup = LevelDB::<E>::open(&backup_dir.join(HOT_DB_DIR))?;
+        let cold_backup = LevelDB::<E>::open(&backup_dir.join(COLD_DB_DIR))?;
+
+        // Holding the split lock prevents the freezer migration from moving the split point
+        // between the two snapshots. The migration writes states to the cold DB before updating
+        // the split, and only deletes them from the hot DB afterwards, so the snapshots always
+        // contain every state either side of `split`.
+        let (split, hot_entries, cold_entries) = {
+            let split = self.split.read_recursive();
+            (
+                *split,
+                self.hot_db.iter_raw_entries(),
+                self.cold_db.iter_raw_entries(),
+            )
+        };
+
+        info!(
+            self.log,
+            "Backing up database";
+            "split_slot" => split.slot,
+            "backup_dir" => ?backup_dir,
+        );
+
+        let hot_db = copy_entries(hot_entries, &hot_backup)?;
+        let cold_db = copy_entries(cold_entries, &cold_backup)?;
+
+        let manifest = BackupManifest {
+            version: BACKUP_VERSION,
+            schema_version: CURRENT_SCHEMA_VERSION.as_u64(),
+            split,
+            hot_db,
+            cold_db,
+        };
+        manifest.store(&backup_dir.join(MANIFEST_FILENAME))?;
+
+        info!(
+            self.log,
+            "Database backup complete";
+            "hot_entries" => hot_db.num_entries,
+            "cold_entries" => cold_db.num_entries,
+        );
+        Ok(manifest)
+    }
+}
+
+impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+    /// If the database at `hot_path` was restored from a backup and has not been opened since,
+    /// check its contents against the backup manifest.
+    pub(crate) fn verify_restored_backup(&self, hot_path: &Path) -> Result<(), Error> {
+        let manifest_path = restored_manifest_path(hot_path);
+        if !manifest_path.exists() {
+            return Ok(());
+        }
+        let manifest = BackupManifest::load(&manifest_path)?;
+
+        info!(
+            self.log,
+            "Verifying restored database";
+            "split_slot" => manifest.split.slot,
+        );
+
+        let hot_db = checksum_entries(self.hot_db.iter_raw_entries())?;
+        check_checksum("hot", &manifest.hot_db, &hot_db)?;
+        let cold_db = checksum_entries(self.cold_db.iter_raw_entries())?;
+        check_checksum("cold", &manifest.cold_db, &cold_db)?;
+
+        let split = self.load_split()?.unwrap_or_default();
+        if split != manifest.split {
+            return Err(Error::BackupSplitMismatch {
+                expected: manifest.split.slot,
+                found: split.slot,
+            });
+        }
+
+        fs::remove_file(&manifest_path).map_err(|e| {
+            Error::BackupError(format!("Unable to remove {:?}: {}", manifest_path, e))
+        })?;
+
+        info!(self.log, "Restored database verified");
+        Ok(())
+    }
+}
+
+/// Restore the backup in `backup_dir` to the hot and cold database paths, which must not exist or
+/// be empty.
+///
+/// The backup is verified against its manifest while it is copied, and the restored databases are
+/// verified again the next time they are opened.
+pub fn restore_backup<E: EthSpec>(
+    backup_dir: &Path,
+    hot_path: &Path,
+    cold_path: &Path,
+    log: &Logger,
+) -> Result<BackupManifest, Error> {
+    let manifest = BackupManifest::load(&backup_dir.join(MANIFEST_FILENAME))?;
+
+    for dir in [HOT_DB_DIR, COLD_DB_DIR] {
+        if !backup_dir.join(dir).is_dir() {
+            return Err(Error::BackupError(format!(
+                "Backup is missing {:?}",
+                backup_dir.join(dir)
+            )));
+        }
+    }
+    ensure_empty_dir(hot_path)?;
+    ensure_empty_dir(cold_path)?;
+
+    info!(
+        log,
+        "Restoring database backup";
+        "split_slot" => manifest.split.slot,
+        "schema_version" => manifest.schema_version,
+    );
+
+    let restore = || -> Result<(), Error> {
+        let hot_backup = LevelDB::<E>::open(&backup_dir.join(HOT_DB_DIR))?;
+        let hot_db = copy_entries(hot_backup.iter_raw_entries(), &LevelDB::open(hot_path)?)?;
+        check_checksum("hot", &manifest.hot_db, &hot_db)?;
+
+        let cold_backup = LevelDB::<E>::open(&backup_dir.join(COLD_DB_DIR))?;
+        let cold_db = copy_entries(cold_backup.iter_raw_entries(), &LevelDB::open(cold_path)?)?;
+        check_checksum("cold", &manifest.cold_db, &cold_db)?;
+
+        manifest.store(&restored_manifest_path(hot_path))
+    };
+
+    if let Err(e) = restore() {
+        // Don't leave a partially restored database behind.
+        let _ = fs::remove_dir_all(hot_path);
+        let _ = fs::remove_dir_all(cold_path);
+        return Err(e);
+    }
+
+    info!(log, "Database backup restored");
+    Ok(manifest)
+}
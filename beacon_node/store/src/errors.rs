@@ -1,6 +1,7 @@
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
 use crate::hot_cold_store::HotColdDBError;
+use crate::verify::Check;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
 use types::{BeaconStateError, Hash256, Slot};
@@ -45,6 +46,21 @@ pub enum Error {
     ResyncRequiredForExecutionPayloadSeparation,
     SlotClockUnavailableForMigration,
     V9MigrationFailure(Hash256),
+    /// A backup could not be created or restored.
+    BackupError(String),
+    /// The contents of a backed-up or restored database do not match the backup manifest.
+    BackupChecksumMismatch {
+        database: &'static str,
+        expected: Hash256,
+        computed: Hash256,
+    },
+    /// The split point of a restored database does not match the backup manifest.
+    BackupSplitMismatch {
+        expected: Slot,
+        found: Slot,
+    },
+    /// One or more consistency checks found issues that were not repaired.
+    VerificationFailed(Vec<Check>),
 }
 
 pub trait HandleUnavailable<T> {
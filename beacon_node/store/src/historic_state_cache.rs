@@ -0,0 +1,287 @@
+//! Cache of states reconstructed from the freezer database.
+//!
+//! Finalized states are keyed by slot, and evicted in least-recently-used order once the estimated
+//! memory used by the cache exceeds its limit. Concurrent requests for the same slot are
+//! deduplicated, so that only one of them replays blocks while the others wait for its result.
+use crate::metrics;
+use parking_lot::Mutex;
+use ssz::Encode;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use types::{BeaconState, EthSpec, Slot};
+
+#[derive(Debug)]
+struct CachedState<E: EthSpec> {
+    state: Arc<BeaconState<E>>,
+    /// Estimated size of the state in memory, in bytes.
+    size: usize,
+    /// Value of `CachedStates::clock` when the state was last used.
+    last_used: u64,
+}
+
+#[derive(Debug)]
+struct CachedStates<E: EthSpec> {
+    states: BTreeMap<Slot, CachedState<E>>,
+    size: usize,
+    clock: u64,
+}
+
+impl<E: EthSpec> CachedStates<E> {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+#[derive(Debug)]
+pub struct HistoricStateCache<E: EthSpec> {
+    /// Maximum estimated memory used by the cached states, in bytes.
+    max_size: usize,
+    cached: Mutex<CachedStates<E>>,
+    /// Locks held while the state at a slot is being loaded.
+    loading: Mutex<HashMap<Slot, Arc<Mutex<()>>>>,
+}
+
+impl<E: EthSpec> HistoricStateCache<E> {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            cached: Mutex::new(CachedStates {
+                states: BTreeMap::new(),
+                size: 0,
+                clock: 0,
+            }),
+            loading: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Return a copy of the state at `slot`, if it is cached.
+    pub fn get(&self, slot: Slot) -> Option<BeaconState<E>> {
+        self.get_closest(slot, slot)
+    }
+
+    /// Return a copy of the cached state with the highest slot in `min_slot..=max_slot`, if any.
+    pub fn get_closest(&self, min_slot: Slot, max_slot: Slot) -> Option<BeaconState<E>> {
+        if min_slot > max_slot {
+            return None;
+        }
+        let state = {
+            let mut cached = self.cached.lock();
+            let clock = cached.tick();
+            let (_, entry) = cached.states.range_mut(min_slot..=max_slot).next_back()?;
+            entry.last_used = clock;
+            entry.state.clone()
+        };
+        // Clone the state after releasing the lock, as it may take some time.
+        Some((*state).clone())
+    }
+
+    /// Add `state` to the cache, evicting the least recently used states to make room for it.
+    ///
+    /// The caches of `state` are dropped first, as they may be several times larger than the
+    /// state itself and are rebuilt on demand. States larger than the whole cache are not added.
+    pub fn insert(&self, mut state: BeaconState<E>) {
+        if state.drop_all_caches().is_err() {
+            return;
+        }
+        // Without its caches, the SSZ length of the state closely estimates its size in memory.
+        let size = state.ssz_bytes_len();
+        if size > self.max_size {
+            return;
+        }
+
+        let mut cached = self.cached.lock();
+        let last_used = cached.tick();
+        let entry = CachedState {
+            state: Arc::new(state),
+            size,
+            last_used,
+        };
+        if let Some(replaced) = cached.states.insert(entry.state.slot(), entry) {
+            cached.size -= replaced.size;
+        }
+        cached.size += size;
+
+        // The new state was used most recently, so it is never evicted.
+        while cached.size > self.max_size {
+            let lru_slot = cached
+                .states
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(slot, _)| *slot);
+            match lru_slot.and_then(|slot| cached.states.remove(&slot)) {
+                Some(evicted) => cached.size -= evicted.size,
+                None => break,
+            }
+        }
+
+        metrics::set_gauge(
+            &metrics::BEACON_HISTORIC_STATE_CACHE_SIZE,
+            cached.size as i64,
+        );
+    }
+
+    /// Return the state at `slot` from the cache, or load it with `load` and cache it.
+    ///
+    /// If another thread is already loading the state at `slot`, wait for it to finish and use
+    /// its result instead.
+    pub fn get_or_load<Err>(
+        &self,
+        slot: Slot,
+        load: impl FnOnce() -> Result<BeaconState<E>, Err>,
+    ) -> Result<BeaconState<E>, Err> {
+        if let Some(state) = self.get(slot) {
+            metrics::inc_counter(&metrics::BEACON_HISTORIC_STATE_CACHE_HIT_COUNT);
+            return Ok(state);
+        }
+
+        let slot_lock = self.loading.lock().entry(slot).or_default().clone();
+        let result = {
+            let _guard = slot_lock.lock();
+
+            // The state may have been loaded while this thread was waiting.
+            if let Some(state) = self.get(slot) {
+                metrics::inc_counter(&metrics::BEACON_HISTORIC_STATE_CACHE_HIT_COUNT);
+                Ok(state)
+            } else {
+                metrics::inc_counter(&metrics::BEACON_HISTORIC_STATE_CACHE_MISS_COUNT);
+                load().map(|state| {
+                    self.insert(state.clone());
+                    state
+                })
+            }
+        };
+
+        // Stop tracking the slot once no other threads are waiting for it.
+        let mut loading = self.loading.lock();
+        if Arc::strong_count(&slot_lock) == 2 {
+            loading.remove(&slot);
+        }
+
+        result
+    }
+
+    /// Remove all states from the cache.
+    pub fn clear(&self) {
+        let mut cached = self.cached.lock();
+        cached.states.clear();
+        cached.size = 0;
+        metrics::set_gauge(&metrics::BEACON_HISTORIC_STATE_CACHE_SIZE, 0);
+    }
+
+    /// Return the slots of all cached states, in ascending order.
+    pub fn slots(&self) -> Vec<Slot> {
+        self.cached.lock().states.keys().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use beacon_chain::test_utils::BeaconChainHarness;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::time::Duration;
+    use types::{MinimalEthSpec, RelativeEpoch};
+
+    type E = MinimalEthSpec;
+
+    fn get_state() -> BeaconState<E> {
+        let harness = BeaconChainHarness::builder(E::default())
+            .default_spec()
+            .deterministic_keypairs(1)
+            .fresh_ephemeral_store()
+            .build();
+        harness.get_current_state()
+    }
+
+    fn with_slot(state: &BeaconState<E>, slot: u64) -> BeaconState<E> {
+        let mut state = state.clone();
+        *state.slot_mut() = Slot::new(slot);
+        state
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let state = get_state();
+        let cache = HistoricStateCache::new(state.ssz_bytes_len() * 2);
+
+        cache.insert(with_slot(&state, 1));
+        cache.insert(with_slot(&state, 2));
+        assert!(cache.get(Slot::new(1)).is_some());
+
+        cache.insert(with_slot(&state, 3));
+        assert_eq!(cache.slots(), vec![Slot::new(1), Slot::new(3)]);
+    }
+
+    #[test]
+    fn get_closest() {
+        let state = get_state();
+        let cache = HistoricStateCache::new(usize::MAX);
+        cache.insert(with_slot(&state, 8));
+        cache.insert(with_slot(&state, 16));
+
+        let closest = |min, max| {
+            cache
+                .get_closest(Slot::new(min), Slot::new(max))
+                .map(|state| state.slot())
+        };
+        assert_eq!(closest(0, 7), None);
+        assert_eq!(closest(0, 15), Some(Slot::new(8)));
+        assert_eq!(closest(9, 15), None);
+        assert_eq!(closest(0, 100), Some(Slot::new(16)));
+    }
+
+    #[test]
+    fn oversized_state_not_cached() {
+        let cache = HistoricStateCache::new(1);
+        cache.insert(with_slot(&get_state(), 1));
+        assert!(cache.slots().is_empty());
+    }
+
+    #[test]
+    fn caches_dropped_on_insert() {
+        let mut state = get_state();
+        state.build_all_caches(&E::default_spec()).unwrap();
+        let cache = HistoricStateCache::new(usize::MAX);
+        cache.insert(state.clone());
+
+        let cached = cache.get(state.slot()).unwrap();
+        assert!(!cached.committee_cache_is_initialized(RelativeEpoch::Current));
+        assert_eq!(cached.canonical_root(), state.canonical_root());
+    }
+
+    #[test]
+    fn concurrent_loads_deduplicated() {
+        let state = get_state();
+        let cache = Arc::new(HistoricStateCache::new(usize::MAX));
+        let num_loads = Arc::new(AtomicUsize::new(0));
+        let num_threads = 4;
+        let barrier = Arc::new(Barrier::new(num_threads));
+
+        let threads = (0..num_threads)
+            .map(|_| {
+                let cache = cache.clone();
+                let num_loads = num_loads.clone();
+                let barrier = barrier.clone();
+                let state = with_slot(&state, 5);
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    cache.get_or_load(Slot::new(5), || {
+                        num_loads.fetch_add(1, Ordering::SeqCst);
+                        // Give the other threads time to queue up behind this load.
+                        std::thread::sleep(Duration::from_millis(100));
+                        Ok::<_, ()>(state)
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            let state = thread.join().unwrap().unwrap();
+            assert_eq!(state.slot(), Slot::new(5));
+        }
+        assert_eq!(num_loads.load(Ordering::SeqCst), 1);
+        assert!(cache.loading.lock().is_empty());
+    }
+}
@@ -6,6 +6,7 @@ use crate::config::{
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
 };
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
+use crate::historic_state_cache::HistoricStateCache;
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{ParentRootBlockIterator, StateRootsIterator};
 use crate::leveldb_store::BytesKey;
@@ -51,7 +52,7 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     /// greater than or equal are in the hot DB.
     pub(crate) split: RwLock<Split>,
     /// The starting slots for the range of blocks & states stored in the database.
-    anchor_info: RwLock<Option<AnchorInfo>>,
+    pub(crate) anchor_info: RwLock<Option<AnchorInfo>>,
     pub(crate) config: StoreConfig,
     /// Cold database containing compact historical data.
     pub cold_db: Cold,
@@ -61,6 +62,8 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     pub hot_db: Hot,
     /// LRU cache of deserialized blocks. Updated whenever a block is loaded.
     block_cache: Mutex<LruCache<Hash256, SignedBeaconBlock<E>>>,
+    /// Cache of states reconstructed from the freezer database.
+    pub(crate) historic_state_cache: HistoricStateCache<E>,
     /// Chain spec.
     pub(crate) spec: ChainSpec,
     /// Logger.
@@ -90,6 +93,7 @@ pub enum HotColdDBError {
     MissingEpochBoundaryState(Hash256),
     MissingSplitState(Hash256, Slot),
     MissingExecutionPayload(Hash256),
+    MissingStateForRestorePoint(Slot),
     MissingFullBlockExecutionPayloadPruned(Hash256, Slot),
     MissingAnchorInfo,
     HotStateSummaryError(BeaconStateError),
@@ -128,6 +132,9 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
             cold_db: MemoryStore::open(),
             hot_db: MemoryStore::open(),
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
+            historic_state_cache: HistoricStateCache::new(
+                config.historic_state_cache_size_mb * 1024 * 1024,
+            ),
             config,
             spec,
             log,
@@ -161,12 +168,21 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             cold_db: LevelDB::open(cold_path)?,
             hot_db: LevelDB::open(hot_path)?,
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
+            historic_state_cache: HistoricStateCache::new(
+                config.historic_state_cache_size_mb * 1024 * 1024,
+            ),
             config,
             spec,
             log,
             _phantom: PhantomData,
         };
 
+        // Check a database restored from a backup before it is used or modified.
+        db.verify_restored_backup(hot_path)?;
+
+        // Roll back or complete any change of slots-per-restore-point which was interrupted.
+        db.resume_restore_point_change()?;
+
         // Allow the slots-per-restore-point value to stay at the previous default if the config
         // uses the new default. Don't error on a failed read because the config itself may need
         // migrating.
@@ -224,7 +240,9 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         db.store_config()?;
 
         // Run a garbage collection pass.
-        db.remove_garbage()?;
+        if db.config.garbage_collect_on_init {
+            db.remove_garbage()?;
+        }
 
         // If configured, run a foreground compaction pass.
         if db.config.compact_on_init {
@@ -334,12 +352,14 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         };
 
         // If the block is after the split point then we should have the full execution payload
-        // stored in the database. Otherwise, just return the blinded block.
+        // stored in the database. Finalized blocks may have had their payloads pruned, in which
+        // case just return the blinded block.
         // Hold the split lock so that it can't change.
         let split = self.split.read_recursive();
 
         let block = if blinded_block.message().execution_payload().is_err()
             || blinded_block.slot() >= split.slot
+            || self.execution_payload_exists(block_root)?
         {
             // Re-constructing the full block should always succeed here.
             let full_block = self.make_full_block(block_root, blinded_block)?;
@@ -438,6 +458,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             .ok_or_else(|| HotColdDBError::MissingExecutionPayload(*block_root).into())
     }
 
+    /// Determine whether the execution payload for a block exists in the database.
+    pub fn execution_payload_exists(&self, block_root: &Hash256) -> Result<bool, Error> {
+        self.hot_db
+            .key_exists(DBColumn::ExecPayload.into(), block_root.as_bytes())
+    }
+
     /// Determine whether a block exists in the database.
     pub fn block_exists(&self, block_root: &Hash256) -> Result<bool, Error> {
         self.hot_db
@@ -827,6 +853,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                     slot,
                     no_state_root_iter(),
                     state_root_strategy,
+                    None,
                 )?
             };
 
@@ -897,13 +924,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let (lower_limit, upper_limit) = self.get_historic_state_limits();
 
         if slot <= lower_limit || slot >= upper_limit {
-            if slot % self.config.slots_per_restore_point == 0 {
-                let restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
-                self.load_restore_point_by_index(restore_point_idx)
-            } else {
-                self.load_cold_intermediate_state(slot)
-            }
-            .map(Some)
+            self.historic_state_cache
+                .get_or_load(slot, || {
+                    if slot % self.config.slots_per_restore_point == 0 {
+                        let restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
+                        self.load_restore_point_by_index(restore_point_idx)
+                    } else {
+                        self.load_cold_intermediate_state(slot)
+                    }
+                })
+                .map(Some)
         } else {
             Ok(None)
         }
@@ -937,40 +967,63 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Load a frozen state that lies between restore points.
+    ///
+    /// Blocks are replayed from the closest cached state below `slot`, or from the restore point
+    /// below `slot` if there is none. The last epoch boundary state before `slot` is cached so
+    /// that nearby states can be loaded without replaying from the restore point again.
     fn load_cold_intermediate_state(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
-        // 1. Load the restore points either side of the intermediate state.
+        // 1. Load the starting state and the restore point above the intermediate state.
         let low_restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
         let high_restore_point_idx = low_restore_point_idx + 1;
+        let low_restore_point_slot =
+            Slot::new(low_restore_point_idx * self.config.slots_per_restore_point);
 
         // Acquire the read lock, so that the split can't change while this is happening.
         let split = self.split.read_recursive();
 
-        let low_restore_point = self.load_restore_point_by_index(low_restore_point_idx)?;
+        let start_state = match self
+            .historic_state_cache
+            .get_closest(low_restore_point_slot, slot - 1)
+        {
+            Some(state) => state,
+            None => self.load_restore_point_by_index(low_restore_point_idx)?,
+        };
+        let start_slot = start_state.slot();
         let high_restore_point = self.get_restore_point(high_restore_point_idx, &split)?;
 
-        // 2. Load the blocks from the high restore point back to the low restore point.
+        // 2. Load the blocks from the high restore point back to the starting state.
         let blocks = self.load_blocks_to_replay(
-            low_restore_point.slot(),
+            start_slot,
             slot,
             self.get_high_restore_point_block_root(&high_restore_point, slot)?,
         )?;
 
-        // 3. Replay the blocks on top of the low restore point.
+        // 3. Replay the blocks on top of the starting state.
         // Use a forwards state root iterator to avoid doing any tree hashing.
         // The state root of the high restore point should never be used, so is safely set to 0.
         let state_root_iter = self.forwards_state_roots_iterator_until(
-            low_restore_point.slot(),
+            start_slot,
             slot,
             || (high_restore_point, Hash256::zero()),
             &self.spec,
         )?;
 
+        let epoch_boundary_slot = slot
+            .epoch(E::slots_per_epoch())
+            .start_slot(E::slots_per_epoch());
+        let cache_slot = if start_slot < epoch_boundary_slot && epoch_boundary_slot < slot {
+            Some(epoch_boundary_slot)
+        } else {
+            None
+        };
+
         self.replay_blocks(
-            low_restore_point,
+            start_state,
             blocks,
             slot,
             Some(state_root_iter),
             StateRootStrategy::Accurate,
+            cache_slot,
         )
     }
 
@@ -1051,6 +1104,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ///
     /// Will skip slots as necessary. The returned state is not guaranteed
     /// to have any caches built, beyond those immediately required by block processing.
+    ///
+    /// If `cache_slot` is set, a copy of the intermediate state at that slot is added to the
+    /// historic state cache.
     fn replay_blocks(
         &self,
         state: BeaconState<E>,
@@ -1058,12 +1114,29 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         target_slot: Slot,
         state_root_iter: Option<impl Iterator<Item = Result<(Hash256, Slot), Error>>>,
         state_root_strategy: StateRootStrategy,
+        cache_slot: Option<Slot>,
     ) -> Result<BeaconState<E>, Error> {
         let mut block_replayer = BlockReplayer::new(state, &self.spec)
             .state_root_strategy(state_root_strategy)
             .no_signature_verification()
             .minimal_block_root_verification();
 
+        if let Some(cache_slot) = cache_slot {
+            block_replayer = block_replayer
+                .post_block_hook(Box::new(move |state, block| {
+                    if block.slot() == cache_slot {
+                        self.historic_state_cache.insert(state.clone());
+                    }
+                    Ok(())
+                }))
+                .post_slot_hook(Box::new(move |state, _, is_skipped_slot| {
+                    if is_skipped_slot && state.slot() == cache_slot {
+                        self.historic_state_cache.insert(state.clone());
+                    }
+                    Ok(())
+                }));
+        }
+
         let have_state_root_iterator = state_root_iter.is_some();
         if let Some(state_root_iter) = state_root_iter {
             block_replayer = block_replayer.state_root_iter(state_root_iter);
@@ -1084,6 +1157,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             })
     }
 
+    /// Get a reference to the cache of states reconstructed from the freezer database.
+    pub fn historic_state_cache(&self) -> &HistoricStateCache<E> {
+        &self.historic_state_cache
+    }
+
     /// Get a reference to the `ChainSpec` used by the database.
     pub fn get_chain_spec(&self) -> &ChainSpec {
         &self.spec
@@ -1196,7 +1274,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Load the anchor info from disk, but do not set `self.anchor_info`.
-    fn load_anchor_info(&self) -> Result<Option<AnchorInfo>, Error> {
+    pub(crate) fn load_anchor_info(&self) -> Result<Option<AnchorInfo>, Error> {
         self.hot_db.get(&ANCHOR_INFO_KEY)
     }
 
@@ -1204,7 +1282,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ///
     /// The argument is intended to be `self.anchor_info`, but is passed manually to avoid issues
     /// with recursive locking.
-    fn store_anchor_info_in_batch(&self, anchor_info: &Option<AnchorInfo>) -> KeyValueStoreOp {
+    pub(crate) fn store_anchor_info_in_batch(
+        &self,
+        anchor_info: &Option<AnchorInfo>,
+    ) -> KeyValueStoreOp {
         if let Some(ref anchor_info) = anchor_info {
             anchor_info.as_kv_store_op(ANCHOR_INFO_KEY)
         } else {
@@ -1281,7 +1362,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Load the split point from disk.
-    fn load_split(&self) -> Result<Option<Split>, Error> {
+    pub(crate) fn load_split(&self) -> Result<Option<Split>, Error> {
         self.hot_db.get(&SPLIT_KEY)
     }
 
@@ -1291,7 +1372,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Load the state root of a restore point.
-    fn load_restore_point_hash(&self, restore_point_index: u64) -> Result<Hash256, Error> {
+    pub(crate) fn load_restore_point_hash(
+        &self,
+        restore_point_index: u64,
+    ) -> Result<Hash256, Error> {
         let key = Self::restore_point_key(restore_point_index);
         self.cold_db
             .get(&key)?
@@ -1312,7 +1396,7 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     }
 
     /// Convert a `restore_point_index` into a database key.
-    fn restore_point_key(restore_point_index: u64) -> Hash256 {
+    pub(crate) fn restore_point_key(restore_point_index: u64) -> Hash256 {
         Hash256::from_low_u64_be(restore_point_index)
     }
 
@@ -1356,7 +1440,9 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     ///
     /// (2) ensures that restore points align with hot state summaries, making it
     /// quick to migrate hot to cold.
-    fn verify_slots_per_restore_point(slots_per_restore_point: u64) -> Result<(), HotColdDBError> {
+    pub(crate) fn verify_slots_per_restore_point(
+        slots_per_restore_point: u64,
+    ) -> Result<(), HotColdDBError> {
         let slots_per_historical_root = E::SlotsPerHistoricalRoot::to_u64();
         let slots_per_epoch = E::slots_per_epoch();
         if slots_per_restore_point > 0
@@ -1384,6 +1470,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.config.compact_on_prune
     }
 
+    /// Return `true` if execution payloads of finalized blocks should be pruned.
+    pub fn prune_payloads(&self) -> bool {
+        self.config.prune_payloads
+    }
+
     /// Load the checkpoint to begin pruning from (the "old finalized checkpoint").
     pub fn load_pruning_checkpoint(&self) -> Result<Option<Checkpoint>, Error> {
         Ok(self
@@ -1403,6 +1494,75 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         PruningCheckpoint { checkpoint }.as_kv_store_op(PRUNING_CHECKPOINT_KEY)
     }
 
+    /// Delete the execution payloads of finalized blocks prior to the split slot.
+    ///
+    /// Payloads are pruned from the most recent pruning checkpoint back towards the Bellatrix
+    /// fork. Unless `force` is set, pruning stops at the first block whose payload has already
+    /// been pruned, as all earlier payloads are assumed to have been pruned too.
+    ///
+    /// Return the number of payloads deleted.
+    pub fn try_prune_execution_payloads(&self, force: bool) -> Result<usize, Error> {
+        let bellatrix_fork_slot = match self.spec.bellatrix_fork_epoch {
+            Some(epoch) => epoch.start_slot(E::slots_per_epoch()),
+            None => {
+                debug!(self.log, "Bellatrix fork is disabled, not pruning payloads");
+                return Ok(0);
+            }
+        };
+        let pruning_checkpoint = match self.load_pruning_checkpoint()? {
+            Some(checkpoint) => checkpoint,
+            None => {
+                debug!(self.log, "No pruning checkpoint, not pruning payloads");
+                return Ok(0);
+            }
+        };
+        let split = self.get_split_info();
+
+        let mut ops = vec![];
+        for res in ParentRootBlockIterator::new(self, pruning_checkpoint.root) {
+            let (block_root, block) = match res {
+                Ok(pair) => pair,
+                // Blocks prior to the anchor of a checkpoint-synced node may not have been
+                // backfilled yet.
+                Err(Error::BlockNotFound(block_root)) => {
+                    debug!(
+                        self.log,
+                        "Stopping payload pruning at missing block";
+                        "block_root" => ?block_root,
+                    );
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+
+            if block.slot() < bellatrix_fork_slot {
+                break;
+            }
+            // Blocks at or after the split slot must retain their payloads.
+            if block.slot() >= split.slot {
+                continue;
+            }
+
+            if self.execution_payload_exists(&block_root)? {
+                ops.push(StoreOp::DeleteExecutionPayload(block_root));
+            } else if !force {
+                break;
+            }
+        }
+
+        let num_pruned = ops.len();
+        if num_pruned > 0 {
+            self.do_atomically(ops)?;
+            debug!(
+                self.log,
+                "Pruned execution payloads";
+                "count" => num_pruned,
+                "split_slot" => split.slot,
+            );
+        }
+        Ok(num_pruned)
+    }
+
     /// Load the timestamp of the last compaction as a `Duration` since the UNIX epoch.
     pub fn load_compaction_timestamp(&self) -> Result<Option<Duration>, Error> {
         Ok(self
@@ -1582,9 +1742,9 @@ fn no_state_root_iter() -> Option<std::iter::Empty<Result<(Hash256, Slot), Error
 /// Allows full reconstruction by replaying blocks.
 #[derive(Debug, Clone, Copy, Default, Encode, Decode)]
 pub struct HotStateSummary {
-    slot: Slot,
+    pub(crate) slot: Slot,
     pub latest_block_root: Hash256,
-    epoch_boundary_state_root: Hash256,
+    pub(crate) epoch_boundary_state_root: Hash256,
 }
 
 impl StoreItem for HotStateSummary {
@@ -1646,8 +1806,8 @@ impl StoreItem for ColdStateSummary {
 
 /// Struct for storing the state root of a restore point in the database.
 #[derive(Debug, Clone, Copy, Default, Encode, Decode)]
-struct RestorePointHash {
-    state_root: Hash256,
+pub(crate) struct RestorePointHash {
+    pub(crate) state_root: Hash256,
 }
 
 impl StoreItem for RestorePointHash {
@@ -219,6 +219,17 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
                 }),
         )
     }
+
+    /// Iterate through all keys and values in the database.
+    ///
+    /// LevelDB iterators read from an implicit snapshot taken when they are created.
+    fn iter_raw_entries(&self) -> RawEntryIter {
+        Box::new(
+            self.db
+                .iter(self.read_options())
+                .map(|(bytes_key, value)| Ok((bytes_key.key, value))),
+        )
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for LevelDB<E> {}
@@ -10,6 +10,7 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod backup;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
@@ -17,6 +18,7 @@ pub mod config;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
+pub mod historic_state_cache;
 pub mod hot_cold_store;
 mod impls;
 mod leveldb_store;
@@ -25,6 +27,8 @@ pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
 pub mod reconstruct;
+pub mod restore_points;
+pub mod verify;
 
 pub mod iter;
 
@@ -45,6 +49,7 @@ pub use types::*;
 
 pub type ColumnIter<'a> = Box<dyn Iterator<Item = Result<(Hash256, Vec<u8>), Error>> + 'a>;
 pub type ColumnKeyIter<'a> = Box<dyn Iterator<Item = Result<Hash256, Error>> + 'a>;
+pub type RawEntryIter<'a> = Box<dyn Iterator<Item = Result<(Vec<u8>, Vec<u8>), Error>> + 'a>;
 
 pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
     /// Retrieve some bytes in `column` with `key`.
@@ -90,6 +95,19 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
         // Default impl for non LevelDB databases
         Box::new(std::iter::empty())
     }
+
+    /// Iterate through all keys (including their column prefix) and values in the database, in
+    /// key order.
+    ///
+    /// The iterator must read from a consistent snapshot of the database taken when it is
+    /// created.
+    fn iter_raw_entries(&self) -> RawEntryIter {
+        // Default impl for databases which can't be backed up. Yield an error rather than an
+        // empty iterator so that a backup can't silently omit the database's contents.
+        Box::new(std::iter::once(Err(Error::BackupError(
+            "database does not support raw iteration".to_string(),
+        ))))
+    }
 }
 
 pub fn get_key_for_col(column: &str, key: &[u8]) -> Vec<u8> {
@@ -211,6 +229,12 @@ pub enum DBColumn {
     /// For Optimistically Imported Merge Transition Blocks
     #[strum(serialize = "otb")]
     OptimisticTransitionBlock,
+    /// For the naive aggregation pool and observed gossip caches.
+    #[strum(serialize = "gsc")]
+    GossipCaches,
+    /// For the per-epoch validator summaries recorded by the validator monitor.
+    #[strum(serialize = "vmh")]
+    ValidatorMonitorHistory,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -1,5 +1,5 @@
 use super::{Error, ItemStore, KeyValueStore, KeyValueStoreOp};
-use crate::{ColumnIter, DBColumn};
+use crate::{ColumnIter, DBColumn, RawEntryIter};
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
@@ -121,6 +121,18 @@ impl<E: EthSpec> KeyValueStore<E> for MemoryStore<E> {
     fn compact(&self) -> Result<(), Error> {
         Ok(())
     }
+
+    /// Iterate through a sorted copy of all keys and values in the database.
+    fn iter_raw_entries(&self) -> RawEntryIter {
+        let mut entries = self
+            .db
+            .read()
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<Vec<_>>();
+        entries.sort_unstable();
+        Box::new(entries.into_iter().map(Ok))
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for MemoryStore<E> {}
@@ -4,7 +4,7 @@ use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use types::{Checkpoint, Hash256, Slot};
 
-pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(11);
+pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(12);
 
 // All the keys that get stored under the `BeaconMeta` column.
 //
@@ -15,6 +15,7 @@ pub const SPLIT_KEY: Hash256 = Hash256::repeat_byte(2);
 pub const PRUNING_CHECKPOINT_KEY: Hash256 = Hash256::repeat_byte(3);
 pub const COMPACTION_TIMESTAMP_KEY: Hash256 = Hash256::repeat_byte(4);
 pub const ANCHOR_INFO_KEY: Hash256 = Hash256::repeat_byte(5);
+pub const RESTORE_POINT_CHANGE_KEY: Hash256 = Hash256::repeat_byte(6);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SchemaVersion(pub u64);
@@ -80,6 +81,33 @@ impl StoreItem for CompactionTimestamp {
     }
 }
 
+/// A change of `slots_per_restore_point` which is in progress.
+///
+/// Stored in the freezer database while the restore points are rewritten, so that an interrupted
+/// change can be rolled back or completed when the database is next opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct RestorePointChange {
+    /// The new spacing of the restore points.
+    pub slots_per_restore_point: u64,
+    /// Whether the restore points of the freezer database have been replaced. If `true` the
+    /// change must be completed, otherwise it must be rolled back.
+    pub cold_db_rewritten: bool,
+}
+
+impl StoreItem for RestorePointChange {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
 /// Database parameters relevant to weak subjectivity sync.
 #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, Serialize, Deserialize)]
 pub struct AnchorInfo {
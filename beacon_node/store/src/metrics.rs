@@ -62,6 +62,18 @@ lazy_static! {
         "store_beacon_state_cache_clone_time",
         "Time to load a beacon block from the block cache"
     );
+    pub static ref BEACON_HISTORIC_STATE_CACHE_HIT_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_historic_state_cache_hit_total",
+        "Number of freezer states loaded from the historic state cache"
+    );
+    pub static ref BEACON_HISTORIC_STATE_CACHE_MISS_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_historic_state_cache_miss_total",
+        "Number of freezer states that were not in the historic state cache"
+    );
+    pub static ref BEACON_HISTORIC_STATE_CACHE_SIZE: Result<IntGauge> = try_create_int_gauge(
+        "store_beacon_historic_state_cache_size_bytes",
+        "Estimated memory used by the historic state cache (bytes)"
+    );
     pub static ref BEACON_STATE_READ_TIMES: Result<Histogram> = try_create_histogram(
         "store_beacon_state_read_seconds",
         "Total time required to read a BeaconState from the database"
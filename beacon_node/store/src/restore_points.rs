@@ -0,0 +1,329 @@
+//! Rewriting and pruning of the restore points in the freezer database.
+//!
+//! Both operations leave blocks and the chunked vectors (block roots, state roots, etc) intact, so
+//! that historic states can later be re-filled by `reconstruct_historic_states`.
+use crate::config::OnDiskStoreConfig;
+use crate::hot_cold_store::{HotColdDB, HotColdDBError, RestorePointHash};
+use crate::metadata::{AnchorInfo, RestorePointChange, CONFIG_KEY, RESTORE_POINT_CHANGE_KEY};
+use crate::{
+    get_key_for_col, DBColumn, Error, ItemStore, KeyValueStore, KeyValueStoreOp,
+    PartialBeaconState, StoreItem,
+};
+use slog::{info, warn};
+use std::collections::HashSet;
+use types::{EthSpec, Hash256, Slot};
+
+/// Round `slot` down to a multiple of `slots_per_restore_point`.
+fn round_down(slot: Slot, slots_per_restore_point: u64) -> Slot {
+    Slot::new(slot.as_u64() / slots_per_restore_point * slots_per_restore_point)
+}
+
+/// Round `slot` up to a multiple of `slots_per_restore_point`.
+fn round_up(slot: Slot, slots_per_restore_point: u64) -> Slot {
+    round_down(slot + slots_per_restore_point - 1, slots_per_restore_point)
+}
+
+/// Historic states which are unavailable remain unavailable. Widen the gap between the limits of
+/// `anchor` so that both lie on restore points spaced `slots_per_restore_point` apart.
+fn widen_anchor(anchor: Option<AnchorInfo>, slots_per_restore_point: u64) -> Option<AnchorInfo> {
+    anchor.map(|anchor| AnchorInfo {
+        state_lower_limit: round_down(anchor.state_lower_limit, slots_per_restore_point),
+        state_upper_limit: round_up(anchor.state_upper_limit, slots_per_restore_point),
+        ..anchor
+    })
+}
+
+impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
+where
+    E: EthSpec,
+    Hot: KeyValueStore<E> + ItemStore<E>,
+    Cold: KeyValueStore<E> + ItemStore<E>,
+{
+    /// Rewrite the restore points of the freezer database with a spacing of
+    /// `new_slots_per_restore_point`, and update the on-disk config to match.
+    ///
+    /// The database must not be used after this function returns, and must be re-opened with the
+    /// new `slots_per_restore_point`.
+    ///
+    /// If the change is interrupted it is rolled back or completed when the database is next
+    /// opened, see `resume_restore_point_change`.
+    pub fn change_slots_per_restore_point(
+        &self,
+        new_slots_per_restore_point: u64,
+    ) -> Result<(), Error> {
+        Self::verify_slots_per_restore_point(new_slots_per_restore_point)?;
+
+        let old_slots_per_restore_point = self.config.slots_per_restore_point;
+        if new_slots_per_restore_point == old_slots_per_restore_point {
+            info!(
+                self.log,
+                "Slots per restore point unchanged";
+                "slots_per_restore_point" => old_slots_per_restore_point,
+            );
+            return Ok(());
+        }
+
+        self.rewrite_restore_points(new_slots_per_restore_point)?;
+        self.complete_restore_point_change(new_slots_per_restore_point)?;
+
+        self.cold_db.compact()?;
+
+        info!(
+            self.log,
+            "Restore points rewritten";
+            "slots_per_restore_point" => new_slots_per_restore_point,
+        );
+        Ok(())
+    }
+
+    /// Replace the restore points of the freezer database with ones spaced
+    /// `new_slots_per_restore_point` apart, without updating the hot database.
+    ///
+    /// This is the first half of `change_slots_per_restore_point`. On return the freezer database
+    /// records a pending `RestorePointChange` which is completed when the database is next opened.
+    pub fn rewrite_restore_points(&self, new_slots_per_restore_point: u64) -> Result<(), Error> {
+        Self::verify_slots_per_restore_point(new_slots_per_restore_point)?;
+
+        let split = self.get_split_info();
+        let new_anchor = widen_anchor(self.get_anchor_info(), new_slots_per_restore_point);
+        let is_available = |slot: Slot| {
+            new_anchor.as_ref().map_or(true, |anchor| {
+                slot <= anchor.state_lower_limit || slot >= anchor.state_upper_limit
+            })
+        };
+
+        info!(
+            self.log,
+            "Rewriting restore points";
+            "old_slots_per_restore_point" => self.config.slots_per_restore_point,
+            "new_slots_per_restore_point" => new_slots_per_restore_point,
+            "split_slot" => split.slot,
+        );
+
+        // 1. Record the change before modifying the freezer database, so that the states stored
+        // below are cleaned up if the change is interrupted.
+        self.cold_db.put_sync(
+            &RESTORE_POINT_CHANGE_KEY,
+            &RestorePointChange {
+                slots_per_restore_point: new_slots_per_restore_point,
+                cold_db_rewritten: false,
+            },
+        )?;
+
+        // 2. Store the states for the new restore points. This only adds states to the database,
+        // so the existing restore points remain usable until they are replaced below.
+        let mut new_restore_points = vec![];
+        for slot in (0..split.slot.as_u64())
+            .step_by(new_slots_per_restore_point as usize)
+            .map(Slot::new)
+            .filter(|slot| is_available(*slot))
+        {
+            let mut state = self
+                .load_cold_state_by_slot(slot)?
+                .ok_or(HotColdDBError::MissingStateForRestorePoint(slot))?;
+            let state_root = state.update_tree_hash_cache()?;
+
+            let partial_state = PartialBeaconState::from_state_forgetful(&state);
+            self.cold_db
+                .do_atomically(vec![partial_state.as_kv_store_op(state_root)])?;
+
+            info!(
+                self.log,
+                "Stored restore point";
+                "slot" => slot,
+                "remaining" => split.slot.saturating_sub(slot + new_slots_per_restore_point),
+            );
+            new_restore_points.push((slot.as_u64() / new_slots_per_restore_point, state_root));
+        }
+
+        // 3. Atomically replace the old restore points with the new ones, and mark the change as
+        // ready to be completed.
+        let new_state_roots = new_restore_points
+            .iter()
+            .map(|(_, state_root)| *state_root)
+            .collect::<HashSet<_>>();
+        let (mut cold_ops, _) =
+            self.delete_restore_points_ops(|state_root| new_state_roots.contains(state_root))?;
+        for (restore_point_index, state_root) in new_restore_points {
+            cold_ops.push(
+                RestorePointHash { state_root }
+                    .as_kv_store_op(Self::restore_point_key(restore_point_index)),
+            );
+        }
+        cold_ops.push(
+            RestorePointChange {
+                slots_per_restore_point: new_slots_per_restore_point,
+                cold_db_rewritten: true,
+            }
+            .as_kv_store_op(RESTORE_POINT_CHANGE_KEY),
+        );
+        self.cold_db.do_atomically(cold_ops)?;
+
+        Ok(())
+    }
+
+    /// Roll back or complete a change of `slots_per_restore_point` which was interrupted.
+    ///
+    /// Called when the database is opened, before the on-disk config is checked.
+    pub(crate) fn resume_restore_point_change(&self) -> Result<(), Error> {
+        let change = match self
+            .cold_db
+            .get::<RestorePointChange>(&RESTORE_POINT_CHANGE_KEY)?
+        {
+            Some(change) => change,
+            None => return Ok(()),
+        };
+
+        if change.cold_db_rewritten {
+            warn!(
+                self.log,
+                "Completing interrupted restore point change";
+                "slots_per_restore_point" => change.slots_per_restore_point,
+            );
+            self.complete_restore_point_change(change.slots_per_restore_point)
+        } else {
+            warn!(
+                self.log,
+                "Rolling back interrupted restore point change";
+                "slots_per_restore_point" => change.slots_per_restore_point,
+            );
+            self.roll_back_restore_point_change()
+        }
+    }
+
+    /// Update the config and anchor of the hot database to match restore points which have been
+    /// rewritten by `rewrite_restore_points`, then clear the pending change.
+    ///
+    /// Widening the anchor is idempotent, so this may safely be repeated.
+    fn complete_restore_point_change(&self, new_slots_per_restore_point: u64) -> Result<(), Error> {
+        let new_anchor = widen_anchor(self.load_anchor_info()?, new_slots_per_restore_point);
+
+        let disk_config = OnDiskStoreConfig {
+            slots_per_restore_point: new_slots_per_restore_point,
+        };
+        let hot_ops = vec![
+            disk_config.as_kv_store_op(CONFIG_KEY),
+            self.store_anchor_info_in_batch(&new_anchor),
+        ];
+        self.hot_db.do_atomically(hot_ops)?;
+        *self.anchor_info.write() = new_anchor;
+
+        self.cold_db.key_delete(
+            DBColumn::BeaconMeta.into(),
+            RESTORE_POINT_CHANGE_KEY.as_bytes(),
+        )
+    }
+
+    /// Delete the states stored by an interrupted `rewrite_restore_points` which are not used by
+    /// the existing restore points, then clear the pending change.
+    fn roll_back_restore_point_change(&self) -> Result<(), Error> {
+        let restore_point_state_roots = self
+            .cold_db
+            .iter_column(DBColumn::BeaconRestorePoint)
+            .map(|res| {
+                let (_, value) = res?;
+                Ok(RestorePointHash::from_store_bytes(&value)?.state_root)
+            })
+            .collect::<Result<HashSet<_>, Error>>()?;
+
+        let mut cold_ops = vec![];
+        for res in self.cold_db.iter_column_keys(DBColumn::BeaconState) {
+            let state_root = res?;
+            if !restore_point_state_roots.contains(&state_root) {
+                cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconState.into(),
+                    state_root.as_bytes(),
+                )));
+            }
+        }
+        cold_ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+            DBColumn::BeaconMeta.into(),
+            RESTORE_POINT_CHANGE_KEY.as_bytes(),
+        )));
+        self.cold_db.do_atomically(cold_ops)
+    }
+
+    /// Delete all historic states from the freezer database, except for the genesis state.
+    ///
+    /// The anchor is updated to mark the states as unavailable, so that they can be re-filled by
+    /// `reconstruct_historic_states`.
+    pub fn prune_historic_states(&self) -> Result<(), Error> {
+        let split = self.get_split_info();
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        let genesis_state_root = self.load_restore_point_hash(0)?;
+
+        let old_anchor = self.get_anchor_info();
+        let state_upper_limit = round_up(split.slot, slots_per_restore_point);
+        let new_anchor = match old_anchor.clone() {
+            Some(anchor) => AnchorInfo {
+                state_lower_limit: self.spec.genesis_slot,
+                state_upper_limit: std::cmp::max(anchor.state_upper_limit, state_upper_limit),
+                ..anchor
+            },
+            None => AnchorInfo {
+                anchor_slot: split.slot,
+                oldest_block_slot: self.spec.genesis_slot,
+                oldest_block_parent: Hash256::zero(),
+                state_upper_limit,
+                state_lower_limit: self.spec.genesis_slot,
+            },
+        };
+
+        info!(
+            self.log,
+            "Pruning historic states";
+            "split_slot" => split.slot,
+            "state_upper_limit" => new_anchor.state_upper_limit,
+        );
+
+        // Update the anchor before deleting the states so that they are never considered
+        // available once deleted.
+        self.compare_and_set_anchor_info_with_write(old_anchor, Some(new_anchor))?;
+        self.historic_state_cache.clear();
+
+        let (mut cold_ops, num_deleted) =
+            self.delete_restore_points_ops(|state_root| *state_root == genesis_state_root)?;
+        cold_ops.push(
+            RestorePointHash {
+                state_root: genesis_state_root,
+            }
+            .as_kv_store_op(Self::restore_point_key(0)),
+        );
+        self.cold_db.do_atomically(cold_ops)?;
+        self.cold_db.compact()?;
+
+        info!(
+            self.log,
+            "Historic states pruned";
+            "states_deleted" => num_deleted,
+        );
+        Ok(())
+    }
+
+    /// Return the ops required to delete every restore point index, and the states of those
+    /// restore points for which `keep_state` returns `false`, along with the number of states
+    /// deleted.
+    fn delete_restore_points_ops(
+        &self,
+        keep_state: impl Fn(&Hash256) -> bool,
+    ) -> Result<(Vec<KeyValueStoreOp>, usize), Error> {
+        let mut ops = vec![];
+        let mut deleted_states = HashSet::new();
+        for res in self.cold_db.iter_column(DBColumn::BeaconRestorePoint) {
+            let (key, value) = res?;
+            let state_root = RestorePointHash::from_store_bytes(&value)?.state_root;
+
+            ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                DBColumn::BeaconRestorePoint.into(),
+                key.as_bytes(),
+            )));
+            if !keep_state(&state_root) && deleted_states.insert(state_root) {
+                ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconState.into(),
+                    state_root.as_bytes(),
+                )));
+            }
+        }
+        Ok((ops, deleted_states.len()))
+    }
+}
@@ -0,0 +1,449 @@
+//! Consistency checks for the hot and cold databases.
+//!
+//! Each check produces a `CheckReport` listing the issues it found. When a check is run with
+//! `repair` set, the issues that can be fixed without re-syncing are fixed, and marked as repaired
+//! in the report.
+use crate::chunked_vector::{
+    chunk_key, BlockRoots, Chunk, Field, HistoricalRoots, RandaoMixes, StateRoots,
+};
+use crate::hot_cold_store::{HotColdDB, HotStateSummary, Split};
+use crate::{DBColumn, Error, ItemStore, KeyValueStore, KeyValueStoreOp, StoreItem, StoreOp};
+use serde_derive::Serialize;
+use state_processing::StateRootStrategy;
+use std::cmp::{max, min};
+use std::ops::Range;
+use strum::{EnumString, EnumVariantNames, IntoStaticStr};
+use types::{BeaconState, EthSpec, Hash256, Slot, Unsigned};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, EnumString, EnumVariantNames, IntoStaticStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Check {
+    /// Temporary states left behind by failed block imports, and hot states older than the split.
+    OrphanedStates,
+    /// Hot state summaries refer to an epoch boundary state and a block that exist.
+    HotStateSummaries,
+    /// Every head in the persisted head tracker has a block and a state.
+    HeadTracker,
+    /// The freezer's chunked vectors have no missing or malformed chunks.
+    FreezerChunks,
+    /// The freezer's block and state roots agree with the split state.
+    RootContinuity,
+}
+
+impl Check {
+    /// All checks, in the order they should be run.
+    pub const ALL: [Check; 5] = [
+        Check::OrphanedStates,
+        Check::HotStateSummaries,
+        Check::HeadTracker,
+        Check::FreezerChunks,
+        Check::RootContinuity,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Issue {
+    TemporaryState {
+        state_root: Hash256,
+    },
+    StaleHotState {
+        state_root: Hash256,
+        slot: Slot,
+    },
+    MissingEpochBoundaryState {
+        state_root: Hash256,
+        slot: Slot,
+        epoch_boundary_state_root: Hash256,
+    },
+    MissingLatestBlock {
+        state_root: Hash256,
+        slot: Slot,
+        latest_block_root: Hash256,
+    },
+    MissingHeadTracker,
+    InvalidHeadTracker {
+        error: String,
+    },
+    MissingHeadBlock {
+        block_root: Hash256,
+        slot: Slot,
+    },
+    MissingHeadState {
+        block_root: Hash256,
+        state_root: Hash256,
+    },
+    MissingChunk {
+        column: &'static str,
+        chunk_index: usize,
+    },
+    InvalidChunk {
+        column: &'static str,
+        chunk_index: usize,
+        error: String,
+    },
+    MissingSplitState {
+        state_root: Hash256,
+    },
+    MissingRoot {
+        column: &'static str,
+        slot: Slot,
+    },
+    RootMismatch {
+        column: &'static str,
+        slot: Slot,
+        freezer_root: Hash256,
+        split_state_root: Hash256,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IssueReport {
+    #[serde(flatten)]
+    pub issue: Issue,
+    pub repaired: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CheckReport {
+    pub check: Check,
+    /// `true` if every issue found was repaired.
+    pub passed: bool,
+    pub items_checked: u64,
+    pub issues: Vec<IssueReport>,
+}
+
+impl CheckReport {
+    pub fn new(check: Check) -> Self {
+        Self {
+            check,
+            passed: true,
+            items_checked: 0,
+            issues: vec![],
+        }
+    }
+
+    pub fn add_issue(&mut self, issue: Issue, repaired: bool) {
+        self.passed &= repaired;
+        self.issues.push(IssueReport { issue, repaired });
+    }
+}
+
+impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
+where
+    E: EthSpec,
+    Hot: KeyValueStore<E> + ItemStore<E>,
+    Cold: KeyValueStore<E> + ItemStore<E>,
+{
+    /// Find temporary states left behind by failed block imports, and hot states older than the
+    /// split that an interrupted freezer migration failed to delete.
+    ///
+    /// Both are deleted on repair.
+    pub fn verify_orphaned_states(&self, repair: bool) -> Result<CheckReport, Error> {
+        let split_slot = self.get_split_slot();
+        let mut report = CheckReport::new(Check::OrphanedStates);
+        let mut delete_ops = vec![];
+
+        for res in self.hot_db.iter_column_keys(DBColumn::BeaconStateTemporary) {
+            let state_root = res?;
+            report.items_checked += 1;
+            if repair {
+                delete_ops.push(StoreOp::DeleteState(state_root, None));
+                delete_ops.push(StoreOp::DeleteStateTemporaryFlag(state_root));
+            }
+            report.add_issue(Issue::TemporaryState { state_root }, repair);
+        }
+
+        for res in self.hot_db.iter_column(DBColumn::BeaconStateSummary) {
+            let (state_root, bytes) = res?;
+            let summary = HotStateSummary::from_store_bytes(&bytes)?;
+            report.items_checked += 1;
+            if summary.slot >= split_slot || self.load_state_temporary_flag(&state_root)?.is_some()
+            {
+                continue;
+            }
+            if repair {
+                delete_ops.push(StoreOp::DeleteState(state_root, Some(summary.slot)));
+            }
+            report.add_issue(
+                Issue::StaleHotState {
+                    state_root,
+                    slot: summary.slot,
+                },
+                repair,
+            );
+        }
+
+        self.do_atomically(delete_ops)?;
+        Ok(report)
+    }
+
+    /// Check that every hot state summary at or after the split refers to an epoch boundary state
+    /// and a block that exist.
+    ///
+    /// Summaries of states that can no longer be loaded are deleted on repair. Epoch boundary
+    /// states are kept even if their block is missing, because later states are replayed from
+    /// them. The split state is never deleted.
+    pub fn verify_hot_state_summaries(&self, repair: bool) -> Result<CheckReport, Error> {
+        let split = self.get_split_info();
+        let mut report = CheckReport::new(Check::HotStateSummaries);
+        let mut delete_ops = vec![];
+
+        for res in self.hot_db.iter_column(DBColumn::BeaconStateSummary) {
+            let (state_root, bytes) = res?;
+            let summary = HotStateSummary::from_store_bytes(&bytes)?;
+
+            // Older and temporary states are covered by `verify_orphaned_states`.
+            if summary.slot < split.slot || self.load_state_temporary_flag(&state_root)?.is_some() {
+                continue;
+            }
+            report.items_checked += 1;
+
+            let is_epoch_boundary = summary.epoch_boundary_state_root == state_root;
+            let (issue, repairable) = if !self.hot_db.key_exists(
+                DBColumn::BeaconState.into(),
+                summary.epoch_boundary_state_root.as_bytes(),
+            )? {
+                (
+                    Issue::MissingEpochBoundaryState {
+                        state_root,
+                        slot: summary.slot,
+                        epoch_boundary_state_root: summary.epoch_boundary_state_root,
+                    },
+                    true,
+                )
+            } else if !self.block_exists(&summary.latest_block_root)? {
+                (
+                    Issue::MissingLatestBlock {
+                        state_root,
+                        slot: summary.slot,
+                        latest_block_root: summary.latest_block_root,
+                    },
+                    !is_epoch_boundary,
+                )
+            } else {
+                continue;
+            };
+
+            let repaired = repair && repairable && state_root != split.state_root;
+            if repaired {
+                delete_ops.push(StoreOp::DeleteState(state_root, Some(summary.slot)));
+            }
+            report.add_issue(issue, repaired);
+        }
+
+        self.do_atomically(delete_ops)?;
+        Ok(report)
+    }
+
+    /// Check that every chunk of the freezer's chunked vectors exists and can be decoded, from the
+    /// oldest available slot up to the last restore point before the split.
+    ///
+    /// Missing chunks can't be recovered without re-syncing, so this check has no repair.
+    pub fn verify_freezer_chunks(&self) -> Result<CheckReport, Error> {
+        let split = self.get_split_info();
+        let mut report = CheckReport::new(Check::FreezerChunks);
+
+        self.verify_field_chunks(BlockRoots, &split, &mut report)?;
+        self.verify_field_chunks(StateRoots, &split, &mut report)?;
+        self.verify_field_chunks(HistoricalRoots, &split, &mut report)?;
+        self.verify_field_chunks(RandaoMixes, &split, &mut report)?;
+
+        Ok(report)
+    }
+
+    /// Check that the block and state roots in the freezer agree with those of the split state,
+    /// for the slots covered by both.
+    ///
+    /// Missing or mismatched roots are overwritten with the split state's roots on repair.
+    pub fn verify_root_continuity(&self, repair: bool) -> Result<CheckReport, Error> {
+        let split = self.get_split_info();
+        let mut report = CheckReport::new(Check::RootContinuity);
+        if split.slot == 0 {
+            return Ok(report);
+        }
+
+        let split_state =
+            match self.load_hot_state(&split.state_root, StateRootStrategy::Accurate)? {
+                Some(state) => state,
+                None => {
+                    report.add_issue(
+                        Issue::MissingSplitState {
+                            state_root: split.state_root,
+                        },
+                        false,
+                    );
+                    return Ok(report);
+                }
+            };
+
+        let mut ops = vec![];
+        self.verify_field_roots(
+            BlockRoots,
+            &split,
+            &split_state,
+            repair,
+            &mut report,
+            &mut ops,
+        )?;
+        self.verify_field_roots(
+            StateRoots,
+            &split,
+            &split_state,
+            repair,
+            &mut report,
+            &mut ops,
+        )?;
+        self.cold_db.do_atomically(ops)?;
+
+        Ok(report)
+    }
+
+    /// Return the range of vector indices of `F` that the freezer should hold.
+    ///
+    /// Vectors are only written at restore points, so the range ends at the last restore point
+    /// before the split. For checkpoint synced databases it starts at the oldest backfilled block
+    /// for block roots, and at the first restore point after the anchor for other fields.
+    fn frozen_vindices<F: Field<E>>(&self, split: &Split) -> Option<Range<usize>> {
+        if split.slot == 0 {
+            return None;
+        }
+        let slots_per_restore_point = self.config.slots_per_restore_point;
+        let last_restore_point = Slot::new(
+            (split.slot.as_u64() - 1) / slots_per_restore_point * slots_per_restore_point,
+        );
+        let (_, end) = F::start_and_end_vindex(last_restore_point, &self.spec);
+
+        let start = match self.get_anchor_info() {
+            None => 0,
+            Some(anchor) if F::column() == DBColumn::BeaconBlockRoots => {
+                anchor.oldest_block_slot.as_usize()
+            }
+            Some(anchor) if last_restore_point < anchor.state_upper_limit => return None,
+            Some(anchor) => F::start_and_end_vindex(anchor.state_upper_limit, &self.spec).0,
+        };
+
+        if start < end {
+            Some(start..end)
+        } else {
+            None
+        }
+    }
+
+    fn verify_field_chunks<F: Field<E>>(
+        &self,
+        _: F,
+        split: &Split,
+        report: &mut CheckReport,
+    ) -> Result<(), Error> {
+        let vindices = match self.frozen_vindices::<F>(split) {
+            Some(vindices) => vindices,
+            None => return Ok(()),
+        };
+        let column = F::column();
+        let chunk_size = F::chunk_size();
+
+        for chunk_index in F::chunk_index(vindices.start)..=F::chunk_index(vindices.end - 1) {
+            report.items_checked += 1;
+            let issue = match self
+                .cold_db
+                .get_bytes(column.into(), &chunk_key(chunk_index))?
+            {
+                None => Issue::MissingChunk {
+                    column: column.into(),
+                    chunk_index,
+                },
+                Some(bytes) => match Chunk::<F::Value>::decode(&bytes) {
+                    Ok(chunk) if chunk.values.len() == chunk_size => continue,
+                    Ok(chunk) => Issue::InvalidChunk {
+                        column: column.into(),
+                        chunk_index,
+                        error: format!(
+                            "expected {} values, found {}",
+                            chunk_size,
+                            chunk.values.len()
+                        ),
+                    },
+                    Err(e) => Issue::InvalidChunk {
+                        column: column.into(),
+                        chunk_index,
+                        error: format!("{:?}", e),
+                    },
+                },
+            };
+            report.add_issue(issue, false);
+        }
+        Ok(())
+    }
+
+    fn verify_field_roots<F: Field<E, Value = Hash256>>(
+        &self,
+        _: F,
+        split: &Split,
+        split_state: &BeaconState<E>,
+        repair: bool,
+        report: &mut CheckReport,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
+        let vindices = match self.frozen_vindices::<F>(split) {
+            Some(vindices) => vindices,
+            None => return Ok(()),
+        };
+        // The split state holds the roots of the `SLOTS_PER_HISTORICAL_ROOT` slots before it.
+        let start = max(
+            vindices.start,
+            split
+                .slot
+                .as_usize()
+                .saturating_sub(E::SlotsPerHistoricalRoot::to_usize()),
+        );
+        if start >= vindices.end {
+            return Ok(());
+        }
+        let column = F::column();
+        let chunk_size = F::chunk_size();
+
+        for chunk_index in F::chunk_index(start)..=F::chunk_index(vindices.end - 1) {
+            let key = chunk_key(chunk_index);
+            let mut chunk = Chunk::load(&self.cold_db, column, &key)?
+                .unwrap_or_else(|| Chunk::new(vec![Hash256::zero(); chunk_size]));
+            let mut modified = false;
+
+            let chunk_start = chunk_index * chunk_size;
+            for vindex in max(start, chunk_start)..min(vindices.end, chunk_start + chunk_size) {
+                report.items_checked += 1;
+                let slot = Slot::new(vindex as u64);
+                let expected = F::get_value(split_state, vindex as u64, &self.spec)?;
+
+                let issue = match chunk.values.get(vindex - chunk_start) {
+                    Some(root) if *root == expected => continue,
+                    Some(root) if !root.is_zero() => Issue::RootMismatch {
+                        column: column.into(),
+                        slot,
+                        freezer_root: *root,
+                        split_state_root: expected,
+                    },
+                    _ => Issue::MissingRoot {
+                        column: column.into(),
+                        slot,
+                    },
+                };
+
+                if repair {
+                    chunk.values.resize(chunk_size, Hash256::zero());
+                    chunk.values[vindex - chunk_start] = expected;
+                    modified = true;
+                }
+                report.add_issue(issue, repair);
+            }
+
+            if modified {
+                chunk.store(column, &key, ops)?;
+            }
+        }
+        Ok(())
+    }
+}
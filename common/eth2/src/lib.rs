@@ -21,10 +21,11 @@ use futures::Stream;
 use futures_util::StreamExt;
 use lighthouse_network::PeerId;
 pub use reqwest;
-use reqwest::{IntoUrl, RequestBuilder, Response};
+use reqwest::{header::CONTENT_TYPE, IntoUrl, RequestBuilder, Response};
 pub use reqwest::{StatusCode, Url};
 pub use sensitive_url::SensitiveUrl;
 use serde::{de::DeserializeOwned, Serialize};
+use ssz::{Decode, Encode};
 use std::convert::TryFrom;
 use std::fmt;
 use std::iter::Iterator;
@@ -35,6 +36,7 @@ pub const V1: EndpointVersion = EndpointVersion(1);
 pub const V2: EndpointVersion = EndpointVersion(2);
 
 pub const CONSENSUS_VERSION_HEADER: &str = "Eth-Consensus-Version";
+pub const EXECUTION_OPTIMISTIC_HEADER: &str = "Eth-Execution-Optimistic";
 
 #[derive(Debug)]
 pub enum Error {
@@ -250,6 +252,50 @@ impl BeaconNodeHttpClient {
         }
     }
 
+    /// Perform a HTTP GET request for an SSZ response, returning `None` on a 404 error.
+    ///
+    /// The `execution_optimistic` status of the response is read from its
+    /// `Eth-Execution-Optimistic` header.
+    async fn get_ssz_opt_execution_optimistic<U: IntoUrl>(
+        &self,
+        url: U,
+    ) -> Result<Option<(Vec<u8>, Option<bool>)>, Error> {
+        let opt_response = self
+            .get_response(url, |b| b.accept(Accept::Ssz))
+            .await
+            .optional()?;
+        match opt_response {
+            Some(resp) => {
+                let execution_optimistic = resp
+                    .headers()
+                    .get(EXECUTION_OPTIMISTIC_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse().ok());
+                let bytes = resp.bytes().await?.into_iter().collect::<Vec<_>>();
+                Ok(Some((bytes, execution_optimistic)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Perform a HTTP GET request for an SSZ response, with an optional custom timeout.
+    async fn get_ssz_bytes<U: IntoUrl>(
+        &self,
+        url: U,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<u8>, Error> {
+        let response = self
+            .get_response(url, |b| {
+                let b = b.accept(Accept::Ssz);
+                match timeout {
+                    Some(timeout) => b.timeout(timeout),
+                    None => b,
+                }
+            })
+            .await?;
+        Ok(response.bytes().await?.into_iter().collect())
+    }
+
     /// Perform a HTTP POST request.
     async fn post<T: Serialize, U: IntoUrl>(&self, url: U, body: &T) -> Result<(), Error> {
         self.post_generic(url, body, None).await?;
@@ -310,6 +356,25 @@ impl BeaconNodeHttpClient {
         ok_or_error(response).await
     }
 
+    /// Perform a HTTP POST request with an SSZ body and a custom timeout.
+    async fn post_ssz_with_timeout<U: IntoUrl>(
+        &self,
+        url: U,
+        body: Vec<u8>,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        let response = self
+            .client
+            .post(url)
+            .timeout(timeout)
+            .header(CONTENT_TYPE, Accept::Ssz.to_string())
+            .body(body)
+            .send()
+            .await?;
+        ok_or_error(response).await?;
+        Ok(())
+    }
+
     /// `GET beacon/genesis`
     ///
     /// ## Errors
@@ -412,15 +477,13 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
-    /// `GET beacon/states/{state_id}/validators?id,status`
-    ///
-    /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_states_validators(
+    /// Path for `v1/beacon/states/{state_id}/validators?id,status`
+    pub fn get_beacon_states_validators_path(
         &self,
         state_id: StateId,
         ids: Option<&[ValidatorId]>,
         statuses: Option<&[ValidatorStatus]>,
-    ) -> Result<Option<ExecutionOptimisticResponse<Vec<ValidatorData>>>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -448,19 +511,53 @@ impl BeaconNodeHttpClient {
             path.query_pairs_mut().append_pair("status", &status_string);
         }
 
+        Ok(path)
+    }
+
+    /// `GET beacon/states/{state_id}/validators?id,status`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_validators(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+    ) -> Result<Option<ExecutionOptimisticResponse<Vec<ValidatorData>>>, Error> {
+        let path = self.get_beacon_states_validators_path(state_id, ids, statuses)?;
+
         self.get_opt(path).await
     }
 
-    /// `GET beacon/states/{state_id}/committees?slot,index,epoch`
+    /// `GET beacon/states/{state_id}/validators?id,status` as SSZ
     ///
     /// Returns `Ok(None)` on a 404 error.
-    pub async fn get_beacon_states_committees(
+    pub async fn get_beacon_states_validators_ssz(
+        &self,
+        state_id: StateId,
+        ids: Option<&[ValidatorId]>,
+        statuses: Option<&[ValidatorStatus]>,
+    ) -> Result<Option<ExecutionOptimisticResponse<Vec<ValidatorData>>>, Error> {
+        let path = self.get_beacon_states_validators_path(state_id, ids, statuses)?;
+
+        self.get_ssz_opt_execution_optimistic(path)
+            .await?
+            .map(|(bytes, execution_optimistic)| {
+                Ok(ExecutionOptimisticResponse {
+                    execution_optimistic,
+                    data: Vec::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)?,
+                })
+            })
+            .transpose()
+    }
+
+    /// Path for `v1/beacon/states/{state_id}/committees?slot,index,epoch`
+    pub fn get_beacon_states_committees_path(
         &self,
         state_id: StateId,
         slot: Option<Slot>,
         index: Option<u64>,
         epoch: Option<Epoch>,
-    ) -> Result<Option<ExecutionOptimisticResponse<Vec<CommitteeData>>>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -485,9 +582,47 @@ impl BeaconNodeHttpClient {
                 .append_pair("epoch", &epoch.to_string());
         }
 
+        Ok(path)
+    }
+
+    /// `GET beacon/states/{state_id}/committees?slot,index,epoch`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_committees(
+        &self,
+        state_id: StateId,
+        slot: Option<Slot>,
+        index: Option<u64>,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<ExecutionOptimisticResponse<Vec<CommitteeData>>>, Error> {
+        let path = self.get_beacon_states_committees_path(state_id, slot, index, epoch)?;
+
         self.get_opt(path).await
     }
 
+    /// `GET beacon/states/{state_id}/committees?slot,index,epoch` as SSZ
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_states_committees_ssz(
+        &self,
+        state_id: StateId,
+        slot: Option<Slot>,
+        index: Option<u64>,
+        epoch: Option<Epoch>,
+    ) -> Result<Option<ExecutionOptimisticResponse<Vec<CommitteeData>>>, Error> {
+        let path = self.get_beacon_states_committees_path(state_id, slot, index, epoch)?;
+
+        self.get_ssz_opt_execution_optimistic(path)
+            .await?
+            .map(|(bytes, execution_optimistic)| {
+                Ok(ExecutionOptimisticResponse {
+                    execution_optimistic,
+                    data: Vec::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)?,
+                })
+            })
+            .transpose()
+    }
+
     /// `GET beacon/states/{state_id}/sync_committees?epoch`
     pub async fn get_beacon_states_sync_committees(
         &self,
@@ -598,6 +733,22 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
+    /// `POST beacon/blocks` with an SSZ body
+    pub async fn post_beacon_blocks_ssz<T: EthSpec, Payload: ExecPayload<T>>(
+        &self,
+        block: &SignedBeaconBlock<T, Payload>,
+    ) -> Result<(), Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("blocks");
+
+        self.post_ssz_with_timeout(path, block.as_ssz_bytes(), self.timeouts.proposal)
+            .await
+    }
+
     /// `POST beacon/blinded_blocks`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -618,6 +769,22 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
+    /// `POST beacon/blinded_blocks` with an SSZ body
+    pub async fn post_beacon_blinded_blocks_ssz<T: EthSpec, Payload: ExecPayload<T>>(
+        &self,
+        block: &SignedBeaconBlock<T, Payload>,
+    ) -> Result<(), Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("blinded_blocks");
+
+        self.post_ssz_with_timeout(path, block.as_ssz_bytes(), self.timeouts.proposal)
+            .await
+    }
+
     /// Path for `v2/beacon/blocks`
     pub fn get_beacon_blocks_path(&self, block_id: BlockId) -> Result<Url, Error> {
         let mut path = self.eth_path(V2)?;
@@ -745,6 +912,75 @@ impl BeaconNodeHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET beacon/rewards/blocks/{block_id}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_beacon_rewards_blocks(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<ExecutionOptimisticResponse<StandardBlockReward>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("rewards")
+            .push("blocks")
+            .push(&block_id.to_string());
+
+        self.get_opt(path).await
+    }
+
+    /// `POST beacon/rewards/sync_committee/{block_id}`
+    ///
+    /// An empty `validators` slice requests rewards for the entire sync committee.
+    pub async fn post_beacon_rewards_sync_committee(
+        &self,
+        block_id: BlockId,
+        validators: &[ValidatorId],
+    ) -> Result<ExecutionOptimisticResponse<Vec<SyncCommitteeReward>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("rewards")
+            .push("sync_committee")
+            .push(&block_id.to_string());
+
+        let validators = validators
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        self.post_with_response(path, &validators).await
+    }
+
+    /// `POST beacon/rewards/attestations/{epoch}`
+    ///
+    /// An empty `validators` slice requests rewards for all eligible validators.
+    pub async fn post_beacon_rewards_attestations(
+        &self,
+        epoch: Epoch,
+        validators: &[ValidatorId],
+    ) -> Result<ExecutionOptimisticResponse<StandardAttestationRewards>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("rewards")
+            .push("attestations")
+            .push(&epoch.to_string());
+
+        let validators = validators
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        self.post_with_response(path, &validators).await
+    }
+
     /// `POST beacon/pool/attestations`
     pub async fn post_beacon_pool_attestations<T: EthSpec>(
         &self,
@@ -1203,6 +1439,18 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET v1/debug/fork_choice`
+    pub async fn get_debug_fork_choice(&self) -> Result<ForkChoice, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("debug")
+            .push("fork_choice");
+
+        self.get(path).await
+    }
+
     /// `GET validator/duties/proposer/{epoch}`
     pub async fn get_validator_duties_proposer(
         &self,
@@ -1232,14 +1480,14 @@ impl BeaconNodeHttpClient {
             .await
     }
 
-    /// `GET v2/validator/blocks/{slot}`
-    pub async fn get_validator_blocks_with_verify_randao<T: EthSpec, Payload: ExecPayload<T>>(
+    /// Path for `v2/validator/blocks/{slot}`
+    pub fn get_validator_blocks_path(
         &self,
         slot: Slot,
         randao_reveal: Option<&SignatureBytes>,
         graffiti: Option<&Graffiti>,
         verify_randao: Option<bool>,
-    ) -> Result<ForkVersionedResponse<BeaconBlock<T, Payload>>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V2)?;
 
         path.path_segments_mut()
@@ -1263,9 +1511,36 @@ impl BeaconNodeHttpClient {
                 .append_pair("verify_randao", &verify_randao.to_string());
         }
 
+        Ok(path)
+    }
+
+    /// `GET v2/validator/blocks/{slot}`
+    pub async fn get_validator_blocks_with_verify_randao<T: EthSpec, Payload: ExecPayload<T>>(
+        &self,
+        slot: Slot,
+        randao_reveal: Option<&SignatureBytes>,
+        graffiti: Option<&Graffiti>,
+        verify_randao: Option<bool>,
+    ) -> Result<ForkVersionedResponse<BeaconBlock<T, Payload>>, Error> {
+        let path = self.get_validator_blocks_path(slot, randao_reveal, graffiti, verify_randao)?;
+
         self.get(path).await
     }
 
+    /// `GET v2/validator/blocks/{slot}` as SSZ
+    pub async fn get_validator_blocks_ssz<T: EthSpec, Payload: ExecPayload<T>>(
+        &self,
+        slot: Slot,
+        randao_reveal: &SignatureBytes,
+        graffiti: Option<&Graffiti>,
+        spec: &ChainSpec,
+    ) -> Result<BeaconBlock<T, Payload>, Error> {
+        let path = self.get_validator_blocks_path(slot, Some(randao_reveal), graffiti, None)?;
+
+        let bytes = self.get_ssz_bytes(path, None).await?;
+        BeaconBlock::from_ssz_bytes(&bytes, spec).map_err(Error::InvalidSsz)
+    }
+
     /// `GET v2/validator/blinded_blocks/{slot}`
     pub async fn get_validator_blinded_blocks<T: EthSpec, Payload: ExecPayload<T>>(
         &self,
@@ -1282,17 +1557,14 @@ impl BeaconNodeHttpClient {
         .await
     }
 
-    /// `GET v1/validator/blinded_blocks/{slot}`
-    pub async fn get_validator_blinded_blocks_with_verify_randao<
-        T: EthSpec,
-        Payload: ExecPayload<T>,
-    >(
+    /// Path for `v1/validator/blinded_blocks/{slot}`
+    pub fn get_validator_blinded_blocks_path(
         &self,
         slot: Slot,
         randao_reveal: Option<&SignatureBytes>,
         graffiti: Option<&Graffiti>,
         verify_randao: Option<bool>,
-    ) -> Result<ForkVersionedResponse<BeaconBlock<T, Payload>>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -1316,15 +1588,47 @@ impl BeaconNodeHttpClient {
                 .append_pair("verify_randao", &verify_randao.to_string());
         }
 
+        Ok(path)
+    }
+
+    /// `GET v1/validator/blinded_blocks/{slot}`
+    pub async fn get_validator_blinded_blocks_with_verify_randao<
+        T: EthSpec,
+        Payload: ExecPayload<T>,
+    >(
+        &self,
+        slot: Slot,
+        randao_reveal: Option<&SignatureBytes>,
+        graffiti: Option<&Graffiti>,
+        verify_randao: Option<bool>,
+    ) -> Result<ForkVersionedResponse<BeaconBlock<T, Payload>>, Error> {
+        let path =
+            self.get_validator_blinded_blocks_path(slot, randao_reveal, graffiti, verify_randao)?;
+
         self.get(path).await
     }
 
-    /// `GET validator/attestation_data?slot,committee_index`
-    pub async fn get_validator_attestation_data(
+    /// `GET v1/validator/blinded_blocks/{slot}` as SSZ
+    pub async fn get_validator_blinded_blocks_ssz<T: EthSpec, Payload: ExecPayload<T>>(
+        &self,
+        slot: Slot,
+        randao_reveal: &SignatureBytes,
+        graffiti: Option<&Graffiti>,
+        spec: &ChainSpec,
+    ) -> Result<BeaconBlock<T, Payload>, Error> {
+        let path =
+            self.get_validator_blinded_blocks_path(slot, Some(randao_reveal), graffiti, None)?;
+
+        let bytes = self.get_ssz_bytes(path, None).await?;
+        BeaconBlock::from_ssz_bytes(&bytes, spec).map_err(Error::InvalidSsz)
+    }
+
+    /// Path for `v1/validator/attestation_data?slot,committee_index`
+    pub fn get_validator_attestation_data_path(
         &self,
         slot: Slot,
         committee_index: CommitteeIndex,
-    ) -> Result<GenericResponse<AttestationData>, Error> {
+    ) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
@@ -1336,9 +1640,34 @@ impl BeaconNodeHttpClient {
             .append_pair("slot", &slot.to_string())
             .append_pair("committee_index", &committee_index.to_string());
 
+        Ok(path)
+    }
+
+    /// `GET validator/attestation_data?slot,committee_index`
+    pub async fn get_validator_attestation_data(
+        &self,
+        slot: Slot,
+        committee_index: CommitteeIndex,
+    ) -> Result<GenericResponse<AttestationData>, Error> {
+        let path = self.get_validator_attestation_data_path(slot, committee_index)?;
+
         self.get_with_timeout(path, self.timeouts.attestation).await
     }
 
+    /// `GET validator/attestation_data?slot,committee_index` as SSZ
+    pub async fn get_validator_attestation_data_ssz(
+        &self,
+        slot: Slot,
+        committee_index: CommitteeIndex,
+    ) -> Result<AttestationData, Error> {
+        let path = self.get_validator_attestation_data_path(slot, committee_index)?;
+
+        let bytes = self
+            .get_ssz_bytes(path, Some(self.timeouts.attestation))
+            .await?;
+        AttestationData::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)
+    }
+
     /// `GET validator/aggregate_attestation?slot,attestation_data_root`
     pub async fn get_validator_aggregate_attestation<T: EthSpec>(
         &self,
@@ -3,10 +3,17 @@
 mod attestation_performance;
 mod block_packing_efficiency;
 mod block_rewards;
+mod fork_choice_votes;
+mod proposal_diagnostics;
+mod slasher_slashings;
+mod validator_monitor_history;
 
 use crate::{
     ok_or_error,
-    types::{BeaconState, ChainSpec, Epoch, EthSpec, GenericResponse, ValidatorId},
+    types::{
+        BeaconState, BlockId, ChainSpec, Epoch, EthSpec, GenericResponse, PublicKeyBytes, Slot,
+        ValidatorId,
+    },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, StateId, StatusCode,
 };
 use proto_array::core::ProtoArray;
@@ -14,6 +21,7 @@ use reqwest::IntoUrl;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
+use std::path::PathBuf;
 use store::{AnchorInfo, Split, StoreConfig};
 
 pub use attestation_performance::{
@@ -23,7 +31,14 @@ pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
+pub use fork_choice_votes::{ForkChoiceQueuedAttestation, ForkChoiceVoteDiff, ForkChoiceVotes};
 pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use proposal_diagnostics::{
+    PayloadSelectionReason, PayloadSource, ProposalDiagnostics, ProposalPayloadDiagnostics,
+};
+pub use slasher_slashings::{SlasherSlashing, SlasherSlashingsQuery, SlashingKind};
+pub use store::backup::BackupManifest;
+pub use validator_monitor_history::{ValidatorMonitorEpochSummary, ValidatorMonitorHistoryQuery};
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
 // selector.
@@ -339,6 +354,28 @@ pub struct DatabaseInfo {
     pub anchor: Option<AnchorInfo>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseBackupRequest {
+    /// The directory to write the backup to, relative to the beacon node's `--http-backup-dir`.
+    /// It must not exist or be empty.
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseBackupStatus {
+    /// The directory the backup is written to, relative to the beacon node's `--http-backup-dir`.
+    pub path: PathBuf,
+    pub state: DatabaseBackupState,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum DatabaseBackupState {
+    InProgress,
+    Complete { manifest: BackupManifest },
+    Failed { error: String },
+}
+
 impl BeaconNodeHttpClient {
     /// Perform a HTTP GET request, returning `None` on a 404 error.
     async fn get_bytes_opt<U: IntoUrl>(&self, url: U) -> Result<Option<Vec<u8>>, Error> {
@@ -405,6 +442,43 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/fork_choice/votes/{block_id}`
+    pub async fn get_lighthouse_fork_choice_votes(
+        &self,
+        block_id: BlockId,
+    ) -> Result<GenericResponse<ForkChoiceVotes>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice")
+            .push("votes")
+            .push(&block_id.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/fork_choice/votes/{block_id}/diff/{other_block_id}`
+    pub async fn get_lighthouse_fork_choice_vote_diff(
+        &self,
+        block_id: BlockId,
+        other_block_id: BlockId,
+    ) -> Result<GenericResponse<ForkChoiceVoteDiff>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice")
+            .push("votes")
+            .push(&block_id.to_string())
+            .push("diff")
+            .push(&other_block_id.to_string());
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/validator_inclusion/{epoch}/global`
     pub async fn get_lighthouse_validator_inclusion_global(
         &self,
@@ -544,4 +618,127 @@ impl BeaconNodeHttpClient {
 
         self.post_with_response(path, &()).await
     }
+
+    /// `POST lighthouse/database/backup`
+    ///
+    /// Starts a backup in the background. Poll `get_lighthouse_database_backup` for its status.
+    pub async fn post_lighthouse_database_backup(
+        &self,
+        request: &DatabaseBackupRequest,
+    ) -> Result<DatabaseBackupStatus, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("backup");
+
+        let response = self.client.post(path).json(request).send().await?;
+        if response.status() == StatusCode::ACCEPTED {
+            response.json().await.map_err(Error::Reqwest)
+        } else {
+            let status = ok_or_error(response).await?.status();
+            Err(Error::StatusCode(status))
+        }
+    }
+
+    /// `GET lighthouse/database/backup`
+    pub async fn get_lighthouse_database_backup(&self) -> Result<DatabaseBackupStatus, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("backup");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/validator_monitor/{pubkey}/history`
+    pub async fn get_lighthouse_validator_monitor_history(
+        &self,
+        pubkey: &PublicKeyBytes,
+        from_epoch: Option<Epoch>,
+        to_epoch: Option<Epoch>,
+    ) -> Result<GenericResponse<Vec<ValidatorMonitorEpochSummary>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push(&pubkey.to_string())
+            .push("history");
+
+        if let Some(from_epoch) = from_epoch {
+            path.query_pairs_mut()
+                .append_pair("from_epoch", &from_epoch.to_string());
+        }
+
+        if let Some(to_epoch) = to_epoch {
+            path.query_pairs_mut()
+                .append_pair("to_epoch", &to_epoch.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/proposals/{slot}`
+    pub async fn get_lighthouse_proposals(
+        &self,
+        slot: Slot,
+    ) -> Result<GenericResponse<Vec<ProposalDiagnostics>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("proposals")
+            .push(&slot.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/slasher/slashings`
+    pub async fn get_lighthouse_slasher_slashings<T: EthSpec>(
+        &self,
+        query: &SlasherSlashingsQuery,
+    ) -> Result<GenericResponse<Vec<SlasherSlashing<T>>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("slashings");
+
+        if let Some(validator_index) = query.validator_index {
+            path.query_pairs_mut()
+                .append_pair("validator_index", &validator_index.to_string());
+        }
+
+        if let Some(kind) = query.kind {
+            path.query_pairs_mut()
+                .append_pair("kind", &kind.to_string());
+        }
+
+        if let Some(from_epoch) = query.from_epoch {
+            path.query_pairs_mut()
+                .append_pair("from_epoch", &from_epoch.to_string());
+        }
+
+        if let Some(to_epoch) = query.to_epoch {
+            path.query_pairs_mut()
+                .append_pair("to_epoch", &to_epoch.to_string());
+        }
+
+        if let Some(included) = query.included {
+            path.query_pairs_mut()
+                .append_pair("included", &included.to_string());
+        }
+
+        self.get(path).await
+    }
 }
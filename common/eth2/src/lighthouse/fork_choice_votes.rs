@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use types::{Epoch, Hash256, Slot};
+
+/// The latest messages in fork choice which support a block, along with the votes which are
+/// disregarded or not yet applied.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ForkChoiceVotes {
+    pub block_root: Hash256,
+    /// Validators whose latest message is for the block or one of its descendants.
+    pub supporting_validators: Vec<u64>,
+    /// Validators which have equivocated and whose votes are therefore disregarded.
+    pub equivocating_validators: Vec<u64>,
+    /// Attestations which have been received but not yet applied to fork choice.
+    pub queued_attestations: Vec<ForkChoiceQueuedAttestation>,
+}
+
+/// An attestation queued in fork choice until the slot after its own.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ForkChoiceQueuedAttestation {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub target_epoch: Epoch,
+    pub attesting_indices: Vec<u64>,
+}
+
+/// A comparison of the validators whose latest messages support each of a pair of blocks.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ForkChoiceVoteDiff {
+    pub first_root: Hash256,
+    pub second_root: Hash256,
+    /// Validators supporting the first block but not the second.
+    pub first_only: Vec<u64>,
+    /// Validators supporting the second block but not the first.
+    pub second_only: Vec<u64>,
+    /// Validators supporting both blocks.
+    pub both: Vec<u64>,
+}
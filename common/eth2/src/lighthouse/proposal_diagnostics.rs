@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use types::{Hash256, Slot, Uint256};
+
+/// The origin of the execution payload included in a produced block.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadSource {
+    /// The payload header was provided by a connected builder.
+    Builder,
+    /// The payload was provided by the local execution engine.
+    Local,
+}
+
+/// The reason that the execution payload was obtained from its `PayloadSource`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadSelectionReason {
+    /// A full block was requested, so no builder was consulted.
+    FullBlockRequested,
+    /// No builder is configured.
+    NoBuilder,
+    /// The builder provided a valid bid.
+    BuilderBidValid,
+    /// The local execution engine failed, so the builder's bid was used without comparison.
+    LocalEngineFailed,
+    /// The request to the builder failed.
+    BuilderRequestFailed,
+    /// The builder did not provide a bid.
+    BuilderNoBid,
+    /// The builder's bid had an incorrect parent hash.
+    InvalidBuilderParentHash,
+    /// The builder's bid had an incorrect `prev_randao`.
+    InvalidBuilderPrevRandao,
+    /// The builder's bid had an incorrect timestamp.
+    InvalidBuilderTimestamp,
+    /// The builder's bid had an incorrect block number.
+    InvalidBuilderBlockNumber,
+    /// The builder's bid was for an unexpected fork.
+    InvalidBuilderFork,
+    /// The builder's bid had an invalid signature.
+    InvalidBuilderSignature,
+    /// The chain was unhealthy, so the builder was not consulted.
+    ChainUnhealthy,
+    /// The execution engine was syncing, so the builder was not consulted.
+    ExecutionEngineOptimistic,
+    /// The merge had not occurred, so the builder was not consulted.
+    PreMerge,
+}
+
+/// Describes how the execution payload of a produced block was obtained.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ProposalPayloadDiagnostics {
+    pub source: PayloadSource,
+    pub reason: PayloadSelectionReason,
+    /// The value (in wei) of the bid received from the builder, if any, regardless of whether it
+    /// was used.
+    pub builder_bid_value: Option<Uint256>,
+    /// The time taken to obtain the payload from the execution engine and/or builder, in
+    /// milliseconds.
+    pub fetch_time_ms: u64,
+}
+
+/// A per-phase breakdown of the production of a block by this beacon node.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ProposalDiagnostics {
+    pub slot: Slot,
+    pub proposer_index: u64,
+    pub parent_root: Hash256,
+    pub block_root: Hash256,
+    /// `true` if a blinded block was produced.
+    pub blinded: bool,
+    /// The delay between the start of `slot` and the production request, in milliseconds.
+    pub request_delay_ms: Option<u64>,
+    /// The time taken to load the parent state, if it was loaded by the beacon node, in
+    /// milliseconds.
+    pub state_load_time_ms: Option<u64>,
+    /// The time taken to advance the parent state to `slot`, in milliseconds.
+    pub state_advance_time_ms: u64,
+    /// The time taken to pack operations from the op pool, in milliseconds.
+    pub op_pool_packing_time_ms: u64,
+    /// The time spent waiting for the execution payload after packing operations, in
+    /// milliseconds.
+    pub payload_wait_time_ms: u64,
+    /// The time taken to apply the block to the state and compute the state root, in
+    /// milliseconds.
+    pub block_completion_time_ms: u64,
+    /// The total time taken to produce the block, in milliseconds.
+    pub total_time_ms: u64,
+    /// Details of the execution payload, if one was obtained from the execution layer.
+    pub payload: Option<ProposalPayloadDiagnostics>,
+    /// The delay between returning the unsigned block and it being published, which includes
+    /// signing by the validator client, in milliseconds.
+    pub signing_round_trip_ms: Option<u64>,
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use types::{AttesterSlashing, Epoch, EthSpec, ProposerSlashing};
+
+/// The kind of slashable offence detected by the slasher.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashingKind {
+    Proposer,
+    Attester,
+}
+
+impl fmt::Display for SlashingKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SlashingKind::Proposer => write!(f, "proposer"),
+            SlashingKind::Attester => write!(f, "attester"),
+        }
+    }
+}
+
+/// A slashing detected by the slasher of a beacon node, along with its evidence.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+pub struct SlasherSlashing<T: EthSpec> {
+    /// Sequential identifier assigned to the slashing by the slasher.
+    pub id: u64,
+    pub kind: SlashingKind,
+    /// The epoch during which the slashing was detected.
+    pub detected_epoch: Epoch,
+    /// The validators made slashable by the evidence.
+    pub validator_indices: Vec<u64>,
+    /// `true` if all of `validator_indices` are slashed in the head state, i.e. this slashing (or
+    /// equivalent evidence) has been included on chain.
+    pub included: bool,
+    /// The evidence, if `kind` is `proposer`.
+    pub proposer_slashing: Option<ProposerSlashing>,
+    /// The evidence, if `kind` is `attester`.
+    pub attester_slashing: Option<AttesterSlashing<T>>,
+}
+
+/// Query parameters for filtering the slashings returned by `GET lighthouse/slasher/slashings`.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SlasherSlashingsQuery {
+    /// Only return slashings of this validator.
+    pub validator_index: Option<u64>,
+    pub kind: Option<SlashingKind>,
+    /// Only return slashings detected at or after this epoch.
+    pub from_epoch: Option<Epoch>,
+    /// Only return slashings detected at or before this epoch.
+    pub to_epoch: Option<Epoch>,
+    /// Only return slashings which have (or have not) been included on chain.
+    pub included: Option<bool>,
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use ssz::four_byte_option_impl;
+use ssz_derive::{Decode, Encode};
+use types::Epoch;
+
+four_byte_option_impl!(four_byte_option_u64, u64);
+
+/// The performance of a single validator during a single epoch, as recorded by the validator
+/// monitor of a beacon node.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ValidatorMonitorEpochSummary {
+    pub epoch: Epoch,
+    pub validator_index: u64,
+    /// `true` if any attestation from the validator for `epoch` was included on-chain.
+    ///
+    /// Post-Altair, only attestations which matched a "timely" flag are counted.
+    pub attestation_hit: bool,
+    /// `true` if an on-chain attestation for `epoch` matched the head.
+    pub attestation_head_hit: bool,
+    /// `true` if an on-chain attestation for `epoch` matched the target.
+    pub attestation_target_hit: bool,
+    /// The minimum inclusion distance of an attestation for `epoch`, if known.
+    #[ssz(with = "four_byte_option_u64")]
+    pub attestation_min_inclusion_distance: Option<u64>,
+    /// The number of blocks produced by the validator with a slot in `epoch`.
+    pub blocks_proposed: u64,
+    /// `true` if the validator was a member of the sync committee during `epoch`.
+    pub sync_committee_member: bool,
+    /// The number of the validator's sync committee signatures included in blocks during `epoch`.
+    pub sync_signature_block_inclusions: u64,
+    /// The balance of the validator at the start of `epoch`, if known.
+    #[ssz(with = "four_byte_option_u64")]
+    pub start_balance: Option<u64>,
+    /// The balance of the validator at the start of the epoch following `epoch`, if known.
+    ///
+    /// The balance delta for `epoch` is `end_balance - start_balance`.
+    #[ssz(with = "four_byte_option_u64")]
+    pub end_balance: Option<u64>,
+}
+
+/// Query parameters for the `/lighthouse/validator_monitor/{pubkey}/history` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorMonitorHistoryQuery {
+    pub from_epoch: Option<Epoch>,
+    pub to_epoch: Option<Epoch>,
+}
@@ -397,6 +397,21 @@ impl ValidatorClientHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET lighthouse/ui/sync_committee_performance`
+    pub async fn get_lighthouse_ui_sync_committee_performance(
+        &self,
+    ) -> Result<GenericResponse<Vec<SyncCommitteePerformanceData>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("ui")
+            .push("sync_committee_performance");
+
+        self.get(path).await
+    }
+
     /// `POST lighthouse/validators`
     pub async fn post_lighthouse_validators(
         &self,
@@ -412,6 +427,40 @@ impl ValidatorClientHttpClient {
         self.post(path, &validators).await
     }
 
+    /// `POST lighthouse/validators/batch`
+    pub async fn post_lighthouse_validators_batch(
+        &self,
+        request: &BatchValidatorsRequest,
+    ) -> Result<BatchValidatorsResponse, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("batch");
+
+        self.post(path, &request).await
+    }
+
+    /// `POST lighthouse/validators/move`
+    ///
+    /// The response can be posted to `eth/v1/keystores` on the destination validator client.
+    pub async fn post_lighthouse_validators_move(
+        &self,
+        request: &MoveValidatorsRequest,
+    ) -> Result<ImportKeystoresRequest, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push("move");
+
+        self.post(path, &request).await
+    }
+
     /// `POST lighthouse/validators/mnemonic`
     pub async fn post_lighthouse_validators_mnemonic(
         &self,
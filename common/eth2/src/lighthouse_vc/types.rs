@@ -144,3 +144,57 @@ pub struct UpdateGasLimitRequest {
     #[serde(with = "eth2_serde_utils::quoted_u64")]
     pub gas_limit: u64,
 }
+
+/// Cumulative sync committee performance of a single local validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncCommitteePerformanceData {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub pubkey: PublicKeyBytes,
+    pub messages_included: u64,
+    pub messages_missed: u64,
+    pub missed_not_published: u64,
+    pub missed_wrong_head: u64,
+    pub missed_not_included: u64,
+    pub missed_no_block: u64,
+    pub contributions_published: u64,
+    pub contributions_included: u64,
+    pub latest_slot: Option<Slot>,
+    pub latest_missed_slot: Option<Slot>,
+}
+
+/// A batch of validator imports and deletions which is applied atomically.
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchValidatorsRequest {
+    #[serde(default)]
+    pub keystores: Vec<KeystoreJsonStr>,
+    #[serde(default)]
+    pub passwords: Vec<ZeroizeString>,
+    #[serde(default)]
+    pub slashing_protection: Option<InterchangeJsonStr>,
+    #[serde(default)]
+    pub delete: Vec<PublicKeyBytes>,
+    /// Check that the batch would apply without making any changes.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BatchValidatorsResponse {
+    pub dry_run: bool,
+    pub imported: Vec<PublicKeyBytes>,
+    pub deleted: Vec<PublicKeyBytes>,
+    /// Slashing protection data for the deleted validators.
+    pub slashing_protection: InterchangeJsonStr,
+}
+
+/// Remove validators from this validator client, returning everything required to import them
+/// into another validator client.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MoveValidatorsRequest {
+    pub pubkeys: Vec<PublicKeyBytes>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
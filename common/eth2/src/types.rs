@@ -5,6 +5,7 @@ use crate::Error as ServerError;
 use lighthouse_network::{ConnectionDirection, Enr, Multiaddr, PeerConnectionStatus};
 use mime::{Mime, APPLICATION, JSON, OCTET_STREAM, STAR};
 use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
 use std::cmp::Reverse;
 use std::convert::TryFrom;
 use std::fmt;
@@ -13,7 +14,7 @@ use std::time::Duration;
 pub use types::*;
 
 #[cfg(feature = "lighthouse")]
-use crate::lighthouse::BlockReward;
+use crate::lighthouse::{BlockReward, ProposalDiagnostics};
 
 /// An API error serializable to JSON.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -300,7 +301,9 @@ impl fmt::Display for ValidatorId {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The SSZ encoding of this type is not defined by the specification; it is the container of its
+/// fields in declaration order, as documented in the Lighthouse book.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct ValidatorData {
     #[serde(with = "eth2_serde_utils::quoted_u64")]
     pub index: u64,
@@ -344,6 +347,64 @@ pub enum ValidatorStatus {
     Withdrawal,
 }
 
+/// Encodes the status as a single byte, in order of declaration.
+///
+/// This encoding is not defined by the specification and is documented in the Lighthouse book, so
+/// variants must not be reordered.
+impl ssz::Encode for ValidatorStatus {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        1
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        1
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.push(*self as u8)
+    }
+}
+
+impl ssz::Decode for ValidatorStatus {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        1
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        match bytes {
+            [0] => Ok(ValidatorStatus::PendingInitialized),
+            [1] => Ok(ValidatorStatus::PendingQueued),
+            [2] => Ok(ValidatorStatus::ActiveOngoing),
+            [3] => Ok(ValidatorStatus::ActiveExiting),
+            [4] => Ok(ValidatorStatus::ActiveSlashed),
+            [5] => Ok(ValidatorStatus::ExitedUnslashed),
+            [6] => Ok(ValidatorStatus::ExitedSlashed),
+            [7] => Ok(ValidatorStatus::WithdrawalPossible),
+            [8] => Ok(ValidatorStatus::WithdrawalDone),
+            [9] => Ok(ValidatorStatus::Active),
+            [10] => Ok(ValidatorStatus::Pending),
+            [11] => Ok(ValidatorStatus::Exited),
+            [12] => Ok(ValidatorStatus::Withdrawal),
+            [byte] => Err(ssz::DecodeError::BytesInvalid(format!(
+                "invalid validator status: {}",
+                byte
+            ))),
+            _ => Err(ssz::DecodeError::InvalidByteLength {
+                len: bytes.len(),
+                expected: 1,
+            }),
+        }
+    }
+}
+
 impl ValidatorStatus {
     pub fn from_validator(validator: &Validator, epoch: Epoch, far_future_epoch: Epoch) -> Self {
         if validator.is_withdrawable_at(epoch) {
@@ -470,7 +531,9 @@ pub struct ValidatorsQuery {
     pub status: Option<Vec<ValidatorStatus>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The SSZ encoding of this type is not defined by the specification; it is the container of its
+/// fields in declaration order, as documented in the Lighthouse book.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct CommitteeData {
     #[serde(with = "eth2_serde_utils::quoted_u64")]
     pub index: u64,
@@ -493,6 +556,87 @@ pub struct SyncSubcommittee {
     pub indices: Vec<u64>,
 }
 
+/// Details about the rewards paid to a block proposer for proposing a block.
+///
+/// All rewards in GWei.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StandardBlockReward {
+    /// Proposer of the block.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    /// Total block reward.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub total: u64,
+    /// Rewards due to the inclusion of attestations.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub attestations: u64,
+    /// Rewards due to the inclusion of the sync aggregate.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub sync_aggregate: u64,
+    /// Rewards due to the inclusion of proposer slashings.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub proposer_slashings: u64,
+    /// Rewards due to the inclusion of attester slashings.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub attester_slashings: u64,
+}
+
+/// The reward (or penalty) applied to a single sync committee member by a block.
+///
+/// Rewards are in GWei and may be negative when the member did not participate.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SyncCommitteeReward {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// Net reward for all of the validator's positions in the committee.
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub reward: i64,
+}
+
+/// The rewards a validator with `effective_balance` would receive for a perfect attestation.
+///
+/// All rewards in GWei.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct IdealAttestationRewards {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub effective_balance: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub head: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub target: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub source: u64,
+    /// Only present for phase0 epochs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inclusion_delay: Option<eth2_serde_utils::quoted_u64::Quoted<u64>>,
+}
+
+/// The rewards and penalties actually applied to a validator for its attestation in an epoch.
+///
+/// All rewards in GWei. Penalties are represented as negative values.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TotalAttestationRewards {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub head: i64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub target: i64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub source: i64,
+    /// Only present for phase0 epochs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inclusion_delay: Option<eth2_serde_utils::quoted_u64::Quoted<u64>>,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub inactivity: i64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StandardAttestationRewards {
+    pub ideal_rewards: Vec<IdealAttestationRewards>,
+    pub total_rewards: Vec<TotalAttestationRewards>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct HeadersQuery {
     pub slot: Option<Slot>,
@@ -527,6 +671,77 @@ pub struct ChainHeadData {
     pub execution_optimistic: Option<bool>,
 }
 
+/// The full fork choice tree, as returned by `GET debug/fork_choice`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoice {
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    pub fork_choice_nodes: Vec<ForkChoiceNode>,
+    pub extra_data: ForkChoiceExtraData,
+}
+
+/// Lighthouse-specific information about the fork choice tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceExtraData {
+    /// The proposer boost applied to node weights during the last head computation.
+    pub proposer_boost: ForkChoiceProposerBoost,
+    pub justified_balances: ForkChoiceJustifiedBalances,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceProposerBoost {
+    pub root: Hash256,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub score: u64,
+}
+
+/// A summary of the balances of the justified state used to weigh votes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceJustifiedBalances {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub total_effective_balance: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub num_active_validators: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceNode {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub parent_root: Option<Hash256>,
+    pub justified_epoch: Epoch,
+    pub finalized_epoch: Epoch,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub weight: u64,
+    pub validity: ForkChoiceNodeValidity,
+    /// `None` if the block does not contain an execution payload.
+    pub execution_block_hash: Option<ExecutionBlockHash>,
+    pub extra_data: ForkChoiceNodeExtraData,
+}
+
+/// Lighthouse-specific information about a `ForkChoiceNode`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceNodeExtraData {
+    pub justified_checkpoint: Option<Checkpoint>,
+    pub finalized_checkpoint: Option<Checkpoint>,
+    pub unrealized_justified_checkpoint: Option<Checkpoint>,
+    pub unrealized_finalized_checkpoint: Option<Checkpoint>,
+}
+
+/// The execution payload status of a `ForkChoiceNode`.
+///
+/// Blocks without an execution payload are `Valid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ForkChoiceNodeValidity {
+    /// The payload has been verified by an execution node.
+    Valid,
+    /// The payload has been found invalid by an execution node.
+    Invalid,
+    /// The payload has been imported optimistically and not yet verified.
+    Optimistic,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IdentityData {
     pub peer_id: String,
@@ -875,6 +1090,38 @@ pub struct SseLateHead {
     pub execution_optimistic: bool,
 }
 
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SsePayloadAttributes {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub timestamp: u64,
+    pub prev_randao: Hash256,
+    pub suggested_fee_recipient: Address,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseExtendedPayloadAttributes {
+    pub proposal_slot: Slot,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    pub parent_block_root: Hash256,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub parent_block_number: u64,
+    pub parent_block_hash: ExecutionBlockHash,
+    pub payload_attributes: SsePayloadAttributes,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseBlockGossip {
+    pub slot: Slot,
+    pub block: Hash256,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    /// Time at which the block was first received, since the UNIX epoch.
+    pub seen_timestamp: Duration,
+    /// Time at which the block was first received, since the start of its slot.
+    pub block_delay: Duration,
+}
+
 #[derive(PartialEq, Debug, Serialize, Clone)]
 #[serde(bound = "T: EthSpec", untagged)]
 pub enum EventKind<T: EthSpec> {
@@ -888,6 +1135,12 @@ pub enum EventKind<T: EthSpec> {
     LateHead(SseLateHead),
     #[cfg(feature = "lighthouse")]
     BlockReward(BlockReward),
+    PayloadAttributes(ForkVersionedResponse<SseExtendedPayloadAttributes>),
+    ProposerSlashing(Box<ProposerSlashing>),
+    AttesterSlashing(Box<AttesterSlashing<T>>),
+    BlockGossip(Box<SseBlockGossip>),
+    #[cfg(feature = "lighthouse")]
+    ProposalDiagnostics(Box<ProposalDiagnostics>),
 }
 
 impl<T: EthSpec> EventKind<T> {
@@ -903,6 +1156,12 @@ impl<T: EthSpec> EventKind<T> {
             EventKind::LateHead(_) => "late_head",
             #[cfg(feature = "lighthouse")]
             EventKind::BlockReward(_) => "block_reward",
+            EventKind::PayloadAttributes(_) => "payload_attributes",
+            EventKind::ProposerSlashing(_) => "proposer_slashing",
+            EventKind::AttesterSlashing(_) => "attester_slashing",
+            EventKind::BlockGossip(_) => "block_gossip",
+            #[cfg(feature = "lighthouse")]
+            EventKind::ProposalDiagnostics(_) => "proposal_diagnostics",
         }
     }
 
@@ -959,6 +1218,32 @@ impl<T: EthSpec> EventKind<T> {
             "block_reward" => Ok(EventKind::BlockReward(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Block Reward: {:?}", e)),
             )?)),
+            "payload_attributes" => Ok(EventKind::PayloadAttributes(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Payload Attributes: {:?}", e))
+                })?,
+            )),
+            "proposer_slashing" => Ok(EventKind::ProposerSlashing(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Proposer Slashing: {:?}", e))
+                })?,
+            ))),
+            "attester_slashing" => Ok(EventKind::AttesterSlashing(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Attester Slashing: {:?}", e))
+                })?,
+            ))),
+            "block_gossip" => Ok(EventKind::BlockGossip(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Block Gossip: {:?}", e))
+                })?,
+            ))),
+            #[cfg(feature = "lighthouse")]
+            "proposal_diagnostics" => Ok(EventKind::ProposalDiagnostics(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Proposal Diagnostics: {:?}", e))
+                })?,
+            ))),
             _ => Err(ServerError::InvalidServerSentEvent(
                 "Could not parse event tag".to_string(),
             )),
@@ -986,6 +1271,12 @@ pub enum EventTopic {
     LateHead,
     #[cfg(feature = "lighthouse")]
     BlockReward,
+    PayloadAttributes,
+    ProposerSlashing,
+    AttesterSlashing,
+    BlockGossip,
+    #[cfg(feature = "lighthouse")]
+    ProposalDiagnostics,
 }
 
 impl FromStr for EventTopic {
@@ -1003,6 +1294,12 @@ impl FromStr for EventTopic {
             "late_head" => Ok(EventTopic::LateHead),
             #[cfg(feature = "lighthouse")]
             "block_reward" => Ok(EventTopic::BlockReward),
+            "payload_attributes" => Ok(EventTopic::PayloadAttributes),
+            "proposer_slashing" => Ok(EventTopic::ProposerSlashing),
+            "attester_slashing" => Ok(EventTopic::AttesterSlashing),
+            "block_gossip" => Ok(EventTopic::BlockGossip),
+            #[cfg(feature = "lighthouse")]
+            "proposal_diagnostics" => Ok(EventTopic::ProposalDiagnostics),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -1021,6 +1318,12 @@ impl fmt::Display for EventTopic {
             EventTopic::LateHead => write!(f, "late_head"),
             #[cfg(feature = "lighthouse")]
             EventTopic::BlockReward => write!(f, "block_reward"),
+            EventTopic::PayloadAttributes => write!(f, "payload_attributes"),
+            EventTopic::ProposerSlashing => write!(f, "proposer_slashing"),
+            EventTopic::AttesterSlashing => write!(f, "attester_slashing"),
+            EventTopic::BlockGossip => write!(f, "block_gossip"),
+            #[cfg(feature = "lighthouse")]
+            EventTopic::ProposalDiagnostics => write!(f, "proposal_diagnostics"),
         }
     }
 }
@@ -1124,4 +1427,21 @@ mod tests {
             Err("accept header is not supported".to_string())
         )
     }
+
+    #[test]
+    fn validator_status_ssz_round_trip() {
+        use ssz::{Decode, Encode};
+
+        for status in [
+            ValidatorStatus::PendingInitialized,
+            ValidatorStatus::ActiveSlashed,
+            ValidatorStatus::Withdrawal,
+        ] {
+            assert_eq!(
+                ValidatorStatus::from_ssz_bytes(&status.as_ssz_bytes()),
+                Ok(status)
+            );
+        }
+        assert!(ValidatorStatus::from_ssz_bytes(&[13]).is_err());
+    }
 }
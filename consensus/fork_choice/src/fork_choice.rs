@@ -1,11 +1,14 @@
 use crate::{ForkChoiceStore, InvalidationOperation};
-use proto_array::{Block as ProtoBlock, ExecutionStatus, ProtoArrayForkChoice};
+use proto_array::{
+    Block as ProtoBlock, ExecutionStatus, ProposerHeadError, ProposerHeadInfo,
+    ProtoArrayForkChoice, ReOrgThreshold,
+};
 use ssz_derive::{Decode, Encode};
 use state_processing::{
     per_block_processing::errors::AttesterSlashingValidationError, per_epoch_processing,
 };
 use std::cmp::Ordering;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::marker::PhantomData;
 use std::time::Duration;
 use types::{
@@ -59,6 +62,13 @@ pub enum Error<T> {
     UnrealizedVoteProcessing(state_processing::EpochProcessingError),
     ParticipationCacheBuild(BeaconStateError),
     ValidatorStatuses(BeaconStateError),
+    WrongSlotForGetProposerHead {
+        current_slot: Slot,
+        fc_store_slot: Slot,
+    },
+    ProposerBoostNotExpiredForGetProposerHead {
+        proposer_boost_root: Hash256,
+    },
 }
 
 impl<T> From<InvalidAttestation> for Error<T> {
@@ -252,6 +262,24 @@ pub struct QueuedAttestation {
     target_epoch: Epoch,
 }
 
+impl QueuedAttestation {
+    pub fn slot(&self) -> Slot {
+        self.slot
+    }
+
+    pub fn attesting_indices(&self) -> &[u64] {
+        &self.attesting_indices
+    }
+
+    pub fn block_root(&self) -> Hash256 {
+        self.block_root
+    }
+
+    pub fn target_epoch(&self) -> Epoch {
+        self.target_epoch
+    }
+}
+
 impl<E: EthSpec> From<&IndexedAttestation<E>> for QueuedAttestation {
     fn from(a: &IndexedAttestation<E>) -> Self {
         Self {
@@ -279,6 +307,20 @@ fn dequeue_attestations(
     std::mem::replace(queued_attestations, remaining)
 }
 
+/// Partitions the validators whose latest messages support either of a pair of blocks, as returned
+/// by `ForkChoice::vote_diff`.
+///
+/// Each list is sorted by validator index.
+#[derive(Debug, Default, PartialEq)]
+pub struct VoteDiff {
+    /// Validators supporting the first block but not the second.
+    pub first_only: Vec<u64>,
+    /// Validators supporting the second block but not the first.
+    pub second_only: Vec<u64>,
+    /// Validators supporting both blocks, i.e. a descendant of both.
+    pub both: Vec<u64>,
+}
+
 /// Denotes whether an attestation we are processing was received from a block or from gossip.
 /// Equivalent to the `is_from_block` `bool` in:
 ///
@@ -1390,6 +1432,58 @@ where
         self.proto_array.latest_message(validator_index)
     }
 
+    /// Returns the indices of all validators whose latest message is for `block_root` or one of
+    /// its descendants, sorted by validator index.
+    ///
+    /// Equivocating validators are excluded since their votes carry no weight.
+    ///
+    /// ## Notes
+    ///
+    /// Attestations in `Self::queued_attestations` have not yet been applied, so it may be prudent
+    /// to call `Self::update_time` before calling this function.
+    pub fn supporting_validators(&self, block_root: Hash256) -> Vec<u64> {
+        self.vote_diff(block_root, block_root).both
+    }
+
+    /// Compares the validators whose latest messages support `first_root` against those whose
+    /// latest messages support `second_root`.
+    ///
+    /// Equivocating validators are excluded, as in `Self::supporting_validators`.
+    pub fn vote_diff(&self, first_root: Hash256, second_root: Hash256) -> VoteDiff {
+        let equivocating_indices = self.fc_store.equivocating_indices();
+
+        // Many validators share a vote, so cache the ancestry check for each vote root.
+        let mut supports_cache: HashMap<Hash256, (bool, bool)> = HashMap::new();
+        let mut diff = VoteDiff::default();
+
+        for (validator_index, vote_root, _) in self.proto_array.latest_messages() {
+            let validator_index = validator_index as u64;
+            if equivocating_indices.contains(&validator_index) {
+                continue;
+            }
+
+            let supports = *supports_cache.entry(vote_root).or_insert_with(|| {
+                (
+                    self.proto_array.is_descendant(first_root, vote_root),
+                    self.proto_array.is_descendant(second_root, vote_root),
+                )
+            });
+            match supports {
+                (true, true) => diff.both.push(validator_index),
+                (true, false) => diff.first_only.push(validator_index),
+                (false, true) => diff.second_only.push(validator_index),
+                (false, false) => (),
+            }
+        }
+
+        diff
+    }
+
+    /// Returns the indices of all validators which have equivocated.
+    pub fn equivocating_indices(&self) -> &BTreeSet<u64> {
+        self.fc_store.equivocating_indices()
+    }
+
     /// Returns a reference to the underlying fork choice DAG.
     pub fn proto_array(&self) -> &ProtoArrayForkChoice {
         &self.proto_array
@@ -1416,6 +1510,68 @@ where
         self.fc_store.proposer_boost_root()
     }
 
+    /// Determine whether the proposer at `current_slot` should re-org `canonical_head` by
+    /// building upon its parent.
+    ///
+    /// Fork choice must already have been run for `current_slot`, so that the proposer boost of the
+    /// head has expired and the head's weight reflects the attestations received during its slot.
+    pub fn get_proposer_head(
+        &self,
+        current_slot: Slot,
+        canonical_head: Hash256,
+        re_org_threshold: ReOrgThreshold,
+        max_epochs_since_finalization: Epoch,
+    ) -> Result<ProposerHeadInfo, ProposerHeadError<Error<T::Error>>> {
+        let fc_store_slot = self.fc_store.get_current_slot();
+        if current_slot != fc_store_slot {
+            return Err(ProposerHeadError::Error(
+                Error::WrongSlotForGetProposerHead {
+                    current_slot,
+                    fc_store_slot,
+                },
+            ));
+        }
+
+        let proposer_boost_root = self.fc_store.proposer_boost_root();
+        if !proposer_boost_root.is_zero() {
+            return Err(ProposerHeadError::Error(
+                Error::ProposerBoostNotExpiredForGetProposerHead {
+                    proposer_boost_root,
+                },
+            ));
+        }
+
+        self.proto_array
+            .get_proposer_head::<E>(
+                current_slot,
+                canonical_head,
+                self.fc_store.justified_balances(),
+                re_org_threshold,
+                max_epochs_since_finalization,
+            )
+            .map_err(|e| e.map_inner_error(|e| Error::ProtoArrayError(format!("{:?}", e))))
+    }
+
+    /// Check the conditions for re-orging `canonical_head` which can be checked before the
+    /// proposal slot, i.e. all but the distance from the current slot and the weight of the head.
+    ///
+    /// This is used to decide which block to prepare an execution payload upon.
+    pub fn get_preliminary_proposer_head(
+        &self,
+        canonical_head: Hash256,
+        re_org_threshold: ReOrgThreshold,
+        max_epochs_since_finalization: Epoch,
+    ) -> Result<ProposerHeadInfo, ProposerHeadError<Error<T::Error>>> {
+        self.proto_array
+            .get_proposer_head_info::<E>(
+                canonical_head,
+                self.fc_store.justified_balances(),
+                re_org_threshold,
+                max_epochs_since_finalization,
+            )
+            .map_err(|e| e.map_inner_error(|e| Error::ProtoArrayError(format!("{:?}", e))))
+    }
+
     /// Prunes the underlying fork choice DAG.
     pub fn prune(&mut self) -> Result<(), Error<T::Error>> {
         let finalized_root = self.fc_store.finalized_checkpoint().root;
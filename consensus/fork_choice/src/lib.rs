@@ -4,7 +4,10 @@ mod fork_choice_store;
 pub use crate::fork_choice::{
     AttestationFromBlock, CountUnrealized, Error, ForkChoice, ForkChoiceView,
     ForkchoiceUpdateParameters, InvalidAttestation, InvalidBlock, PayloadVerificationStatus,
-    PersistedForkChoice, QueuedAttestation,
+    PersistedForkChoice, QueuedAttestation, VoteDiff,
 };
 pub use fork_choice_store::ForkChoiceStore;
-pub use proto_array::{Block as ProtoBlock, ExecutionStatus, InvalidationOperation};
+pub use proto_array::{
+    Block as ProtoBlock, DoNotReOrg, ExecutionStatus, InvalidationOperation, ProposerHeadError,
+    ProposerHeadInfo, ReOrgThreshold,
+};
@@ -1295,3 +1295,40 @@ async fn weak_subjectivity_check_epoch_boundary_is_skip_slot_failure() {
         .assert_finalized_epoch_is_less_than(checkpoint.epoch)
         .assert_shutdown_signal_sent();
 }
+
+/// Ensure the validators supporting a block are determined from their latest messages.
+#[tokio::test]
+async fn supporting_validators_and_vote_diff() {
+    let test = ForkChoiceTest::new()
+        .apply_blocks(E::slots_per_epoch() as usize * 2)
+        .await;
+
+    let genesis_root = test.harness.chain.genesis_block_root;
+    let head_root = test.harness.head_block_root();
+    let fork_choice = test.harness.chain.canonical_head.fork_choice_read_lock();
+
+    // Every validator has attested and every block descends from genesis.
+    let all_validators = (0..VALIDATOR_COUNT as u64).collect::<Vec<_>>();
+    assert_eq!(
+        fork_choice.supporting_validators(genesis_root),
+        all_validators
+    );
+    assert!(fork_choice
+        .supporting_validators(Hash256::repeat_byte(42))
+        .is_empty());
+
+    // Every validator supporting the head also supports its ancestors.
+    let head_supporters = fork_choice.supporting_validators(head_root);
+    let diff = fork_choice.vote_diff(head_root, genesis_root);
+    assert!(diff.first_only.is_empty());
+    assert_eq!(diff.both, head_supporters);
+
+    let mut diff_validators = diff.both.clone();
+    diff_validators.extend_from_slice(&diff.second_only);
+    diff_validators.sort_unstable();
+    assert_eq!(diff_validators, all_validators);
+
+    let self_diff = fork_choice.vote_diff(head_root, head_root);
+    assert!(self_diff.first_only.is_empty() && self_diff.second_only.is_empty());
+    assert_eq!(self_diff.both, head_supporters);
+}
@@ -223,7 +223,7 @@ impl ProtoArray {
                     && !execution_status_is_invalid
                 {
                     proposer_score =
-                        calculate_proposer_boost::<E>(new_balances, proposer_score_boost)
+                        calculate_committee_fraction::<E>(new_balances, proposer_score_boost)
                             .ok_or(Error::ProposerBoostOverflow(node_index))?;
                     node_delta = node_delta
                         .checked_add(proposer_score as i64)
@@ -972,8 +972,9 @@ impl ProtoArray {
     }
 }
 
-/// A helper method to calculate the proposer boost based on the given `validator_balances`.
-/// This does *not* do any verification about whether a boost should or should not be applied.
+/// A helper method to calculate `committee_percent` percent of the weight of a single committee,
+/// based on the given `validator_balances`. This is used both for the proposer boost and for the
+/// proposer re-org threshold, and does *not* verify whether either should be applied.
 /// The `validator_balances` array used here is assumed to be structured like the one stored in
 /// the `BalancesCache`, where *effective* balances are stored and inactive balances are defaulted
 /// to zero.
@@ -981,9 +982,9 @@ impl ProtoArray {
 /// Returns `None` if there is an overflow or underflow when calculating the score.
 ///
 /// https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/fork-choice.md#get_latest_attesting_balance
-pub fn calculate_proposer_boost<E: EthSpec>(
+pub fn calculate_committee_fraction<E: EthSpec>(
     validator_balances: &[u64],
-    proposer_score_boost: u64,
+    committee_percent: u64,
 ) -> Option<u64> {
     let mut total_balance: u64 = 0;
     let mut num_validators: u64 = 0;
@@ -1000,7 +1001,7 @@ pub fn calculate_proposer_boost<E: EthSpec>(
     let committee_size = num_validators.checked_div(E::slots_per_epoch())?;
     let committee_weight = committee_size.checked_mul(average_balance)?;
     committee_weight
-        .checked_mul(proposer_score_boost)?
+        .checked_mul(committee_percent)?
         .checked_div(100)
 }
 
@@ -1,12 +1,13 @@
 use crate::error::Error;
 use crate::proto_array::{
-    calculate_proposer_boost, InvalidationOperation, Iter, ProposerBoost, ProtoArray, ProtoNode,
+    calculate_committee_fraction, InvalidationOperation, Iter, ProposerBoost, ProtoArray, ProtoNode,
 };
 use crate::ssz_container::SszContainer;
 use serde_derive::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::collections::{BTreeSet, HashMap};
+use std::fmt;
 use types::{
     AttestationShufflingId, ChainSpec, Checkpoint, Epoch, EthSpec, ExecutionBlockHash, Hash256,
     Slot,
@@ -142,6 +143,109 @@ pub struct Block {
     pub unrealized_finalized_checkpoint: Option<Checkpoint>,
 }
 
+/// The maximum weight of a head block which a proposer may re-org, as a percentage of the weight
+/// of a single committee.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReOrgThreshold(pub u64);
+
+/// Information about the head block and its parent, used by a proposer to decide whether to
+/// re-org the head.
+#[derive(Debug, Clone)]
+pub struct ProposerHeadInfo {
+    /// The node for the canonical head block.
+    pub head_node: ProtoNode,
+    /// The node for the parent of the canonical head block.
+    pub parent_node: ProtoNode,
+    /// The weight of the head block, excluding any proposer boost it currently holds.
+    pub head_weight: u64,
+    /// The computed fraction of the committee weight below which the head may be re-orged.
+    pub re_org_weight_threshold: u64,
+}
+
+/// The reason a proposer should not re-org the head, or an error encountered whilst deciding.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProposerHeadError<E> {
+    DoNotReOrg(DoNotReOrg),
+    Error(E),
+}
+
+impl<E> From<DoNotReOrg> for ProposerHeadError<E> {
+    fn from(reason: DoNotReOrg) -> ProposerHeadError<E> {
+        Self::DoNotReOrg(reason)
+    }
+}
+
+impl From<Error> for ProposerHeadError<Error> {
+    fn from(error: Error) -> Self {
+        Self::Error(error)
+    }
+}
+
+impl<E1> ProposerHeadError<E1> {
+    pub fn convert_inner_error<E2>(self) -> ProposerHeadError<E2>
+    where
+        E2: From<E1>,
+    {
+        self.map_inner_error(E2::from)
+    }
+
+    pub fn map_inner_error<E2>(self, f: impl FnOnce(E1) -> E2) -> ProposerHeadError<E2> {
+        match self {
+            ProposerHeadError::DoNotReOrg(reason) => ProposerHeadError::DoNotReOrg(reason),
+            ProposerHeadError::Error(error) => ProposerHeadError::Error(f(error)),
+        }
+    }
+}
+
+/// The reasons a proposer may decline to re-org the head.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DoNotReOrg {
+    MissingHeadOrParentNode,
+    MissingHeadFinalizedCheckpoint,
+    ParentDistance,
+    HeadDistance,
+    ShufflingUnstable,
+    JustificationAndFinalizationNotCompetitive,
+    ChainNotFinalizing {
+        epochs_since_finalization: u64,
+    },
+    HeadNotWeak {
+        head_weight: u64,
+        re_org_weight_threshold: u64,
+    },
+}
+
+impl fmt::Display for DoNotReOrg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingHeadOrParentNode => write!(f, "unknown head or parent"),
+            Self::MissingHeadFinalizedCheckpoint => write!(f, "finalized checkpoint missing"),
+            Self::ParentDistance => write!(f, "parent too far from head"),
+            Self::HeadDistance => write!(f, "head too far from current slot"),
+            Self::ShufflingUnstable => write!(f, "shuffling unstable at epoch boundary"),
+            Self::JustificationAndFinalizationNotCompetitive => {
+                write!(f, "justification or finalization not competitive")
+            }
+            Self::ChainNotFinalizing {
+                epochs_since_finalization,
+            } => write!(
+                f,
+                "chain not finalizing ({} epochs since finalization)",
+                epochs_since_finalization
+            ),
+            Self::HeadNotWeak {
+                head_weight,
+                re_org_weight_threshold,
+            } => write!(
+                f,
+                "head not weak ({}/{})",
+                head_weight, re_org_weight_threshold
+            ),
+        }
+    }
+}
+
 /// A Vec-wrapper which will grow to match any request.
 ///
 /// E.g., a `get` or `insert` to an out-of-bounds element will cause the Vec to grow (using
@@ -317,6 +421,122 @@ impl ProtoArrayForkChoice {
             .map_err(|e| format!("find_head failed: {:?}", e))
     }
 
+    /// Determine whether a proposer at `current_slot` should build upon the parent of
+    /// `canonical_head` rather than the head itself, thereby re-orging the head.
+    ///
+    /// The head may be re-orged if it is in the slot immediately prior to `current_slot` and the
+    /// conditions of `get_proposer_head_info` are satisfied, and if its weight is less than
+    /// `re_org_threshold` percent of a committee's weight.
+    pub fn get_proposer_head<E: EthSpec>(
+        &self,
+        current_slot: Slot,
+        canonical_head: Hash256,
+        justified_balances: &[u64],
+        re_org_threshold: ReOrgThreshold,
+        max_epochs_since_finalization: Epoch,
+    ) -> Result<ProposerHeadInfo, ProposerHeadError<Error>> {
+        let info = self.get_proposer_head_info::<E>(
+            canonical_head,
+            justified_balances,
+            re_org_threshold,
+            max_epochs_since_finalization,
+        )?;
+
+        // Only re-org a single slot. This prevents cascading failures during asynchrony.
+        if info.head_node.slot + 1 != current_slot {
+            return Err(DoNotReOrg::HeadDistance.into());
+        }
+
+        if info.head_weight >= info.re_org_weight_threshold {
+            return Err(DoNotReOrg::HeadNotWeak {
+                head_weight: info.head_weight,
+                re_org_weight_threshold: info.re_org_weight_threshold,
+            }
+            .into());
+        }
+
+        Ok(info)
+    }
+
+    /// Check the conditions for re-orging `canonical_head` which do not depend on the current
+    /// slot or on the weight of the head.
+    ///
+    /// A proposal in the slot after the head may re-org it only if:
+    ///
+    /// - The head's parent is in the slot immediately prior to the head.
+    /// - The proposal is not the first slot of an epoch, so the proposer shuffling is stable.
+    /// - The head does not improve upon the (unrealized) justification and finalization of its
+    ///   parent.
+    /// - The chain has finalized within `max_epochs_since_finalization` of the proposal.
+    pub fn get_proposer_head_info<E: EthSpec>(
+        &self,
+        canonical_head: Hash256,
+        justified_balances: &[u64],
+        re_org_threshold: ReOrgThreshold,
+        max_epochs_since_finalization: Epoch,
+    ) -> Result<ProposerHeadInfo, ProposerHeadError<Error>> {
+        let mut nodes = self
+            .proto_array
+            .iter_nodes(&canonical_head)
+            .take(2)
+            .cloned()
+            .collect::<Vec<_>>();
+        let parent_node = nodes.pop().ok_or(DoNotReOrg::MissingHeadOrParentNode)?;
+        let head_node = nodes.pop().ok_or(DoNotReOrg::MissingHeadOrParentNode)?;
+
+        let re_org_block_slot = head_node.slot + 1;
+
+        let finalized_epoch = head_node
+            .unrealized_finalized_checkpoint
+            .ok_or(DoNotReOrg::MissingHeadFinalizedCheckpoint)?
+            .epoch;
+        let epochs_since_finalization = re_org_block_slot
+            .epoch(E::slots_per_epoch())
+            .saturating_sub(finalized_epoch)
+            .as_u64();
+        if epochs_since_finalization > max_epochs_since_finalization.as_u64() {
+            return Err(DoNotReOrg::ChainNotFinalizing {
+                epochs_since_finalization,
+            }
+            .into());
+        }
+
+        if parent_node.slot + 1 != head_node.slot {
+            return Err(DoNotReOrg::ParentDistance.into());
+        }
+
+        if re_org_block_slot % E::slots_per_epoch() == 0 {
+            return Err(DoNotReOrg::ShufflingUnstable.into());
+        }
+
+        if parent_node.unrealized_justified_checkpoint != head_node.unrealized_justified_checkpoint
+            || parent_node.unrealized_finalized_checkpoint
+                != head_node.unrealized_finalized_checkpoint
+        {
+            return Err(DoNotReOrg::JustificationAndFinalizationNotCompetitive.into());
+        }
+
+        // The head's weight may still include the proposer boost it received in its own slot,
+        // which must not count towards its weight for the purposes of a re-org.
+        let boost = &self.proto_array.previous_proposer_boost;
+        let head_weight = if boost.root == head_node.root {
+            head_node.weight.saturating_sub(boost.score)
+        } else {
+            head_node.weight
+        };
+
+        let re_org_weight_threshold =
+            calculate_committee_fraction::<E>(justified_balances, re_org_threshold.0)
+                .ok_or(Error::ReOrgThresholdOverflow)?;
+
+        Ok(ProposerHeadInfo {
+            head_node,
+            parent_node,
+            head_weight,
+            re_org_weight_threshold,
+        })
+    }
+
     /// For all nodes, regardless of their relationship to the finalized block, set their execution
     /// status to be optimistic.
     ///
@@ -368,9 +588,11 @@ impl ProtoArrayForkChoice {
                             // Compute the score based upon the current balances. We can't rely on
                             // the `previous_proposr_boost.score` since it is set to zero with an
                             // invalid node.
-                            let proposer_score =
-                                calculate_proposer_boost::<E>(&self.balances, proposer_score_boost)
-                                    .ok_or("Failed to compute proposer boost")?;
+                            let proposer_score = calculate_committee_fraction::<E>(
+                                &self.balances,
+                                proposer_score_boost,
+                            )
+                            .ok_or("Failed to compute proposer boost")?;
                             // Store the score we've applied here so it can be removed in
                             // a later call to `apply_score_changes`.
                             self.proto_array.previous_proposer_boost.score = proposer_score;
@@ -511,6 +733,17 @@ impl ProtoArrayForkChoice {
         }
     }
 
+    /// Returns an iterator over the latest message of each validator that has one, as
+    /// `(validator_index, block_root, target_epoch)`.
+    pub fn latest_messages(&self) -> impl Iterator<Item = (usize, Hash256, Epoch)> + '_ {
+        self.votes
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, vote)| **vote != VoteTracker::default())
+            .map(|(validator_index, vote)| (validator_index, vote.next_root, vote.next_epoch))
+    }
+
     /// See `ProtoArray::iter_nodes`
     pub fn iter_nodes<'a>(&'a self, block_root: &Hash256) -> Iter<'a> {
         self.proto_array.iter_nodes(block_root)
@@ -1267,3 +1500,192 @@ mod test_compute_deltas {
         assert_eq!(deltas, vec![0, 0]);
     }
 }
+
+#[cfg(test)]
+mod test_proposer_head {
+    use super::*;
+    use types::{ChainSpec, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 64;
+    const BALANCE: u64 = 32_000_000_000;
+    const RE_ORG_THRESHOLD: ReOrgThreshold = ReOrgThreshold(20);
+    const MAX_EPOCHS_SINCE_FINALIZATION: Epoch = Epoch::new(2);
+
+    fn root(i: u64) -> Hash256 {
+        Hash256::from_low_u64_be(i)
+    }
+
+    fn checkpoint() -> Checkpoint {
+        Checkpoint {
+            epoch: Epoch::new(0),
+            root: root(1),
+        }
+    }
+
+    /// Build a fork choice containing a chain of blocks at `slots`, descending from a genesis
+    /// block with root `1`. The block at `slots[i]` has root `i + 2`.
+    fn fork_choice(slots: &[u64]) -> ProtoArrayForkChoice {
+        let shuffling_id = AttestationShufflingId::from_components(Epoch::new(0), Hash256::zero());
+        let mut fc = ProtoArrayForkChoice::new::<E>(
+            Slot::new(0),
+            Hash256::zero(),
+            checkpoint(),
+            checkpoint(),
+            shuffling_id.clone(),
+            shuffling_id.clone(),
+            ExecutionStatus::irrelevant(),
+        )
+        .unwrap();
+
+        for (i, slot) in slots.iter().enumerate() {
+            let i = i as u64;
+            fc.process_block::<E>(
+                Block {
+                    slot: Slot::new(*slot),
+                    root: root(i + 2),
+                    parent_root: Some(root(i + 1)),
+                    state_root: Hash256::zero(),
+                    target_root: root(1),
+                    current_epoch_shuffling_id: shuffling_id.clone(),
+                    next_epoch_shuffling_id: shuffling_id.clone(),
+                    justified_checkpoint: checkpoint(),
+                    finalized_checkpoint: checkpoint(),
+                    execution_status: ExecutionStatus::irrelevant(),
+                    unrealized_justified_checkpoint: Some(checkpoint()),
+                    unrealized_finalized_checkpoint: Some(checkpoint()),
+                },
+                Slot::new(*slot),
+            )
+            .unwrap();
+        }
+
+        fc
+    }
+
+    /// Apply `votes` votes to `head` and run fork choice at `current_slot`.
+    fn apply_votes(
+        fc: &mut ProtoArrayForkChoice,
+        head: Hash256,
+        votes: usize,
+        proposer_boost_root: Hash256,
+        current_slot: Slot,
+    ) {
+        for validator_index in 0..votes {
+            fc.process_attestation(validator_index, head, Epoch::new(0))
+                .unwrap();
+        }
+        assert_eq!(
+            fc.find_head::<E>(
+                checkpoint(),
+                checkpoint(),
+                &[BALANCE; VALIDATOR_COUNT],
+                proposer_boost_root,
+                &BTreeSet::new(),
+                current_slot,
+                &ChainSpec::minimal(),
+            )
+            .unwrap(),
+            head
+        );
+    }
+
+    fn get_proposer_head(
+        fc: &ProtoArrayForkChoice,
+        current_slot: u64,
+        head: Hash256,
+    ) -> Result<ProposerHeadInfo, ProposerHeadError<Error>> {
+        fc.get_proposer_head::<E>(
+            Slot::new(current_slot),
+            head,
+            &[BALANCE; VALIDATOR_COUNT],
+            RE_ORG_THRESHOLD,
+            MAX_EPOCHS_SINCE_FINALIZATION,
+        )
+    }
+
+    fn do_not_re_org(result: Result<ProposerHeadInfo, ProposerHeadError<Error>>) -> DoNotReOrg {
+        match result {
+            Err(ProposerHeadError::DoNotReOrg(reason)) => reason,
+            other => panic!("expected re-org to be declined, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn re_org_weak_head() {
+        let mut fc = fork_choice(&[1, 2]);
+        let head = root(3);
+        // The head's proposer boost does not count towards its weight.
+        apply_votes(&mut fc, head, 1, head, Slot::new(2));
+
+        let info = get_proposer_head(&fc, 3, head).unwrap();
+        assert_eq!(info.parent_node.root, root(2));
+        assert_eq!(info.head_weight, BALANCE);
+        // 20% of a committee of 8 validators.
+        assert_eq!(info.re_org_weight_threshold, BALANCE * 8 / 5);
+    }
+
+    #[test]
+    fn do_not_re_org_strong_head() {
+        let mut fc = fork_choice(&[1, 2]);
+        let head = root(3);
+        apply_votes(&mut fc, head, 2, Hash256::zero(), Slot::new(2));
+
+        assert_eq!(
+            do_not_re_org(get_proposer_head(&fc, 3, head)),
+            DoNotReOrg::HeadNotWeak {
+                head_weight: 2 * BALANCE,
+                re_org_weight_threshold: BALANCE * 8 / 5,
+            }
+        );
+    }
+
+    #[test]
+    fn do_not_re_org_distant_blocks() {
+        let mut fc = fork_choice(&[1, 2]);
+        apply_votes(&mut fc, root(3), 0, Hash256::zero(), Slot::new(2));
+        assert_eq!(
+            do_not_re_org(get_proposer_head(&fc, 4, root(3))),
+            DoNotReOrg::HeadDistance
+        );
+
+        let mut fc = fork_choice(&[1, 3]);
+        apply_votes(&mut fc, root(3), 0, Hash256::zero(), Slot::new(3));
+        assert_eq!(
+            do_not_re_org(get_proposer_head(&fc, 4, root(3))),
+            DoNotReOrg::ParentDistance
+        );
+    }
+
+    #[test]
+    fn do_not_re_org_at_epoch_boundary() {
+        let slots_per_epoch = E::slots_per_epoch();
+        let mut fc = fork_choice(&[slots_per_epoch - 2, slots_per_epoch - 1]);
+        apply_votes(
+            &mut fc,
+            root(3),
+            0,
+            Hash256::zero(),
+            Slot::new(slots_per_epoch - 1),
+        );
+        assert_eq!(
+            do_not_re_org(get_proposer_head(&fc, slots_per_epoch, root(3))),
+            DoNotReOrg::ShufflingUnstable
+        );
+    }
+
+    #[test]
+    fn do_not_re_org_without_finality() {
+        let slots_per_epoch = E::slots_per_epoch();
+        let slots = [3 * slots_per_epoch + 1, 3 * slots_per_epoch + 2];
+        let mut fc = fork_choice(&slots);
+        apply_votes(&mut fc, root(3), 0, Hash256::zero(), Slot::new(slots[1]));
+        assert_eq!(
+            do_not_re_org(get_proposer_head(&fc, slots[1] + 1, root(3))),
+            DoNotReOrg::ChainNotFinalizing {
+                epochs_since_finalization: 3
+            }
+        );
+    }
+}
@@ -155,6 +155,12 @@ pub mod quoted_u64 {
     define_mod!(u64, visit_u64);
 }
 
+pub mod quoted_i64 {
+    use super::*;
+
+    define_mod!(i64, visit_i64);
+}
+
 pub mod quoted_u256 {
     use super::*;
 
@@ -216,4 +222,17 @@ mod test {
     fn u256_without_quotes() {
         serde_json::from_str::<WrappedU256>("1").unwrap_err();
     }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct WrappedI64(#[serde(with = "quoted_i64")] i64);
+
+    #[test]
+    fn negative_i64_with_quotes() {
+        assert_eq!(&serde_json::to_string(&WrappedI64(-5)).unwrap(), "\"-5\"");
+        assert_eq!(
+            serde_json::from_str::<WrappedI64>("\"-5\"").unwrap(),
+            WrappedI64(-5)
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use integer_sqrt::IntegerSquareRoot;
-use safe_arith::SafeArith;
+use safe_arith::{ArithError, SafeArith};
 use types::*;
 
 /// Returns the base reward for some validator.
@@ -10,10 +10,22 @@ pub fn get_base_reward<T: EthSpec>(
     total_active_balance: u64,
     spec: &ChainSpec,
 ) -> Result<u64, BeaconStateError> {
-    state
-        .get_effective_balance(index)?
+    get_base_reward_from_effective_balance(
+        state.get_effective_balance(index)?,
+        total_active_balance,
+        spec,
+    )
+    .map_err(Into::into)
+}
+
+/// Returns the base reward for a validator with the given `effective_balance`.
+pub fn get_base_reward_from_effective_balance(
+    effective_balance: u64,
+    total_active_balance: u64,
+    spec: &ChainSpec,
+) -> Result<u64, ArithError> {
+    effective_balance
         .safe_mul(spec.base_reward_factor)?
         .safe_div(total_active_balance.integer_sqrt())?
         .safe_div(spec.base_rewards_per_epoch)
-        .map_err(Into::into)
 }
@@ -1,6 +1,5 @@
-use super::{process_registry_updates, process_slashings, EpochProcessingSummary, Error};
+use super::{EpochProcessingSummary, Error};
 use crate::per_epoch_processing::{
-    effective_balance_updates::process_effective_balance_updates,
     historical_roots_update::process_historical_roots_update,
     resets::{process_eth1_data_reset, process_randao_mixes_reset, process_slashings_reset},
 };
@@ -9,6 +8,7 @@ pub use justification_and_finalization::process_justification_and_finalization;
 pub use participation_cache::ParticipationCache;
 pub use participation_flag_updates::process_participation_flag_updates;
 pub use rewards_and_penalties::process_rewards_and_penalties;
+pub use single_pass::{process_epoch_single_pass, SinglePassConfig};
 pub use sync_committee_updates::process_sync_committee_updates;
 use types::{BeaconState, ChainSpec, EthSpec, RelativeEpoch};
 
@@ -17,11 +17,21 @@ pub mod justification_and_finalization;
 pub mod participation_cache;
 pub mod participation_flag_updates;
 pub mod rewards_and_penalties;
+pub mod single_pass;
 pub mod sync_committee_updates;
 
 pub fn process_epoch<T: EthSpec>(
     state: &mut BeaconState<T>,
     spec: &ChainSpec,
+) -> Result<EpochProcessingSummary<T>, Error> {
+    process_epoch_with_config(state, SinglePassConfig::default(), spec)
+}
+
+/// Performs per-epoch processing, dividing the validator registry according to `config`.
+pub fn process_epoch_with_config<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    config: SinglePassConfig,
+    spec: &ChainSpec,
 ) -> Result<EpochProcessingSummary<T>, Error> {
     // Ensure the committee caches are built.
     state.build_committee_cache(RelativeEpoch::Previous, spec)?;
@@ -37,27 +47,13 @@ pub fn process_epoch<T: EthSpec>(
         process_justification_and_finalization(state, &participation_cache)?;
     justification_and_finalization_state.apply_changes_to_state(state);
 
-    process_inactivity_updates(state, &participation_cache, spec)?;
-
-    // Rewards and Penalties.
-    process_rewards_and_penalties(state, &participation_cache, spec)?;
-
-    // Registry Updates.
-    process_registry_updates(state, spec)?;
-
-    // Slashings.
-    process_slashings(
-        state,
-        participation_cache.current_epoch_total_active_balance(),
-        spec,
-    )?;
+    // Inactivity updates, rewards and penalties, registry updates, slashings and effective
+    // balance updates.
+    process_epoch_single_pass(state, &participation_cache, config, spec)?;
 
     // Reset eth1 data votes.
     process_eth1_data_reset(state)?;
 
-    // Update effective balances with hysteresis (lag).
-    process_effective_balance_updates(state, spec)?;
-
     // Reset slashings
     process_slashings_reset(state)?;
 
@@ -0,0 +1,492 @@
+//! Performs the per-validator portion of the Altair epoch transition in a single iteration of the
+//! validator registry.
+//!
+//! The specification applies the following functions one after the other, each of which iterates
+//! the entire registry:
+//!
+//! - `process_inactivity_updates`
+//! - `process_rewards_and_penalties`
+//! - `process_registry_updates`
+//! - `process_slashings`
+//! - `process_effective_balance_updates`
+//!
+//! For each validator, the result of these functions depends only on that validator's own fields
+//! and on values which are fixed prior to the first of them (total balances, participation and
+//! the finalized checkpoint). The exceptions are in `process_registry_updates`:
+//!
+//! - Ejections must be applied in index order, since each exit consumes the exit churn.
+//! - The activation queue is sorted across all validators and truncated to the churn limit.
+//!
+//! Both are collected during the pass and applied sequentially afterwards. An ejection sets the
+//! `withdrawable_epoch` which is read by `process_slashings`, so the slashing and effective balance
+//! updates of ejected validators are deferred until after their exit has been initiated.
+//!
+//! Since validators are otherwise independent the registry may be split into chunks which are
+//! processed in parallel. The result is identical to that of the specification regardless of the
+//! chunking.
+use super::ParticipationCache;
+use crate::common::{altair::BaseRewardPerIncrement, initiate_validator_exit};
+use crate::per_epoch_processing::{
+    altair::{
+        participation_cache::UnslashedParticipatingIndices, rewards_and_penalties::get_flag_weight,
+    },
+    Delta, Error,
+};
+use rayon::prelude::*;
+use safe_arith::{SafeArith, SafeArithIter};
+use std::cmp::{max, min};
+use types::{
+    consts::altair::{
+        NUM_FLAG_INDICES, TIMELY_HEAD_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX, WEIGHT_DENOMINATOR,
+    },
+    BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec, Unsigned, Validator,
+};
+
+/// The default value of `SinglePassConfig::min_chunk_size`.
+///
+/// Below this size the overhead of distributing the work outweighs the benefit.
+pub const DEFAULT_MIN_CHUNK_SIZE: usize = 1_024;
+
+/// Controls which steps `process_epoch_single_pass` applies and how it divides the validator
+/// registry.
+///
+/// Disabling steps is only useful for testing a single step in isolation, e.g. against the
+/// `epoch_processing` consensus spec tests. All steps are enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SinglePassConfig {
+    /// Process chunks of the registry in parallel using the global `rayon` thread pool.
+    pub parallel: bool,
+    /// The minimum number of validators in each chunk when `parallel` is `true`.
+    pub min_chunk_size: usize,
+    pub inactivity_updates: bool,
+    pub rewards_and_penalties: bool,
+    pub registry_updates: bool,
+    pub slashings: bool,
+    pub effective_balance_updates: bool,
+}
+
+impl Default for SinglePassConfig {
+    fn default() -> Self {
+        Self::enable_all()
+    }
+}
+
+impl SinglePassConfig {
+    /// Applies every step, processing the registry in parallel.
+    pub fn enable_all() -> Self {
+        Self {
+            parallel: true,
+            min_chunk_size: DEFAULT_MIN_CHUNK_SIZE,
+            inactivity_updates: true,
+            rewards_and_penalties: true,
+            registry_updates: true,
+            slashings: true,
+            effective_balance_updates: true,
+        }
+    }
+
+    /// Applies no steps, processing the registry in parallel.
+    pub fn disable_all() -> Self {
+        Self {
+            parallel: true,
+            min_chunk_size: DEFAULT_MIN_CHUNK_SIZE,
+            inactivity_updates: false,
+            rewards_and_penalties: false,
+            registry_updates: false,
+            slashings: false,
+            effective_balance_updates: false,
+        }
+    }
+}
+
+/// Values for a single participation flag which are shared by all validators.
+struct FlagContext<'a> {
+    unslashed_participating_indices: UnslashedParticipatingIndices<'a>,
+    weight: u64,
+    unslashed_participating_increments: u64,
+}
+
+/// Values which are computed once, prior to the pass, and shared by all validators.
+struct SinglePassContext<'a> {
+    current_epoch: Epoch,
+    previous_epoch: Epoch,
+    next_epoch: Epoch,
+    finalized_epoch: Epoch,
+    /// Inactivity updates and rewards are skipped during the genesis epoch.
+    is_genesis_epoch: bool,
+    is_in_inactivity_leak: bool,
+    flags: Vec<FlagContext<'a>>,
+    target_attesters: UnslashedParticipatingIndices<'a>,
+    active_increments: u64,
+    base_reward_per_increment: BaseRewardPerIncrement,
+    inactivity_penalty_denominator: u64,
+    slashings_withdrawable_epoch: Epoch,
+    adjusted_total_slashing_balance: u64,
+    total_active_balance: u64,
+    downward_threshold: u64,
+    upward_threshold: u64,
+    config: SinglePassConfig,
+    spec: &'a ChainSpec,
+}
+
+/// The registry updates which must be applied sequentially once the pass is complete.
+#[derive(Default)]
+struct ChunkOutput {
+    /// Validators to eject, in ascending index order.
+    ejections: Vec<usize>,
+    /// `(activation_eligibility_epoch, index)` for each validator eligible for activation.
+    activation_queue: Vec<(Epoch, usize)>,
+}
+
+/// Applies inactivity updates, rewards and penalties, registry updates, slashings and effective
+/// balance updates to `state`, skipping any step which is disabled in `config`.
+///
+/// Equivalent to calling each of the enabled functions in the order given by the specification.
+/// Must be called after justification and finalization has been applied to `state`.
+pub fn process_epoch_single_pass<T: EthSpec>(
+    state: &mut BeaconState<T>,
+    participation_cache: &ParticipationCache,
+    config: SinglePassConfig,
+    spec: &ChainSpec,
+) -> Result<(), Error> {
+    let ctxt = SinglePassContext::new(state, participation_cache, config, spec)?;
+
+    let (validators, balances, inactivity_scores) =
+        state.validators_balances_and_inactivity_scores_mut()?;
+    let num_validators = validators.len();
+    if balances.len() != num_validators || inactivity_scores.len() != num_validators {
+        return Err(Error::InconsistentRegistryLengths {
+            validators: num_validators,
+            balances: balances.len(),
+            inactivity_scores: inactivity_scores.len(),
+        });
+    }
+
+    let outputs = if config.parallel {
+        let chunk_size = max(
+            max(config.min_chunk_size, 1),
+            num_validators
+                .safe_div(rayon::current_num_threads())?
+                .safe_add(1)?,
+        );
+        validators
+            .par_chunks_mut(chunk_size)
+            .zip(balances.par_chunks_mut(chunk_size))
+            .zip(inactivity_scores.par_chunks_mut(chunk_size))
+            .enumerate()
+            .map(|(i, ((validators, balances), inactivity_scores))| {
+                ctxt.process_chunk(
+                    i.safe_mul(chunk_size)?,
+                    validators,
+                    balances,
+                    inactivity_scores,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        vec![ctxt.process_chunk(0, validators, balances, inactivity_scores)?]
+    };
+
+    // Initiate the exits of ejected validators in index order, then apply the updates which were
+    // deferred until after their `withdrawable_epoch` was set.
+    for &index in outputs.iter().flat_map(|output| &output.ejections) {
+        initiate_validator_exit(state, index, spec)?;
+
+        let (validators, balances) = state.validators_and_balances_mut();
+        let validator = validators
+            .get_mut(index)
+            .ok_or(BeaconStateError::UnknownValidator(index))?;
+        let balance = balances
+            .get_mut(index)
+            .ok_or(BeaconStateError::BalancesOutOfBounds(index))?;
+        ctxt.process_slashing(validator, balance)?;
+        ctxt.process_effective_balance_update(validator, *balance)?;
+    }
+
+    if !config.registry_updates {
+        return Ok(());
+    }
+
+    // Queue validators eligible for activation and not dequeued for activation prior to finalized
+    // epoch.
+    let mut activation_queue = outputs
+        .into_iter()
+        .flat_map(|output| output.activation_queue)
+        .collect::<Vec<_>>();
+    activation_queue.sort_unstable();
+
+    // Dequeue validators for activation up to churn limit.
+    let churn_limit = state.get_churn_limit(spec)? as usize;
+    let delayed_activation_epoch = state.compute_activation_exit_epoch(ctxt.current_epoch, spec)?;
+    for (_, index) in activation_queue.into_iter().take(churn_limit) {
+        state.get_validator_mut(index)?.activation_epoch = delayed_activation_epoch;
+    }
+
+    Ok(())
+}
+
+impl<'a> SinglePassContext<'a> {
+    fn new<T: EthSpec>(
+        state: &BeaconState<T>,
+        participation_cache: &'a ParticipationCache,
+        config: SinglePassConfig,
+        spec: &'a ChainSpec,
+    ) -> Result<Self, Error> {
+        let current_epoch = state.current_epoch();
+        let previous_epoch = state.previous_epoch();
+        let total_active_balance = participation_cache.current_epoch_total_active_balance();
+
+        let flags = (0..NUM_FLAG_INDICES)
+            .map(|flag_index| {
+                let unslashed_participating_indices = participation_cache
+                    .get_unslashed_participating_indices(flag_index, previous_epoch)?;
+                let unslashed_participating_increments = unslashed_participating_indices
+                    .total_balance()?
+                    .safe_div(spec.effective_balance_increment)?;
+                Ok(FlagContext {
+                    unslashed_participating_indices,
+                    weight: get_flag_weight(flag_index)?,
+                    unslashed_participating_increments,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let sum_slashings = state.get_all_slashings().iter().copied().safe_sum()?;
+        let adjusted_total_slashing_balance = min(
+            sum_slashings.safe_mul(spec.proportional_slashing_multiplier_for_state(state))?,
+            total_active_balance,
+        );
+
+        let hysteresis_increment = spec
+            .effective_balance_increment
+            .safe_div(spec.hysteresis_quotient)?;
+
+        Ok(Self {
+            current_epoch,
+            previous_epoch,
+            next_epoch: current_epoch.safe_add(1)?,
+            finalized_epoch: state.finalized_checkpoint().epoch,
+            is_genesis_epoch: current_epoch == T::genesis_epoch(),
+            is_in_inactivity_leak: state.is_in_inactivity_leak(previous_epoch, spec),
+            flags,
+            target_attesters: participation_cache
+                .get_unslashed_participating_indices(TIMELY_TARGET_FLAG_INDEX, previous_epoch)?,
+            active_increments: total_active_balance.safe_div(spec.effective_balance_increment)?,
+            base_reward_per_increment: BaseRewardPerIncrement::new(total_active_balance, spec)?,
+            inactivity_penalty_denominator: spec
+                .inactivity_score_bias
+                .safe_mul(spec.inactivity_penalty_quotient_for_state(state))?,
+            slashings_withdrawable_epoch: current_epoch
+                .safe_add(T::EpochsPerSlashingsVector::to_u64().safe_div(2)?)?,
+            adjusted_total_slashing_balance,
+            total_active_balance,
+            downward_threshold: hysteresis_increment
+                .safe_mul(spec.hysteresis_downward_multiplier)?,
+            upward_threshold: hysteresis_increment.safe_mul(spec.hysteresis_upward_multiplier)?,
+            config,
+            spec,
+        })
+    }
+
+    /// Processes a contiguous chunk of the registry starting at validator index `offset`.
+    fn process_chunk(
+        &self,
+        offset: usize,
+        validators: &mut [Validator],
+        balances: &mut [u64],
+        inactivity_scores: &mut [u64],
+    ) -> Result<ChunkOutput, Error> {
+        let mut output = ChunkOutput::default();
+
+        for (i, ((validator, balance), inactivity_score)) in validators
+            .iter_mut()
+            .zip(balances.iter_mut())
+            .zip(inactivity_scores.iter_mut())
+            .enumerate()
+        {
+            self.process_validator(
+                offset.safe_add(i)?,
+                validator,
+                balance,
+                inactivity_score,
+                &mut output,
+            )?;
+        }
+
+        Ok(output)
+    }
+
+    fn process_validator(
+        &self,
+        index: usize,
+        validator: &mut Validator,
+        balance: &mut u64,
+        inactivity_score: &mut u64,
+        output: &mut ChunkOutput,
+    ) -> Result<(), Error> {
+        let spec = self.spec;
+
+        // Equivalent to `BeaconState::is_eligible_validator`.
+        let is_eligible = validator.is_active_at(self.previous_epoch)
+            || (validator.slashed
+                && self.previous_epoch.safe_add(1)? < validator.withdrawable_epoch);
+
+        if is_eligible && !self.is_genesis_epoch {
+            let is_target_attester = self.target_attesters.contains(index)?;
+
+            if self.config.inactivity_updates {
+                self.process_inactivity_update(is_target_attester, inactivity_score)?;
+            }
+            if self.config.rewards_and_penalties {
+                self.process_rewards_and_penalties(
+                    index,
+                    is_target_attester,
+                    validator,
+                    balance,
+                    *inactivity_score,
+                )?;
+            }
+        }
+
+        // Registry updates.
+        let mut is_ejected = false;
+        if self.config.registry_updates {
+            if validator.is_eligible_for_activation_queue(spec) {
+                validator.activation_eligibility_epoch = self.next_epoch;
+            }
+            is_ejected = validator.is_active_at(self.current_epoch)
+                && validator.effective_balance <= spec.ejection_balance
+                && validator.exit_epoch == spec.far_future_epoch;
+            if is_ejected {
+                output.ejections.push(index);
+            }
+            if validator.activation_eligibility_epoch <= self.finalized_epoch
+                && validator.activation_epoch == spec.far_future_epoch
+            {
+                output
+                    .activation_queue
+                    .push((validator.activation_eligibility_epoch, index));
+            }
+        }
+
+        // Slashings and effective balance updates, unless deferred until after the ejection.
+        if !is_ejected {
+            self.process_slashing(validator, balance)?;
+            self.process_effective_balance_update(validator, *balance)?;
+        }
+
+        Ok(())
+    }
+
+    /// Equivalent to the per-validator portion of `process_inactivity_updates`.
+    fn process_inactivity_update(
+        &self,
+        is_target_attester: bool,
+        inactivity_score: &mut u64,
+    ) -> Result<(), Error> {
+        let spec = self.spec;
+
+        if is_target_attester {
+            inactivity_score.safe_sub_assign(min(1, *inactivity_score))?;
+        } else {
+            inactivity_score.safe_add_assign(spec.inactivity_score_bias)?;
+        }
+        if !self.is_in_inactivity_leak {
+            inactivity_score
+                .safe_sub_assign(min(spec.inactivity_score_recovery_rate, *inactivity_score))?;
+        }
+
+        Ok(())
+    }
+
+    /// Equivalent to the per-validator portion of `process_rewards_and_penalties`.
+    fn process_rewards_and_penalties(
+        &self,
+        index: usize,
+        is_target_attester: bool,
+        validator: &Validator,
+        balance: &mut u64,
+        inactivity_score: u64,
+    ) -> Result<(), Error> {
+        let spec = self.spec;
+
+        let base_reward = validator
+            .effective_balance
+            .safe_div(spec.effective_balance_increment)?
+            .safe_mul(self.base_reward_per_increment.as_u64())?;
+        let mut delta = Delta::default();
+
+        for (flag_index, flag) in self.flags.iter().enumerate() {
+            if flag.unslashed_participating_indices.contains(index)? {
+                if !self.is_in_inactivity_leak {
+                    let reward_numerator = base_reward
+                        .safe_mul(flag.weight)?
+                        .safe_mul(flag.unslashed_participating_increments)?;
+                    delta.reward(
+                        reward_numerator
+                            .safe_div(self.active_increments.safe_mul(WEIGHT_DENOMINATOR)?)?,
+                    )?;
+                }
+            } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
+                delta.penalize(
+                    base_reward
+                        .safe_mul(flag.weight)?
+                        .safe_div(WEIGHT_DENOMINATOR)?,
+                )?;
+            }
+        }
+
+        if !is_target_attester {
+            let penalty_numerator = validator.effective_balance.safe_mul(inactivity_score)?;
+            delta.penalize(penalty_numerator.safe_div(self.inactivity_penalty_denominator)?)?;
+        }
+
+        // Equivalent to `increase_balance` followed by `decrease_balance`.
+        balance.safe_add_assign(delta.rewards)?;
+        *balance = balance.saturating_sub(delta.penalties);
+
+        Ok(())
+    }
+
+    /// Equivalent to the per-validator portion of `process_slashings`.
+    fn process_slashing(&self, validator: &Validator, balance: &mut u64) -> Result<(), Error> {
+        if self.config.slashings
+            && validator.slashed
+            && self.slashings_withdrawable_epoch == validator.withdrawable_epoch
+        {
+            let increment = self.spec.effective_balance_increment;
+            let penalty_numerator = validator
+                .effective_balance
+                .safe_div(increment)?
+                .safe_mul(self.adjusted_total_slashing_balance)?;
+            let penalty = penalty_numerator
+                .safe_div(self.total_active_balance)?
+                .safe_mul(increment)?;
+            *balance = balance.saturating_sub(penalty);
+        }
+        Ok(())
+    }
+
+    /// Equivalent to the per-validator portion of `process_effective_balance_updates`.
+    fn process_effective_balance_update(
+        &self,
+        validator: &mut Validator,
+        balance: u64,
+    ) -> Result<(), Error> {
+        if !self.config.effective_balance_updates {
+            return Ok(());
+        }
+        if balance.safe_add(self.downward_threshold)? < validator.effective_balance
+            || validator
+                .effective_balance
+                .safe_add(self.upward_threshold)?
+                < balance
+        {
+            validator.effective_balance = min(
+                balance.safe_sub(balance.safe_rem(self.spec.effective_balance_increment)?)?,
+                self.spec.max_effective_balance,
+            );
+        }
+        Ok(())
+    }
+}
@@ -216,7 +216,10 @@ fn get_head_delta(
     )
 }
 
-fn get_inclusion_delay_delta(
+/// Return the inclusion delay reward for `validator`, along with the proposer's share of it.
+///
+/// The proposer's share is returned as `(proposer_index, proposer_delta)`.
+pub fn get_inclusion_delay_delta(
     validator: &ValidatorStatus,
     base_reward: u64,
     spec: &ChainSpec,
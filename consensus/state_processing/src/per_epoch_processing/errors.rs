@@ -25,6 +25,11 @@ pub enum EpochProcessingError {
     InvalidJustificationBit(ssz_types::Error),
     InvalidFlagIndex(usize),
     ParticipationCache(ParticipationCacheError),
+    InconsistentRegistryLengths {
+        validators: usize,
+        balances: usize,
+        inactivity_scores: usize,
+    },
 }
 
 impl From<InclusionError> for EpochProcessingError {
@@ -1,10 +1,19 @@
 #![cfg(test)]
-use crate::per_epoch_processing::process_epoch;
-use beacon_chain::test_utils::BeaconChainHarness;
+use crate::per_epoch_processing::{
+    altair::{
+        process_epoch_single_pass, process_inactivity_updates,
+        process_justification_and_finalization, process_rewards_and_penalties, ParticipationCache,
+        SinglePassConfig,
+    },
+    effective_balance_updates::process_effective_balance_updates,
+    process_epoch, process_registry_updates, process_slashings,
+};
+use crate::EpochProcessingError;
+use beacon_chain::test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy};
 use beacon_chain::types::{EthSpec, MinimalEthSpec};
 use bls::Hash256;
 use env_logger::{Builder, Env};
-use types::Slot;
+use types::{BeaconState, ChainSpec, Epoch, ParticipationFlags, Slot, Unsigned, Validator};
 
 #[tokio::test]
 async fn runs_without_error() {
@@ -38,14 +47,174 @@ async fn runs_without_error() {
     process_epoch(&mut new_head_state, &spec).unwrap();
 }
 
+/// Applies the passes which are combined by `process_epoch_single_pass` one after the other, in
+/// the order given by the specification.
+fn process_epoch_multi_pass<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<(), EpochProcessingError> {
+    let participation_cache = ParticipationCache::new(state, spec)?;
+    process_justification_and_finalization(state, &participation_cache)?
+        .apply_changes_to_state(state);
+    process_inactivity_updates(state, &participation_cache, spec)?;
+    process_rewards_and_penalties(state, &participation_cache, spec)?;
+    process_registry_updates(state, spec)?;
+    process_slashings(
+        state,
+        participation_cache.current_epoch_total_active_balance(),
+        spec,
+    )?;
+    process_effective_balance_updates(state, spec)
+}
+
+fn process_epoch_with_single_pass<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    config: SinglePassConfig,
+    spec: &ChainSpec,
+) -> Result<(), EpochProcessingError> {
+    let participation_cache = ParticipationCache::new(state, spec)?;
+    process_justification_and_finalization(state, &participation_cache)?
+        .apply_changes_to_state(state);
+    process_epoch_single_pass(state, &participation_cache, config, spec)
+}
+
+#[tokio::test]
+async fn single_pass_matches_multi_pass() {
+    type E = MinimalEthSpec;
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    let slots_per_epoch = E::slots_per_epoch();
+
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec.clone())
+        .deterministic_keypairs(16)
+        .fresh_ephemeral_store()
+        .build();
+    harness.advance_slot();
+    harness
+        .extend_chain(
+            (slots_per_epoch * 4 - 1) as usize,
+            BlockStrategy::OnCanonicalHead,
+            // Leave some validators inactive so that they incur penalties.
+            AttestationStrategy::SomeValidators((0..12).collect()),
+        )
+        .await;
+
+    let mut state = harness.get_current_state();
+    assert_eq!(
+        state.slot(),
+        state.current_epoch().end_slot(slots_per_epoch)
+    );
+    state.build_all_committee_caches(&spec).unwrap();
+    let current_epoch = state.current_epoch();
+
+    // A validator which will be ejected.
+    state.get_validator_mut(0).unwrap().effective_balance = spec.ejection_balance;
+
+    // A slashed validator which will receive the slashing penalty.
+    let slashed = state.get_validator_mut(1).unwrap();
+    slashed.slashed = true;
+    slashed.exit_epoch = current_epoch + 1;
+    slashed.withdrawable_epoch =
+        current_epoch + <E as EthSpec>::EpochsPerSlashingsVector::to_u64() / 2;
+    state
+        .set_slashings(current_epoch, spec.max_effective_balance)
+        .unwrap();
+
+    // Validators which are pending activation, or which are yet to become eligible for it.
+    for activation_eligibility_epoch in [Epoch::new(0), spec.far_future_epoch] {
+        let validator = Validator {
+            activation_eligibility_epoch,
+            activation_epoch: spec.far_future_epoch,
+            exit_epoch: spec.far_future_epoch,
+            withdrawable_epoch: spec.far_future_epoch,
+            effective_balance: spec.max_effective_balance,
+            ..Validator::default()
+        };
+        state.validators_mut().push(validator).unwrap();
+        state
+            .balances_mut()
+            .push(spec.max_effective_balance)
+            .unwrap();
+        state.inactivity_scores_mut().unwrap().push(0).unwrap();
+        state
+            .previous_epoch_participation_mut()
+            .unwrap()
+            .push(ParticipationFlags::default())
+            .unwrap();
+        state
+            .current_epoch_participation_mut()
+            .unwrap()
+            .push(ParticipationFlags::default())
+            .unwrap();
+    }
+    let num_validators = state.validators().len();
+
+    let mut expected = state.clone();
+    process_epoch_multi_pass(&mut expected, &spec).unwrap();
+
+    assert_ne!(
+        expected.get_validator(0).unwrap().exit_epoch,
+        spec.far_future_epoch
+    );
+    assert_ne!(
+        expected
+            .get_validator(num_validators - 2)
+            .unwrap()
+            .activation_epoch,
+        spec.far_future_epoch
+    );
+    assert_eq!(
+        expected
+            .get_validator(num_validators - 1)
+            .unwrap()
+            .activation_eligibility_epoch,
+        current_epoch + 1
+    );
+
+    let configs = [
+        SinglePassConfig {
+            parallel: false,
+            ..SinglePassConfig::default()
+        },
+        SinglePassConfig::default(),
+        SinglePassConfig {
+            parallel: true,
+            min_chunk_size: 1,
+            ..SinglePassConfig::default()
+        },
+    ];
+    for config in configs {
+        let mut single_pass = state.clone();
+        process_epoch_with_single_pass(&mut single_pass, config, &spec).unwrap();
+
+        assert_eq!(
+            single_pass.validators(),
+            expected.validators(),
+            "{:?}",
+            config
+        );
+        assert_eq!(single_pass.balances(), expected.balances(), "{:?}", config);
+        assert_eq!(
+            single_pass.inactivity_scores().unwrap(),
+            expected.inactivity_scores().unwrap(),
+            "{:?}",
+            config
+        );
+        assert_eq!(
+            single_pass.canonical_root(),
+            expected.canonical_root(),
+            "{:?}",
+            config
+        );
+    }
+}
+
 #[cfg(not(debug_assertions))]
 mod release_tests {
     use super::*;
-    use crate::{
-        per_slot_processing::per_slot_processing, EpochProcessingError, SlotProcessingError,
-    };
-    use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
-    use types::{Epoch, ForkName, InconsistentFork, MainnetEthSpec};
+    use crate::{per_slot_processing::per_slot_processing, SlotProcessingError};
+    use types::{ForkName, InconsistentFork, MainnetEthSpec};
 
     #[tokio::test]
     async fn altair_state_on_base_fork() {
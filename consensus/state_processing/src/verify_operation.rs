@@ -2,9 +2,14 @@ use crate::per_block_processing::{
     errors::{
         AttesterSlashingValidationError, ExitValidationError, ProposerSlashingValidationError,
     },
+    signature_sets::{
+        attester_slashing_signature_sets, exit_signature_set, get_pubkey_from_state,
+        proposer_slashing_signature_set, Error as SignatureSetError,
+    },
     verify_attester_slashing, verify_exit, verify_proposer_slashing,
 };
 use crate::VerifySignatures;
+use bls::SignatureSet;
 use types::{
     AttesterSlashing, BeaconState, ChainSpec, EthSpec, ProposerSlashing, SignedVoluntaryExit,
 };
@@ -35,6 +40,23 @@ pub trait VerifyOperation<E: EthSpec>: Sized {
         state: &BeaconState<E>,
         spec: &ChainSpec,
     ) -> Result<SigVerifiedOp<Self>, Self::Error>;
+
+    /// Performs the same checks as `Self::validate`, except for signature verification.
+    ///
+    /// The caller must have already verified the sets returned by `Self::signature_sets` (e.g.,
+    /// as part of a batch), otherwise an invalid operation may be wrapped in a `SigVerifiedOp`.
+    fn validate_with_verified_signatures(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self>, Self::Error>;
+
+    /// Returns the signature sets which must be valid for `self` to be valid.
+    fn signature_sets<'a>(
+        &'a self,
+        state: &'a BeaconState<E>,
+        spec: &'a ChainSpec,
+    ) -> Result<Vec<SignatureSet<'a>>, SignatureSetError>;
 }
 
 impl<E: EthSpec> VerifyOperation<E> for SignedVoluntaryExit {
@@ -48,6 +70,28 @@ impl<E: EthSpec> VerifyOperation<E> for SignedVoluntaryExit {
         verify_exit(state, &self, VerifySignatures::True, spec)?;
         Ok(SigVerifiedOp(self))
     }
+
+    fn validate_with_verified_signatures(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self>, Self::Error> {
+        verify_exit(state, &self, VerifySignatures::False, spec)?;
+        Ok(SigVerifiedOp(self))
+    }
+
+    fn signature_sets<'a>(
+        &'a self,
+        state: &'a BeaconState<E>,
+        spec: &'a ChainSpec,
+    ) -> Result<Vec<SignatureSet<'a>>, SignatureSetError> {
+        Ok(vec![exit_signature_set(
+            state,
+            |i| get_pubkey_from_state(state, i),
+            self,
+            spec,
+        )?])
+    }
 }
 
 impl<E: EthSpec> VerifyOperation<E> for AttesterSlashing<E> {
@@ -61,6 +105,29 @@ impl<E: EthSpec> VerifyOperation<E> for AttesterSlashing<E> {
         verify_attester_slashing(state, &self, VerifySignatures::True, spec)?;
         Ok(SigVerifiedOp(self))
     }
+
+    fn validate_with_verified_signatures(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self>, Self::Error> {
+        verify_attester_slashing(state, &self, VerifySignatures::False, spec)?;
+        Ok(SigVerifiedOp(self))
+    }
+
+    fn signature_sets<'a>(
+        &'a self,
+        state: &'a BeaconState<E>,
+        spec: &'a ChainSpec,
+    ) -> Result<Vec<SignatureSet<'a>>, SignatureSetError> {
+        let (set_1, set_2) = attester_slashing_signature_sets(
+            state,
+            |i| get_pubkey_from_state(state, i),
+            self,
+            spec,
+        )?;
+        Ok(vec![set_1, set_2])
+    }
 }
 
 impl<E: EthSpec> VerifyOperation<E> for ProposerSlashing {
@@ -74,4 +141,27 @@ impl<E: EthSpec> VerifyOperation<E> for ProposerSlashing {
         verify_proposer_slashing(&self, state, VerifySignatures::True, spec)?;
         Ok(SigVerifiedOp(self))
     }
+
+    fn validate_with_verified_signatures(
+        self,
+        state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<SigVerifiedOp<Self>, Self::Error> {
+        verify_proposer_slashing(&self, state, VerifySignatures::False, spec)?;
+        Ok(SigVerifiedOp(self))
+    }
+
+    fn signature_sets<'a>(
+        &'a self,
+        state: &'a BeaconState<E>,
+        spec: &'a ChainSpec,
+    ) -> Result<Vec<SignatureSet<'a>>, SignatureSetError> {
+        let (set_1, set_2) = proposer_slashing_signature_set(
+            state,
+            |i| get_pubkey_from_state(state, i),
+            self,
+            spec,
+        )?;
+        Ok(vec![set_1, set_2])
+    }
 }
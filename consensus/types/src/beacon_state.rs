@@ -1105,6 +1105,28 @@ impl<T: EthSpec> BeaconState<T> {
         }
     }
 
+    /// Convenience accessor for validators, balances and inactivity scores simultaneously.
+    ///
+    /// Will error if the state lacks an `inactivity_scores` field.
+    #[allow(clippy::type_complexity)]
+    pub fn validators_balances_and_inactivity_scores_mut(
+        &mut self,
+    ) -> Result<(&mut [Validator], &mut [u64], &mut [u64]), Error> {
+        match self {
+            BeaconState::Base(_) => Err(Error::IncorrectStateVariant),
+            BeaconState::Altair(state) => Ok((
+                &mut state.validators,
+                &mut state.balances,
+                &mut state.inactivity_scores,
+            )),
+            BeaconState::Merge(state) => Ok((
+                &mut state.validators,
+                &mut state.balances,
+                &mut state.inactivity_scores,
+            )),
+        }
+    }
+
     /// Generate a seed for the given `epoch`.
     pub fn get_seed(
         &self,
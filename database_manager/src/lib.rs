@@ -1,15 +1,24 @@
 use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, schema_change::migrate_schema,
-    slot_clock::SystemTimeSlotClock,
+    slot_clock::SystemTimeSlotClock, verify_head_tracker,
 };
 use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
 use clap::{App, Arg, ArgMatches};
 use environment::{Environment, RuntimeContext};
-use slog::{info, Logger};
+use slog::{info, warn, Logger};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
 use store::{
+    backup,
+    config::OnDiskStoreConfig,
     errors::Error,
-    metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    metadata::{
+        RestorePointChange, SchemaVersion, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
+        RESTORE_POINT_CHANGE_KEY,
+    },
+    verify::Check,
+    DBColumn, HotColdDB, ItemStore, KeyValueStore, LevelDB,
 };
 use strum::{EnumString, EnumVariantNames, VariantNames};
 use types::EthSpec;
@@ -59,6 +68,109 @@ pub fn inspect_cli_app<'a, 'b>() -> App<'a, 'b> {
         )
 }
 
+pub fn prune_payloads_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("prune-payloads")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about("Delete the execution payloads of all finalized blocks")
+}
+
+pub fn set_slots_per_restore_point_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("set-slots-per-restore-point")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Rewrite the freezer database's restore points with a new spacing. The beacon node \
+             must be started with the new --slots-per-restore-point value afterwards.",
+        )
+        .arg(
+            Arg::with_name("to")
+                .long("to")
+                .value_name("SLOT_COUNT")
+                .help("New number of slots between restore points")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn prune_states_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("prune-states")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Delete all historic states from the freezer database, except for the genesis \
+             state. Blocks are retained, so the states can be re-created later using \
+             --reconstruct-historic-states.",
+        )
+        .arg(
+            Arg::with_name("confirm")
+                .long("confirm")
+                .help("Delete the states. Without this flag no changes are made.")
+                .takes_value(false),
+        )
+}
+
+pub fn backup_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("backup")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Write a backup of the database to a directory. To back up a running beacon node use \
+             the /lighthouse/database/backup HTTP API endpoint instead.",
+        )
+        .arg(
+            Arg::with_name("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help("Directory to write the backup to. Must not exist or be empty.")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn restore_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("restore")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Restore a database backup. The existing database must be moved or deleted first. \
+             The restored database is verified when the beacon node next starts.",
+        )
+        .arg(
+            Arg::with_name("backup-dir")
+                .long("backup-dir")
+                .value_name("DIR")
+                .help("Directory containing the backup to restore")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+fn checks_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("checks")
+        .long("checks")
+        .value_name("CHECKS")
+        .help("Comma-separated list of checks to run [default: all]")
+        .takes_value(true)
+        .use_delimiter(true)
+        .possible_values(Check::VARIANTS)
+}
+
+pub fn verify_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("verify")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Check the database for inconsistencies without modifying it. A JSON report is \
+             printed for each check.",
+        )
+        .arg(checks_arg())
+}
+
+pub fn repair_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new("repair")
+        .setting(clap::AppSettings::ColoredHelp)
+        .about(
+            "Check the database for inconsistencies and fix those that can be fixed without \
+             re-syncing. A JSON report is printed for each check.",
+        )
+        .arg(checks_arg())
+}
+
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
         .visible_aliases(&["db"])
@@ -85,6 +197,13 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .subcommand(migrate_cli_app())
         .subcommand(version_cli_app())
         .subcommand(inspect_cli_app())
+        .subcommand(prune_payloads_cli_app())
+        .subcommand(set_slots_per_restore_point_cli_app())
+        .subcommand(prune_states_cli_app())
+        .subcommand(backup_cli_app())
+        .subcommand(restore_cli_app())
+        .subcommand(verify_cli_app())
+        .subcommand(repair_cli_app())
 }
 
 fn parse_client_config<E: EthSpec>(
@@ -257,6 +376,233 @@ pub fn migrate_db<E: EthSpec>(
     )
 }
 
+pub fn prune_payloads<E: EthSpec>(
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let db = open_with_on_disk_config(client_config, runtime_context, log.clone())?;
+
+    // If the prune is triggered manually then ignore the check on already-pruned payloads, so
+    // that payloads left behind by an interrupted prune are also deleted.
+    let num_pruned = db.try_prune_execution_payloads(true)?;
+    info!(log, "Pruned execution payloads"; "count" => num_pruned);
+
+    db.compact()?;
+    info!(log, "Database compaction complete");
+
+    Ok(())
+}
+
+/// Open the database using the slots-per-restore-point value stored on disk, so that it can be
+/// modified regardless of the `--slots-per-restore-point` flag.
+///
+/// If an interrupted change of the value will be completed when the database is opened, the new
+/// value is used.
+fn open_with_on_disk_config<E: EthSpec>(
+    mut client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>, Error> {
+    let spec = &runtime_context.eth2_config.spec;
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+
+    let on_disk_config = LevelDB::<E>::open(&hot_path)?.get::<OnDiskStoreConfig>(&CONFIG_KEY)?;
+    let restore_point_change = LevelDB::<E>::open(&cold_path)?
+        .get::<RestorePointChange>(&RESTORE_POINT_CHANGE_KEY)?
+        .filter(|change| change.cold_db_rewritten);
+    let on_disk_slots_per_restore_point = restore_point_change
+        .map(|change| change.slots_per_restore_point)
+        .or_else(|| on_disk_config.map(|config| config.slots_per_restore_point));
+    if let Some(slots_per_restore_point) = on_disk_slots_per_restore_point {
+        client_config.store.slots_per_restore_point = slots_per_restore_point;
+        client_config.store.slots_per_restore_point_set_explicitly = true;
+    }
+
+    HotColdDB::open(
+        &hot_path,
+        &cold_path,
+        |_, _, _| Ok(()),
+        client_config.store,
+        spec.clone(),
+        log,
+    )
+}
+
+pub struct SetSlotsPerRestorePointConfig {
+    to: u64,
+}
+
+fn parse_set_slots_per_restore_point_config(
+    cli_args: &ArgMatches,
+) -> Result<SetSlotsPerRestorePointConfig, String> {
+    let to = clap_utils::parse_required(cli_args, "to")?;
+
+    Ok(SetSlotsPerRestorePointConfig { to })
+}
+
+pub fn set_slots_per_restore_point<E: EthSpec>(
+    set_config: SetSlotsPerRestorePointConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let db = open_with_on_disk_config(client_config, runtime_context, log.clone())?;
+
+    db.change_slots_per_restore_point(set_config.to)?;
+
+    info!(
+        log,
+        "Start the beacon node with the new value";
+        "flag" => format!("--slots-per-restore-point {}", set_config.to),
+    );
+    Ok(())
+}
+
+pub struct PruneStatesConfig {
+    confirm: bool,
+}
+
+fn parse_prune_states_config(cli_args: &ArgMatches) -> PruneStatesConfig {
+    PruneStatesConfig {
+        confirm: cli_args.is_present("confirm"),
+    }
+}
+
+pub fn prune_states<E: EthSpec>(
+    prune_config: PruneStatesConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let db = open_with_on_disk_config(client_config, runtime_context, log.clone())?;
+
+    if !prune_config.confirm {
+        info!(
+            log,
+            "Historic states would be pruned";
+            "split_slot" => db.get_split_slot(),
+            "info" => "re-run with --confirm to delete the states",
+        );
+        return Ok(());
+    }
+
+    db.prune_historic_states()
+}
+
+pub struct BackupConfig {
+    output_dir: PathBuf,
+}
+
+fn parse_backup_config(cli_args: &ArgMatches) -> Result<BackupConfig, String> {
+    let output_dir = clap_utils::parse_required(cli_args, "output-dir")?;
+
+    Ok(BackupConfig { output_dir })
+}
+
+pub fn backup_db<E: EthSpec>(
+    backup_config: BackupConfig,
+    client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    let db = open_with_on_disk_config(client_config, runtime_context, log)?;
+
+    db.backup(&backup_config.output_dir)?;
+    Ok(())
+}
+
+pub struct RestoreConfig {
+    backup_dir: PathBuf,
+}
+
+fn parse_restore_config(cli_args: &ArgMatches) -> Result<RestoreConfig, String> {
+    let backup_dir = clap_utils::parse_required(cli_args, "backup-dir")?;
+
+    Ok(RestoreConfig { backup_dir })
+}
+
+pub fn restore_db<E: EthSpec>(
+    restore_config: RestoreConfig,
+    client_config: ClientConfig,
+    log: Logger,
+) -> Result<(), Error> {
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+
+    backup::restore_backup::<E>(&restore_config.backup_dir, &hot_path, &cold_path, &log)?;
+    Ok(())
+}
+
+pub struct VerifyConfig {
+    checks: Vec<Check>,
+    repair: bool,
+}
+
+fn parse_verify_config(cli_args: &ArgMatches, repair: bool) -> Result<VerifyConfig, String> {
+    let checks = match cli_args.values_of("checks") {
+        Some(values) => {
+            let selected = values
+                .map(|value| {
+                    Check::from_str(value).map_err(|e| format!("Invalid check {}: {}", value, e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Check::ALL
+                .into_iter()
+                .filter(|check| selected.contains(check))
+                .collect()
+        }
+        None => Check::ALL.to_vec(),
+    };
+
+    Ok(VerifyConfig { checks, repair })
+}
+
+pub fn verify_db<E: EthSpec>(
+    verify_config: VerifyConfig,
+    mut client_config: ClientConfig,
+    runtime_context: &RuntimeContext<E>,
+    log: Logger,
+) -> Result<(), Error> {
+    // Leave temporary states in place so that they can be reported.
+    client_config.store.garbage_collect_on_init = false;
+    let db = open_with_on_disk_config(client_config, runtime_context, log.clone())?;
+    let repair = verify_config.repair;
+
+    let mut failed_checks = vec![];
+    for check in verify_config.checks {
+        let report = match check {
+            Check::OrphanedStates => db.verify_orphaned_states(repair)?,
+            Check::HotStateSummaries => db.verify_hot_state_summaries(repair)?,
+            Check::HeadTracker => verify_head_tracker(&db, repair)?,
+            Check::FreezerChunks => db.verify_freezer_chunks()?,
+            Check::RootContinuity => db.verify_root_continuity(repair)?,
+        };
+
+        let json = serde_json::to_string(&report).map_err(|e| Error::DBError {
+            message: format!("Unable to serialize report: {}", e),
+        })?;
+        println!("{}", json);
+
+        if !report.passed {
+            failed_checks.push(check);
+        }
+    }
+
+    if !failed_checks.is_empty() {
+        warn!(
+            log,
+            "Database verification failed";
+            "failed_checks" => ?failed_checks,
+        );
+        return Err(Error::VerificationFailed(failed_checks));
+    }
+
+    info!(log, "Database verification passed"; "repair" => repair);
+    Ok(())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<T: EthSpec>(cli_args: &ArgMatches<'_>, mut env: Environment<T>) -> Result<(), String> {
     let client_config = parse_client_config(cli_args, &env)?;
@@ -273,6 +619,31 @@ pub fn run<T: EthSpec>(cli_args: &ArgMatches<'_>, mut env: Environment<T>) -> Re
             let inspect_config = parse_inspect_config(cli_args)?;
             inspect_db(inspect_config, client_config, &context, log)
         }
+        ("prune-payloads", Some(_)) => prune_payloads(client_config, &context, log),
+        ("set-slots-per-restore-point", Some(cli_args)) => {
+            let set_config = parse_set_slots_per_restore_point_config(cli_args)?;
+            set_slots_per_restore_point(set_config, client_config, &context, log)
+        }
+        ("prune-states", Some(cli_args)) => {
+            let prune_config = parse_prune_states_config(cli_args);
+            prune_states(prune_config, client_config, &context, log)
+        }
+        ("backup", Some(cli_args)) => {
+            let backup_config = parse_backup_config(cli_args)?;
+            backup_db(backup_config, client_config, &context, log)
+        }
+        ("restore", Some(cli_args)) => {
+            let restore_config = parse_restore_config(cli_args)?;
+            restore_db::<T>(restore_config, client_config, log)
+        }
+        ("verify", Some(cli_args)) => {
+            let verify_config = parse_verify_config(cli_args, false)?;
+            verify_db(verify_config, client_config, &context, log)
+        }
+        ("repair", Some(cli_args)) => {
+            let verify_config = parse_verify_config(cli_args, true)?;
+            verify_db(verify_config, client_config, &context, log)
+        }
         _ => {
             return Err("Unknown subcommand, for help `lighthouse database_manager --help`".into())
         }
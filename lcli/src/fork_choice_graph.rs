@@ -0,0 +1,195 @@
+//! # Fork-Choice-Graph
+//!
+//! Use this tool to render the fork choice tree of a beacon node as a Graphviz graph. Useful for
+//! investigating re-orgs and optimistic sync invalidations.
+//!
+//! The fork choice tree can be downloaded from the `/eth/v1/debug/fork_choice` endpoint of a
+//! beacon node, or loaded from a JSON file containing a previous response from that endpoint.
+//!
+//! ## Examples
+//!
+//! ### Example 1.
+//!
+//! Render the fork choice tree of a local node to an SVG:
+//!
+//! ```ignore
+//! lcli fork-choice-graph \
+//!     --beacon-url http://localhost:5052 \
+//!     --output-path /tmp/fork_choice.dot
+//! dot -Tsvg /tmp/fork_choice.dot > /tmp/fork_choice.svg
+//! ```
+//!
+//! ### Example 2.
+//!
+//! Render only the blocks from slot 4000000 onwards from a saved response:
+//!
+//! ```ignore
+//! lcli fork-choice-graph \
+//!     --input-path /tmp/fork_choice.json \
+//!     --min-slot 4000000
+//! ```
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use eth2::{
+    types::{ForkChoice, ForkChoiceNode, ForkChoiceNodeValidity},
+    BeaconNodeHttpClient, SensitiveUrl, Timeouts,
+};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use types::{EthSpec, Hash256, Slot};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub fn run<T: EthSpec>(mut env: Environment<T>, matches: &ArgMatches) -> Result<(), String> {
+    let executor = env.core_context().executor;
+
+    let input_path: Option<PathBuf> = parse_optional(matches, "input-path")?;
+    let beacon_url: Option<SensitiveUrl> = parse_optional(matches, "beacon-url")?;
+    let output_path: Option<PathBuf> = parse_optional(matches, "output-path")?;
+    let min_slot: Slot = parse_required(matches, "min-slot")?;
+
+    let fork_choice = match (input_path, beacon_url) {
+        (Some(input_path), None) => {
+            let file = File::open(&input_path)
+                .map_err(|e| format!("Unable to open {:?}: {:?}", input_path, e))?;
+            serde_json::from_reader(file)
+                .map_err(|e| format!("Unable to parse {:?}: {:?}", input_path, e))?
+        }
+        (None, Some(beacon_url)) => {
+            let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+            executor
+                .handle()
+                .ok_or("shutdown in progress")?
+                .block_on(async move {
+                    client
+                        .get_debug_fork_choice()
+                        .await
+                        .map_err(|e| format!("Failed to download fork choice: {:?}", e))
+                })?
+        }
+        _ => return Err("must supply either --input-path or --beacon-url".into()),
+    };
+
+    let dot = render_dot(&fork_choice, min_slot);
+
+    if let Some(output_path) = output_path {
+        let mut file = File::create(&output_path)
+            .map_err(|e| format!("Unable to create {:?}: {:?}", output_path, e))?;
+        file.write_all(dot.as_bytes())
+            .map_err(|e| format!("Unable to write to {:?}: {:?}", output_path, e))?;
+        info!(
+            "Wrote {} fork choice nodes to {:?}",
+            fork_choice.fork_choice_nodes.len(),
+            output_path
+        );
+    } else {
+        print!("{}", dot);
+    }
+
+    Ok(())
+}
+
+/// Render `fork_choice` as a Graphviz `digraph`, omitting nodes prior to `min_slot`.
+///
+/// Each node points to its parent. Nodes are coloured by the validity of their execution payload
+/// and the justified, finalized and proposer boost roots are highlighted.
+fn render_dot(fork_choice: &ForkChoice, min_slot: Slot) -> String {
+    let mut dot = String::new();
+
+    // Writing to a `String` is infallible.
+    let _ = writeln!(dot, "digraph fork_choice {{");
+    let _ = writeln!(dot, "    rankdir=BT;");
+    let _ = writeln!(dot, "    node [shape=box, style=filled, fillcolor=white];");
+
+    let nodes = fork_choice
+        .fork_choice_nodes
+        .iter()
+        .filter(|node| node.slot >= min_slot)
+        .collect::<Vec<_>>();
+
+    for node in &nodes {
+        let mut label = format!(
+            "slot {}\\n{}\\nweight {}\\n{}",
+            node.slot,
+            short_root(node.block_root),
+            node.weight,
+            validity_str(node)
+        );
+        let mut peripheries = 1;
+
+        if node.block_root == fork_choice.justified_checkpoint.root {
+            label.push_str("\\njustified");
+            peripheries = 2;
+        }
+        if node.block_root == fork_choice.finalized_checkpoint.root {
+            label.push_str("\\nfinalized");
+            peripheries = 3;
+        }
+        let proposer_boost = &fork_choice.extra_data.proposer_boost;
+        if node.block_root == proposer_boost.root {
+            let _ = write!(label, "\\nproposer boost {}", proposer_boost.score);
+        }
+
+        let _ = writeln!(
+            dot,
+            "    \"{:?}\" [label=\"{}\", fillcolor={}, peripheries={}];",
+            node.block_root,
+            label,
+            fill_colour(node),
+            peripheries
+        );
+    }
+
+    let rendered_roots = nodes
+        .iter()
+        .map(|node| node.block_root)
+        .collect::<HashSet<_>>();
+
+    for node in &nodes {
+        if let Some(parent_root) = node.parent_root {
+            if rendered_roots.contains(&parent_root) {
+                let _ = writeln!(
+                    dot,
+                    "    \"{:?}\" -> \"{:?}\";",
+                    node.block_root, parent_root
+                );
+            }
+        }
+    }
+
+    let _ = writeln!(dot, "}}");
+
+    dot
+}
+
+fn short_root(root: Hash256) -> String {
+    let root = format!("{:?}", root);
+    root.chars().take(10).collect()
+}
+
+fn validity_str(node: &ForkChoiceNode) -> &'static str {
+    if node.execution_block_hash.is_none() {
+        return "pre-merge";
+    }
+    match node.validity {
+        ForkChoiceNodeValidity::Valid => "valid",
+        ForkChoiceNodeValidity::Invalid => "invalid",
+        ForkChoiceNodeValidity::Optimistic => "optimistic",
+    }
+}
+
+fn fill_colour(node: &ForkChoiceNode) -> &'static str {
+    if node.execution_block_hash.is_none() {
+        return "white";
+    }
+    match node.validity {
+        ForkChoiceNodeValidity::Valid => "palegreen",
+        ForkChoiceNodeValidity::Invalid => "lightcoral",
+        ForkChoiceNodeValidity::Optimistic => "lightyellow",
+    }
+}
@@ -5,6 +5,7 @@ mod check_deposit_data;
 mod create_payload_header;
 mod deploy_deposit_contract;
 mod eth1_genesis;
+mod fork_choice_graph;
 mod generate_bootnode_enr;
 mod indexed_attestations;
 mod insecure_validators;
@@ -12,7 +13,9 @@ mod interop_genesis;
 mod new_testnet;
 mod parse_ssz;
 mod replace_state_pubkeys;
+mod replay;
 mod skip_slots;
+mod slashing_evidence;
 mod transition_blocks;
 
 use clap::{App, Arg, ArgMatches, SubCommand};
@@ -50,6 +53,78 @@ fn main() {
                 .global(true)
                 .help("The testnet dir. Defaults to ~/.lighthouse/testnet"),
         )
+        .subcommand(
+            SubCommand::with_name("slashing-evidence")
+                .about("Exports the slashings detected by the slasher of a beacon node as JSON or \
+                    SSZ, optionally submitting them to another beacon node.")
+                .arg(
+                    Arg::with_name("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .takes_value(true)
+                        .required(true)
+                        .help("URL of a beacon node with the slasher enabled."),
+                )
+                .arg(
+                    Arg::with_name("validator-index")
+                        .long("validator-index")
+                        .value_name("INDEX")
+                        .takes_value(true)
+                        .help("Only export slashings of this validator."),
+                )
+                .arg(
+                    Arg::with_name("kind")
+                        .long("kind")
+                        .value_name("KIND")
+                        .takes_value(true)
+                        .possible_values(&["proposer", "attester"])
+                        .help("Only export slashings of this kind."),
+                )
+                .arg(
+                    Arg::with_name("from-epoch")
+                        .long("from-epoch")
+                        .value_name("EPOCH")
+                        .takes_value(true)
+                        .help("Only export slashings detected at or after this epoch."),
+                )
+                .arg(
+                    Arg::with_name("to-epoch")
+                        .long("to-epoch")
+                        .value_name("EPOCH")
+                        .takes_value(true)
+                        .help("Only export slashings detected at or before this epoch."),
+                )
+                .arg(
+                    Arg::with_name("not-included")
+                        .long("not-included")
+                        .takes_value(false)
+                        .help("Only export slashings which have not been included on chain."),
+                )
+                .arg(
+                    Arg::with_name("output-dir")
+                        .long("output-dir")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Directory to write each slashing to."),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .value_name("FORMAT")
+                        .takes_value(true)
+                        .default_value("json")
+                        .possible_values(&["json", "ssz"])
+                        .help("Format of the files written to --output-dir."),
+                )
+                .arg(
+                    Arg::with_name("submit-url")
+                        .long("submit-url")
+                        .value_name("URL")
+                        .takes_value(true)
+                        .help("URL of a beacon node to submit slashings which are not yet \
+                            included on chain to."),
+                )
+        )
         .subcommand(
             SubCommand::with_name("skip-slots")
                 .about(
@@ -200,6 +275,79 @@ fn main() {
                             the block."),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("replay")
+                .about("Replays a sequence of blocks atop a pre-state, checking the state root \
+                    after each block.")
+                .arg(
+                    Arg::with_name("pre-state-path")
+                        .long("pre-state-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .conflicts_with("state-id")
+                        .help("Path to load a BeaconState from file as SSZ."),
+                )
+                .arg(
+                    Arg::with_name("blocks-dir")
+                        .long("blocks-dir")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Path to a directory of SignedBeaconBlocks stored as SSZ files. \
+                            Blocks prior to the pre-state or after --end-slot are ignored."),
+                )
+                .arg(
+                    Arg::with_name("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .takes_value(true)
+                        .help("URL to a beacon-API provider."),
+                )
+                .arg(
+                    Arg::with_name("state-id")
+                        .long("state-id")
+                        .value_name("STATE_ID")
+                        .takes_value(true)
+                        .requires("beacon-url")
+                        .help("Identifier for the pre-state as per beacon-API standards \
+                            (slot, root, etc.)"),
+                )
+                .arg(
+                    Arg::with_name("end-slot")
+                        .long("end-slot")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .help("The slot to replay through to (inclusive). Required to download \
+                            blocks from --beacon-url when --blocks-dir is not supplied."),
+                )
+                .arg(
+                    Arg::with_name("post-state-output-path")
+                        .long("post-state-output-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Path to output the post-state."),
+                )
+                .arg(
+                    Arg::with_name("pre-state-output-path")
+                        .long("pre-state-output-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Path to output the pre-state, useful when used with --beacon-url."),
+                )
+                .arg(
+                    Arg::with_name("blocks-output-dir")
+                        .long("blocks-output-dir")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Directory to output the blocks to, useful when used with \
+                            --beacon-url."),
+                )
+                .arg(
+                    Arg::with_name("no-signature-verification")
+                        .long("no-signature-verification")
+                        .takes_value(false)
+                        .help("Disable signature verification.")
+                )
+        )
         .subcommand(
             SubCommand::with_name("pretty-ssz")
                 .about("Parses SSZ-encoded data from a file")
@@ -714,6 +862,40 @@ fn main() {
                         .help("List of Attestations to convert to indexed form (JSON)"),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("fork-choice-graph")
+                .about("Renders the fork choice tree of a beacon node as a Graphviz graph.")
+                .arg(
+                    Arg::with_name("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .takes_value(true)
+                        .conflicts_with("input-path")
+                        .help("URL to a beacon-API provider."),
+                )
+                .arg(
+                    Arg::with_name("input-path")
+                        .long("input-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Path to a JSON response from the /eth/v1/debug/fork_choice endpoint."),
+                )
+                .arg(
+                    Arg::with_name("output-path")
+                        .long("output-path")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Path to write the Graphviz file to. Defaults to stdout."),
+                )
+                .arg(
+                    Arg::with_name("min-slot")
+                        .long("min-slot")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Omit blocks from slots prior to this slot."),
+                )
+        )
         .get_matches();
 
     let result = matches
@@ -764,6 +946,11 @@ fn run<T: EthSpec>(
     match matches.subcommand() {
         ("transition-blocks", Some(matches)) => transition_blocks::run::<T>(env, matches)
             .map_err(|e| format!("Failed to transition blocks: {}", e)),
+        ("replay", Some(matches)) => {
+            replay::run::<T>(env, matches).map_err(|e| format!("Failed to replay blocks: {}", e))
+        }
+        ("slashing-evidence", Some(matches)) => slashing_evidence::run::<T>(env, matches)
+            .map_err(|e| format!("Failed to export slashing evidence: {}", e)),
         ("skip-slots", Some(matches)) => {
             skip_slots::run::<T>(env, matches).map_err(|e| format!("Failed to skip slots: {}", e))
         }
@@ -798,6 +985,8 @@ fn run<T: EthSpec>(
             .map_err(|e| format!("Failed to run insecure-validators command: {}", e)),
         ("indexed-attestations", Some(matches)) => indexed_attestations::run::<T>(matches)
             .map_err(|e| format!("Failed to run indexed-attestations command: {}", e)),
+        ("fork-choice-graph", Some(matches)) => fork_choice_graph::run::<T>(env, matches)
+            .map_err(|e| format!("Failed to run fork-choice-graph command: {}", e)),
         (other, _) => Err(format!("Unknown subcommand {}. See --help.", other)),
     }
 }
@@ -0,0 +1,373 @@
+//! # Replay
+//!
+//! Use this tool to replay a sequence of `SignedBeaconBlock`s atop a `BeaconState` using the
+//! `BlockReplayer`. Useful for bisecting consensus bugs and reproducing incidents locally.
+//!
+//! The post-state root of each block is checked against the state root in that block and replay
+//! stops at the first mismatch. For each block the processing time and post-state root are
+//! logged, along with a summary of the rewards and penalties applied at each epoch transition.
+//!
+//! The pre-state can be loaded from file or pulled from a beaconAPI. Blocks can be loaded from a
+//! directory of SSZ files or pulled from a beaconAPI for a range of slots.
+//!
+//! ## Examples
+//!
+//! ### Example 1.
+//!
+//! Download a state and all blocks up to slot 4000100 from a beaconAPI, replay them and save the
+//! state and blocks to disk for subsequent runs:
+//!
+//! ```ignore
+//! lcli replay \
+//!     --beacon-url http://localhost:5052 \
+//!     --state-id 4000000 \
+//!     --end-slot 4000100 \
+//!     --pre-state-output-path /tmp/state-4000000.ssz \
+//!     --blocks-output-dir /tmp/blocks
+//! ```
+//!
+//! ### Example 2.
+//!
+//! Replay the blocks downloaded in the previous example up to slot 4000050 (inclusive) and save
+//! the post-state, e.g. to inspect the state prior to a state root mismatch at slot 4000051:
+//!
+//! ```ignore
+//! lcli replay \
+//!     --pre-state-path /tmp/state-4000000.ssz \
+//!     --blocks-dir /tmp/blocks \
+//!     --end-slot 4000050 \
+//!     --post-state-output-path /tmp/state-4000050.ssz
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use clap_utils::parse_optional;
+use environment::Environment;
+use eth2::{
+    types::{BlockId, StateId},
+    BeaconNodeHttpClient, SensitiveUrl, Timeouts,
+};
+use ssz::Encode;
+use state_processing::{
+    per_epoch_processing::{
+        altair::participation_cache::Error as ParticipationCacheError, EpochProcessingSummary,
+    },
+    BlockReplayError, BlockReplayer,
+};
+use std::cell::{Cell, RefCell};
+use std::fs::{self, File};
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use types::{
+    BeaconState, BeaconStateError, ChainSpec, EthSpec, Hash256, SignedBeaconBlock,
+    SignedBlindedBeaconBlock, Slot,
+};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+enum Error {
+    BlockReplay(BlockReplayError),
+    BeaconState(BeaconStateError),
+    ParticipationCache(ParticipationCacheError),
+    StateRootMismatch {
+        slot: Slot,
+        block_root: Hash256,
+        expected: Hash256,
+        computed: Hash256,
+    },
+}
+
+impl From<BlockReplayError> for Error {
+    fn from(e: BlockReplayError) -> Self {
+        Error::BlockReplay(e)
+    }
+}
+
+impl From<BeaconStateError> for Error {
+    fn from(e: BeaconStateError) -> Self {
+        Error::BeaconState(e)
+    }
+}
+
+impl From<ParticipationCacheError> for Error {
+    fn from(e: ParticipationCacheError) -> Self {
+        Error::ParticipationCache(e)
+    }
+}
+
+pub fn run<T: EthSpec>(mut env: Environment<T>, matches: &ArgMatches) -> Result<(), String> {
+    let spec = &T::default_spec();
+    let executor = env.core_context().executor;
+
+    let pre_state_path: Option<PathBuf> = parse_optional(matches, "pre-state-path")?;
+    let blocks_dir: Option<PathBuf> = parse_optional(matches, "blocks-dir")?;
+    let beacon_url: Option<SensitiveUrl> = parse_optional(matches, "beacon-url")?;
+    let state_id: Option<StateId> = parse_optional(matches, "state-id")?;
+    let end_slot: Option<Slot> = parse_optional(matches, "end-slot")?;
+    let post_state_output_path: Option<PathBuf> =
+        parse_optional(matches, "post-state-output-path")?;
+    let pre_state_output_path: Option<PathBuf> = parse_optional(matches, "pre-state-output-path")?;
+    let blocks_output_dir: Option<PathBuf> = parse_optional(matches, "blocks-output-dir")?;
+    let no_signature_verification = matches.is_present("no-signature-verification");
+
+    info!("Using {} spec", T::spec_name());
+
+    let client = beacon_url
+        .map(|beacon_url| BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT)));
+    let handle = executor.handle().ok_or("shutdown in progress")?;
+
+    /*
+     * Load the pre-state from disk or beaconAPI URL.
+     */
+
+    let mut pre_state: BeaconState<T> = match (pre_state_path, state_id, &client) {
+        (Some(pre_state_path), None, _) => {
+            info!("Pre-state path: {:?}", pre_state_path);
+            load_from_ssz_with(&pre_state_path, spec, BeaconState::from_ssz_bytes)?
+        }
+        (None, Some(state_id), Some(client)) => handle.block_on(async {
+            client
+                .get_debug_beacon_states::<T>(state_id)
+                .await
+                .map_err(|e| format!("Failed to download state: {:?}", e))?
+                .ok_or_else(|| format!("Unable to locate state at {:?}", state_id))
+                .map(|response| response.data)
+        })?,
+        _ => {
+            return Err("must supply either --pre-state-path or --state-id and --beacon-url".into())
+        }
+    };
+
+    info!("Pre-state slot: {}", pre_state.slot());
+
+    /*
+     * Load the blocks from disk or beaconAPI URL.
+     */
+
+    let mut blocks: Vec<SignedBeaconBlock<T>> = match (blocks_dir, end_slot, &client) {
+        (Some(blocks_dir), _, _) => {
+            info!("Blocks directory: {:?}", blocks_dir);
+            load_blocks_from_dir(&blocks_dir, spec)?
+        }
+        (None, Some(end_slot), Some(client)) => {
+            let start_slot = pre_state.slot() + 1;
+            info!(
+                "Downloading blocks from slot {} to {}",
+                start_slot, end_slot
+            );
+            handle.block_on(async {
+                let mut blocks = vec![];
+                for slot in start_slot.as_u64()..=end_slot.as_u64() {
+                    let slot = Slot::new(slot);
+                    if let Some(response) = client
+                        .get_beacon_blocks::<T>(BlockId::Slot(slot))
+                        .await
+                        .map_err(|e| format!("Failed to download block at {}: {:?}", slot, e))?
+                    {
+                        // Ignore any block from an earlier slot that is served for a skip slot.
+                        if response.data.slot() == slot {
+                            blocks.push(response.data);
+                        }
+                    }
+                }
+                Ok::<_, String>(blocks)
+            })?
+        }
+        _ => return Err("must supply either --blocks-dir or --end-slot and --beacon-url".into()),
+    };
+
+    blocks.retain(|block| {
+        block.slot() > pre_state.slot()
+            && end_slot.map_or(true, |end_slot| block.slot() <= end_slot)
+    });
+    blocks.sort_by_key(|block| block.slot());
+
+    if let Some(pair) = blocks
+        .windows(2)
+        .find(|pair| pair[0].slot() == pair[1].slot())
+    {
+        return Err(format!("Multiple blocks at slot {}", pair[0].slot()));
+    }
+
+    info!("Replaying {} blocks", blocks.len());
+
+    /*
+     * Write the inputs to disk, if required.
+     */
+
+    if let Some(path) = pre_state_output_path {
+        write_ssz(&path, &pre_state)?;
+    }
+
+    if let Some(dir) = blocks_output_dir {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Unable to create directory {:?}: {:?}", dir, e))?;
+        for block in &blocks {
+            write_ssz(&dir.join(format!("block_{}.ssz", block.slot())), block)?;
+        }
+    }
+
+    /*
+     * Replay the blocks.
+     */
+
+    pre_state
+        .build_all_caches(spec)
+        .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+
+    let blocks: Vec<SignedBlindedBeaconBlock<T>> = blocks.into_iter().map(Into::into).collect();
+
+    let block_start = Cell::new(None);
+    let epoch_start = Cell::new(None);
+    let pre_epoch_balances = RefCell::new(None);
+
+    let mut replayer: BlockReplayer<T, Error> = BlockReplayer::new(pre_state, spec)
+        .pre_slot_hook(Box::new(|state| {
+            if (state.slot() + 1).as_u64() % T::slots_per_epoch() == 0 {
+                *pre_epoch_balances.borrow_mut() = Some(state.balances().to_vec());
+                epoch_start.set(Some(Instant::now()));
+            }
+            Ok(())
+        }))
+        .post_slot_hook(Box::new(|state, summary, _| {
+            if let Some(summary) = summary {
+                let epoch_time = epoch_start.take().map(|start| start.elapsed());
+                let pre_balances = pre_epoch_balances.borrow_mut().take().unwrap_or_default();
+                log_epoch_summary(state, &summary, &pre_balances, epoch_time)?;
+            }
+            Ok(())
+        }))
+        .pre_block_hook(Box::new(|_, _| {
+            block_start.set(Some(Instant::now()));
+            Ok(())
+        }))
+        .post_block_hook(Box::new(|state, block| {
+            let block_time = block_start.take().map(|start| start.elapsed());
+
+            let t = Instant::now();
+            let state_root = state.update_tree_hash_cache()?;
+            let tree_hash_time = t.elapsed();
+
+            let block_root = block.canonical_root();
+            info!(
+                "Slot {}: block {:?}, state root {:?}, process block {:?}, tree hash {:?}",
+                block.slot(),
+                block_root,
+                state_root,
+                block_time.unwrap_or_default(),
+                tree_hash_time
+            );
+
+            if state_root != block.state_root() {
+                return Err(Error::StateRootMismatch {
+                    slot: block.slot(),
+                    block_root,
+                    expected: block.state_root(),
+                    computed: state_root,
+                });
+            }
+            Ok(())
+        }));
+
+    if no_signature_verification {
+        replayer = replayer.no_signature_verification();
+    }
+
+    let t = Instant::now();
+    let post_state = replayer
+        .apply_blocks(blocks, end_slot)
+        .map_err(|e| format!("Replay failed: {:?}", e))?
+        .into_state();
+    info!(
+        "Replayed to slot {} in {:?}",
+        post_state.slot(),
+        t.elapsed()
+    );
+
+    /*
+     * Write the post-state to disk, if required.
+     */
+
+    if let Some(path) = post_state_output_path {
+        write_ssz(&path, &post_state)?;
+    }
+
+    Ok(())
+}
+
+/// Logs the rewards and penalties applied during the epoch transition which has just completed,
+/// given the balances of all validators immediately prior to that transition.
+fn log_epoch_summary<T: EthSpec>(
+    state: &BeaconState<T>,
+    summary: &EpochProcessingSummary<T>,
+    pre_balances: &[u64],
+    epoch_time: Option<Duration>,
+) -> Result<(), Error> {
+    let mut num_rewarded = 0_usize;
+    let mut num_penalized = 0_usize;
+    let mut total_rewards = 0;
+    let mut total_penalties = 0;
+    for (pre_balance, post_balance) in pre_balances.iter().zip(state.balances().iter()) {
+        if post_balance > pre_balance {
+            num_rewarded += 1;
+            total_rewards += post_balance - pre_balance;
+        } else if post_balance < pre_balance {
+            num_penalized += 1;
+            total_penalties += pre_balance - post_balance;
+        }
+    }
+
+    let total_active_balance = summary.previous_epoch_total_active_balance();
+    let target_attesting_balance = summary.previous_epoch_target_attesting_balance()?;
+    let target_participation = if total_active_balance > 0 {
+        target_attesting_balance as f64 * 100.0 / total_active_balance as f64
+    } else {
+        0.0
+    };
+
+    info!(
+        "Epoch {}: {} validators rewarded {} gwei, {} validators penalized {} gwei, \
+        target participation {:.2}%, epoch processing {:?}",
+        state.previous_epoch(),
+        num_rewarded,
+        total_rewards,
+        num_penalized,
+        total_penalties,
+        target_participation,
+        epoch_time.unwrap_or_default()
+    );
+
+    Ok(())
+}
+
+/// Loads every file in `dir` as an SSZ-encoded `SignedBeaconBlock`.
+fn load_blocks_from_dir<T: EthSpec>(
+    dir: &Path,
+    spec: &ChainSpec,
+) -> Result<Vec<SignedBeaconBlock<T>>, String> {
+    let mut blocks = vec![];
+    for entry in
+        fs::read_dir(dir).map_err(|e| format!("Unable to read directory {:?}: {:?}", dir, e))?
+    {
+        let path = entry
+            .map_err(|e| format!("Unable to read directory {:?}: {:?}", dir, e))?
+            .path();
+        if path.is_file() {
+            blocks.push(load_from_ssz_with(
+                &path,
+                spec,
+                SignedBeaconBlock::from_ssz_bytes,
+            )?);
+        }
+    }
+    Ok(blocks)
+}
+
+fn write_ssz(path: &Path, item: &impl Encode) -> Result<(), String> {
+    let mut output_file = File::create(path)
+        .map_err(|e| format!("Unable to create output file {:?}: {:?}", path, e))?;
+    output_file
+        .write_all(&item.as_ssz_bytes())
+        .map_err(|e| format!("Unable to write to output file {:?}: {:?}", path, e))
+}
@@ -0,0 +1,175 @@
+//! # Slashing Evidence
+//!
+//! Use this tool to export the slashings detected by the slasher of a beacon node, e.g. for
+//! submission to another beacon node when the local node is unable to include them in a block.
+//!
+//! Slashings are fetched from the `/lighthouse/slasher/slashings` endpoint and may be filtered by
+//! validator, kind, detection epoch and whether they have already been included on chain. Each
+//! slashing is written to `{kind}_slashing_{id}.{json|ssz}` in the output directory and/or
+//! submitted to the operation pool of another beacon node.
+//!
+//! ## Examples
+//!
+//! ### Example 1.
+//!
+//! Export all slashings of validator 42 as JSON:
+//!
+//! ```ignore
+//! lcli slashing-evidence \
+//!     --beacon-url http://localhost:5052 \
+//!     --validator-index 42 \
+//!     --output-dir /tmp/slashings
+//! ```
+//!
+//! ### Example 2.
+//!
+//! Submit all slashings which have not yet been included on chain to another beacon node:
+//!
+//! ```ignore
+//! lcli slashing-evidence \
+//!     --beacon-url http://localhost:5052 \
+//!     --not-included \
+//!     --submit-url http://other-node:5052
+//! ```
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use eth2::{
+    lighthouse::{SlasherSlashing, SlasherSlashingsQuery, SlashingKind},
+    BeaconNodeHttpClient, SensitiveUrl, Timeouts,
+};
+use ssz::Encode;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use types::{Epoch, EthSpec};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub fn run<T: EthSpec>(mut env: Environment<T>, matches: &ArgMatches) -> Result<(), String> {
+    let executor = env.core_context().executor;
+
+    let beacon_url: SensitiveUrl = parse_required(matches, "beacon-url")?;
+    let validator_index: Option<u64> = parse_optional(matches, "validator-index")?;
+    let kind = match matches.value_of("kind") {
+        Some("proposer") => Some(SlashingKind::Proposer),
+        Some("attester") => Some(SlashingKind::Attester),
+        Some(other) => return Err(format!("Unknown slashing kind: {}", other)),
+        None => None,
+    };
+    let from_epoch: Option<Epoch> = parse_optional(matches, "from-epoch")?;
+    let to_epoch: Option<Epoch> = parse_optional(matches, "to-epoch")?;
+    let not_included = matches.is_present("not-included");
+    let output_dir: Option<PathBuf> = parse_optional(matches, "output-dir")?;
+    let format: String = parse_required(matches, "format")?;
+    let submit_url: Option<SensitiveUrl> = parse_optional(matches, "submit-url")?;
+
+    if output_dir.is_none() && submit_url.is_none() {
+        return Err("At least one of --output-dir or --submit-url is required".into());
+    }
+
+    let query = SlasherSlashingsQuery {
+        validator_index,
+        kind,
+        from_epoch,
+        to_epoch,
+        included: if not_included { Some(false) } else { None },
+    };
+
+    let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+    let submit_client =
+        submit_url.map(|url| BeaconNodeHttpClient::new(url, Timeouts::set_all(HTTP_TIMEOUT)));
+    let handle = executor.handle().ok_or("shutdown in progress")?;
+
+    let slashings = handle
+        .block_on(async { client.get_lighthouse_slasher_slashings::<T>(&query).await })
+        .map_err(|e| format!("Failed to download slashings: {:?}", e))?
+        .data;
+
+    info!("Found {} slashings", slashings.len());
+
+    if let Some(output_dir) = output_dir {
+        fs::create_dir_all(&output_dir)
+            .map_err(|e| format!("Unable to create {:?}: {:?}", output_dir, e))?;
+        for slashing in &slashings {
+            write_slashing(slashing, &output_dir, &format)?;
+        }
+        info!("Wrote {} slashings to {:?}", slashings.len(), output_dir);
+    }
+
+    if let Some(submit_client) = submit_client {
+        for slashing in &slashings {
+            if slashing.included {
+                info!(
+                    "Skipping {} slashing {} which is already included",
+                    slashing.kind, slashing.id
+                );
+                continue;
+            }
+
+            let result = handle.block_on(async {
+                if let Some(proposer_slashing) = &slashing.proposer_slashing {
+                    submit_client
+                        .post_beacon_pool_proposer_slashings(proposer_slashing)
+                        .await
+                } else if let Some(attester_slashing) = &slashing.attester_slashing {
+                    submit_client
+                        .post_beacon_pool_attester_slashings(attester_slashing)
+                        .await
+                } else {
+                    Ok(())
+                }
+            });
+
+            match result {
+                Ok(()) => info!("Submitted {} slashing {}", slashing.kind, slashing.id),
+                Err(e) => warn!(
+                    "Failed to submit {} slashing {}: {:?}",
+                    slashing.kind, slashing.id, e
+                ),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_slashing<T: EthSpec>(
+    slashing: &SlasherSlashing<T>,
+    output_dir: &Path,
+    format: &str,
+) -> Result<(), String> {
+    let path = output_dir.join(format!(
+        "{}_slashing_{}.{}",
+        slashing.kind, slashing.id, format
+    ));
+
+    let bytes = match format {
+        "json" => {
+            let json = if let Some(proposer_slashing) = &slashing.proposer_slashing {
+                serde_json::to_vec_pretty(proposer_slashing)
+            } else if let Some(attester_slashing) = &slashing.attester_slashing {
+                serde_json::to_vec_pretty(attester_slashing)
+            } else {
+                return Err(format!("Slashing {} has no evidence", slashing.id));
+            };
+            json.map_err(|e| format!("Unable to serialize slashing {}: {:?}", slashing.id, e))?
+        }
+        "ssz" => {
+            if let Some(proposer_slashing) = &slashing.proposer_slashing {
+                proposer_slashing.as_ssz_bytes()
+            } else if let Some(attester_slashing) = &slashing.attester_slashing {
+                attester_slashing.as_ssz_bytes()
+            } else {
+                return Err(format!("Slashing {} has no evidence", slashing.id));
+            }
+        }
+        other => return Err(format!("Unknown format: {}", other)),
+    };
+
+    let mut file =
+        File::create(&path).map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+    file.write_all(&bytes)
+        .map_err(|e| format!("Unable to write to {:?}: {:?}", path, e))
+}
@@ -1,3 +1,7 @@
+use beacon_node::beacon_chain::chain_config::{
+    ReOrgThreshold, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_THRESHOLD,
+    DEFAULT_VALIDATOR_MONITOR_HISTORY_EPOCHS,
+};
 use beacon_node::ClientConfig as Config;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
@@ -132,6 +136,52 @@ fn fork_choice_before_proposal_timeout_zero() {
         .with_config(|config| assert_eq!(config.chain.fork_choice_before_proposal_timeout_ms, 0));
 }
 
+#[test]
+fn proposer_re_orgs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.re_org_threshold, None);
+            assert_eq!(
+                config.chain.re_org_max_epochs_since_finalization,
+                DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION
+            );
+        });
+}
+
+#[test]
+fn proposer_re_orgs_enabled() {
+    CommandLineTest::new()
+        .flag("enable-proposer-re-orgs", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.re_org_threshold,
+                Some(DEFAULT_RE_ORG_THRESHOLD)
+            );
+            assert_eq!(
+                config.chain.re_org_max_epochs_since_finalization,
+                DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION
+            );
+        });
+}
+
+#[test]
+fn proposer_re_org_threshold_and_epochs() {
+    CommandLineTest::new()
+        .flag("enable-proposer-re-orgs", None)
+        .flag("proposer-re-org-threshold", Some("10"))
+        .flag("proposer-re-org-epochs-since-finalization", Some("8"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.re_org_threshold, Some(ReOrgThreshold(10)));
+            assert_eq!(
+                config.chain.re_org_max_epochs_since_finalization,
+                Epoch::new(8)
+            );
+        });
+}
+
 #[test]
 fn count_unrealized_default() {
     CommandLineTest::new()
@@ -925,6 +975,25 @@ fn http_allow_sync_stalled_flag() {
         .with_config(|config| assert_eq!(config.http_api.allow_sync_stalled, true));
 }
 #[test]
+fn http_auth_config_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("auth.yaml");
+    CommandLineTest::new()
+        .flag("http-auth-config", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.auth_config, Some(path.clone())));
+}
+#[test]
+fn http_backup_dir_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("http-backup-dir", dir.path().as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.backup_dir, Some(dir.path().to_path_buf()))
+        });
+}
+#[test]
 fn http_tls_flags() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
     CommandLineTest::new()
@@ -946,6 +1015,34 @@ fn http_tls_flags() {
                 .expect("tls_config was empty.");
             assert_eq!(tls_config.cert, dir.path().join("certificate.crt"));
             assert_eq!(tls_config.key, dir.path().join("private.key"));
+            assert_eq!(tls_config.client_ca, None);
+        });
+}
+#[test]
+fn http_tls_client_ca_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("http-enable-tls", None)
+        .flag(
+            "http-tls-cert",
+            dir.path().join("certificate.crt").as_os_str().to_str(),
+        )
+        .flag(
+            "http-tls-key",
+            dir.path().join("private.key").as_os_str().to_str(),
+        )
+        .flag(
+            "http-tls-client-ca",
+            dir.path().join("client-ca.crt").as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let tls_config = config
+                .http_api
+                .tls_config
+                .as_ref()
+                .expect("tls_config was empty.");
+            assert_eq!(tls_config.client_ca, Some(dir.path().join("client-ca.crt")));
         });
 }
 
@@ -1060,6 +1157,24 @@ fn validator_monitor_file_flag() {
             assert_eq!(config.validator_monitor_pubkeys[1].to_string(), "0xbeefdeadbeefdeaddeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef");
         });
 }
+#[test]
+fn validator_monitor_history_epochs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.validator_monitor_history_epochs,
+                DEFAULT_VALIDATOR_MONITOR_HISTORY_EPOCHS
+            )
+        });
+}
+#[test]
+fn validator_monitor_history_epochs_flag() {
+    CommandLineTest::new()
+        .flag("validator-monitor-history-epochs", Some("64"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.validator_monitor_history_epochs, 64));
+}
 
 // Tests for Store flags.
 #[test]
@@ -1111,6 +1226,13 @@ fn block_cache_size_flag() {
         .with_config(|config| assert_eq!(config.store.block_cache_size, 4_usize));
 }
 #[test]
+fn historic_state_cache_size_flag() {
+    CommandLineTest::new()
+        .flag("historic-state-cache-size", Some("64"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.historic_state_cache_size_mb, 64_usize));
+}
+#[test]
 fn auto_compact_db_flag() {
     CommandLineTest::new()
         .flag("auto-compact-db", Some("false"))
@@ -1126,6 +1248,19 @@ fn compact_db_flag() {
         .with_config(|config| assert!(config.store.compact_on_init));
 }
 #[test]
+fn prune_payloads_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.store.prune_payloads));
+}
+#[test]
+fn prune_payloads_flag() {
+    CommandLineTest::new()
+        .flag("prune-payloads", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.store.prune_payloads));
+}
+#[test]
 fn reconstruct_historic_states_flag() {
     CommandLineTest::new()
         .flag("reconstruct-historic-states", None)
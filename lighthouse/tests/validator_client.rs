@@ -9,6 +9,7 @@ use std::path::PathBuf;
 use std::process::Command;
 use std::str::FromStr;
 use std::string::ToString;
+use std::time::Duration;
 use tempfile::TempDir;
 use types::Address;
 
@@ -160,6 +161,47 @@ fn beacon_nodes_tls_certs_flag() {
         });
 }
 
+#[test]
+fn beacon_nodes_client_config_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let config_path = dir.path().join("beacon-nodes.yaml");
+    let mut file = File::create(&config_path).expect("Unable to create config file");
+    write!(
+        file,
+        "- url: http://localhost:5052/\n  \
+           bearer_token_path: {}\n  \
+           headers:\n    X-Api-Key: secret\n",
+        dir.path().join("token.txt").display()
+    )
+    .expect("Unable to write config file");
+
+    CommandLineTest::new()
+        .flag("beacon-nodes-client-config", config_path.to_str())
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.beacon_node_client_configs.len(), 1);
+            let client_config = &config.beacon_node_client_configs[0];
+            assert_eq!(client_config.url, config.beacon_nodes[0]);
+            assert_eq!(
+                client_config.bearer_token_path,
+                Some(dir.path().join("token.txt"))
+            );
+            assert_eq!(client_config.headers.get("X-Api-Key").unwrap(), "secret");
+        });
+}
+#[test]
+#[should_panic]
+fn beacon_nodes_client_config_unknown_url() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let config_path = dir.path().join("beacon-nodes.yaml");
+    let mut file = File::create(&config_path).expect("Unable to create config file");
+    write!(file, "- url: http://unknown:5052/\n").expect("Unable to write config file");
+
+    CommandLineTest::new()
+        .flag("beacon-nodes-client-config", config_path.to_str())
+        .run();
+}
+
 // Tests for Graffiti flags.
 #[test]
 fn graffiti_flag() {
@@ -443,3 +485,44 @@ fn no_strict_fee_recipient_flag() {
         .run()
         .with_config(|config| assert!(!config.strict_fee_recipient));
 }
+#[test]
+fn use_ssz_blocks_flag() {
+    CommandLineTest::new()
+        .flag("use-ssz-blocks", None)
+        .run()
+        .with_config(|config| assert!(config.use_ssz_blocks));
+}
+#[test]
+fn no_use_ssz_blocks_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.use_ssz_blocks));
+}
+#[test]
+fn attest_on_head_event_flag() {
+    CommandLineTest::new()
+        .flag("attest-on-head-event", None)
+        .run()
+        .with_config(|config| assert!(config.attest_on_head_event));
+}
+#[test]
+fn no_attest_on_head_event_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.attest_on_head_event));
+}
+#[test]
+fn aggregation_offset_flag() {
+    CommandLineTest::new()
+        .flag("aggregation-offset-ms", Some("9000"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.aggregation_offset, Some(Duration::from_millis(9000)))
+        });
+}
+#[test]
+fn aggregation_offset_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.aggregation_offset, None));
+}
@@ -4,7 +4,7 @@ mod mdbx_impl;
 
 use crate::{
     metrics, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord, Config, Error,
-    ProposerSlashingStatus,
+    ProposerSlashingStatus, SlashingEvidence, SlashingRecord,
 };
 use byteorder::{BigEndian, ByteOrder};
 use interface::{Environment, OpenDatabases, RwTransaction};
@@ -47,9 +47,13 @@ const MAX_TARGETS_DB: &str = "max_targets";
 const CURRENT_EPOCHS_DB: &str = "current_epochs";
 /// Map from `(slot, validator_index)` to `SignedBeaconBlockHeader`.
 const PROPOSERS_DB: &str = "proposers";
+/// Map from `slashing_id` to `SlashingRecord`, for every slashing detected.
+///
+/// Slashing IDs are allocated sequentially and records are never pruned.
+const SLASHINGS_DB: &str = "slashings";
 
 /// The number of DBs for MDBX to use (equal to the number of DBs defined above).
-const MAX_NUM_DBS: usize = 9;
+const MAX_NUM_DBS: usize = 10;
 
 /// Constant key under which the schema version is stored in the `metadata_db`.
 const METADATA_VERSION_KEY: &[u8] = &[0];
@@ -58,6 +62,7 @@ const METADATA_CONFIG_KEY: &[u8] = &[1];
 
 const ATTESTER_KEY_SIZE: usize = 7;
 const PROPOSER_KEY_SIZE: usize = 16;
+const SLASHING_ID_SIZE: usize = 8;
 const CURRENT_EPOCH_KEY_SIZE: usize = 8;
 const INDEXED_ATTESTATION_ID_SIZE: usize = 6;
 const INDEXED_ATTESTATION_ID_KEY_SIZE: usize = 40;
@@ -244,6 +249,14 @@ fn ssz_decode<T: Decode>(bytes: Cow<[u8]>) -> Result<T, Error> {
     Ok(T::from_ssz_bytes(bytes.borrow())?)
 }
 
+fn parse_slashing_id(data: Cow<[u8]>) -> Result<u64, Error> {
+    if data.len() == SLASHING_ID_SIZE {
+        Ok(BigEndian::read_u64(&data))
+    } else {
+        Err(Error::SlashingIdCorrupt { length: data.len() })
+    }
+}
+
 impl<E: EthSpec> SlasherDB<E> {
     pub fn open(config: Arc<Config>, log: Logger) -> Result<Self, Error> {
         info!(log, "Opening slasher database"; "backend" => %config.backend);
@@ -663,6 +676,57 @@ impl<E: EthSpec> SlasherDB<E> {
         }
     }
 
+    /// Persist newly detected slashings with their evidence, in the order given.
+    pub fn store_slashings(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        detected_epoch: Epoch,
+        evidence: impl IntoIterator<Item = SlashingEvidence<E>>,
+    ) -> Result<(), Error> {
+        let mut cursor = txn.cursor(&self.databases.slashings_db)?;
+
+        let mut next_id = match cursor.last_key()? {
+            None => 0,
+            Some(key_bytes) => parse_slashing_id(key_bytes)? + 1,
+        };
+
+        for evidence in evidence {
+            let record = SlashingRecord {
+                detected_epoch,
+                evidence,
+            };
+            cursor.put(next_id.to_be_bytes(), record.as_ssz_bytes())?;
+            next_id += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Return all persisted slashings, along with their IDs, in the order they were detected.
+    pub fn get_slashings(
+        &self,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<Vec<(u64, SlashingRecord<E>)>, Error> {
+        let mut cursor = txn.cursor(&self.databases.slashings_db)?;
+        let mut slashings = vec![];
+
+        // Position cursor at first key, bailing out if the database is empty.
+        if cursor.first_key()?.is_none() {
+            return Ok(slashings);
+        }
+
+        loop {
+            let (key_bytes, value) = cursor.get_current()?.ok_or(Error::MissingSlashingId)?;
+            slashings.push((parse_slashing_id(key_bytes)?, ssz_decode(value)?));
+
+            if cursor.next_key()?.is_none() {
+                break;
+            }
+        }
+
+        Ok(slashings)
+    }
+
     /// Attempt to prune the database, deleting old blocks and attestations.
     pub fn prune(&self, current_epoch: Epoch) -> Result<(), Error> {
         let mut txn = self.begin_rw_txn()?;
@@ -45,6 +45,7 @@ pub struct OpenDatabases<'env> {
     pub max_targets_db: Database<'env>,
     pub current_epochs_db: Database<'env>,
     pub proposers_db: Database<'env>,
+    pub slashings_db: Database<'env>,
     pub metadata_db: Database<'env>,
 }
 
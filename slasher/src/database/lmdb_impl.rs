@@ -61,6 +61,7 @@ impl Environment {
             .env
             .create_db(Some(CURRENT_EPOCHS_DB), Self::db_flags())?;
         let proposers_db = self.env.create_db(Some(PROPOSERS_DB), Self::db_flags())?;
+        let slashings_db = self.env.create_db(Some(SLASHINGS_DB), Self::db_flags())?;
         let metadata_db = self.env.create_db(Some(METADATA_DB), Self::db_flags())?;
 
         let wrap = |db| {
@@ -79,6 +80,7 @@ impl Environment {
             max_targets_db: wrap(max_targets_db),
             current_epochs_db: wrap(current_epochs_db),
             proposers_db: wrap(proposers_db),
+            slashings_db: wrap(slashings_db),
             metadata_db: wrap(metadata_db),
         })
     }
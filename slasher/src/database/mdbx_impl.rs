@@ -54,6 +54,7 @@ impl Environment {
         txn.create_db(MAX_TARGETS_DB)?;
         txn.create_db(CURRENT_EPOCHS_DB)?;
         txn.create_db(PROPOSERS_DB)?;
+        txn.create_db(SLASHINGS_DB)?;
         txn.create_db(METADATA_DB)?;
 
         // This is all rather nasty
@@ -73,6 +74,7 @@ impl Environment {
             max_targets_db: next_db(),
             current_epochs_db: next_db(),
             proposers_db: next_db(),
+            slashings_db: next_db(),
             metadata_db: next_db(),
         })
     }
@@ -58,6 +58,9 @@ pub enum Error {
     IndexedAttestationIdCorrupt {
         length: usize,
     },
+    SlashingIdCorrupt {
+        length: usize,
+    },
     MissingIndexedAttestation {
         id: u64,
     },
@@ -65,6 +68,7 @@ pub enum Error {
     MissingProposerKey,
     MissingIndexedAttestationId,
     MissingIndexedAttestationIdKey,
+    MissingSlashingId,
     InconsistentAttestationDataRoot,
 }
 
@@ -15,6 +15,7 @@ mod error;
 pub mod metrics;
 mod migrate;
 mod slasher;
+mod slashing_record;
 pub mod test_utils;
 
 pub use crate::slasher::Slasher;
@@ -27,6 +28,7 @@ pub use database::{
     IndexedAttestationId, SlasherDB,
 };
 pub use error::Error;
+pub use slashing_record::{SlashingEvidence, SlashingRecord};
 
 use types::{AttesterSlashing, EthSpec, IndexedAttestation, ProposerSlashing};
 
@@ -7,6 +7,7 @@ use crate::metrics::{
 use crate::{
     array, AttestationBatch, AttestationQueue, AttesterRecord, BlockQueue, Config, Error,
     IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
+    SlashingEvidence, SlashingRecord,
 };
 use parking_lot::Mutex;
 use slog::{debug, error, info, Logger};
@@ -57,6 +58,17 @@ impl<E: EthSpec> Slasher<E> {
         std::mem::take(&mut self.proposer_slashings.lock())
     }
 
+    /// Return every slashing detected by the slasher along with its ID, in the order detected.
+    ///
+    /// Unlike `Self::get_attester_slashings` and `Self::get_proposer_slashings` this does not
+    /// remove any slashings, and includes those detected prior to a restart.
+    pub fn get_slashing_records(&self) -> Result<Vec<(u64, SlashingRecord<E>)>, Error> {
+        let mut txn = self.db.begin_rw_txn()?;
+        let slashings = self.db.get_slashings(&mut txn)?;
+        txn.commit()?;
+        Ok(slashings)
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
@@ -78,7 +90,7 @@ impl<E: EthSpec> Slasher<E> {
     /// Apply queued blocks and attestations to the on-disk database, and detect slashings!
     pub fn process_queued(&self, current_epoch: Epoch) -> Result<BatchStats, Error> {
         let mut txn = self.db.begin_rw_txn()?;
-        let block_stats = self.process_blocks(&mut txn)?;
+        let block_stats = self.process_blocks(current_epoch, &mut txn)?;
         let attestation_stats = self.process_attestations(current_epoch, &mut txn)?;
         txn.commit()?;
         Ok(BatchStats {
@@ -90,7 +102,11 @@ impl<E: EthSpec> Slasher<E> {
     /// Apply queued blocks to the on-disk database.
     ///
     /// Return the number of blocks
-    pub fn process_blocks(&self, txn: &mut RwTransaction<'_>) -> Result<BlockStats, Error> {
+    pub fn process_blocks(
+        &self,
+        current_epoch: Epoch,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<BlockStats, Error> {
         let blocks = self.block_queue.dequeue();
         let num_processed = blocks.len();
         let mut slashings = vec![];
@@ -112,6 +128,11 @@ impl<E: EthSpec> Slasher<E> {
                 "Found {} new proposer slashings!",
                 slashings.len(),
             );
+            self.db.store_slashings(
+                txn,
+                current_epoch,
+                slashings.iter().cloned().map(SlashingEvidence::Proposer),
+            )?;
             self.proposer_slashings.lock().extend(slashings);
         }
 
@@ -187,9 +208,21 @@ impl<E: EthSpec> Slasher<E> {
         );
 
         // Group attestations into chunked batches and process them.
+        let mut slashings = HashSet::new();
         let grouped_attestations = batch.group_by_validator_chunk_index(&self.config);
         for (subqueue_id, subqueue) in grouped_attestations.subqueues.into_iter().enumerate() {
-            self.process_batch(txn, subqueue_id, subqueue, current_epoch)?;
+            self.process_batch(txn, subqueue_id, subqueue, current_epoch, &mut slashings)?;
+        }
+
+        // Persist the slashings found in this batch once, as a single slashing may be found by
+        // multiple subqueues.
+        if !slashings.is_empty() {
+            self.db.store_slashings(
+                txn,
+                current_epoch,
+                slashings.iter().cloned().map(SlashingEvidence::Attester),
+            )?;
+            self.attester_slashings.lock().extend(slashings);
         }
 
         metrics::set_gauge(
@@ -200,13 +233,15 @@ impl<E: EthSpec> Slasher<E> {
         Ok(AttestationStats { num_processed })
     }
 
-    /// Process a batch of attestations for a range of validator indices.
+    /// Process a batch of attestations for a range of validator indices, adding any slashings
+    /// found to `slashings`.
     fn process_batch(
         &self,
         txn: &mut RwTransaction<'_>,
         subqueue_id: usize,
         batch: SimpleBatch<E>,
         current_epoch: Epoch,
+        slashings: &mut HashSet<AttesterSlashing<E>>,
     ) -> Result<(), Error> {
         // First, check for double votes.
         for attestation in &batch {
@@ -218,15 +253,15 @@ impl<E: EthSpec> Slasher<E> {
                 &attestation.record,
                 indexed_attestation_id,
             ) {
-                Ok(slashings) => {
-                    if !slashings.is_empty() {
+                Ok(double_votes) => {
+                    if !double_votes.is_empty() {
                         info!(
                             self.log,
                             "Found {} new double-vote slashings!",
-                            slashings.len()
+                            double_votes.len()
                         );
                     }
-                    self.attester_slashings.lock().extend(slashings);
+                    slashings.extend(double_votes);
                 }
                 Err(e) => {
                     error!(
@@ -248,15 +283,15 @@ impl<E: EthSpec> Slasher<E> {
             current_epoch,
             &self.config,
         ) {
-            Ok(slashings) => {
-                if !slashings.is_empty() {
+            Ok(surrounds) => {
+                if !surrounds.is_empty() {
                     info!(
                         self.log,
                         "Found {} new surround slashings!",
-                        slashings.len()
+                        surrounds.len()
                     );
                 }
-                self.attester_slashings.lock().extend(slashings);
+                slashings.extend(surrounds);
             }
             Err(e) => {
                 error!(
@@ -0,0 +1,62 @@
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use std::collections::BTreeSet;
+use types::{AttesterSlashing, Epoch, EthSpec, ProposerSlashing};
+
+/// The evidence for a slashable offence detected by the slasher.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode)]
+#[serde(bound = "E: EthSpec", rename_all = "snake_case")]
+#[ssz(enum_behaviour = "union")]
+pub enum SlashingEvidence<E: EthSpec> {
+    Proposer(ProposerSlashing),
+    Attester(AttesterSlashing<E>),
+}
+
+impl<E: EthSpec> SlashingEvidence<E> {
+    pub fn proposer_slashing(&self) -> Option<&ProposerSlashing> {
+        match self {
+            SlashingEvidence::Proposer(slashing) => Some(slashing),
+            SlashingEvidence::Attester(_) => None,
+        }
+    }
+
+    pub fn attester_slashing(&self) -> Option<&AttesterSlashing<E>> {
+        match self {
+            SlashingEvidence::Proposer(_) => None,
+            SlashingEvidence::Attester(slashing) => Some(slashing),
+        }
+    }
+
+    /// Returns the indices of the validators made slashable by this evidence, in ascending order.
+    pub fn slashable_indices(&self) -> Vec<u64> {
+        match self {
+            SlashingEvidence::Proposer(slashing) => {
+                vec![slashing.signed_header_1.message.proposer_index]
+            }
+            SlashingEvidence::Attester(slashing) => {
+                let attesters_2 = slashing
+                    .attestation_2
+                    .attesting_indices
+                    .iter()
+                    .collect::<BTreeSet<_>>();
+                slashing
+                    .attestation_1
+                    .attesting_indices
+                    .iter()
+                    .filter(|index| attesters_2.contains(index))
+                    .copied()
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A slashing detected by the slasher, as persisted in its database.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct SlashingRecord<E: EthSpec> {
+    /// The epoch of the slasher's clock when the slashing was detected.
+    pub detected_epoch: Epoch,
+    pub evidence: SlashingEvidence<E>,
+}
@@ -6,7 +6,7 @@ use rayon::prelude::*;
 use slasher::{
     config::DEFAULT_CHUNK_SIZE,
     test_utils::{att_slashing, indexed_att, slashed_validators_from_slashings, E},
-    Config, Slasher,
+    Config, Slasher, SlashingEvidence,
 };
 use std::collections::HashSet;
 use tempfile::tempdir;
@@ -190,6 +190,18 @@ fn slasher_test(
 
     assert_eq!(&slashings, expected);
 
+    // Every slashing found should have been persisted.
+    let persisted = slasher
+        .get_slashing_records()
+        .unwrap()
+        .into_iter()
+        .map(|(_, record)| match record.evidence {
+            SlashingEvidence::Attester(slashing) => slashing,
+            SlashingEvidence::Proposer(slashing) => panic!("unexpected slashing: {:?}", slashing),
+        })
+        .collect::<HashSet<_>>();
+    assert_eq!(&persisted, expected);
+
     // Pruning should not error.
     slasher.prune_database(current_epoch).unwrap();
     // windows won't delete the temporary directory if you don't do this..
@@ -3,7 +3,7 @@
 use logging::test_logger;
 use slasher::{
     test_utils::{block as test_block, E},
-    Config, Slasher,
+    Config, Slasher, SlashingEvidence,
 };
 use tempfile::tempdir;
 use types::{Epoch, EthSpec};
@@ -62,3 +62,38 @@ fn block_pruning() {
         .epoch(slots_per_epoch)
         > current_epoch - config.history_length as u64));
 }
+
+#[test]
+fn proposer_slashings_persisted() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    let proposer_index = 42;
+
+    slasher.accept_block_header(test_block(1, proposer_index, 0));
+    slasher.process_queued(Epoch::new(0)).unwrap();
+    assert!(slasher.get_slashing_records().unwrap().is_empty());
+
+    slasher.accept_block_header(test_block(1, proposer_index, 1));
+    slasher.accept_block_header(test_block(40, proposer_index, 0));
+    slasher.process_queued(Epoch::new(1)).unwrap();
+    slasher.accept_block_header(test_block(40, proposer_index, 1));
+    slasher.process_queued(Epoch::new(1)).unwrap();
+
+    let proposer_slashings = slasher.get_proposer_slashings();
+    assert_eq!(proposer_slashings.len(), 2);
+
+    // Harvesting the slashings should not remove their records.
+    let records = slasher.get_slashing_records().unwrap();
+    assert_eq!(records.len(), 2);
+    for (i, (id, record)) in records.iter().enumerate() {
+        assert_eq!(*id, i as u64);
+        assert_eq!(record.detected_epoch, Epoch::new(1));
+        assert_eq!(record.evidence.slashable_indices(), vec![proposer_index]);
+
+        match &record.evidence {
+            SlashingEvidence::Proposer(slashing) => assert!(proposer_slashings.contains(slashing)),
+            SlashingEvidence::Attester(slashing) => panic!("unexpected slashing: {:?}", slashing),
+        }
+    }
+}
@@ -6,7 +6,8 @@ use crate::type_name;
 use crate::type_name::TypeName;
 use serde_derive::Deserialize;
 use state_processing::per_epoch_processing::{
-    altair, base,
+    altair::{self, SinglePassConfig},
+    base,
     effective_balance_updates::process_effective_balance_updates,
     historical_roots_update::process_historical_roots_update,
     process_registry_updates, process_slashings,
@@ -82,6 +83,16 @@ type_name!(SyncCommitteeUpdates, "sync_committee_updates");
 type_name!(InactivityUpdates, "inactivity_updates");
 type_name!(ParticipationFlagUpdates, "participation_flag_updates");
 
+/// Applies the steps of `altair::process_epoch_single_pass` which are enabled in `config`.
+fn process_epoch_single_pass<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    config: SinglePassConfig,
+    spec: &ChainSpec,
+) -> Result<(), EpochProcessingError> {
+    let participation_cache = altair::ParticipationCache::new(state, spec).unwrap();
+    altair::process_epoch_single_pass(state, &participation_cache, config, spec)
+}
+
 impl<E: EthSpec> EpochTransition<E> for JustificationAndFinalization {
     fn run(state: &mut BeaconState<E>, spec: &ChainSpec) -> Result<(), EpochProcessingError> {
         match state {
@@ -118,20 +129,31 @@ impl<E: EthSpec> EpochTransition<E> for RewardsAndPenalties {
                 validator_statuses.process_attestations(state)?;
                 base::process_rewards_and_penalties(state, &mut validator_statuses, spec)
             }
-            BeaconState::Altair(_) | BeaconState::Merge(_) => {
-                altair::process_rewards_and_penalties(
-                    state,
-                    &altair::ParticipationCache::new(state, spec).unwrap(),
-                    spec,
-                )
-            }
+            BeaconState::Altair(_) | BeaconState::Merge(_) => process_epoch_single_pass(
+                state,
+                SinglePassConfig {
+                    rewards_and_penalties: true,
+                    ..SinglePassConfig::disable_all()
+                },
+                spec,
+            ),
         }
     }
 }
 
 impl<E: EthSpec> EpochTransition<E> for RegistryUpdates {
     fn run(state: &mut BeaconState<E>, spec: &ChainSpec) -> Result<(), EpochProcessingError> {
-        process_registry_updates(state, spec)
+        match state {
+            BeaconState::Base(_) => process_registry_updates(state, spec),
+            BeaconState::Altair(_) | BeaconState::Merge(_) => process_epoch_single_pass(
+                state,
+                SinglePassConfig {
+                    registry_updates: true,
+                    ..SinglePassConfig::disable_all()
+                },
+                spec,
+            ),
+        }
     }
 }
 
@@ -148,11 +170,12 @@ impl<E: EthSpec> EpochTransition<E> for Slashings {
                 )?;
             }
             BeaconState::Altair(_) | BeaconState::Merge(_) => {
-                process_slashings(
+                process_epoch_single_pass(
                     state,
-                    altair::ParticipationCache::new(state, spec)
-                        .unwrap()
-                        .current_epoch_total_active_balance(),
+                    SinglePassConfig {
+                        slashings: true,
+                        ..SinglePassConfig::disable_all()
+                    },
                     spec,
                 )?;
             }
@@ -169,7 +192,17 @@ impl<E: EthSpec> EpochTransition<E> for Eth1DataReset {
 
 impl<E: EthSpec> EpochTransition<E> for EffectiveBalanceUpdates {
     fn run(state: &mut BeaconState<E>, spec: &ChainSpec) -> Result<(), EpochProcessingError> {
-        process_effective_balance_updates(state, spec)
+        match state {
+            BeaconState::Base(_) => process_effective_balance_updates(state, spec),
+            BeaconState::Altair(_) | BeaconState::Merge(_) => process_epoch_single_pass(
+                state,
+                SinglePassConfig {
+                    effective_balance_updates: true,
+                    ..SinglePassConfig::disable_all()
+                },
+                spec,
+            ),
+        }
     }
 }
 
@@ -216,9 +249,12 @@ impl<E: EthSpec> EpochTransition<E> for InactivityUpdates {
     fn run(state: &mut BeaconState<E>, spec: &ChainSpec) -> Result<(), EpochProcessingError> {
         match state {
             BeaconState::Base(_) => Ok(()),
-            BeaconState::Altair(_) | BeaconState::Merge(_) => altair::process_inactivity_updates(
+            BeaconState::Altair(_) | BeaconState::Merge(_) => process_epoch_single_pass(
                 state,
-                &altair::ParticipationCache::new(state, spec).unwrap(),
+                SinglePassConfig {
+                    inactivity_updates: true,
+                    ..SinglePassConfig::disable_all()
+                },
                 spec,
             ),
         }
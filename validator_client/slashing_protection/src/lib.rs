@@ -4,6 +4,7 @@ mod extra_interchange_tests;
 pub mod interchange;
 pub mod interchange_test;
 mod parallel_tests;
+mod pruning_tests;
 mod registration_tests;
 mod signed_attestation;
 mod signed_block;
@@ -13,7 +14,7 @@ pub mod test_utils;
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
 pub use crate::slashing_database::{
-    InterchangeError, InterchangeImportOutcome, SlashingDatabase,
+    IntegrityReport, InterchangeError, InterchangeImportOutcome, PruneSummary, SlashingDatabase,
     SUPPORTED_INTERCHANGE_FORMAT_VERSION,
 };
 use rusqlite::Error as SQLError;
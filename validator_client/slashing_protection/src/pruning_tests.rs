@@ -0,0 +1,120 @@
+#![cfg(test)]
+
+use crate::attestation_tests::attestation_data_builder;
+use crate::block_tests::block;
+use crate::test_utils::*;
+use crate::*;
+use rusqlite::params;
+use tempfile::tempdir;
+use types::{Epoch, Slot};
+
+fn sign_history(slashing_db: &SlashingDatabase, pubkey: &PublicKeyBytes, num_epochs: u64) {
+    for epoch in 0..num_epochs {
+        slashing_db
+            .check_and_insert_attestation(
+                pubkey,
+                &attestation_data_builder(epoch, epoch + 1),
+                DEFAULT_DOMAIN,
+            )
+            .unwrap();
+        slashing_db
+            .check_and_insert_block_proposal(pubkey, &block(epoch + 1), DEFAULT_DOMAIN)
+            .unwrap();
+    }
+}
+
+#[test]
+fn prune_all_validators_includes_disabled() {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let enabled = pubkey(0);
+    let disabled = pubkey(1);
+    slashing_db
+        .register_validators([enabled, disabled].iter())
+        .unwrap();
+    sign_history(&slashing_db, &enabled, 10);
+    sign_history(&slashing_db, &disabled, 10);
+
+    let disabled_id = slashing_db.get_validator_id(&disabled).unwrap();
+    slashing_db
+        .with_transaction(|txn| slashing_db.update_validator_status(txn, disabled_id, false))
+        .unwrap();
+
+    let summary = slashing_db
+        .prune_all_validators(Slot::new(6), Epoch::new(6))
+        .unwrap();
+
+    // Slots 1..=5 and targets 1..=5 are pruned for both validators.
+    assert_eq!(
+        summary,
+        PruneSummary {
+            blocks_pruned: 10,
+            attestations_pruned: 10,
+        }
+    );
+    slashing_db.vacuum().unwrap();
+    assert!(slashing_db.check_integrity().unwrap().is_consistent());
+}
+
+#[test]
+fn prune_all_validators_preserves_minimal_export() {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let pk = pubkey(0);
+    slashing_db.register_validator(pk).unwrap();
+    sign_history(&slashing_db, &pk, 5);
+
+    let before = slashing_db
+        .export_all_interchange_info(DEFAULT_GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+
+    // Pruning beyond the latest messages must never remove the maximum block and attestation.
+    slashing_db
+        .prune_all_validators(Slot::new(100), Epoch::new(100))
+        .unwrap();
+
+    let after = slashing_db
+        .export_all_interchange_info(DEFAULT_GENESIS_VALIDATORS_ROOT)
+        .unwrap();
+    assert_eq!(after.data[0].signed_blocks.len(), 1);
+    assert_eq!(after.data[0].signed_attestations.len(), 1);
+    assert!(before.minify().unwrap().equiv(&after.minify().unwrap()));
+}
+
+#[test]
+fn integrity_check_detects_surround_vote() {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let pk = pubkey(0);
+    let empty = pubkey(1);
+    slashing_db.register_validators([pk, empty].iter()).unwrap();
+    sign_history(&slashing_db, &pk, 3);
+
+    let report = slashing_db.check_integrity().unwrap();
+    assert!(report.is_consistent());
+    assert_eq!(report.validators_without_records, vec![empty]);
+
+    // Bypass the slashing checks to insert an attestation surrounding 1 => 2.
+    let validator_id = slashing_db.get_validator_id(&pk).unwrap();
+    slashing_db
+        .with_transaction(|txn| {
+            txn.execute(
+                "INSERT INTO signed_attestations (validator_id, source_epoch, target_epoch, signing_root)
+                 VALUES (?1, 0, 5, ?2)",
+                params![validator_id, Hash256::repeat_byte(1).as_bytes()],
+            )
+            .map_err(NotSafe::from)
+        })
+        .unwrap();
+
+    let report = slashing_db.check_integrity().unwrap();
+    assert!(!report.is_consistent());
+    assert_eq!(report.surround_votes, vec![pk]);
+    assert_eq!(report.watermark_violations, vec![]);
+}
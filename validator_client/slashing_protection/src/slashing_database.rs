@@ -176,6 +176,19 @@ impl SlashingDatabase {
 
     /// Execute a database transaction as a closure, committing if `f` returns `Ok`.
     pub fn with_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&Transaction) -> Result<T, E>,
+        E: From<NotSafe>,
+    {
+        self.with_transaction_or_dry_run(false, f)
+    }
+
+    /// Execute a database transaction as a closure, committing if `f` returns `Ok` and `dry_run`
+    /// is `false`.
+    ///
+    /// With `dry_run` set the transaction is always rolled back, which allows checking whether a
+    /// set of changes would apply cleanly without persisting them.
+    pub fn with_transaction_or_dry_run<T, E, F>(&self, dry_run: bool, f: F) -> Result<T, E>
     where
         F: FnOnce(&Transaction) -> Result<T, E>,
         E: From<NotSafe>,
@@ -183,7 +196,9 @@ impl SlashingDatabase {
         let mut conn = self.conn_pool.get().map_err(NotSafe::from)?;
         let txn = conn.transaction().map_err(NotSafe::from)?;
         let value = f(&txn)?;
-        txn.commit().map_err(NotSafe::from)?;
+        if !dry_run {
+            txn.commit().map_err(NotSafe::from)?;
+        }
         Ok(value)
     }
 
@@ -678,6 +693,23 @@ impl SlashingDatabase {
         &self,
         interchange: Interchange,
         genesis_validators_root: Hash256,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
+        // Create a single transaction for the entire batch, which will only be committed if
+        // all records are imported successfully.
+        self.with_transaction(|txn| {
+            self.import_interchange_info_in_txn(interchange, genesis_validators_root, txn)
+        })
+    }
+
+    /// Import an interchange within an existing transaction.
+    ///
+    /// An error is returned if *any* record cannot be imported, in which case the caller must not
+    /// commit `txn`.
+    pub fn import_interchange_info_in_txn(
+        &self,
+        interchange: Interchange,
+        genesis_validators_root: Hash256,
+        txn: &Transaction,
     ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
         let version = interchange.metadata.interchange_format_version;
         if version != SUPPORTED_INTERCHANGE_FORMAT_VERSION {
@@ -691,17 +723,12 @@ impl SlashingDatabase {
             });
         }
 
-        // Create a single transaction for the entire batch, which will only be committed if
-        // all records are imported successfully.
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction()?;
-
         let mut import_outcomes = vec![];
         let mut commit = true;
 
         for record in interchange.data {
             let pubkey = record.pubkey;
-            match self.import_interchange_record(record, &txn) {
+            match self.import_interchange_record(record, txn) {
                 Ok(summary) => {
                     import_outcomes.push(InterchangeImportOutcome::Success { pubkey, summary });
                 }
@@ -713,7 +740,6 @@ impl SlashingDatabase {
         }
 
         if commit {
-            txn.commit()?;
             Ok(import_outcomes)
         } else {
             Err(InterchangeError::AtomicBatchAborted(import_outcomes))
@@ -1012,6 +1038,158 @@ impl SlashingDatabase {
         Ok(())
     }
 
+    /// Prune the signed blocks and attestations of *every* registered validator.
+    ///
+    /// Unlike `prune_all_signed_blocks` and `prune_all_signed_attestations` this includes
+    /// disabled validators, and validators which are no longer known to the validator client.
+    ///
+    /// As with the other pruning functions, the maximum block and the maximum attestation of each
+    /// validator are never pruned. This means that a pruned database exports the same minimal
+    /// interchange as it did prior to pruning.
+    pub fn prune_all_validators(
+        &self,
+        new_min_slot: Slot,
+        new_min_target: Epoch,
+    ) -> Result<PruneSummary, NotSafe> {
+        self.with_transaction(|txn| {
+            let blocks_pruned = txn.execute(
+                "DELETE FROM signed_blocks
+                 WHERE
+                    slot < ?1 AND
+                    slot < (SELECT MAX(slot)
+                            FROM signed_blocks AS max_blocks
+                            WHERE max_blocks.validator_id = signed_blocks.validator_id)",
+                params![new_min_slot],
+            )?;
+
+            let attestations_pruned = txn.execute(
+                "DELETE FROM signed_attestations
+                 WHERE
+                    target_epoch < ?1 AND
+                    target_epoch < (SELECT MAX(target_epoch)
+                                    FROM signed_attestations AS max_atts
+                                    WHERE max_atts.validator_id = signed_attestations.validator_id)",
+                params![new_min_target],
+            )?;
+
+            Ok(PruneSummary {
+                blocks_pruned,
+                attestations_pruned,
+            })
+        })
+    }
+
+    /// Rebuild the database file, returning the space freed by pruning to the file system.
+    ///
+    /// SQLite does not shrink the database file when rows are deleted, so this should be run
+    /// after pruning a large number of records. It requires free disk space of up to twice the
+    /// size of the database.
+    pub fn vacuum(&self) -> Result<(), NotSafe> {
+        let conn = self.conn_pool.get()?;
+        conn.execute("VACUUM", params![])?;
+        Ok(())
+    }
+
+    /// Check the internal consistency of the database.
+    ///
+    /// This checks the SQLite file structure, and that the stored data respects the invariants
+    /// that the slashing protection checks rely upon. A database that fails these checks should
+    /// not be used for signing until the cause has been investigated.
+    pub fn check_integrity(&self) -> Result<IntegrityReport, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+
+        let sqlite_errors = txn
+            .prepare("PRAGMA integrity_check")?
+            .query_map(params![], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?
+            .into_iter()
+            .filter(|message| message != "ok")
+            .collect();
+
+        let foreign_key_violations = txn
+            .prepare("PRAGMA foreign_key_check")?
+            .query_map(params![], |_| Ok(()))?
+            .count();
+
+        // Attestations are the only records which may be stored without a validator ID.
+        let orphaned_attestations = txn
+            .prepare(
+                "SELECT COUNT(*)
+                 FROM signed_attestations
+                 WHERE validator_id IS NULL
+                    OR validator_id NOT IN (SELECT id FROM validators)",
+            )?
+            .query_row(params![], |row| row.get(0))?;
+
+        let pubkey_query = |sql: &str| -> Result<Vec<PublicKeyBytes>, NotSafe> {
+            txn.prepare(sql)?
+                .query_map(params![], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?
+                .into_iter()
+                .map(|pubkey_str| {
+                    pubkey_str
+                        .parse()
+                        .map_err(|e| NotSafe::SQLError(format!("invalid pubkey: {:?}", e)))
+                })
+                .collect()
+        };
+
+        let source_exceeds_target = pubkey_query(
+            "SELECT DISTINCT validators.public_key
+             FROM signed_attestations
+             INNER JOIN validators ON validators.id = signed_attestations.validator_id
+             WHERE source_epoch > target_epoch",
+        )?;
+
+        let surround_votes = pubkey_query(
+            "SELECT DISTINCT validators.public_key
+             FROM signed_attestations AS outer_att
+             INNER JOIN signed_attestations AS inner_att
+                ON inner_att.validator_id = outer_att.validator_id
+             INNER JOIN validators ON validators.id = outer_att.validator_id
+             WHERE outer_att.source_epoch < inner_att.source_epoch
+                AND outer_att.target_epoch > inner_att.target_epoch",
+        )?;
+
+        // The lower-bound checks treat the minimum source and minimum target as a single low
+        // watermark, which is only sound if one attestation holds both minimums.
+        let watermark_violations = pubkey_query(
+            "SELECT validators.public_key
+             FROM validators
+             INNER JOIN signed_attestations ON signed_attestations.validator_id = validators.id
+             GROUP BY validators.id
+             HAVING MIN(signed_attestations.source_epoch) <> (
+                SELECT min_target_att.source_epoch
+                FROM signed_attestations AS min_target_att
+                WHERE min_target_att.validator_id = validators.id
+                ORDER BY min_target_att.target_epoch ASC
+                LIMIT 1
+             )",
+        )?;
+
+        let validators_without_records = pubkey_query(
+            "SELECT public_key
+             FROM validators
+             WHERE id NOT IN (SELECT validator_id FROM signed_blocks)
+                AND id NOT IN (
+                    SELECT validator_id
+                    FROM signed_attestations
+                    WHERE validator_id IS NOT NULL
+                )",
+        )?;
+
+        Ok(IntegrityReport {
+            sqlite_errors,
+            foreign_key_violations,
+            orphaned_attestations,
+            source_exceeds_target,
+            surround_votes,
+            watermark_violations,
+            validators_without_records,
+        })
+    }
+
     pub fn num_validator_rows(&self) -> Result<u32, NotSafe> {
         let mut conn = self.conn_pool.get()?;
         let txn = conn.transaction()?;
@@ -1104,6 +1282,49 @@ impl ValidatorSummary {
     }
 }
 
+/// The number of records removed by `SlashingDatabase::prune_all_validators`.
+#[derive(Debug, Default, PartialEq)]
+pub struct PruneSummary {
+    pub blocks_pruned: usize,
+    pub attestations_pruned: usize,
+}
+
+/// The result of `SlashingDatabase::check_integrity`.
+#[derive(Debug, Default, PartialEq)]
+pub struct IntegrityReport {
+    /// Messages from SQLite's `integrity_check`, excluding the "ok" message.
+    pub sqlite_errors: Vec<String>,
+    /// Number of rows which reference a non-existent row in another table.
+    pub foreign_key_violations: usize,
+    /// Number of attestations which do not belong to a registered validator.
+    pub orphaned_attestations: u32,
+    /// Validators with an attestation whose source epoch exceeds its target epoch.
+    pub source_exceeds_target: Vec<PublicKeyBytes>,
+    /// Validators with a pair of attestations where one surrounds the other.
+    pub surround_votes: Vec<PublicKeyBytes>,
+    /// Validators whose minimum source epoch does not belong to their minimum target attestation.
+    pub watermark_violations: Vec<PublicKeyBytes>,
+    /// Validators which are registered but have no signed blocks or attestations.
+    ///
+    /// This is not an error, but may indicate validators that have been removed from the
+    /// validator client.
+    pub validators_without_records: Vec<PublicKeyBytes>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if no inconsistencies were found.
+    ///
+    /// Validators without records are not considered inconsistent.
+    pub fn is_consistent(&self) -> bool {
+        self.sqlite_errors.is_empty()
+            && self.foreign_key_violations == 0
+            && self.orphaned_attestations == 0
+            && self.source_exceeds_target.is_empty()
+            && self.surround_votes.is_empty()
+            && self.watermark_violations.is_empty()
+    }
+}
+
 /// Take the maximum of `opt_x` and `y`, returning `y` if `opt_x` is `None`.
 fn max_or<T: Copy + Ord>(opt_x: Option<T>, y: T) -> T {
     opt_x.map_or(y, |x| std::cmp::max(x, y))
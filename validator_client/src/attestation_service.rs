@@ -5,12 +5,15 @@ use crate::{
     validator_store::ValidatorStore,
 };
 use environment::RuntimeContext;
+use eth2::types::{EventKind, EventTopic};
 use futures::future::join_all;
-use slog::{crit, error, info, trace};
+use futures::{Stream, StreamExt};
+use slog::{crit, debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::Arc;
+use tokio::sync::watch;
 use tokio::time::{sleep, sleep_until, Duration, Instant};
 use tree_hash::TreeHash;
 use types::{
@@ -25,6 +28,8 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
+    attest_on_head_event: bool,
+    aggregation_offset: Option<Duration>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
@@ -35,6 +40,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             slot_clock: None,
             beacon_nodes: None,
             context: None,
+            attest_on_head_event: false,
+            aggregation_offset: None,
         }
     }
 
@@ -63,7 +70,23 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    /// Attest as soon as the beacon node imports a block for the current slot, rather than
+    /// always waiting until 1/3rd of the way through the slot.
+    pub fn attest_on_head_event(mut self, attest_on_head_event: bool) -> Self {
+        self.attest_on_head_event = attest_on_head_event;
+        self
+    }
+
+    /// Produce aggregates at `offset` from the start of the slot, rather than 2/3rds of the way
+    /// through the slot.
+    pub fn aggregation_offset(mut self, offset: Option<Duration>) -> Self {
+        self.aggregation_offset = offset;
+        self
+    }
+
     pub fn build(self) -> Result<AttestationService<T, E>, String> {
+        let (head_slot_tx, head_slot_rx) = watch::channel(None);
+
         Ok(AttestationService {
             inner: Arc::new(Inner {
                 duties_service: self
@@ -81,6 +104,10 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
+                attest_on_head_event: self.attest_on_head_event,
+                aggregation_offset: self.aggregation_offset,
+                head_slot_tx,
+                head_slot_rx,
             }),
         })
     }
@@ -93,10 +120,37 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    attest_on_head_event: bool,
+    aggregation_offset: Option<Duration>,
+    /// The slot of the most recent head block announced by the beacon node.
+    head_slot_tx: watch::Sender<Option<Slot>>,
+    head_slot_rx: watch::Receiver<Option<Slot>>,
+}
+
+/// The event which caused attestations to be produced for a slot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AttestationTrigger {
+    /// The beacon node imported a block for the slot.
+    HeadEvent,
+    /// 1/3rd of the slot elapsed without a block for the slot.
+    Deadline,
+}
+
+impl AttestationTrigger {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AttestationTrigger::HeadEvent => metrics::HEAD_EVENT,
+            AttestationTrigger::Deadline => metrics::DEADLINE,
+        }
+    }
 }
 
 /// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
 ///
+/// If `attest_on_head_event` is set, attestations are instead produced as soon as the beacon node
+/// announces a head block for the current slot, falling back to 1/3rd of the way through the slot
+/// if no such block arrives.
+///
 /// If any validators are on the same committee, a single attestation will be downloaded and
 /// returned to the beacon node. This attestation will have a signature from each of the
 /// validators.
@@ -131,21 +185,72 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .duration_to_next_slot()
             .ok_or("Unable to determine duration to next slot")?;
 
+        if let Some(offset) = self.aggregation_offset {
+            if offset <= slot_duration / 3 || offset >= slot_duration {
+                return Err(format!(
+                    "Aggregation offset of {}ms must be between 1/3rd of the slot and the \
+                     end of the slot",
+                    offset.as_millis()
+                ));
+            }
+        }
+
         info!(
             log,
             "Attestation production service started";
-            "next_update_millis" => duration_to_next_slot.as_millis()
+            "next_update_millis" => duration_to_next_slot.as_millis(),
+            "attest_on_head_event" => self.attest_on_head_event,
         );
 
         let executor = self.context.executor.clone();
 
+        if self.attest_on_head_event {
+            executor.spawn(
+                self.clone().head_event_listener(slot_duration),
+                "attestation_head_events",
+            );
+        }
+
         let interval_fut = async move {
             loop {
                 if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
+                    sleep(duration_to_next_slot).await;
                     let log = self.context.log();
 
-                    if let Err(e) = self.spawn_attestation_tasks(slot_duration) {
+                    let slot = match self.slot_clock.now() {
+                        Some(slot) => slot,
+                        None => {
+                            error!(log, "Failed to read slot clock");
+                            sleep(slot_duration).await;
+                            continue;
+                        }
+                    };
+
+                    // When attesting on head events, group the duties for the slot before
+                    // waiting so that attestations can be produced without delay once a block
+                    // arrives.
+                    let early_duties = if self.attest_on_head_event {
+                        Some(self.duties_by_committee_index(slot))
+                    } else {
+                        None
+                    };
+
+                    let trigger = self.wait_for_attestation_trigger(slot, slot_duration).await;
+                    metrics::inc_counter_vec(
+                        &metrics::ATTESTATION_SERVICE_TRIGGERS,
+                        &[trigger.as_str()],
+                    );
+
+                    // At the deadline there is no rush, so pick up any duties or selection proofs
+                    // updated whilst waiting.
+                    let duties_by_committee_index = match (trigger, early_duties) {
+                        (AttestationTrigger::HeadEvent, Some(duties)) => duties,
+                        _ => self.duties_by_committee_index(slot),
+                    };
+
+                    if let Err(e) =
+                        self.spawn_attestation_tasks(slot, duties_by_committee_index, slot_duration)
+                    {
                         crit!(
                             log,
                             "Failed to spawn attestation tasks";
@@ -170,33 +275,100 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         Ok(())
     }
 
+    /// Wait until attestations for `slot` should be produced.
+    ///
+    /// This is 1/3rd of the way through the slot, or earlier if `attest_on_head_event` is set and
+    /// the beacon node announces a head block for `slot`.
+    async fn wait_for_attestation_trigger(
+        &self,
+        slot: Slot,
+        slot_duration: Duration,
+    ) -> AttestationTrigger {
+        let deadline = Instant::now()
+            + self
+                .slot_clock
+                .start_of(slot)
+                .map(|slot_start| slot_start + slot_duration / 3)
+                .and_then(|deadline| deadline.checked_sub(self.slot_clock.now_duration()?))
+                .unwrap_or_else(|| Duration::from_secs(0));
+
+        if !self.attest_on_head_event {
+            sleep_until(deadline).await;
+            return AttestationTrigger::Deadline;
+        }
+
+        wait_for_head_or_deadline(self.head_slot_rx.clone(), slot, deadline).await
+    }
+
+    /// Subscribe to head events from the beacon nodes, recording the slot of each new head.
+    ///
+    /// Events are only received from a single beacon node at a time, so the block may not yet
+    /// have been imported by a fallback node which is used to produce the attestation data.
+    async fn head_event_listener(self, slot_duration: Duration) {
+        let log = self.context.log();
+        let head_slot_tx = &self.head_slot_tx;
+
+        loop {
+            let result = self
+                .beacon_nodes
+                .first_success(RequireSynced::No, |beacon_node| async move {
+                    let events = beacon_node
+                        .get_events::<E>(&[EventTopic::Head])
+                        .await
+                        .map_err(|e| format!("Failed to subscribe to head events: {:?}", e))?;
+
+                    forward_head_events(events, head_slot_tx, log).await
+                })
+                .await;
+
+            if let Err(e) = result {
+                warn!(
+                    log,
+                    "Head event stream failed";
+                    "info" => "attestations will be produced 1/3rd of the way through the slot",
+                    "error" => %e,
+                );
+            }
+
+            // Avoid hammering the beacon nodes if the stream closes immediately.
+            sleep(slot_duration).await;
+        }
+    }
+
+    /// Returns the attestation duties for `slot`, grouped by committee index.
+    fn duties_by_committee_index(&self, slot: Slot) -> HashMap<CommitteeIndex, Vec<DutyAndProof>> {
+        self.duties_service.attesters(slot).into_iter().fold(
+            HashMap::new(),
+            |mut map, duty_and_proof| {
+                map.entry(duty_and_proof.duty.committee_index)
+                    .or_insert_with(Vec::new)
+                    .push(duty_and_proof);
+                map
+            },
+        )
+    }
+
     /// For each each required attestation, spawn a new task that downloads, signs and uploads the
     /// attestation to the beacon node.
-    fn spawn_attestation_tasks(&self, slot_duration: Duration) -> Result<(), String> {
-        let slot = self.slot_clock.now().ok_or("Failed to read slot clock")?;
+    fn spawn_attestation_tasks(
+        &self,
+        slot: Slot,
+        duties_by_committee_index: HashMap<CommitteeIndex, Vec<DutyAndProof>>,
+        slot_duration: Duration,
+    ) -> Result<(), String> {
         let duration_to_next_slot = self
             .slot_clock
             .duration_to_next_slot()
             .ok_or("Unable to determine duration to next slot")?;
 
         // If a validator needs to publish an aggregate attestation, they must do so at 2/3
-        // through the slot. This delay triggers at this time
+        // through the slot (unless configured otherwise). This delay triggers at this time
+        let aggregation_offset = self.aggregation_offset.unwrap_or(slot_duration * 2 / 3);
         let aggregate_production_instant = Instant::now()
             + duration_to_next_slot
-                .checked_sub(slot_duration / 3)
+                .checked_sub(slot_duration - aggregation_offset)
                 .unwrap_or_else(|| Duration::from_secs(0));
 
-        let duties_by_committee_index: HashMap<CommitteeIndex, Vec<DutyAndProof>> = self
-            .duties_service
-            .attesters(slot)
-            .into_iter()
-            .fold(HashMap::new(), |mut map, duty_and_proof| {
-                map.entry(duty_and_proof.duty.committee_index)
-                    .or_insert_with(Vec::new)
-                    .push(duty_and_proof);
-                map
-            });
-
         // For each committee index for this slot:
         //
         // - Create and publish an `Attestation` for all required validators.
@@ -425,16 +597,19 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             })
             .await
         {
-            Ok(()) => info!(
-                log,
-                "Successfully published attestations";
-                "count" => attestations.len(),
-                "validator_indices" => ?validator_indices,
-                "head_block" => ?attestation_data.beacon_block_root,
-                "committee_index" => attestation_data.index,
-                "slot" => attestation_data.slot.as_u64(),
-                "type" => "unaggregated",
-            ),
+            Ok(()) => {
+                self.observe_duty_delay(metrics::ATTESTATIONS, slot);
+                info!(
+                    log,
+                    "Successfully published attestations";
+                    "count" => attestations.len(),
+                    "validator_indices" => ?validator_indices,
+                    "head_block" => ?attestation_data.beacon_block_root,
+                    "committee_index" => attestation_data.index,
+                    "slot" => attestation_data.slot.as_u64(),
+                    "type" => "unaggregated",
+                )
+            }
             Err(e) => error!(
                 log,
                 "Unable to publish attestations";
@@ -547,6 +722,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 .await
             {
                 Ok(()) => {
+                    self.observe_duty_delay(metrics::AGGREGATES, attestation_data.slot);
                     for signed_aggregate_and_proof in signed_aggregate_and_proofs {
                         let attestation = &signed_aggregate_and_proof.message.aggregate;
                         info!(
@@ -581,6 +757,17 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         Ok(())
     }
 
+    /// Record the time elapsed since the start of `slot` at which a `duty` was published.
+    fn observe_duty_delay(&self, duty: &str, slot: Slot) {
+        if let Some(delay) = self
+            .slot_clock
+            .start_of(slot)
+            .and_then(|slot_start| self.slot_clock.now_duration()?.checked_sub(slot_start))
+        {
+            metrics::observe_timer_vec(&metrics::ATTESTATION_DUTY_DELAY_TIMES, &[duty], delay);
+        }
+    }
+
     /// Spawn a blocking task to run the slashing protection pruning process.
     ///
     /// Start the task at `pruning_instant` to avoid interference with other tasks.
@@ -608,11 +795,102 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
     }
 }
 
+/// Wait until `head_slot_rx` announces a head block at or after `slot`, or until `deadline`,
+/// whichever comes first.
+async fn wait_for_head_or_deadline(
+    mut head_slot_rx: watch::Receiver<Option<Slot>>,
+    slot: Slot,
+    deadline: Instant,
+) -> AttestationTrigger {
+    loop {
+        if head_slot_rx
+            .borrow()
+            .map_or(false, |head_slot| head_slot >= slot)
+        {
+            return AttestationTrigger::HeadEvent;
+        }
+
+        tokio::select! {
+            _ = sleep_until(deadline) => return AttestationTrigger::Deadline,
+            result = head_slot_rx.changed() => {
+                // The sender lives as long as the service, but don't spin if it is dropped.
+                if result.is_err() {
+                    sleep_until(deadline).await;
+                    return AttestationTrigger::Deadline;
+                }
+            }
+        }
+    }
+}
+
+/// Send the slot of each head event in `events` to `head_slot_tx`.
+///
+/// Returns when the stream ends, or with an error if the stream yields one.
+async fn forward_head_events<E: EthSpec>(
+    events: impl Stream<Item = Result<EventKind<E>, eth2::Error>>,
+    head_slot_tx: &watch::Sender<Option<Slot>>,
+    log: &Logger,
+) -> Result<(), String> {
+    futures::pin_mut!(events);
+
+    while let Some(event) = events.next().await {
+        match event {
+            Ok(EventKind::Head(head)) => {
+                debug!(
+                    log,
+                    "Received head event";
+                    "slot" => head.slot,
+                    "block" => ?head.block,
+                );
+                // Sending only fails if there are no receivers, which is impossible whilst the
+                // service holds `head_slot_rx`.
+                let _ = head_slot_tx.send(Some(head.slot));
+            }
+            Ok(_) => (),
+            Err(e) => {
+                return Err(format!("Error in head event stream: {:?}", e));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use environment::null_logger;
+    use eth2::types::{SseFinalizedCheckpoint, SseHead};
     use futures::future::FutureExt;
     use parking_lot::RwLock;
+    use types::{Epoch, Hash256, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    /// Long enough that a test triggered by a head event never reaches it.
+    const LONG_DEADLINE: Duration = Duration::from_secs(60);
+    const SHORT_DEADLINE: Duration = Duration::from_millis(50);
+
+    fn head_event(slot: u64) -> EventKind<E> {
+        EventKind::Head(SseHead {
+            slot: Slot::new(slot),
+            block: Hash256::repeat_byte(slot as u8),
+            state: Hash256::zero(),
+            current_duty_dependent_root: Hash256::zero(),
+            previous_duty_dependent_root: Hash256::zero(),
+            epoch_transition: false,
+            execution_optimistic: false,
+        })
+    }
+
+    fn finalized_event() -> EventKind<E> {
+        EventKind::FinalizedCheckpoint(SseFinalizedCheckpoint {
+            block: Hash256::zero(),
+            state: Hash256::zero(),
+            epoch: Epoch::new(0),
+            execution_optimistic: false,
+        })
+    }
 
     /// This test is to ensure that a `tokio_timer::Sleep` with an instant in the past will still
     /// trigger.
@@ -631,4 +909,96 @@ mod tests {
             "state should have been updated"
         );
     }
+
+    #[tokio::test]
+    async fn head_event_triggers_before_deadline() {
+        let (head_slot_tx, head_slot_rx) = watch::channel(None);
+        let slot = Slot::new(10);
+        let deadline = Instant::now() + LONG_DEADLINE;
+
+        let wait = tokio::spawn(wait_for_head_or_deadline(head_slot_rx, slot, deadline));
+        head_slot_tx.send(Some(slot)).unwrap();
+
+        assert_eq!(wait.await.unwrap(), AttestationTrigger::HeadEvent);
+        assert!(Instant::now() < deadline);
+    }
+
+    #[tokio::test]
+    async fn head_event_for_earlier_slot_is_ignored() {
+        let (head_slot_tx, head_slot_rx) = watch::channel(None);
+        let slot = Slot::new(10);
+        let deadline = Instant::now() + SHORT_DEADLINE;
+
+        let wait = tokio::spawn(wait_for_head_or_deadline(head_slot_rx, slot, deadline));
+        head_slot_tx.send(Some(slot - 1)).unwrap();
+
+        assert_eq!(wait.await.unwrap(), AttestationTrigger::Deadline);
+        assert!(Instant::now() >= deadline);
+    }
+
+    #[tokio::test]
+    async fn head_event_received_before_waiting_triggers_immediately() {
+        let (_head_slot_tx, head_slot_rx) = watch::channel(Some(Slot::new(11)));
+        let deadline = Instant::now() + LONG_DEADLINE;
+
+        let trigger = wait_for_head_or_deadline(head_slot_rx, Slot::new(10), deadline).await;
+
+        assert_eq!(trigger, AttestationTrigger::HeadEvent);
+    }
+
+    #[tokio::test]
+    async fn deadline_triggers_without_head_event() {
+        let (_head_slot_tx, head_slot_rx) = watch::channel(None);
+        let deadline = Instant::now() + SHORT_DEADLINE;
+
+        let trigger = wait_for_head_or_deadline(head_slot_rx, Slot::new(10), deadline).await;
+
+        assert_eq!(trigger, AttestationTrigger::Deadline);
+        assert!(Instant::now() >= deadline);
+    }
+
+    #[tokio::test]
+    async fn deadline_triggers_when_head_sender_dropped() {
+        let (head_slot_tx, head_slot_rx) = watch::channel(None);
+        let deadline = Instant::now() + SHORT_DEADLINE;
+        drop(head_slot_tx);
+
+        let trigger = wait_for_head_or_deadline(head_slot_rx, Slot::new(10), deadline).await;
+
+        assert_eq!(trigger, AttestationTrigger::Deadline);
+        assert!(Instant::now() >= deadline);
+    }
+
+    #[tokio::test]
+    async fn head_events_are_forwarded() {
+        let log = null_logger().unwrap();
+        let (head_slot_tx, head_slot_rx) = watch::channel(None);
+        let events = futures::stream::iter(vec![
+            Ok(head_event(1)),
+            Ok(finalized_event()),
+            Ok(head_event(2)),
+        ]);
+
+        forward_head_events(events, &head_slot_tx, &log)
+            .await
+            .unwrap();
+
+        assert_eq!(*head_slot_rx.borrow(), Some(Slot::new(2)));
+    }
+
+    #[tokio::test]
+    async fn head_event_stream_error_is_returned() {
+        let log = null_logger().unwrap();
+        let (head_slot_tx, head_slot_rx) = watch::channel(None);
+        let events = futures::stream::iter(vec![
+            Ok(head_event(1)),
+            Err(eth2::Error::InvalidServerSentEvent("bad event".into())),
+            Ok(head_event(2)),
+        ]);
+
+        assert!(forward_head_events(events, &head_slot_tx, &log)
+            .await
+            .is_err());
+        assert_eq!(*head_slot_rx.borrow(), Some(Slot::new(1)));
+    }
 }
@@ -0,0 +1,223 @@
+//! Per-beacon node HTTP client configuration.
+//!
+//! Allows each beacon node to be configured with a TLS client certificate, a bearer token and a
+//! set of custom headers, for beacon nodes which sit behind an authenticating proxy. The
+//! configuration is loaded from a YAML file with the following structure:
+//!
+//! ```yaml
+//! - url: https://bn-1.example.com
+//!   tls_client_cert: /path/to/client.crt
+//!   tls_client_key: /path/to/client.key
+//!   bearer_token_path: /path/to/token.txt
+//!   headers:
+//!     X-Custom-Header: value
+//! ```
+//!
+//! All fields except `url` are optional.
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION},
+    ClientBuilder, Identity,
+};
+use sensitive_url::SensitiveUrl;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BeaconNodeClientConfig {
+    /// The beacon node to which this configuration applies. Must also be present in
+    /// `--beacon-nodes`.
+    pub url: SensitiveUrl,
+    /// A PEM-encoded client certificate to present to the beacon node.
+    #[serde(default)]
+    pub tls_client_cert: Option<PathBuf>,
+    /// The PEM-encoded PKCS #8 private key for `tls_client_cert`.
+    #[serde(default)]
+    pub tls_client_key: Option<PathBuf>,
+    /// A file containing a token to be sent in an `Authorization: Bearer` header.
+    #[serde(default)]
+    pub bearer_token_path: Option<PathBuf>,
+    /// Additional headers to be sent with every request.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl BeaconNodeClientConfig {
+    /// Load and validate a list of configurations from a YAML file.
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, String> {
+        let file = File::open(path.as_ref()).map_err(|e| {
+            format!(
+                "Unable to open beacon node client config {:?}: {}",
+                path.as_ref(),
+                e
+            )
+        })?;
+        let configs: Vec<Self> = serde_yaml::from_reader(file)
+            .map_err(|e| format!("Unable to parse beacon node client config: {:?}", e))?;
+
+        for (i, config) in configs.iter().enumerate() {
+            if configs[..i].iter().any(|other| other.url == config.url) {
+                return Err(format!(
+                    "Duplicate beacon node client config for {}",
+                    config.url
+                ));
+            }
+            config.validate()?;
+        }
+
+        Ok(configs)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.tls_client_cert.is_some() != self.tls_client_key.is_some() {
+            return Err(format!(
+                "Both tls_client_cert and tls_client_key must be provided for {}",
+                self.url
+            ));
+        }
+
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_str(name)
+                .map_err(|e| format!("Invalid header name {:?} for {}: {}", name, self.url, e))?;
+            if name == AUTHORIZATION && self.bearer_token_path.is_some() {
+                return Err(format!(
+                    "Authorization header conflicts with bearer_token_path for {}",
+                    self.url
+                ));
+            }
+            HeaderValue::from_str(value).map_err(|e| {
+                format!("Invalid value for header {} for {}: {}", name, self.url, e)
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Add the client certificate and headers to `builder`.
+    ///
+    /// Files are read each time this function is called, so that a missing or invalid file is
+    /// reported when the HTTP client is built.
+    pub fn apply(&self, mut builder: ClientBuilder) -> Result<ClientBuilder, String> {
+        if let (Some(cert_path), Some(key_path)) = (&self.tls_client_cert, &self.tls_client_key) {
+            let cert = fs::read(cert_path)
+                .map_err(|e| format!("Unable to read client certificate {:?}: {}", cert_path, e))?;
+            let mut pem = fs::read(key_path)
+                .map_err(|e| format!("Unable to read client key {:?}: {}", key_path, e))?;
+            pem.push(b'\n');
+            pem.extend_from_slice(&cert);
+            let identity = Identity::from_pem(&pem)
+                .map_err(|e| format!("Unable to parse client certificate or key: {}", e))?;
+            // A PEM identity is only supported by the rustls backend.
+            builder = builder.use_rustls_tls().identity(identity);
+        }
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_str(name)
+                .map_err(|e| format!("Invalid header name {:?}: {}", name, e))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| format!("Invalid value for header {}: {}", name, e))?;
+            headers.insert(name, value);
+        }
+
+        if let Some(token_path) = &self.bearer_token_path {
+            let token = fs::read_to_string(token_path)
+                .map_err(|e| format!("Unable to read bearer token {:?}: {}", token_path, e))?;
+            let mut value = HeaderValue::from_str(&format!("Bearer {}", token.trim()))
+                .map_err(|_| format!("Invalid bearer token in {:?}", token_path))?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+
+        Ok(builder.default_headers(headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_config(dir: &TempDir, contents: &str) -> PathBuf {
+        let path = dir.path().join("beacon-nodes.yaml");
+        File::create(&path)
+            .unwrap()
+            .write_all(contents.as_bytes())
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn load_valid_config() {
+        let dir = TempDir::new().unwrap();
+        let path = write_config(
+            &dir,
+            "- url: https://bn-1.example.com\n  \
+               tls_client_cert: /client.crt\n  \
+               tls_client_key: /client.key\n  \
+               headers:\n    X-Api-Key: secret\n\
+             - url: http://localhost:5052\n  \
+               bearer_token_path: /token.txt\n",
+        );
+
+        let configs = BeaconNodeClientConfig::load_file(path).unwrap();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(
+            configs[0].tls_client_cert,
+            Some(PathBuf::from("/client.crt"))
+        );
+        assert_eq!(configs[0].headers.get("X-Api-Key").unwrap(), "secret");
+        assert_eq!(
+            configs[1].bearer_token_path,
+            Some(PathBuf::from("/token.txt"))
+        );
+        assert!(configs[1].headers.is_empty());
+    }
+
+    #[test]
+    fn reject_invalid_configs() {
+        let dir = TempDir::new().unwrap();
+        for contents in [
+            // Certificate without a key.
+            "- url: https://bn-1.example.com\n  tls_client_cert: /client.crt\n",
+            // Duplicate URL.
+            "- url: https://bn-1.example.com\n- url: https://bn-1.example.com\n",
+            // Invalid header name.
+            "- url: https://bn-1.example.com\n  headers:\n    \"Bad Header\": value\n",
+            // Conflicting authorization.
+            "- url: https://bn-1.example.com\n  bearer_token_path: /token.txt\n  \
+               headers:\n    Authorization: Basic abc\n",
+            // Unknown field.
+            "- url: https://bn-1.example.com\n  token: abc\n",
+        ] {
+            let path = write_config(&dir, contents);
+            assert!(
+                BeaconNodeClientConfig::load_file(path).is_err(),
+                "{} should be invalid",
+                contents
+            );
+        }
+    }
+
+    #[test]
+    fn apply_client_identity() {
+        let tls_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../testing/web3signer_tests/tls/lighthouse");
+        let mut config = BeaconNodeClientConfig {
+            url: SensitiveUrl::parse("https://bn-1.example.com").unwrap(),
+            tls_client_cert: Some(tls_dir.join("cert.pem")),
+            tls_client_key: Some(tls_dir.join("key.key")),
+            bearer_token_path: None,
+            headers: BTreeMap::new(),
+        };
+        config.apply(ClientBuilder::new()).unwrap().build().unwrap();
+
+        // The certificate is not a valid private key.
+        config.tls_client_key = Some(tls_dir.join("cert.pem"));
+        assert!(config.apply(ClientBuilder::new()).is_err());
+    }
+}
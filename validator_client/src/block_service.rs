@@ -43,6 +43,7 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
     strict_fee_recipient: bool,
+    use_ssz_blocks: bool,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
@@ -55,6 +56,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
             graffiti: None,
             graffiti_file: None,
             strict_fee_recipient: false,
+            use_ssz_blocks: false,
         }
     }
 
@@ -93,6 +95,11 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
+    pub fn use_ssz_blocks(mut self, use_ssz_blocks: bool) -> Self {
+        self.use_ssz_blocks = use_ssz_blocks;
+        self
+    }
+
     pub fn build(self) -> Result<BlockService<T, E>, String> {
         Ok(BlockService {
             inner: Arc::new(Inner {
@@ -111,6 +118,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
                 graffiti: self.graffiti,
                 graffiti_file: self.graffiti_file,
                 strict_fee_recipient: self.strict_fee_recipient,
+                use_ssz_blocks: self.use_ssz_blocks,
             }),
         })
     }
@@ -125,6 +133,7 @@ pub struct Inner<T, E: EthSpec> {
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
     strict_fee_recipient: bool,
+    use_ssz_blocks: bool,
 }
 
 /// Attempts to produce attestations for any block producer(s) at the start of the epoch.
@@ -326,6 +335,8 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         let fee_recipient = self.validator_store.get_fee_recipient(&validator_pubkey);
 
         let strict_fee_recipient = self.strict_fee_recipient;
+        let use_ssz_blocks = self.use_ssz_blocks;
+        let spec = &self.context.eth2_config.spec;
         // Request block from first responsive beacon node.
         let block = self
             .beacon_nodes
@@ -336,40 +347,62 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                             &metrics::BLOCK_SERVICE_TIMES,
                             &[metrics::BEACON_BLOCK_HTTP_GET],
                         );
-                        beacon_node
-                            .get_validator_blocks::<E, Payload>(
-                                slot,
-                                randao_reveal_ref,
-                                graffiti.as_ref(),
-                            )
-                            .await
-                            .map_err(|e| {
-                                BlockError::Recoverable(format!(
-                                    "Error from beacon node when producing block: {:?}",
-                                    e
-                                ))
-                            })?
-                            .data
+                        if use_ssz_blocks {
+                            beacon_node
+                                .get_validator_blocks_ssz::<E, Payload>(
+                                    slot,
+                                    randao_reveal_ref,
+                                    graffiti.as_ref(),
+                                    spec,
+                                )
+                                .await
+                        } else {
+                            beacon_node
+                                .get_validator_blocks::<E, Payload>(
+                                    slot,
+                                    randao_reveal_ref,
+                                    graffiti.as_ref(),
+                                )
+                                .await
+                                .map(|response| response.data)
+                        }
+                        .map_err(|e| {
+                            BlockError::Recoverable(format!(
+                                "Error from beacon node when producing block: {:?}",
+                                e
+                            ))
+                        })?
                     }
                     BlockType::Blinded => {
                         let _get_timer = metrics::start_timer_vec(
                             &metrics::BLOCK_SERVICE_TIMES,
                             &[metrics::BLINDED_BEACON_BLOCK_HTTP_GET],
                         );
-                        beacon_node
-                            .get_validator_blinded_blocks::<E, Payload>(
-                                slot,
-                                randao_reveal_ref,
-                                graffiti.as_ref(),
-                            )
-                            .await
-                            .map_err(|e| {
-                                BlockError::Recoverable(format!(
-                                    "Error from beacon node when producing block: {:?}",
-                                    e
-                                ))
-                            })?
-                            .data
+                        if use_ssz_blocks {
+                            beacon_node
+                                .get_validator_blinded_blocks_ssz::<E, Payload>(
+                                    slot,
+                                    randao_reveal_ref,
+                                    graffiti.as_ref(),
+                                    spec,
+                                )
+                                .await
+                        } else {
+                            beacon_node
+                                .get_validator_blinded_blocks::<E, Payload>(
+                                    slot,
+                                    randao_reveal_ref,
+                                    graffiti.as_ref(),
+                                )
+                                .await
+                                .map(|response| response.data)
+                        }
+                        .map_err(|e| {
+                            BlockError::Recoverable(format!(
+                                "Error from beacon node when producing block: {:?}",
+                                e
+                            ))
+                        })?
                     }
                 };
 
@@ -410,30 +443,36 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                             &metrics::BLOCK_SERVICE_TIMES,
                             &[metrics::BEACON_BLOCK_HTTP_POST],
                         );
-                        beacon_node
-                            .post_beacon_blocks(&signed_block)
-                            .await
-                            .map_err(|e| {
-                                BlockError::Irrecoverable(format!(
-                                    "Error from beacon node when publishing block: {:?}",
-                                    e
-                                ))
-                            })?
+                        if self.use_ssz_blocks {
+                            beacon_node.post_beacon_blocks_ssz(&signed_block).await
+                        } else {
+                            beacon_node.post_beacon_blocks(&signed_block).await
+                        }
+                        .map_err(|e| {
+                            BlockError::Irrecoverable(format!(
+                                "Error from beacon node when publishing block: {:?}",
+                                e
+                            ))
+                        })?
                     }
                     BlockType::Blinded => {
                         let _post_timer = metrics::start_timer_vec(
                             &metrics::BLOCK_SERVICE_TIMES,
                             &[metrics::BLINDED_BEACON_BLOCK_HTTP_POST],
                         );
-                        beacon_node
-                            .post_beacon_blinded_blocks(&signed_block)
-                            .await
-                            .map_err(|e| {
-                                BlockError::Irrecoverable(format!(
-                                    "Error from beacon node when publishing block: {:?}",
-                                    e
-                                ))
-                            })?
+                        if self.use_ssz_blocks {
+                            beacon_node
+                                .post_beacon_blinded_blocks_ssz(&signed_block)
+                                .await
+                        } else {
+                            beacon_node.post_beacon_blinded_blocks(&signed_block).await
+                        }
+                        .map_err(|e| {
+                            BlockError::Irrecoverable(format!(
+                                "Error from beacon node when publishing block: {:?}",
+                                e
+                            ))
+                        })?
                     }
                 }
                 Ok::<_, BlockError>(())
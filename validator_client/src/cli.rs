@@ -112,6 +112,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                         in addition to the OS trust store. Commas must only be used as a \
                         delimiter, and must not be part of the certificate path.")
         )
+        .arg(
+            Arg::with_name("beacon-nodes-client-config")
+                .long("beacon-nodes-client-config")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("Path to a YAML file containing per-beacon node HTTP client configuration. \
+                        Each entry must have a `url` matching one of --beacon-nodes and may \
+                        specify a PEM `tls_client_cert` and PKCS #8 `tls_client_key` for mutual \
+                        TLS, a `bearer_token_path` containing a token to send in the \
+                        Authorization header, and a map of custom `headers`.")
+        )
         // This overwrites the graffiti configured in the beacon node.
         .arg(
             Arg::with_name("graffiti")
@@ -272,6 +283,14 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                      for payload construction, where a strict fee recipient check will still be applied.")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("use-ssz-blocks")
+                .long("use-ssz-blocks")
+                .help("Download blocks from and publish blocks to the beacon node as SSZ rather \
+                    than JSON, which reduces the time taken to propose a block. All beacon nodes \
+                    must support SSZ for the block production and publication endpoints.")
+                .takes_value(false),
+        )
         .arg(
             Arg::with_name("builder-registration-timestamp-override")
                 .long("builder-registration-timestamp-override")
@@ -289,5 +308,23 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     by this validator client. Note this will not necessarily be used if the gas limit \
                     set here moves too far from the previous block's gas limit. [default: 30,000,000]")
                 .requires("builder-proposals"),
-    )
+        )
+        .arg(
+            Arg::with_name("attest-on-head-event")
+                .long("attest-on-head-event")
+                .help("If this flag is set, the validator client will subscribe to head events \
+                    from the beacon node and attest as soon as a block for the current slot has \
+                    been imported. If no block arrives, attestations are produced 1/3rd of the way \
+                    through the slot as usual.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("aggregation-offset-ms")
+                .long("aggregation-offset-ms")
+                .value_name("MILLISECONDS")
+                .help("The time after the start of the slot at which aggregate attestations are \
+                    produced. Must fall between 1/3rd of the slot and the end of the slot. \
+                    [default: 2/3rds of the slot]")
+                .takes_value(true),
+        )
 }
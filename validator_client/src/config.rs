@@ -1,3 +1,4 @@
+use crate::beacon_node_client_config::BeaconNodeClientConfig;
 use crate::graffiti_file::GraffitiFile;
 use crate::{http_api, http_metrics};
 use clap::ArgMatches;
@@ -13,6 +14,7 @@ use slog::{info, warn, Logger};
 use std::fs;
 use std::net::IpAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 use types::{Address, GRAFFITI_BYTES_LEN};
 
 pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
@@ -61,9 +63,20 @@ pub struct Config {
     /// A list of custom certificates that the validator client will additionally use when
     /// connecting to a beacon node over SSL/TLS.
     pub beacon_nodes_tls_certs: Option<Vec<PathBuf>>,
+    /// Client certificates, bearer tokens and headers to use when connecting to specific beacon
+    /// nodes.
+    pub beacon_node_client_configs: Vec<BeaconNodeClientConfig>,
     /// Enabling this will make sure the validator client never signs a block whose `fee_recipient`
     /// does not match the `suggested_fee_recipient`.
     pub strict_fee_recipient: bool,
+    /// Download and publish blocks as SSZ rather than JSON.
+    pub use_ssz_blocks: bool,
+    /// If true, attest as soon as the beacon node announces a block for the current slot, rather
+    /// than waiting until 1/3rd of the way through the slot.
+    pub attest_on_head_event: bool,
+    /// Produce aggregate attestations at this offset from the start of the slot, instead of 2/3rds
+    /// of the way through the slot.
+    pub aggregation_offset: Option<Duration>,
 }
 
 impl Default for Config {
@@ -96,10 +109,14 @@ impl Default for Config {
             monitoring_api: None,
             enable_doppelganger_protection: false,
             beacon_nodes_tls_certs: None,
+            beacon_node_client_configs: vec![],
             builder_proposals: false,
             builder_registration_timestamp_override: None,
             gas_limit: None,
             strict_fee_recipient: false,
+            use_ssz_blocks: false,
+            attest_on_head_event: false,
+            aggregation_offset: None,
         }
     }
 }
@@ -223,6 +240,20 @@ impl Config {
             config.beacon_nodes_tls_certs = Some(tls_certs.split(',').map(PathBuf::from).collect());
         }
 
+        if let Some(path) = parse_optional::<PathBuf>(cli_args, "beacon-nodes-client-config")? {
+            let client_configs = BeaconNodeClientConfig::load_file(&path)?;
+            if let Some(unknown) = client_configs
+                .iter()
+                .find(|client_config| !config.beacon_nodes.contains(&client_config.url))
+            {
+                return Err(format!(
+                    "Beacon node client config provided for {}, which is not in --beacon-nodes",
+                    unknown.url
+                ));
+            }
+            config.beacon_node_client_configs = client_configs;
+        }
+
         /*
          * Http API server
          */
@@ -334,6 +365,20 @@ impl Config {
             config.strict_fee_recipient = true;
         }
 
+        if cli_args.is_present("use-ssz-blocks") {
+            config.use_ssz_blocks = true;
+        }
+
+        /*
+         * Attestation timing
+         */
+        if cli_args.is_present("attest-on-head-event") {
+            config.attest_on_head_event = true;
+        }
+
+        config.aggregation_offset =
+            parse_optional::<u64>(cli_args, "aggregation-offset-ms")?.map(Duration::from_millis);
+
         Ok(config)
     }
 }
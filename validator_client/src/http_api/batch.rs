@@ -0,0 +1,366 @@
+//! Atomic batches of validator imports and deletions, and moving validators between validator
+//! clients.
+//!
+//! Unlike the standard keystore API, which imports and deletes keys one at a time, a batch is
+//! applied in full or not at all. The changes are applied in the following order so that a
+//! failure at any step can be reverted:
+//!
+//! 1. Validate every keystore, password and public key.
+//! 2. Write the new keystores to disk.
+//! 3. Import slashing protection, register new keys and disable deleted keys in a single slashing
+//!    protection transaction.
+//! 4. Update the validator definitions and the set of initialized validators.
+//! 5. Delete the keystores of deleted validators.
+//!
+//! Slashing protection data imported in step 3 is retained if step 4 fails, as additional
+//! slashing protection data is always safe.
+use crate::ValidatorStore;
+use account_utils::{
+    read_password, validator_definitions::SigningDefinition,
+    validator_definitions::ValidatorDefinition, ZeroizeString,
+};
+use eth2::lighthouse_vc::types::{
+    BatchValidatorsRequest, BatchValidatorsResponse, ImportKeystoresRequest, InterchangeJsonStr,
+    KeystoreJsonStr, MoveValidatorsRequest,
+};
+use eth2_keystore::Keystore;
+use slashing_protection::interchange::Interchange;
+use slog::{info, warn, Logger};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use types::{EthSpec, PublicKeyBytes};
+use validator_dir::Builder as ValidatorDirBuilder;
+use warp::Rejection;
+use warp_utils::reject::{custom_bad_request, custom_server_error};
+
+/// The outcome of a successfully applied (or dry-run) batch.
+struct BatchOutcome {
+    imported: Vec<PublicKeyBytes>,
+    deleted: Vec<PublicKeyBytes>,
+    slashing_protection: Interchange,
+}
+
+pub fn apply<T: SlotClock + 'static, E: EthSpec>(
+    request: BatchValidatorsRequest,
+    validator_dir: PathBuf,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<BatchValidatorsResponse, Rejection> {
+    if request.keystores.len() != request.passwords.len() {
+        return Err(custom_bad_request(format!(
+            "mismatched numbers of keystores ({}) and passwords ({})",
+            request.keystores.len(),
+            request.passwords.len(),
+        )));
+    }
+
+    let imports = request
+        .keystores
+        .into_iter()
+        .map(|KeystoreJsonStr(keystore)| keystore)
+        .zip(request.passwords)
+        .collect();
+    let dry_run = request.dry_run;
+
+    let outcome = apply_batch(
+        imports,
+        request.delete,
+        request
+            .slashing_protection
+            .map(|InterchangeJsonStr(interchange)| interchange),
+        dry_run,
+        validator_dir,
+        &validator_store,
+        task_executor,
+        &log,
+    )?;
+
+    Ok(BatchValidatorsResponse {
+        dry_run,
+        imported: outcome.imported,
+        deleted: outcome.deleted,
+        slashing_protection: InterchangeJsonStr(outcome.slashing_protection),
+    })
+}
+
+/// Delete validators from this validator client, returning their keystores, passwords and
+/// slashing protection data in a form that can be imported by another validator client.
+pub fn move_validators<T: SlotClock + 'static, E: EthSpec>(
+    request: MoveValidatorsRequest,
+    validator_dir: PathBuf,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<ImportKeystoresRequest, Rejection> {
+    // Read the keystores and passwords before they are deleted.
+    let mut keystores = Vec::with_capacity(request.pubkeys.len());
+    let mut passwords = Vec::with_capacity(request.pubkeys.len());
+    {
+        let initialized_validators_rwlock = validator_store.initialized_validators();
+        let initialized_validators = initialized_validators_rwlock.read();
+
+        for pubkey_bytes in &request.pubkeys {
+            let def = initialized_validators
+                .validator_definitions()
+                .iter()
+                .find(|def| def.voting_public_key.compress() == *pubkey_bytes)
+                .ok_or_else(|| {
+                    custom_bad_request(format!("unknown validator: {:?}", pubkey_bytes))
+                })?;
+
+            let (keystore, password) = read_keystore_and_password(def).map_err(|e| {
+                custom_bad_request(format!("unable to move {:?}: {}", pubkey_bytes, e))
+            })?;
+            keystores.push(KeystoreJsonStr(keystore));
+            passwords.push(password);
+        }
+    }
+
+    let outcome = apply_batch(
+        vec![],
+        request.pubkeys,
+        None,
+        request.dry_run,
+        validator_dir,
+        &validator_store,
+        task_executor,
+        &log,
+    )?;
+
+    Ok(ImportKeystoresRequest {
+        keystores,
+        passwords,
+        slashing_protection: Some(InterchangeJsonStr(outcome.slashing_protection)),
+    })
+}
+
+fn read_keystore_and_password(
+    def: &ValidatorDefinition,
+) -> Result<(Keystore, ZeroizeString), String> {
+    match &def.signing_definition {
+        SigningDefinition::LocalKeystore {
+            voting_keystore_path,
+            voting_keystore_password_path,
+            voting_keystore_password,
+        } => {
+            let keystore = Keystore::from_json_file(voting_keystore_path)
+                .map_err(|e| format!("unable to read keystore: {:?}", e))?;
+            let password = if let Some(password) = voting_keystore_password {
+                password.clone()
+            } else if let Some(path) = voting_keystore_password_path {
+                read_password(path).map_err(|e| format!("unable to read password: {:?}", e))?
+            } else {
+                return Err("password is not stored by the validator client".into());
+            };
+            Ok((keystore, password))
+        }
+        SigningDefinition::Web3Signer(_) => Err("cannot move a remote signer validator".into()),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apply_batch<T: SlotClock + 'static, E: EthSpec>(
+    imports: Vec<(Keystore, ZeroizeString)>,
+    delete: Vec<PublicKeyBytes>,
+    slashing_protection: Option<Interchange>,
+    dry_run: bool,
+    validator_dir: PathBuf,
+    validator_store: &ValidatorStore<T, E>,
+    task_executor: TaskExecutor,
+    log: &Logger,
+) -> Result<BatchOutcome, Rejection> {
+    let handle = task_executor
+        .handle()
+        .ok_or_else(|| custom_server_error("validator client shutdown".into()))?;
+
+    // 1. Validate the entire batch.
+    let mut seen = HashSet::new();
+    let mut import_pubkeys = Vec::with_capacity(imports.len());
+    let mut delete_pubkeys = Vec::with_capacity(delete.len());
+    {
+        let initialized_validators_rwlock = validator_store.initialized_validators();
+        let initialized_validators = initialized_validators_rwlock.read();
+        let definitions = initialized_validators.validator_definitions();
+
+        for (keystore, password) in &imports {
+            let pubkey = keystore.public_key().ok_or_else(|| {
+                custom_bad_request(format!("invalid pubkey: {}", keystore.pubkey()))
+            })?;
+            let pubkey_bytes = pubkey.compress();
+            if !seen.insert(pubkey_bytes) {
+                return Err(custom_bad_request(format!(
+                    "duplicate pubkey in batch: {:?}",
+                    pubkey_bytes
+                )));
+            }
+            if definitions.iter().any(|def| {
+                def.voting_public_key == pubkey
+                    && (def.enabled || !def.signing_definition.is_local_keystore())
+            }) {
+                return Err(custom_bad_request(format!(
+                    "validator already exists: {:?}",
+                    pubkey_bytes
+                )));
+            }
+            keystore.decrypt_keypair(password.as_ref()).map_err(|e| {
+                custom_bad_request(format!(
+                    "incorrect password for {:?}: {:?}",
+                    pubkey_bytes, e
+                ))
+            })?;
+            import_pubkeys.push(pubkey_bytes);
+        }
+
+        for pubkey_bytes in &delete {
+            if !seen.insert(*pubkey_bytes) {
+                return Err(custom_bad_request(format!(
+                    "duplicate pubkey in batch: {:?}",
+                    pubkey_bytes
+                )));
+            }
+            let pubkey = pubkey_bytes.decompress().map_err(|e| {
+                custom_bad_request(format!("invalid pubkey, {:?}: {:?}", pubkey_bytes, e))
+            })?;
+            match definitions
+                .iter()
+                .find(|def| def.voting_public_key == pubkey)
+            {
+                Some(def) if def.signing_definition.is_local_keystore() => (),
+                Some(_) => {
+                    return Err(custom_bad_request(format!(
+                        "cannot delete remote signer validator: {:?}",
+                        pubkey_bytes
+                    )))
+                }
+                None => {
+                    return Err(custom_bad_request(format!(
+                        "unknown validator: {:?}",
+                        pubkey_bytes
+                    )))
+                }
+            }
+            delete_pubkeys.push(pubkey);
+        }
+    }
+
+    if dry_run {
+        let slashing_protection = validator_store
+            .apply_slashing_protection_batch(slashing_protection, &import_pubkeys, &delete, true)
+            .map_err(|e| {
+                custom_bad_request(format!("slashing protection import failed: {:?}", e))
+            })?;
+
+        return Ok(BatchOutcome {
+            imported: import_pubkeys,
+            deleted: delete,
+            slashing_protection,
+        });
+    }
+
+    info!(
+        log,
+        "Applying validator batch via HTTP API";
+        "import_count" => imports.len(),
+        "delete_count" => delete.len(),
+    );
+
+    // 2. Write the new keystores to disk.
+    let mut created_dirs = Vec::with_capacity(imports.len());
+    let mut new_definitions = Vec::with_capacity(imports.len());
+    let remove_created_dirs = |created_dirs: &[PathBuf]| {
+        for dir in created_dirs {
+            if let Err(e) = fs::remove_dir_all(dir) {
+                warn!(
+                    log,
+                    "Unable to remove validator directory";
+                    "error" => ?e,
+                    "path" => ?dir,
+                );
+            }
+        }
+    };
+
+    for (keystore, password) in imports {
+        let result = ValidatorDirBuilder::new(validator_dir.clone())
+            .voting_keystore(keystore, password.as_ref())
+            .store_withdrawal_keystore(false)
+            .build()
+            .map_err(|e| format!("failed to build validator directory: {:?}", e))
+            .and_then(|validator_dir| {
+                created_dirs.push(validator_dir.dir().to_path_buf());
+                // Drop the validator dir so that the keystore can be re-locked on initialization.
+                let voting_keystore_path = validator_dir.voting_keystore_path();
+                drop(validator_dir);
+                ValidatorDefinition::new_keystore_with_password(
+                    voting_keystore_path,
+                    Some(password),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .map_err(|e| format!("failed to create validator definition: {:?}", e))
+            });
+
+        match result {
+            Ok(def) => new_definitions.push(def),
+            Err(e) => {
+                remove_created_dirs(&created_dirs);
+                return Err(custom_server_error(e));
+            }
+        }
+    }
+
+    // 3. Update slashing protection.
+    let exported_slashing_protection = match validator_store.apply_slashing_protection_batch(
+        slashing_protection,
+        &import_pubkeys,
+        &delete,
+        false,
+    ) {
+        Ok(interchange) => interchange,
+        Err(e) => {
+            remove_created_dirs(&created_dirs);
+            return Err(custom_bad_request(format!(
+                "slashing protection import failed: {:?}",
+                e
+            )));
+        }
+    };
+
+    // 4 & 5. Update the validator definitions, deleting the removed keystores on success.
+    if let Err(e) =
+        handle.block_on(validator_store.apply_definition_batch(new_definitions, &delete_pubkeys))
+    {
+        remove_created_dirs(&created_dirs);
+        if let Err(e) = validator_store.restore_slashing_protection_registrations(&delete) {
+            warn!(
+                log,
+                "Unable to re-enable slashing protection for validators";
+                "error" => ?e,
+            );
+        }
+        return Err(custom_server_error(format!(
+            "unable to update validators: {}",
+            e
+        )));
+    }
+
+    info!(
+        log,
+        "Applied validator batch via HTTP API";
+        "imported" => ?import_pubkeys,
+        "deleted" => ?delete,
+    );
+
+    Ok(BatchOutcome {
+        imported: import_pubkeys,
+        deleted: delete,
+        slashing_protection: exported_slashing_protection,
+    })
+}
@@ -1,9 +1,11 @@
 mod api_secret;
+mod batch;
 mod create_validator;
 mod keystores;
 mod remotekeys;
 mod tests;
 
+use crate::sync_committee_performance::SyncCommitteePerformance;
 use crate::ValidatorStore;
 use account_utils::{
     mnemonic_from_phrase,
@@ -62,6 +64,7 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
     pub validator_dir: Option<PathBuf>,
+    pub sync_committee_performance: Option<Arc<SyncCommitteePerformance>>,
     pub spec: ChainSpec,
     pub config: Config,
     pub log: Logger,
@@ -174,6 +177,17 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_sync_committee_performance = ctx.sync_committee_performance.clone();
+    let sync_committee_performance_filter = warp::any()
+        .map(move || inner_sync_committee_performance.clone())
+        .and_then(|sync_committee_performance: Option<_>| async move {
+            sync_committee_performance.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "sync committee performance is not initialized.".to_string(),
+                )
+            })
+        });
+
     let inner_ctx = ctx.clone();
     let log_filter = warp::any().map(move || inner_ctx.log.clone());
 
@@ -279,6 +293,23 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // GET lighthouse/ui/sync_committee_performance
+    let get_lighthouse_ui_sync_committee_performance = warp::path("lighthouse")
+        .and(warp::path("ui"))
+        .and(warp::path("sync_committee_performance"))
+        .and(warp::path::end())
+        .and(sync_committee_performance_filter)
+        .and(signer.clone())
+        .and_then(
+            |sync_committee_performance: Arc<SyncCommitteePerformance>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    Ok(api_types::GenericResponse::from(
+                        sync_committee_performance.summaries(),
+                    ))
+                })
+            },
+        );
+
     // POST lighthouse/validators/
     let post_validators = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -809,6 +840,50 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         )
         .map(|reply| warp::reply::with_status(reply, warp::http::StatusCode::NO_CONTENT));
 
+    // POST lighthouse/validators/batch
+    let post_validators_batch = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("batch"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_dir_filter.clone())
+        .and(validator_store_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |request, signer, validator_dir, validator_store, task_executor, log| {
+                blocking_signed_json_task(signer, move || {
+                    batch::apply(request, validator_dir, validator_store, task_executor, log)
+                })
+            },
+        );
+
+    // POST lighthouse/validators/move
+    let post_validators_move = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path("move"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_dir_filter.clone())
+        .and(validator_store_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |request, signer, validator_dir, validator_store, task_executor, log| {
+                blocking_signed_json_task(signer, move || {
+                    batch::move_validators(
+                        request,
+                        validator_dir,
+                        validator_store,
+                        task_executor,
+                        log,
+                    )
+                })
+            },
+        );
+
     // GET /eth/v1/keystores
     let get_std_keystores = std_keystores
         .and(signer.clone())
@@ -894,6 +969,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_lighthouse_ui_sync_committee_performance)
                         .or(get_fee_recipient)
                         .or(get_gas_limit)
                         .or(get_std_keystores)
@@ -904,6 +980,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_keystore)
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
+                        .or(post_validators_batch)
+                        .or(post_validators_move)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
                         .or(post_std_keystores)
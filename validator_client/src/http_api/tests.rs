@@ -4,6 +4,7 @@
 mod keystores;
 
 use crate::doppelganger_service::DoppelgangerService;
+use crate::sync_committee_performance::SyncCommitteePerformance;
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
@@ -120,6 +121,7 @@ impl ApiTester {
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            sync_committee_performance: Some(Arc::new(SyncCommitteePerformance::default())),
             spec: E::default_spec(),
             config: HttpConfig {
                 enabled: true,
@@ -233,6 +235,20 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_ui_sync_committee_performance(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_ui_sync_committee_performance()
+            .await
+            .unwrap()
+            .data;
+
+        // No sync committee messages have been produced by the tester.
+        assert!(result.is_empty());
+
+        self
+    }
+
     #[cfg(target_os = "linux")]
     pub async fn test_get_lighthouse_health(self) -> Self {
         self.client.get_lighthouse_health().await.unwrap();
@@ -640,6 +656,10 @@ fn routes_with_invalid_auth() {
                     .await
             })
             .await
+            .test_with_invalid_auth(|client| async move {
+                client.get_lighthouse_ui_sync_committee_performance().await
+            })
+            .await
             .test_with_invalid_auth(|client| async move {
                 client
                     .post_lighthouse_validators(vec![ValidatorRequest {
@@ -718,6 +738,29 @@ fn routes_with_invalid_auth() {
                     .await
             })
             .await
+            .test_with_invalid_auth(|client| async move {
+                let keypair = Keypair::random();
+                client
+                    .post_lighthouse_validators_batch(&BatchValidatorsRequest {
+                        keystores: vec![],
+                        passwords: vec![],
+                        slashing_protection: None,
+                        delete: vec![keypair.pk.compress()],
+                        dry_run: true,
+                    })
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                let keypair = Keypair::random();
+                client
+                    .post_lighthouse_validators_move(&MoveValidatorsRequest {
+                        pubkeys: vec![keypair.pk.compress()],
+                        dry_run: true,
+                    })
+                    .await
+            })
+            .await
     });
 }
 
@@ -733,6 +776,8 @@ fn simple_getters() {
             .test_get_lighthouse_health()
             .await
             .test_get_lighthouse_spec()
+            .await
+            .test_get_lighthouse_ui_sync_committee_performance()
             .await;
     });
 }
@@ -6,7 +6,7 @@ use eth2::lighthouse_vc::types::UpdateFeeRecipientRequest;
 use eth2::lighthouse_vc::{
     http_client::ValidatorClientHttpClient as HttpClient,
     std_types::{KeystoreJsonStr as Keystore, *},
-    types::Web3SignerValidatorRequest,
+    types::{BatchValidatorsRequest, MoveValidatorsRequest, Web3SignerValidatorRequest},
 };
 use itertools::Itertools;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
@@ -2157,3 +2157,202 @@ fn import_remotekey_web3signer_enabled() {
         check_remotekey_get_response(&get_res, expected_responses);
     })
 }
+
+#[test]
+fn batch_import_and_delete() {
+    run_test(|tester| async move {
+        let _ = &tester;
+        let password = random_password_string();
+        let keystores = (0..4)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+
+        // Import the first two keystores using the standard API.
+        let import_res = tester
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores[..2].to_vec(),
+                passwords: vec![password.clone(); 2],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(2));
+
+        // Import the remaining keystores and delete the first in a single batch.
+        let batch_res = tester
+            .client
+            .post_lighthouse_validators_batch(&BatchValidatorsRequest {
+                keystores: keystores[2..].to_vec(),
+                passwords: vec![password.clone(); 2],
+                slashing_protection: None,
+                delete: vec![keystore_pubkey(&keystores[0])],
+                dry_run: false,
+            })
+            .await
+            .unwrap();
+
+        assert!(!batch_res.dry_run);
+        assert_eq!(
+            batch_res.imported,
+            keystores[2..]
+                .iter()
+                .map(keystore_pubkey)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(batch_res.deleted, vec![keystore_pubkey(&keystores[0])]);
+        assert_eq!(batch_res.slashing_protection.0.data.len(), 1);
+
+        let get_res = tester.client.get_keystores().await.unwrap();
+        check_keystore_get_response(&get_res, &keystores[1..]);
+    })
+}
+
+#[test]
+fn batch_dry_run_makes_no_changes() {
+    run_test(|tester| async move {
+        let _ = &tester;
+        let password = random_password_string();
+        let keystores = (0..3)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+
+        let import_res = tester
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores[..1].to_vec(),
+                passwords: vec![password.clone()],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(1));
+
+        let batch_res = tester
+            .client
+            .post_lighthouse_validators_batch(&BatchValidatorsRequest {
+                keystores: keystores[1..].to_vec(),
+                passwords: vec![password.clone(); 2],
+                slashing_protection: None,
+                delete: vec![keystore_pubkey(&keystores[0])],
+                dry_run: true,
+            })
+            .await
+            .unwrap();
+        assert!(batch_res.dry_run);
+        assert_eq!(batch_res.imported.len(), 2);
+        assert_eq!(batch_res.deleted.len(), 1);
+
+        // Nothing should have changed, including the slashing protection status of the key that
+        // would have been deleted.
+        let get_res = tester.client.get_keystores().await.unwrap();
+        check_keystore_get_response(&get_res, &keystores[..1]);
+
+        let mut attestation = make_attestation(0, 1);
+        tester
+            .validator_store
+            .sign_attestation(
+                keystore_pubkey(&keystores[0]),
+                0,
+                &mut attestation,
+                Epoch::new(1),
+            )
+            .await
+            .unwrap();
+    })
+}
+
+#[test]
+fn batch_with_wrong_password_is_atomic() {
+    run_test(|tester| async move {
+        let _ = &tester;
+        let password = random_password_string();
+        let keystores = (0..3)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+
+        let import_res = tester
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores[..1].to_vec(),
+                passwords: vec![password.clone()],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(1));
+
+        tester
+            .client
+            .post_lighthouse_validators_batch(&BatchValidatorsRequest {
+                keystores: keystores[1..].to_vec(),
+                passwords: vec![password.clone(), random_password_string()],
+                slashing_protection: None,
+                delete: vec![keystore_pubkey(&keystores[0])],
+                dry_run: false,
+            })
+            .await
+            .unwrap_err();
+
+        // Neither the import nor the deletion should have been applied.
+        let get_res = tester.client.get_keystores().await.unwrap();
+        check_keystore_get_response(&get_res, &keystores[..1]);
+        assert_eq!(tester.vals_total(), 1);
+    })
+}
+
+#[test]
+fn move_validators_between_vcs() {
+    run_dual_vc_test(move |tester1, tester2| async move {
+        let _ = (&tester1, &tester2);
+        let password = random_password_string();
+        let keystores = (0..3)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+
+        let import_res = tester1
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords: vec![password.clone(); keystores.len()],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(keystores.len()));
+
+        // Sign an attestation with the validator that will be moved.
+        let moved_pubkey = keystore_pubkey(&keystores[0]);
+        let mut attestation = make_attestation(1, 2);
+        tester1
+            .validator_store
+            .sign_attestation(moved_pubkey, 0, &mut attestation, Epoch::new(2))
+            .await
+            .unwrap();
+
+        let move_res = tester1
+            .client
+            .post_lighthouse_validators_move(&MoveValidatorsRequest {
+                pubkeys: vec![moved_pubkey],
+                dry_run: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(move_res.keystores, keystores[..1].to_vec());
+
+        let get_res = tester1.client.get_keystores().await.unwrap();
+        check_keystore_get_response(&get_res, &keystores[1..]);
+
+        // The response can be imported into the second VC as-is.
+        let import_res = tester2.client.post_keystores(&move_res).await.unwrap();
+        check_keystore_import_response(&import_res, all_imported(1));
+
+        // The slashing protection data must have moved with the key.
+        let mut surrounding = make_attestation(0, 3);
+        tester2
+            .validator_store
+            .sign_attestation(moved_pubkey, 0, &mut surrounding, Epoch::new(3))
+            .await
+            .unwrap_err();
+    })
+}
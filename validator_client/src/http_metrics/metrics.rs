@@ -35,6 +35,11 @@ pub const UPDATE_PROPOSERS: &str = "update_proposers";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
 pub const WEB3SIGNER: &str = "web3signer";
+pub const INCLUDED: &str = "included";
+pub const PARTIAL: &str = "partial";
+pub const MISSED: &str = "missed";
+pub const HEAD_EVENT: &str = "head_event";
+pub const DEADLINE: &str = "deadline";
 
 pub use lighthouse_metrics::*;
 
@@ -102,6 +107,16 @@ lazy_static::lazy_static! {
         "Duration to perform attestation service tasks",
         &["task"]
     );
+    pub static ref ATTESTATION_DUTY_DELAY_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "vc_attestation_duty_delay_seconds",
+        "Time from the start of the slot until an attestation duty was published",
+        &["duty"]
+    );
+    pub static ref ATTESTATION_SERVICE_TRIGGERS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_attestation_service_triggers_total",
+        "Count of the events which triggered attestation production",
+        &["trigger"]
+    );
     pub static ref SLASHING_PROTECTION_PRUNE_TIMES: Result<Histogram> = try_create_histogram(
         "vc_slashing_protection_prune_times_seconds",
         "Time required to prune the slashing protection DB",
@@ -125,6 +140,23 @@ lazy_static::lazy_static! {
         "vc_beacon_block_proposal_changed",
         "A duties update discovered a new block proposer for the current slot",
     );
+    /*
+     * Sync committee inclusion metrics
+     */
+    pub static ref SYNC_COMMITTEE_MESSAGES_INCLUDED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "vc_sync_committee_messages_included_total",
+        "Total count of local sync committee messages included in a block",
+    );
+    pub static ref SYNC_COMMITTEE_MESSAGES_MISSED_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_sync_committee_messages_missed_total",
+        "Total count of local sync committee messages not included in a block",
+        &["reason"]
+    );
+    pub static ref SYNC_COMMITTEE_CONTRIBUTIONS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_sync_committee_contributions_inclusion_total",
+        "Total count of local sync committee contributions by inclusion status",
+        &["status"]
+    );
     /*
      * Endpoint metrics
      */
@@ -588,6 +588,126 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Add and remove a batch of local keystore validators as a single update.
+    ///
+    /// Either all validators in `add` are enabled and all validators in `remove` are disabled, or
+    /// an error is returned and the previous definitions are restored, both in memory and on
+    /// disk. Any disabled definitions with the same public key as a definition in `add` are
+    /// replaced.
+    ///
+    /// Once the update has been committed the keystores of the removed validators are deleted.
+    /// Failure to delete a keystore is logged but does not revert the update, as the validator has
+    /// already been removed from the definitions.
+    pub async fn apply_definition_batch(
+        &mut self,
+        add: Vec<ValidatorDefinition>,
+        remove: &[PublicKey],
+    ) -> Result<(), Error> {
+        // Check that every change is valid before modifying anything.
+        for pubkey in remove {
+            let def = self
+                .definitions
+                .as_slice()
+                .iter()
+                .find(|def| &def.voting_public_key == pubkey)
+                .ok_or_else(|| Error::ValidatorNotInitialized(pubkey.clone()))?;
+            if !def.signing_definition.is_local_keystore() {
+                return Err(Error::InvalidActionOnValidator);
+            }
+        }
+        for new_def in &add {
+            if self.definitions.as_slice().iter().any(|existing| {
+                existing.voting_public_key == new_def.voting_public_key
+                    && (existing.enabled || remove.contains(&existing.voting_public_key))
+            }) {
+                return Err(Error::DuplicatePublicKey);
+            }
+        }
+
+        let previous_definitions = self.definitions.as_slice().to_vec();
+
+        // Disable removed validators rather than deleting their definitions, so that a crash
+        // before their keystores are deleted does not allow auto-discovery to re-activate them.
+        for def in self.definitions.as_mut_slice() {
+            if remove.contains(&def.voting_public_key) {
+                def.enabled = false;
+            }
+        }
+        let removed_validators = remove
+            .iter()
+            .filter_map(|pubkey| {
+                let pubkey_bytes = pubkey.compress();
+                let initialized_validator = self.validators.remove(&pubkey_bytes)?;
+                Some((pubkey_bytes, initialized_validator))
+            })
+            .collect::<Vec<_>>();
+        self.definitions.retain(|existing| {
+            existing.enabled
+                || !add
+                    .iter()
+                    .any(|new_def| new_def.voting_public_key == existing.voting_public_key)
+        });
+        for def in add.iter().cloned() {
+            self.definitions.push(def);
+        }
+
+        let update_result = match self.update_validators().await {
+            Ok(()) => self
+                .definitions
+                .save(&self.validators_dir)
+                .map_err(Error::UnableToSaveDefinitions),
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = update_result {
+            // Restore the previous definitions. Validators from `add` may have been initialized
+            // before the failure, so they must be removed explicitly. The removed validators are
+            // re-inserted as-is, which avoids contention on their keystore lockfiles.
+            for def in &add {
+                self.validators.remove(&def.voting_public_key.compress());
+            }
+            self.validators.extend(removed_validators);
+            self.definitions = previous_definitions.into();
+            self.definitions
+                .save(&self.validators_dir)
+                .map_err(Error::UnableToSaveDefinitions)?;
+            self.update_validators().await?;
+            return Err(e);
+        }
+
+        // The update is committed, delete the keystores of removed validators.
+        for (_, initialized_validator) in removed_validators {
+            if let SigningMethod::LocalKeystore {
+                ref voting_keystore_path,
+                ref voting_keystore_lockfile,
+                ref voting_keystore,
+                ..
+            } = *initialized_validator.signing_method
+            {
+                drop(voting_keystore_lockfile.lock().take());
+
+                if let Err(e) =
+                    self.delete_keystore_or_validator_dir(voting_keystore_path, voting_keystore)
+                {
+                    warn!(
+                        self.log,
+                        "Unable to delete keystore of removed validator";
+                        "error" => ?e,
+                        "path" => ?voting_keystore_path,
+                    );
+                }
+            }
+        }
+
+        self.definitions
+            .retain(|def| !remove.contains(&def.voting_public_key));
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        Ok(())
+    }
+
     /// Attempt to delete the voting keystore file, or its entire validator directory.
     ///
     /// Some parts of the VC assume the existence of a validator based on the existence of a
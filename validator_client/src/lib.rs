@@ -1,4 +1,5 @@
 mod attestation_service;
+mod beacon_node_client_config;
 mod beacon_node_fallback;
 mod block_service;
 mod check_synced;
@@ -16,6 +17,7 @@ mod sync_committee_service;
 mod doppelganger_service;
 pub mod http_api;
 pub mod initialized_validators;
+pub mod sync_committee_performance;
 pub mod validator_store;
 
 pub use cli::cli_app;
@@ -263,6 +265,16 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                     }
                 }
 
+                // Add the client certificate and headers for this beacon node, if any.
+                if let Some(client_config) = config
+                    .beacon_node_client_configs
+                    .iter()
+                    .find(|client_config| client_config.url == *url)
+                {
+                    beacon_node_http_client_builder =
+                        client_config.apply(beacon_node_http_client_builder)?;
+                }
+
                 let beacon_node_http_client = beacon_node_http_client_builder
                     // Set default timeout to be the full slot duration.
                     .timeout(slot_duration)
@@ -414,6 +426,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .graffiti(config.graffiti)
             .graffiti_file(config.graffiti_file.clone())
             .strict_fee_recipient(config.strict_fee_recipient)
+            .use_ssz_blocks(config.use_ssz_blocks)
             .build()?;
 
         let attestation_service = AttestationServiceBuilder::new()
@@ -422,6 +435,8 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
+            .attest_on_head_event(config.attest_on_head_event)
+            .aggregation_offset(config.aggregation_offset)
             .build()?;
 
         let preparation_service = PreparationServiceBuilder::new()
@@ -514,6 +529,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
+                sync_committee_performance: Some(self.sync_committee_service.performance()),
                 spec: self.context.eth2_config.spec.clone(),
                 config: self.config.http_api.clone(),
                 log: log.clone(),
@@ -0,0 +1,516 @@
+//! Tracks the inclusion of sync committee messages and contributions produced by local validators.
+//!
+//! The `SyncCommitteeService` registers each message and contribution as it is produced. Once the
+//! block that should contain them has been imported, the service passes that block's
+//! `SyncAggregate` to this tracker, which determines which messages were included and assigns a
+//! reason to each one that was missed.
+//!
+//! Messages signed at slot `N` can only be included in the block at slot `N + 1`. To give that
+//! block ample time to arrive, it is checked one slot later than strictly necessary (see
+//! `SYNC_PERFORMANCE_LOOKBACK_SLOTS`).
+
+use crate::http_metrics::metrics;
+use eth2::lighthouse_vc::types::SyncCommitteePerformanceData;
+use parking_lot::RwLock;
+use slog::{debug, info, warn, Logger};
+use std::collections::{BTreeMap, HashMap};
+use types::{
+    EthSpec, Hash256, PublicKeyBytes, Slot, SyncAggregate, SyncCommitteeContribution, SyncDuty,
+};
+
+/// The number of slots between the signing of a sync committee message and the check for its
+/// inclusion.
+///
+/// Messages are signed at slot `N`, may be included in the block at `N + 1` and are checked at
+/// `N + 2`.
+pub const SYNC_PERFORMANCE_LOOKBACK_SLOTS: u64 = 2;
+
+/// The reason that a sync committee message from a local validator was not included on chain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MissReason {
+    /// The validator client did not publish the message (e.g. signing failed, the head was
+    /// optimistic or no beacon node accepted it).
+    NotPublished,
+    /// The message was published but voted for a different head than the block that included
+    /// the `SyncAggregate`.
+    WrongHead,
+    /// The message was published for the correct head but was not included in the
+    /// `SyncAggregate`.
+    NotIncluded,
+    /// There was no block at the inclusion slot, so no message could have been included.
+    NoBlock,
+}
+
+impl MissReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MissReason::NotPublished => "not_published",
+            MissReason::WrongHead => "wrong_head",
+            MissReason::NotIncluded => "not_included",
+            MissReason::NoBlock => "no_block",
+        }
+    }
+}
+
+/// A sync committee message that is awaiting an inclusion check.
+struct PendingMessage {
+    pubkey: PublicKeyBytes,
+    /// The positions of the validator in the sync committee.
+    sync_committee_indices: Vec<u64>,
+    /// The head block root that was signed, `None` if the message was not published.
+    beacon_block_root: Option<Hash256>,
+}
+
+/// A sync committee contribution that is awaiting an inclusion check.
+struct PendingContribution {
+    aggregator_index: u64,
+    subcommittee_index: u64,
+    /// The positions in the sync committee covered by the contribution.
+    participant_indices: Vec<usize>,
+}
+
+/// All messages and contributions produced at a single slot.
+#[derive(Default)]
+struct PendingSlot {
+    messages: HashMap<u64, PendingMessage>,
+    contributions: Vec<PendingContribution>,
+}
+
+/// Cumulative sync committee performance for a single validator.
+struct ValidatorPerformance {
+    pubkey: PublicKeyBytes,
+    messages_included: u64,
+    missed_not_published: u64,
+    missed_wrong_head: u64,
+    missed_not_included: u64,
+    missed_no_block: u64,
+    contributions_published: u64,
+    contributions_included: u64,
+    latest_slot: Option<Slot>,
+    latest_missed_slot: Option<Slot>,
+}
+
+impl ValidatorPerformance {
+    fn new(pubkey: PublicKeyBytes) -> Self {
+        Self {
+            pubkey,
+            messages_included: 0,
+            missed_not_published: 0,
+            missed_wrong_head: 0,
+            missed_not_included: 0,
+            missed_no_block: 0,
+            contributions_published: 0,
+            contributions_included: 0,
+            latest_slot: None,
+            latest_missed_slot: None,
+        }
+    }
+
+    fn record_miss(&mut self, slot: Slot, reason: MissReason) {
+        match reason {
+            MissReason::NotPublished => self.missed_not_published += 1,
+            MissReason::WrongHead => self.missed_wrong_head += 1,
+            MissReason::NotIncluded => self.missed_not_included += 1,
+            MissReason::NoBlock => self.missed_no_block += 1,
+        }
+        self.latest_missed_slot = Some(slot);
+    }
+
+    fn as_api_data(&self, validator_index: u64) -> SyncCommitteePerformanceData {
+        SyncCommitteePerformanceData {
+            validator_index,
+            pubkey: self.pubkey,
+            messages_included: self.messages_included,
+            messages_missed: self.missed_not_published
+                + self.missed_wrong_head
+                + self.missed_not_included
+                + self.missed_no_block,
+            missed_not_published: self.missed_not_published,
+            missed_wrong_head: self.missed_wrong_head,
+            missed_not_included: self.missed_not_included,
+            missed_no_block: self.missed_no_block,
+            contributions_published: self.contributions_published,
+            contributions_included: self.contributions_included,
+            latest_slot: self.latest_slot,
+            latest_missed_slot: self.latest_missed_slot,
+        }
+    }
+}
+
+/// Records the sync committee messages and contributions of local validators and checks their
+/// inclusion in blocks.
+#[derive(Default)]
+pub struct SyncCommitteePerformance {
+    /// Messages and contributions awaiting inclusion checks, keyed by the slot they were signed at.
+    pending: RwLock<BTreeMap<Slot, PendingSlot>>,
+    /// Cumulative performance keyed by validator index.
+    validators: RwLock<HashMap<u64, ValidatorPerformance>>,
+}
+
+impl SyncCommitteePerformance {
+    /// Register that the validators in `duties` are expected to produce a message at `slot`.
+    ///
+    /// Until `register_published_messages` is called, the messages are considered unpublished.
+    pub fn register_duties(&self, slot: Slot, duties: &[SyncDuty]) {
+        let mut pending = self.pending.write();
+        let pending_slot = pending.entry(slot).or_default();
+        for duty in duties {
+            pending_slot
+                .messages
+                .entry(duty.validator_index)
+                .or_insert_with(|| PendingMessage {
+                    pubkey: duty.pubkey,
+                    sync_committee_indices: duty.validator_sync_committee_indices.clone(),
+                    beacon_block_root: None,
+                });
+        }
+    }
+
+    /// Register that the given validators published a message for `beacon_block_root` at `slot`.
+    pub fn register_published_messages(
+        &self,
+        slot: Slot,
+        beacon_block_root: Hash256,
+        validator_indices: impl Iterator<Item = u64>,
+    ) {
+        if let Some(pending_slot) = self.pending.write().get_mut(&slot) {
+            for validator_index in validator_indices {
+                if let Some(message) = pending_slot.messages.get_mut(&validator_index) {
+                    message.beacon_block_root = Some(beacon_block_root);
+                }
+            }
+        }
+    }
+
+    /// Register that the given aggregators published `contribution`.
+    pub fn register_published_contribution<E: EthSpec>(
+        &self,
+        contribution: &SyncCommitteeContribution<E>,
+        aggregators: impl Iterator<Item = (u64, PublicKeyBytes)>,
+    ) {
+        let offset = contribution.subcommittee_index as usize * E::sync_subcommittee_size();
+        let participant_indices = contribution
+            .aggregation_bits
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| *bit)
+            .map(|(i, _)| offset + i)
+            .collect::<Vec<_>>();
+
+        let mut pending = self.pending.write();
+        let pending_slot = pending.entry(contribution.slot).or_default();
+        let mut validators = self.validators.write();
+        for (aggregator_index, aggregator_pubkey) in aggregators {
+            pending_slot.contributions.push(PendingContribution {
+                aggregator_index,
+                subcommittee_index: contribution.subcommittee_index,
+                participant_indices: participant_indices.clone(),
+            });
+            validators
+                .entry(aggregator_index)
+                .or_insert_with(|| ValidatorPerformance::new(aggregator_pubkey))
+                .contributions_published += 1;
+        }
+    }
+
+    /// Returns `true` if there are messages or contributions signed at or prior to `slot` that
+    /// are awaiting an inclusion check.
+    pub fn has_pending_up_to(&self, slot: Slot) -> bool {
+        self.pending
+            .read()
+            .keys()
+            .next()
+            .map_or(false, |first_slot| *first_slot <= slot)
+    }
+
+    /// Check the inclusion of all messages and contributions produced at `block_slot - 1`.
+    ///
+    /// The `parent_root` and `sync_aggregate` should be taken from the canonical block at
+    /// `block_slot`, or be `None` if that slot was skipped.
+    ///
+    /// Any pending messages prior to `block_slot - 1` are discarded, since their inclusion
+    /// slot has passed without a check.
+    pub fn process_block<E: EthSpec>(
+        &self,
+        block_slot: Slot,
+        block: Option<(Hash256, &SyncAggregate<E>)>,
+        log: &Logger,
+    ) {
+        let message_slot = if let Some(slot) = block_slot.as_u64().checked_sub(1) {
+            Slot::new(slot)
+        } else {
+            return;
+        };
+
+        let pending_slot = {
+            let mut pending = self.pending.write();
+            let remaining = pending.split_off(&message_slot);
+            let stale = std::mem::replace(&mut *pending, remaining);
+            for stale_slot in stale.keys() {
+                debug!(
+                    log,
+                    "Unable to check sync committee inclusion";
+                    "slot" => stale_slot,
+                );
+            }
+            match pending.remove(&message_slot) {
+                Some(pending_slot) => pending_slot,
+                None => return,
+            }
+        };
+
+        let mut validators = self.validators.write();
+
+        for (validator_index, message) in &pending_slot.messages {
+            let outcome = match (block, message.beacon_block_root) {
+                (_, None) => Err(MissReason::NotPublished),
+                (None, Some(_)) => Err(MissReason::NoBlock),
+                (Some((parent_root, sync_aggregate)), Some(signed_root)) => {
+                    let included = !message.sync_committee_indices.is_empty()
+                        && message.sync_committee_indices.iter().all(|index| {
+                            sync_aggregate
+                                .sync_committee_bits
+                                .get(*index as usize)
+                                .unwrap_or(false)
+                        });
+
+                    if included {
+                        Ok(())
+                    } else if signed_root != parent_root {
+                        Err(MissReason::WrongHead)
+                    } else {
+                        Err(MissReason::NotIncluded)
+                    }
+                }
+            };
+
+            let performance = validators
+                .entry(*validator_index)
+                .or_insert_with(|| ValidatorPerformance::new(message.pubkey));
+            performance.latest_slot = Some(message_slot);
+
+            match outcome {
+                Ok(()) => {
+                    performance.messages_included += 1;
+                    metrics::inc_counter(&metrics::SYNC_COMMITTEE_MESSAGES_INCLUDED_TOTAL);
+                }
+                Err(reason) => {
+                    performance.record_miss(message_slot, reason);
+                    metrics::inc_counter_vec(
+                        &metrics::SYNC_COMMITTEE_MESSAGES_MISSED_TOTAL,
+                        &[reason.as_str()],
+                    );
+                    warn!(
+                        log,
+                        "Sync committee message not included";
+                        "reason" => reason.as_str(),
+                        "signed_root" => ?message.beacon_block_root,
+                        "inclusion_slot" => block_slot,
+                        "slot" => message_slot,
+                        "validator_index" => validator_index,
+                        "pubkey" => ?message.pubkey,
+                    );
+                }
+            }
+        }
+
+        for contribution in &pending_slot.contributions {
+            let status = match block {
+                Some((_, sync_aggregate)) => {
+                    let num_included = contribution
+                        .participant_indices
+                        .iter()
+                        .filter(|index| {
+                            sync_aggregate
+                                .sync_committee_bits
+                                .get(**index)
+                                .unwrap_or(false)
+                        })
+                        .count();
+
+                    if num_included == 0 {
+                        metrics::MISSED
+                    } else if num_included == contribution.participant_indices.len() {
+                        metrics::INCLUDED
+                    } else {
+                        metrics::PARTIAL
+                    }
+                }
+                None => metrics::MISSED,
+            };
+
+            metrics::inc_counter_vec(&metrics::SYNC_COMMITTEE_CONTRIBUTIONS_TOTAL, &[status]);
+
+            if status == metrics::MISSED {
+                warn!(
+                    log,
+                    "Sync committee contribution not included";
+                    "reason" => if block.is_some() { "not_included" } else { "no_block" },
+                    "subcommittee_index" => contribution.subcommittee_index,
+                    "inclusion_slot" => block_slot,
+                    "slot" => message_slot,
+                    "aggregator_index" => contribution.aggregator_index,
+                );
+            } else if let Some(performance) = validators.get_mut(&contribution.aggregator_index) {
+                performance.contributions_included += 1;
+            }
+        }
+
+        let num_messages = pending_slot.messages.len();
+        if num_messages > 0 {
+            info!(
+                log,
+                "Checked sync committee inclusion";
+                "messages" => num_messages,
+                "contributions" => pending_slot.contributions.len(),
+                "slot" => message_slot,
+            );
+        }
+    }
+
+    /// Returns the cumulative sync committee performance of all local validators which have had
+    /// at least one message or contribution checked.
+    pub fn summaries(&self) -> Vec<SyncCommitteePerformanceData> {
+        let mut summaries = self
+            .validators
+            .read()
+            .iter()
+            .map(|(validator_index, performance)| performance.as_api_data(*validator_index))
+            .collect::<Vec<_>>();
+        summaries.sort_by_key(|summary| summary.validator_index);
+        summaries
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use environment::null_logger;
+    use types::{BitVector, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    fn duty(validator_index: u64, sync_committee_indices: Vec<u64>) -> SyncDuty {
+        SyncDuty {
+            pubkey: PublicKeyBytes::empty(),
+            validator_index,
+            validator_sync_committee_indices: sync_committee_indices,
+        }
+    }
+
+    fn aggregate_with_bits(indices: &[usize]) -> SyncAggregate<E> {
+        let mut sync_aggregate = SyncAggregate::new();
+        for index in indices {
+            sync_aggregate
+                .sync_committee_bits
+                .set(*index, true)
+                .unwrap();
+        }
+        sync_aggregate
+    }
+
+    fn summary(
+        performance: &SyncCommitteePerformance,
+        validator_index: u64,
+    ) -> SyncCommitteePerformanceData {
+        performance
+            .summaries()
+            .into_iter()
+            .find(|summary| summary.validator_index == validator_index)
+            .expect("summary should exist")
+    }
+
+    #[test]
+    fn message_inclusion_and_miss_reasons() {
+        let log = null_logger().unwrap();
+        let performance = SyncCommitteePerformance::default();
+        let slot = Slot::new(10);
+        let head = Hash256::repeat_byte(1);
+        let other_head = Hash256::repeat_byte(2);
+
+        performance.register_duties(
+            slot,
+            &[
+                duty(0, vec![0]),
+                duty(1, vec![1, 5]),
+                duty(2, vec![2]),
+                duty(3, vec![3]),
+            ],
+        );
+        performance.register_published_messages(slot, head, vec![0, 1].into_iter());
+        performance.register_published_messages(slot, other_head, vec![2].into_iter());
+
+        // Validator 1 is only partially included, validator 3 never published.
+        let sync_aggregate = aggregate_with_bits(&[0, 1]);
+        performance.process_block(slot + 1, Some((head, &sync_aggregate)), &log);
+
+        assert_eq!(summary(&performance, 0).messages_included, 1);
+        assert_eq!(summary(&performance, 1).missed_not_included, 1);
+        assert_eq!(summary(&performance, 2).missed_wrong_head, 1);
+        assert_eq!(summary(&performance, 3).missed_not_published, 1);
+        assert_eq!(summary(&performance, 3).latest_missed_slot, Some(slot));
+    }
+
+    #[test]
+    fn skipped_inclusion_slot() {
+        let log = null_logger().unwrap();
+        let performance = SyncCommitteePerformance::default();
+        let slot = Slot::new(10);
+
+        performance.register_duties(slot, &[duty(0, vec![0])]);
+        performance.register_published_messages(slot, Hash256::repeat_byte(1), vec![0].into_iter());
+        performance.process_block::<E>(slot + 1, None, &log);
+
+        let summary = summary(&performance, 0);
+        assert_eq!(summary.messages_missed, 1);
+        assert_eq!(summary.missed_no_block, 1);
+    }
+
+    #[test]
+    fn contribution_inclusion() {
+        let log = null_logger().unwrap();
+        let performance = SyncCommitteePerformance::default();
+        let slot = Slot::new(10);
+        let head = Hash256::repeat_byte(1);
+
+        let mut aggregation_bits = BitVector::default();
+        aggregation_bits.set(0, true).unwrap();
+        aggregation_bits.set(1, true).unwrap();
+        let contribution = SyncCommitteeContribution::<E> {
+            slot,
+            beacon_block_root: head,
+            subcommittee_index: 1,
+            aggregation_bits,
+            signature: bls::AggregateSignature::empty(),
+        };
+        performance.register_published_contribution(
+            &contribution,
+            vec![(7, PublicKeyBytes::empty())].into_iter(),
+        );
+
+        let offset = E::sync_subcommittee_size();
+        let sync_aggregate = aggregate_with_bits(&[offset, offset + 1]);
+        performance.process_block(slot + 1, Some((head, &sync_aggregate)), &log);
+
+        let summary = summary(&performance, 7);
+        assert_eq!(summary.contributions_published, 1);
+        assert_eq!(summary.contributions_included, 1);
+    }
+
+    #[test]
+    fn stale_slots_are_pruned() {
+        let log = null_logger().unwrap();
+        let performance = SyncCommitteePerformance::default();
+
+        performance.register_duties(Slot::new(5), &[duty(0, vec![0])]);
+        performance.register_duties(Slot::new(10), &[duty(1, vec![1])]);
+        performance.process_block::<E>(Slot::new(11), None, &log);
+
+        assert!(performance.pending.read().is_empty());
+        // The stale slot is dropped without affecting the validator's record.
+        assert!(performance
+            .summaries()
+            .iter()
+            .all(|summary| summary.validator_index != 0));
+    }
+}
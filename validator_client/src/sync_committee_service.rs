@@ -1,4 +1,7 @@
 use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::sync_committee_performance::{
+    SyncCommitteePerformance, SYNC_PERFORMANCE_LOOKBACK_SLOTS,
+};
 use crate::{duties_service::DutiesService, validator_store::ValidatorStore};
 use environment::RuntimeContext;
 use eth2::types::BlockId;
@@ -48,6 +51,8 @@ pub struct Inner<T: SlotClock + 'static, E: EthSpec> {
     ///
     /// This acts as a latch that fires once upon start-up, and then never again.
     first_subscription_done: AtomicBool,
+    /// Tracks the inclusion of the messages and contributions produced by this service.
+    performance: Arc<SyncCommitteePerformance>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
@@ -66,10 +71,16 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 beacon_nodes,
                 context,
                 first_subscription_done: AtomicBool::new(false),
+                performance: Arc::new(SyncCommitteePerformance::default()),
             }),
         }
     }
 
+    /// Returns the tracker of sync committee message and contribution inclusion.
+    pub fn performance(&self) -> Arc<SyncCommitteePerformance> {
+        self.performance.clone()
+    }
+
     /// Check if the Altair fork has been activated and therefore sync duties should be performed.
     ///
     /// Slot clock errors are mapped to `false`.
@@ -127,6 +138,9 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
                     // Do subscriptions for future slots/epochs.
                     self.spawn_subscription_tasks();
+
+                    // Check the inclusion of messages from prior slots.
+                    self.spawn_performance_tasks();
                 } else {
                     error!(log, "Failed to read slot clock");
                     // If we can't read the slot clock, just wait another slot.
@@ -174,6 +188,9 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
             return Ok(());
         }
 
+        // Any message that is not published from here on is recorded as a missed duty.
+        self.performance.register_duties(slot, &slot_duties.duties);
+
         // Fetch `block_root` and `execution_optimistic` for `SyncCommitteeContribution`.
         let response = self
             .beacon_nodes
@@ -299,6 +316,14 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 );
             })?;
 
+        self.performance.register_published_messages(
+            slot,
+            beacon_block_root,
+            committee_signatures
+                .iter()
+                .map(|signature| signature.validator_index),
+        );
+
         info!(
             log,
             "Successfully published sync committee messages";
@@ -382,6 +407,11 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
             })?
             .data;
 
+        let aggregators = subnet_aggregators
+            .iter()
+            .map(|(aggregator_index, aggregator_pk, _)| (*aggregator_index, *aggregator_pk))
+            .collect::<HashMap<_, _>>();
+
         // Create futures to produce signed contributions.
         let signature_futures = subnet_aggregators.into_iter().map(
             |(aggregator_index, aggregator_pk, selection_proof)| async move {
@@ -433,6 +463,17 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 );
             })?;
 
+        self.performance.register_published_contribution(
+            contribution,
+            signed_contributions
+                .iter()
+                .filter_map(|signed_contribution| {
+                    let aggregator_index = signed_contribution.message.aggregator_index;
+                    let aggregator_pk = aggregators.get(&aggregator_index)?;
+                    Some((aggregator_index, *aggregator_pk))
+                }),
+        );
+
         info!(
             log,
             "Successfully published sync contributions";
@@ -445,6 +486,79 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         Ok(())
     }
 
+    fn spawn_performance_tasks(&self) {
+        let slot = match self.slot_clock.now() {
+            Some(slot) => slot,
+            None => return,
+        };
+
+        // Messages signed at `message_slot` can only be included in the block at `block_slot`.
+        let message_slot = match slot.as_u64().checked_sub(SYNC_PERFORMANCE_LOOKBACK_SLOTS) {
+            Some(message_slot) => Slot::new(message_slot),
+            None => return,
+        };
+        let block_slot = message_slot + 1;
+
+        if !self.performance.has_pending_up_to(message_slot) {
+            return;
+        }
+
+        let service = self.clone();
+        let log = self.context.log().clone();
+        self.inner.context.executor.spawn(
+            async move {
+                service
+                    .check_sync_committee_inclusion(block_slot)
+                    .await
+                    .unwrap_or_else(|e| {
+                        error!(
+                            log,
+                            "Error checking sync committee inclusion";
+                            "block_slot" => block_slot,
+                            "error" => e,
+                        )
+                    });
+            },
+            "sync_committee_performance",
+        );
+    }
+
+    /// Fetch the canonical block at `block_slot` and check which of the messages and contributions
+    /// from the previous slot were included in its `SyncAggregate`.
+    async fn check_sync_committee_inclusion(&self, block_slot: Slot) -> Result<(), String> {
+        let log = self.context.log();
+
+        let response = self
+            .beacon_nodes
+            .first_success(RequireSynced::Yes, |beacon_node| async move {
+                beacon_node
+                    .get_beacon_blocks::<E>(BlockId::Slot(block_slot))
+                    .await
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+
+        match response {
+            Some(response) => {
+                let block = response.data;
+                let sync_aggregate = block
+                    .message()
+                    .body()
+                    .sync_aggregate()
+                    .map_err(|e| format!("Block has no sync aggregate: {:?}", e))?;
+                self.performance.process_block(
+                    block_slot,
+                    Some((block.parent_root(), sync_aggregate)),
+                    log,
+                );
+            }
+            // A 404 indicates that `block_slot` was skipped.
+            None => self.performance.process_block::<E>(block_slot, None, log),
+        }
+
+        Ok(())
+    }
+
     fn spawn_subscription_tasks(&self) {
         let service = self.clone();
         let log = self.context.log().clone();
@@ -20,10 +20,11 @@ use task_executor::TaskExecutor;
 use types::{
     attestation::Error as AttestationError, graffiti::GraffitiString, Address, AggregateAndProof,
     Attestation, BeaconBlock, BlindedPayload, ChainSpec, ContributionAndProof, Domain, Epoch,
-    EthSpec, ExecPayload, Fork, Graffiti, Hash256, Keypair, PublicKeyBytes, SelectionProof,
-    Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof, SignedRoot,
-    SignedValidatorRegistrationData, Slot, SyncAggregatorSelectionData, SyncCommitteeContribution,
-    SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId, ValidatorRegistrationData,
+    EthSpec, ExecPayload, Fork, Graffiti, Hash256, Keypair, PublicKey, PublicKeyBytes,
+    SelectionProof, Signature, SignedAggregateAndProof, SignedBeaconBlock,
+    SignedContributionAndProof, SignedRoot, SignedValidatorRegistrationData, Slot,
+    SyncAggregatorSelectionData, SyncCommitteeContribution, SyncCommitteeMessage,
+    SyncSelectionProof, SyncSubnetId, ValidatorRegistrationData,
 };
 use validator_dir::ValidatorDir;
 
@@ -216,6 +217,34 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(validator_def)
     }
 
+    /// Add and remove a batch of validators as a single update, see
+    /// `InitializedValidators::apply_definition_batch`.
+    ///
+    /// The slashing protection database must already have been updated for the batch using
+    /// `apply_slashing_protection_batch`.
+    // FIXME: ignore this clippy lint until the validator store is refactored to use async locks
+    #[allow(clippy::await_holding_lock)]
+    pub async fn apply_definition_batch(
+        &self,
+        add: Vec<ValidatorDefinition>,
+        remove: &[PublicKey],
+    ) -> Result<(), String> {
+        if let Some(doppelganger_service) = &self.doppelganger_service {
+            for def in &add {
+                doppelganger_service.register_new_validator::<E, _>(
+                    def.voting_public_key.compress(),
+                    &self.slot_clock,
+                )?;
+            }
+        }
+
+        self.validators
+            .write()
+            .apply_definition_batch(add, remove)
+            .await
+            .map_err(|e| format!("Unable to apply definitions: {:?}", e))
+    }
+
     /// Returns `ProposalData` for the provided `pubkey` if it exists in `InitializedValidators`.
     /// `ProposalData` fields include defaulting logic described in `get_fee_recipient_defaulting`,
     /// `get_gas_limit_defaulting`, and `get_builder_proposals_defaulting`.
@@ -845,28 +874,65 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         pubkeys: &[PublicKeyBytes],
     ) -> Result<Interchange, InterchangeError> {
-        self.slashing_protection.with_transaction(|txn| {
-            let known_pubkeys = pubkeys
-                .iter()
-                .filter_map(|pubkey| {
-                    let validator_id = self
-                        .slashing_protection
-                        .get_validator_id_ignoring_status(txn, pubkey)
-                        .ok()?;
-
-                    Some(
-                        self.slashing_protection
-                            .update_validator_status(txn, validator_id, false)
-                            .map(|()| *pubkey),
-                    )
-                })
-                .collect::<Result<Vec<PublicKeyBytes>, _>>()?;
-            self.slashing_protection.export_interchange_info_in_txn(
-                self.genesis_validators_root,
-                Some(&known_pubkeys),
-                txn,
-            )
-        })
+        self.apply_slashing_protection_batch(None, &[], pubkeys, false)
+    }
+
+    /// Apply the slashing protection changes for a batch of validator imports and deletions in a
+    /// single transaction.
+    ///
+    /// The `interchange` is imported and `import_pubkeys` are registered. The `delete_pubkeys`
+    /// are disabled and their slashing protection data is exported, as per
+    /// `export_slashing_protection_for_keys`. With `dry_run` set the transaction is rolled back
+    /// once all changes have been applied.
+    pub fn apply_slashing_protection_batch(
+        &self,
+        interchange: Option<Interchange>,
+        import_pubkeys: &[PublicKeyBytes],
+        delete_pubkeys: &[PublicKeyBytes],
+        dry_run: bool,
+    ) -> Result<Interchange, InterchangeError> {
+        self.slashing_protection
+            .with_transaction_or_dry_run(dry_run, |txn| {
+                if let Some(interchange) = interchange {
+                    self.slashing_protection.import_interchange_info_in_txn(
+                        interchange,
+                        self.genesis_validators_root,
+                        txn,
+                    )?;
+                }
+
+                self.slashing_protection
+                    .register_validators_in_txn(import_pubkeys.iter(), txn)?;
+
+                let known_pubkeys = delete_pubkeys
+                    .iter()
+                    .filter_map(|pubkey| {
+                        let validator_id = self
+                            .slashing_protection
+                            .get_validator_id_ignoring_status(txn, pubkey)
+                            .ok()?;
+
+                        Some(
+                            self.slashing_protection
+                                .update_validator_status(txn, validator_id, false)
+                                .map(|()| *pubkey),
+                        )
+                    })
+                    .collect::<Result<Vec<PublicKeyBytes>, _>>()?;
+                self.slashing_protection.export_interchange_info_in_txn(
+                    self.genesis_validators_root,
+                    Some(&known_pubkeys),
+                    txn,
+                )
+            })
+    }
+
+    /// Re-enable validators in the slashing protection database after a failed batch deletion.
+    pub fn restore_slashing_protection_registrations(
+        &self,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<(), NotSafe> {
+        self.slashing_protection.register_validators(pubkeys.iter())
     }
 
     /// Prune the slashing protection database so that it remains performant.